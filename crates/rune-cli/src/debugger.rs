@@ -0,0 +1,265 @@
+//! A command line debugger, which is used with `rune --debug <path>`.
+
+use rune::termcolor::StandardStream;
+use rune::EmitSource as _;
+use runestick::debugger::{Debugger, Stop};
+use runestick::{SourceId, Value, VmError, VmExecution};
+use std::io;
+use std::io::{BufRead as _, Write as _};
+use std::path::Path;
+
+const HELP: &str = "\
+commands:
+  b, break <loc>     set a breakpoint on a line, like `10` or `lib.rn:10`
+  d, delete <loc>    clear the breakpoint on a line
+  c, continue        run until a breakpoint is hit
+  n, next            step to the next line, stepping over calls
+  s, step            step to the next line, stepping into calls
+  f, finish          run until the current function returns
+  bt, backtrace      print the call frames
+  frame <n>          select the frame to inspect
+  l, locals          print the local variables of the selected frame
+  p, print <path>    print the value of a variable, like `object.field.0`
+  q, quit            stop debugging
+  h, help            print this help";
+
+/// Run the given execution under the debugger, reading commands from stdin.
+///
+/// Returns the execution, and its result unless debugging was stopped before
+/// it completed.
+pub(crate) fn run(
+    out: &mut StandardStream,
+    execution: VmExecution,
+    sources: &rune::Sources,
+) -> io::Result<(VmExecution, Option<Result<Value, VmError>>)> {
+    let mut debugger = Debugger::new(execution);
+
+    let mut source_id = 0;
+
+    while let Some(source) = sources.get(source_id) {
+        debugger.insert_source(source_id, source.clone());
+        source_id += 1;
+    }
+
+    let result = repl(out, &mut debugger, sources)?;
+    Ok((debugger.into_execution(), result))
+}
+
+/// Read and run commands until the execution completes or the user quits.
+///
+/// Lines are one-based in the interface of the debugger.
+fn repl(
+    out: &mut StandardStream,
+    debugger: &mut Debugger,
+    sources: &rune::Sources,
+) -> io::Result<Option<Result<Value, VmError>>> {
+    writeln!(out, "type `help` for a list of commands")?;
+    print_location(out, debugger, sources, 0)?;
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut frame = 0;
+
+    loop {
+        write!(out, "(rune) ")?;
+        out.flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(None),
+        };
+
+        let mut it = line.split_whitespace();
+
+        let command = match it.next() {
+            Some(command) => command,
+            None => continue,
+        };
+
+        let argument = it.next();
+
+        let stop = match command {
+            "c" | "continue" => debugger.resume(),
+            "n" | "next" => debugger.step_over(),
+            "s" | "step" => debugger.step_into(),
+            "f" | "finish" => debugger.step_out(),
+            "b" | "break" | "d" | "delete" => {
+                let (source_id, line) = match argument {
+                    Some(argument) => match location(debugger, sources, frame, argument) {
+                        Some(location) => location,
+                        None => {
+                            writeln!(out, "expected a line like `10` or `lib.rn:10`")?;
+                            continue;
+                        }
+                    },
+                    None => {
+                        writeln!(out, "expected a line number")?;
+                        continue;
+                    }
+                };
+
+                if let "d" | "delete" = command {
+                    if !debugger.clear_breakpoint(source_id, line) {
+                        writeln!(out, "no breakpoint on line {}", line + 1)?;
+                    }
+                } else if !debugger.set_breakpoint(source_id, line) {
+                    writeln!(out, "warning: no code on line {}", line + 1)?;
+                }
+
+                continue;
+            }
+            "bt" | "backtrace" => {
+                for (n, f) in debugger.frames().iter().enumerate() {
+                    let marker = if n == frame { "*" } else { " " };
+
+                    match f.signature() {
+                        Some(signature) => writeln!(out, "{} #{} {}", marker, n, signature)?,
+                        None => writeln!(out, "{} #{} <unknown>", marker, n)?,
+                    }
+                }
+
+                continue;
+            }
+            "frame" => {
+                let n = argument.and_then(|a| a.parse::<usize>().ok());
+
+                match n {
+                    Some(n) if n < debugger.frames().len() => {
+                        frame = n;
+                        print_location(out, debugger, sources, frame)?;
+                    }
+                    _ => writeln!(out, "no such frame")?,
+                }
+
+                continue;
+            }
+            "l" | "locals" => {
+                if let Some(f) = debugger.frames().get(frame) {
+                    for (name, value) in f.locals() {
+                        writeln!(out, "  {} = {:?}", name, value)?;
+                    }
+                }
+
+                continue;
+            }
+            "p" | "print" => {
+                let path = match argument {
+                    Some(path) => path,
+                    None => {
+                        writeln!(out, "expected a path to print")?;
+                        continue;
+                    }
+                };
+
+                match debugger.evaluate(frame, path) {
+                    Some(value) => writeln!(out, "{} = {:?}", path, value)?,
+                    None => writeln!(out, "{}: no such value", path)?,
+                }
+
+                continue;
+            }
+            "q" | "quit" => return Ok(None),
+            "h" | "help" => {
+                writeln!(out, "{}", HELP)?;
+                continue;
+            }
+            other => {
+                writeln!(out, "unknown command `{}`, try `help`", other)?;
+                continue;
+            }
+        };
+
+        frame = 0;
+
+        match stop {
+            Ok(Stop::Complete(value)) => return Ok(Some(Ok(value))),
+            Ok(Stop::Breakpoint(line)) => {
+                writeln!(out, "breakpoint on line {}", line.line + 1)?;
+            }
+            Ok(Stop::Step) => (),
+            Err(error) => return Ok(Some(Err(error))),
+        }
+
+        print_location(out, debugger, sources, frame)?;
+    }
+}
+
+/// Parse a one-based breakpoint location, like `10` or `lib.rn:10`, into a
+/// source id and a zero-based line.
+///
+/// A line without a path belongs to the source of the selected frame.
+fn location(
+    debugger: &Debugger,
+    sources: &rune::Sources,
+    frame: usize,
+    argument: &str,
+) -> Option<(SourceId, usize)> {
+    let (source_id, line) = match argument.rfind(':') {
+        Some(n) => {
+            let name = &argument[..n];
+            let path = Path::new(name);
+            let mut source_id = 0;
+
+            loop {
+                let source = sources.get(source_id)?;
+
+                let found = match source.path() {
+                    Some(p) => p.ends_with(path),
+                    None => source.name() == name,
+                };
+
+                if found {
+                    break;
+                }
+
+                source_id += 1;
+            }
+
+            (source_id, &argument[n + 1..])
+        }
+        None => {
+            let source_id = debugger
+                .frames()
+                .get(frame)
+                .and_then(|f| Some(f.span()?.0))
+                .unwrap_or_default();
+
+            (source_id, argument)
+        }
+    };
+
+    match line.parse::<usize>().ok()? {
+        0 => None,
+        line => Some((source_id, line - 1)),
+    }
+}
+
+/// Print the location of the given frame.
+fn print_location(
+    out: &mut StandardStream,
+    debugger: &Debugger,
+    sources: &rune::Sources,
+    frame: usize,
+) -> io::Result<()> {
+    let frames = debugger.frames();
+
+    let f = match frames.get(frame) {
+        Some(f) => f,
+        None => return Ok(()),
+    };
+
+    if let Some(signature) = f.signature() {
+        writeln!(out, "in fn {}", signature)?;
+    }
+
+    let location = f
+        .span()
+        .and_then(|(source_id, span)| Some((sources.get(source_id)?, span)));
+
+    if let Some((source, span)) = location {
+        let mut out = out.lock();
+        source.emit_source_line(&mut out, span)?;
+    }
+
+    Ok(())
+}
@@ -51,7 +51,7 @@
 
 use anyhow::{Context as _, Result};
 use rune::termcolor::{ColorChoice, StandardStream};
-use rune::{DumpInstructions as _, EmitDiagnostics as _, EmitSource as _};
+use rune::{DumpInstructions as _, EmitDiagnostics as _};
 use std::fs;
 use std::io;
 use std::io::Write as _;
@@ -59,7 +59,12 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use structopt::StructOpt;
 
-use runestick::{Unit, Value, VmExecution};
+use runestick::coverage::{Coverage, CoverageReport};
+use runestick::profiler::Profiler;
+use runestick::Unit;
+
+mod debugger;
+mod trace;
 
 pub const VERSION: &str = include_str!(concat!(env!("OUT_DIR"), "/version.txt"));
 
@@ -79,6 +84,26 @@ struct Args {
     /// Provide detailed tracing for each instruction executed.
     #[structopt(short, long)]
     trace: bool,
+    /// Run the script under an interactive debugger, which reads commands
+    /// from stdin.
+    #[structopt(long)]
+    debug: bool,
+    /// Profile the execution and write the recorded stacks to the given path,
+    /// in the folded format used by flamegraph tools.
+    ///
+    /// If the path ends with `.json`, a summary of the time spent in each
+    /// function and span of source code is written instead.
+    #[structopt(long, parse(from_os_str))]
+    profile: Option<PathBuf>,
+    /// Record which lines of code are executed and write an lcov tracefile to
+    /// the given path.
+    ///
+    /// If the path ends with `.json`, the number of times each line was
+    /// executed is written as JSON instead. When used with `--test`, the
+    /// scripts are executed and a single report covering all of them is
+    /// written.
+    #[structopt(long, parse(from_os_str))]
+    coverage: Option<PathBuf>,
     /// Dump everything.
     #[structopt(short, long)]
     dump: bool,
@@ -116,7 +141,8 @@ struct Args {
     /// Recursively load all files in the given directory.
     #[structopt(long)]
     recursive: bool,
-    /// Only test that the specified files compile, but don't execute them.
+    /// Only test that the specified files compile, but don't execute them
+    /// unless `--coverage` is used.
     #[structopt(long)]
     test: bool,
     /// Rune scripts to run.
@@ -136,6 +162,8 @@ struct Args {
     /// macros[=<true/false>] - Enable or disable macros (experimental).
     ///
     /// bytecode[=<true/false>] - Enable or disable bytecode caching (experimental).
+    ///
+    /// strict-let[=<true/false>] - Make `let` patterns which might not match without an `else` branch a compile error.
     #[structopt(name = "option", short = "O", number_of_values = 1)]
     compiler_options: Vec<String>,
 }
@@ -181,11 +209,12 @@ async fn try_main() -> Result<ExitCode> {
 
     let paths = walk_paths(args.recursive, std::mem::take(&mut args.paths));
     let mut status = ExitCode::Success;
+    let mut coverage = args.coverage.as_ref().map(|_| CoverageReport::new());
 
     for path in paths {
         let path = path?;
 
-        match run_path(&args, &options, &path).await? {
+        match run_path(&args, &options, &path, coverage.as_mut()).await? {
             ExitCode::Success => (),
             other => {
                 if args.test {
@@ -193,11 +222,16 @@ async fn try_main() -> Result<ExitCode> {
                     continue;
                 }
 
-                return Ok(other);
+                status = other;
+                break;
             }
         }
     }
 
+    if let (Some(coverage_path), Some(coverage)) = (&args.coverage, &coverage) {
+        write_coverage(coverage_path, coverage)?;
+    }
+
     Ok(status)
 }
 
@@ -239,7 +273,15 @@ fn walk_paths(recursive: bool, paths: Vec<PathBuf>) -> impl Iterator<Item = io::
 }
 
 /// Run a single path.
-async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<ExitCode> {
+///
+/// If coverage is being recorded, the coverage of the path is added to the
+/// given report.
+async fn run_path(
+    args: &Args,
+    options: &rune::Options,
+    path: &Path,
+    report: Option<&mut CoverageReport>,
+) -> Result<ExitCode> {
     let choice = match args.color.as_str() {
         "always" => ColorChoice::Always,
         "ansi" => ColorChoice::AlwaysAnsi,
@@ -387,7 +429,7 @@ async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<E
         }
     }
 
-    if args.test {
+    if args.test && report.is_none() {
         return Ok(ExitCode::Success);
     }
 
@@ -395,20 +437,41 @@ async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<E
 
     let mut execution: runestick::VmExecution = vm.execute(&["main"], ())?;
 
-    let result = if args.trace {
-        match do_trace(
-            &mut out,
+    let profiler = match &args.profile {
+        Some(profile_path) => {
+            let profiler = Profiler::new();
+            execution.set_profiler(Some(profiler.clone()))?;
+            Some((profile_path, profiler))
+        }
+        None => None,
+    };
+
+    let coverage = match report {
+        Some(report) => {
+            let coverage = Coverage::new();
+            execution.set_coverage(Some(coverage.clone()))?;
+            Some((report, coverage))
+        }
+        None => None,
+    };
+
+    let result = if args.debug {
+        let (returned, result) = debugger::run(&mut out, execution, &sources)?;
+        execution = returned;
+
+        match result {
+            Some(result) => result,
+            None => return Ok(ExitCode::Success),
+        }
+    } else if args.trace {
+        trace::run(
+            choice,
             &mut execution,
             &sources,
             args.dump_stack,
             args.with_source,
         )
-        .await
-        {
-            Ok(value) => Ok(value),
-            Err(TraceError::Io(io)) => return Err(io.into()),
-            Err(TraceError::VmError(vm)) => Err(vm),
-        }
+        .await?
     } else {
         execution.async_complete().await
     };
@@ -428,6 +491,19 @@ async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<E
         }
     };
 
+    if let Some((profile_path, profiler)) = profiler {
+        write_profile(profile_path, &profiler, &context)?;
+    }
+
+    if let Some((report, coverage)) = coverage {
+        let mut source_id = 0;
+
+        while let Some(source) = sources.get(source_id) {
+            report.insert(coverage.lines(source_id, source));
+            source_id += 1;
+        }
+    }
+
     if args.dump_stack {
         writeln!(out, "# full stack dump after halting")?;
 
@@ -482,6 +558,41 @@ async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<E
     }
 }
 
+/// Write the profile recorded by the given profiler to the given path.
+fn write_profile(path: &Path, profiler: &Profiler, context: &runestick::Context) -> Result<()> {
+    let mut profile = profiler.profile();
+    profile.resolve_names(context);
+
+    let f =
+        fs::File::create(path).with_context(|| format!("creating profile: {}", path.display()))?;
+    let mut f = io::BufWriter::new(f);
+
+    if path.extension().and_then(|e| e.to_str()) == Some("json") {
+        serde_json::to_writer_pretty(&mut f, &profile)?;
+    } else {
+        profile.write_folded(&mut f)?;
+    }
+
+    f.flush()?;
+    Ok(())
+}
+
+/// Write the coverage report to the given path.
+fn write_coverage(path: &Path, report: &CoverageReport) -> Result<()> {
+    let f =
+        fs::File::create(path).with_context(|| format!("creating coverage: {}", path.display()))?;
+    let mut f = io::BufWriter::new(f);
+
+    if path.extension().and_then(|e| e.to_str()) == Some("json") {
+        serde_json::to_writer_pretty(&mut f, report)?;
+    } else {
+        report.write_lcov(&mut f)?;
+    }
+
+    f.flush()?;
+    Ok(())
+}
+
 // Our own private ExitCode since std::process::ExitCode is nightly only.
 // Note that these numbers are actually meaningful on Windows, but we don't
 // care.
@@ -505,111 +616,6 @@ async fn main() {
     }
 }
 
-enum TraceError {
-    Io(std::io::Error),
-    VmError(runestick::VmError),
-}
-
-impl From<std::io::Error> for TraceError {
-    fn from(error: std::io::Error) -> Self {
-        Self::Io(error)
-    }
-}
-
-/// Perform a detailed trace of the program.
-async fn do_trace(
-    out: &mut StandardStream,
-    execution: &mut VmExecution,
-    sources: &rune::Sources,
-    dump_stack: bool,
-    with_source: bool,
-) -> Result<Value, TraceError> {
-    let mut current_frame_len = execution
-        .vm()
-        .map_err(TraceError::VmError)?
-        .call_frames()
-        .len();
-
-    loop {
-        {
-            let vm = execution.vm().map_err(TraceError::VmError)?;
-            let mut out = out.lock();
-
-            if let Some((hash, signature)) =
-                vm.unit().debug_info().and_then(|d| d.function_at(vm.ip()))
-            {
-                writeln!(out, "fn {} ({}):", signature, hash)?;
-            }
-
-            let debug = vm
-                .unit()
-                .debug_info()
-                .and_then(|d| d.instruction_at(vm.ip()));
-
-            if with_source {
-                let debug_info = debug.and_then(|d| sources.get(d.source_id).map(|s| (s, d.span)));
-                if let Some((source, span)) = debug_info {
-                    source.emit_source_line(&mut out, span)?;
-                }
-            }
-
-            if let Some(label) = debug.and_then(|d| d.label.as_ref()) {
-                writeln!(out, "{}:", label)?;
-            }
-
-            if let Some(inst) = vm.unit().instruction_at(vm.ip()) {
-                write!(out, "  {:04} = {}", vm.ip(), inst)?;
-            } else {
-                write!(out, "  {:04} = *out of bounds*", vm.ip())?;
-            }
-
-            if let Some(comment) = debug.and_then(|d| d.comment.as_ref()) {
-                write!(out, " // {}", comment)?;
-            }
-
-            writeln!(out)?;
-        }
-
-        let result = match execution.async_step().await {
-            Ok(result) => result,
-            Err(e) => return Err(TraceError::VmError(e)),
-        };
-
-        let mut out = out.lock();
-
-        if dump_stack {
-            let vm = execution.vm().map_err(TraceError::VmError)?;
-            let frames = vm.call_frames();
-
-            let stack = vm.stack();
-
-            if current_frame_len != frames.len() {
-                if current_frame_len < frames.len() {
-                    writeln!(out, "=> frame {} ({}):", frames.len(), stack.stack_bottom())?;
-                } else {
-                    writeln!(out, "<= frame {} ({}):", frames.len(), stack.stack_bottom())?;
-                }
-
-                current_frame_len = frames.len();
-            }
-
-            let values = stack.get(stack.stack_bottom()..).expect("bad stack slice");
-
-            if values.is_empty() {
-                writeln!(out, "    *empty*")?;
-            }
-
-            for (n, value) in values.iter().enumerate() {
-                writeln!(out, "    {}+{} = {:?}", stack.stack_bottom(), n, value)?;
-            }
-        }
-
-        if let Some(result) = result {
-            break Ok(result);
-        }
-    }
-}
-
 /// Test if path `a` is newer than path `b`.
 fn should_cache_be_used(source: &Path, cached: &Path) -> io::Result<bool> {
     let source = fs::metadata(source)?;
@@ -0,0 +1,170 @@
+//! Instruction tracing, which is used with `rune --trace <path>`.
+
+use rune::termcolor::{ColorChoice, StandardStream};
+use rune::EmitSource as _;
+use runestick::{Inst, Source, Value, Vm, VmError, VmExecution, VmObserver};
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::io::Write as _;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Run the given execution to completion while printing each instruction
+/// executed.
+pub(crate) async fn run(
+    choice: ColorChoice,
+    execution: &mut VmExecution,
+    sources: &rune::Sources,
+    dump_stack: bool,
+    with_source: bool,
+) -> io::Result<Result<Value, VmError>> {
+    let mut tracer = Tracer {
+        out: RefCell::new(StandardStream::stdout(choice)),
+        sources: Vec::new(),
+        dump_stack,
+        with_source,
+        started: Cell::new(false),
+        error: RefCell::new(None),
+    };
+
+    let mut source_id = 0;
+
+    while let Some(source) = sources.get(source_id) {
+        tracer.sources.push(source.clone());
+        source_id += 1;
+    }
+
+    let tracer = Rc::new(tracer);
+
+    if let Err(error) = execution.set_observer(Some(tracer.clone())) {
+        return Ok(Err(error));
+    }
+
+    let result = execution.async_complete().await;
+
+    if let Some(error) = tracer.error.borrow_mut().take() {
+        return Err(error);
+    }
+
+    if dump_stack {
+        if let Ok(vm) = execution.vm() {
+            tracer.write_stack(vm)?;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Observer which prints each instruction as it's executed.
+struct Tracer {
+    out: RefCell<StandardStream>,
+    sources: Vec<Arc<Source>>,
+    dump_stack: bool,
+    with_source: bool,
+    /// If an instruction has been printed.
+    started: Cell<bool>,
+    /// The first error raised while printing.
+    error: RefCell<Option<io::Error>>,
+}
+
+impl Tracer {
+    /// Record the error of a failed write, unless one has already been
+    /// recorded.
+    fn record(&self, result: io::Result<()>) {
+        if let Err(error) = result {
+            self.error.borrow_mut().get_or_insert(error);
+        }
+    }
+
+    fn write_instruction(&self, vm: &Vm, inst: &Inst) -> io::Result<()> {
+        // NB: the stack after the previous instruction.
+        if self.dump_stack && self.started.replace(true) {
+            self.write_stack(vm)?;
+        }
+
+        let out = self.out.borrow_mut();
+        let mut out = out.lock();
+
+        if let Some((hash, signature)) = vm.unit().debug_info().and_then(|d| d.function_at(vm.ip()))
+        {
+            writeln!(out, "fn {} ({}):", signature, hash)?;
+        }
+
+        let debug = vm
+            .unit()
+            .debug_info()
+            .and_then(|d| d.instruction_at(vm.ip()));
+
+        if self.with_source {
+            let debug_info = debug.and_then(|d| Some((self.sources.get(d.source_id)?, d.span)));
+
+            if let Some((source, span)) = debug_info {
+                source.emit_source_line(&mut out, span)?;
+            }
+        }
+
+        if let Some(label) = debug.and_then(|d| d.label.as_ref()) {
+            writeln!(out, "{}:", label)?;
+        }
+
+        write!(out, "  {:04} = {}", vm.ip(), inst)?;
+
+        if let Some(comment) = debug.and_then(|d| d.comment.as_ref()) {
+            write!(out, " // {}", comment)?;
+        }
+
+        writeln!(out)?;
+        Ok(())
+    }
+
+    fn write_stack(&self, vm: &Vm) -> io::Result<()> {
+        let out = self.out.borrow_mut();
+        let mut out = out.lock();
+
+        let stack = vm.stack();
+        let values = stack.get(stack.stack_bottom()..).expect("bad stack slice");
+
+        if values.is_empty() {
+            writeln!(out, "    *empty*")?;
+        }
+
+        for (n, value) in values.iter().enumerate() {
+            writeln!(out, "    {}+{} = {:?}", stack.stack_bottom(), n, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl VmObserver for Tracer {
+    fn on_call(&self, vm: &Vm, _: usize) {
+        // NB: the first function of each virtual machine has no frame.
+        if !self.dump_stack || vm.call_frames().is_empty() {
+            return;
+        }
+
+        let out = self.out.borrow_mut();
+        let mut out = out.lock();
+        let frames = vm.call_frames().len();
+        let bottom = vm.stack().stack_bottom();
+        self.record(writeln!(out, "=> frame {} ({}):", frames, bottom));
+    }
+
+    fn on_return(&self, vm: &Vm, _: &Value) {
+        let frames = vm.call_frames();
+
+        let frame = match frames.last() {
+            Some(frame) if self.dump_stack => frame,
+            _ => return,
+        };
+
+        let out = self.out.borrow_mut();
+        let mut out = out.lock();
+        let bottom = frame.stack_bottom();
+        self.record(writeln!(out, "<= frame {} ({}):", frames.len() - 1, bottom));
+    }
+
+    fn on_instruction(&self, vm: &Vm, inst: &Inst) {
+        self.record(self.write_instruction(vm, inst));
+    }
+}
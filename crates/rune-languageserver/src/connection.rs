@@ -126,6 +126,16 @@ impl Output {
         Ok(())
     }
 
+    /// Send the given message as is, without wrapping it in an envelope.
+    pub async fn message<T>(&self, message: &T) -> Result<()>
+    where
+        T: serde::Serialize,
+    {
+        let mut bytes = serde_json::to_vec(message)?;
+        self.write_response(&mut bytes).await?;
+        Ok(())
+    }
+
     /// Write the given response body.
     async fn write_response(&self, bytes: &mut Vec<u8>) -> Result<()> {
         use std::io::Write as _;
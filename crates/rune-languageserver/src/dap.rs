@@ -0,0 +1,855 @@
+//! A debug adapter for Rune scripts, which implements the [Debug Adapter
+//! Protocol] over stdin and stdout.
+//!
+//! The adapter is started with `rune-languageserver --dap`, and drives the
+//! script through a [runestick::debugger::Debugger].
+//!
+//! [Debug Adapter Protocol]: https://microsoft.github.io/debug-adapter-protocol/
+
+use crate::connection::stdio;
+use anyhow::{anyhow, Result};
+use hashbrown::HashMap;
+use rune::EmitDiagnostics as _;
+use runestick::debugger::{Debugger, Mode, Stop};
+use runestick::{ContextError, Module, Panic, Source, SourceId, Stack, Value, VmError};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TryRecvError;
+
+/// The only thread of the script being debugged.
+const THREAD_ID: u64 = 1;
+
+/// The number of instructions the program runs before checking for incoming
+/// requests, like a request to pause.
+const BUDGET: usize = 10_000;
+
+/// Run the debug adapter over stdin and stdout until the client disconnects.
+///
+/// The context should not provide the `std::io` module, since anything
+/// written to stdout would corrupt the protocol. Output is instead captured
+/// and sent to the client as output events.
+pub fn run(mut context: runestick::Context, options: rune::Options) -> Result<()> {
+    let (mut input, output) = stdio()?;
+
+    context.install(&output_module()?)?;
+
+    let mut session = Session::new(context, options);
+
+    tokio::runtime::Runtime::new()?.block_on(async {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        // NB: requests are read on a separate task, so that they can be
+        // checked for between running parts of the program.
+        tokio::spawn(async move {
+            loop {
+                let request = match input.next().await {
+                    Ok(Some(frame)) => serde_json::from_slice::<Request>(frame.content)
+                        .map_err(anyhow::Error::from),
+                    Ok(None) => break,
+                    Err(error) => Err(error),
+                };
+
+                if tx.send(request).is_err() {
+                    break;
+                }
+            }
+        });
+
+        loop {
+            let request = if session.is_running() {
+                match rx.try_recv() {
+                    Ok(request) => Some(request),
+                    Err(TryRecvError::Empty) => None,
+                    Err(TryRecvError::Closed) => break,
+                }
+            } else {
+                match rx.recv().await {
+                    Some(request) => Some(request),
+                    None => break,
+                }
+            };
+
+            let messages = match request {
+                Some(request) => session.process(request?),
+                None => session.poll(),
+            };
+
+            for message in messages {
+                output.message(&message).await?;
+            }
+
+            if session.is_disconnected() {
+                break;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// An incoming request.
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    /// The sequence number of the request.
+    pub seq: u64,
+    /// The command being requested.
+    pub command: String,
+    /// The arguments of the command.
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct Response<'a> {
+    seq: u64,
+    #[serde(rename = "type")]
+    ty: &'static str,
+    request_seq: u64,
+    success: bool,
+    command: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    body: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct Event {
+    seq: u64,
+    #[serde(rename = "type")]
+    ty: &'static str,
+    event: &'static str,
+    body: serde_json::Value,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitializeArguments {
+    lines_start_at1: Option<bool>,
+    columns_start_at1: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchArguments {
+    program: PathBuf,
+    #[serde(default)]
+    stop_on_entry: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SourceArgument {
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SourceBreakpoint {
+    line: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetBreakpointsArguments {
+    source: SourceArgument,
+    #[serde(default)]
+    breakpoints: Vec<SourceBreakpoint>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScopesArguments {
+    frame_id: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VariablesArguments {
+    variables_reference: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EvaluateArguments {
+    expression: String,
+    frame_id: Option<usize>,
+}
+
+/// Something which can be expanded into variables by the client.
+#[derive(Debug, Clone)]
+enum Handle {
+    /// The locals of the frame with the given index.
+    Locals(usize),
+    /// The fields of a value.
+    Value(Value),
+}
+
+/// A program being debugged.
+struct Program {
+    debugger: Debugger,
+    sources: rune::Sources,
+    stop_on_entry: bool,
+}
+
+impl Program {
+    /// Find the id of the source loaded from the given canonical path.
+    fn source_id(&self, path: &Path) -> Option<SourceId> {
+        source_id(&self.sources, path)
+    }
+}
+
+/// Find the id of the source loaded from the given canonical path.
+fn source_id(sources: &rune::Sources, path: &Path) -> Option<SourceId> {
+    let mut source_id = 0;
+
+    while let Some(source) = sources.get(source_id) {
+        let canonical = source.path().and_then(|p| fs::canonicalize(p).ok());
+
+        if canonical.as_deref() == Some(path) {
+            return Some(source_id);
+        }
+
+        source_id += 1;
+    }
+
+    None
+}
+
+lazy_static::lazy_static! {
+    /// Output captured from scripts.
+    static ref OUTPUT: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+}
+
+/// Construct a `std::io` module, which captures output so that it can be
+/// sent to the client.
+pub fn output_module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", &["io"]);
+    module.function(&["print"], print_impl)?;
+    module.function(&["println"], println_impl)?;
+    module.raw_fn(&["dbg"], dbg_impl)?;
+    Ok(module)
+}
+
+fn print_impl(m: &str) -> Result<(), Panic> {
+    write!(OUTPUT.lock().unwrap(), "{}", m).map_err(Panic::custom)
+}
+
+fn println_impl(m: &str) -> Result<(), Panic> {
+    writeln!(OUTPUT.lock().unwrap(), "{}", m).map_err(Panic::custom)
+}
+
+fn dbg_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
+    let mut o = OUTPUT.lock().unwrap();
+
+    for value in stack.drain_stack_top(args)? {
+        writeln!(o, "{:?}", value).map_err(VmError::panic)?;
+    }
+
+    stack.push(Value::Unit);
+    Ok(())
+}
+
+/// Take the output captured so far.
+fn take_output() -> String {
+    let o = std::mem::take(&mut *OUTPUT.lock().unwrap());
+    String::from_utf8_lossy(&o).into_owned()
+}
+
+/// The state of a debug session.
+pub struct Session {
+    context: runestick::Context,
+    options: rune::Options,
+    /// Sequence number of the next outgoing message.
+    seq: u64,
+    lines_start_at1: bool,
+    columns_start_at1: bool,
+    /// Zero-based lines with breakpoints, by canonical path.
+    breakpoints: HashMap<PathBuf, Vec<usize>>,
+    program: Option<Program>,
+    /// If the client is done configuring the session.
+    configured: bool,
+    /// If the program has started running.
+    started: bool,
+    /// How to run the program once the current request has been responded
+    /// to.
+    pending: Option<Mode>,
+    /// If the program is running, in which case it's advanced with
+    /// [Session::poll].
+    running: bool,
+    /// If the program was paused by the current request, which is reported
+    /// once it has been responded to.
+    paused: bool,
+    disconnected: bool,
+    /// Handles which can be expanded into variables. They're valid until
+    /// the execution is resumed.
+    handles: Vec<Handle>,
+    /// Messages to send.
+    outgoing: Vec<serde_json::Value>,
+}
+
+impl Session {
+    /// Construct a new session.
+    ///
+    /// The context should capture output with [output_module].
+    pub fn new(context: runestick::Context, options: rune::Options) -> Self {
+        Self {
+            context,
+            options,
+            seq: 1,
+            lines_start_at1: true,
+            columns_start_at1: true,
+            breakpoints: HashMap::new(),
+            program: None,
+            configured: false,
+            started: false,
+            pending: None,
+            running: false,
+            paused: false,
+            disconnected: false,
+            handles: Vec::new(),
+            outgoing: Vec::new(),
+        }
+    }
+
+    /// Test if the client has disconnected.
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+
+    /// Test if the program is running, in which case it should be advanced
+    /// with [Session::poll] until it stops.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Run the program for a bounded number of instructions, and return the
+    /// messages to send.
+    ///
+    /// This should be called repeatedly while the program is running, so that
+    /// requests like `pause` can be processed in between.
+    pub fn poll(&mut self) -> Vec<serde_json::Value> {
+        if self.running {
+            if let Some(program) = &mut self.program {
+                let result = program.debugger.advance(BUDGET).transpose();
+
+                let output = take_output();
+
+                if !output.is_empty() {
+                    self.output("stdout", output);
+                }
+
+                if let Some(result) = result {
+                    self.running = false;
+                    self.report(result);
+                }
+            } else {
+                self.running = false;
+            }
+        }
+
+        std::mem::take(&mut self.outgoing)
+    }
+
+    /// Process a request, and return the messages to send in response.
+    ///
+    /// If the request resumes the program, it's run with [Session::poll].
+    pub fn process(&mut self, request: Request) -> Vec<serde_json::Value> {
+        log::info!("dap request: {}", request.command);
+
+        match self.handle(&request) {
+            Ok(body) => self.response(&request, Ok(body)),
+            Err(error) => self.response(&request, Err(error.to_string())),
+        }
+
+        // NB: the client only starts configuring the session once it has
+        // received the capabilities of the adapter.
+        if request.command == "initialize" {
+            self.event("initialized", json!({}));
+        }
+
+        // NB: the program is started once it's both launched and configured,
+        // after the response to the request which completed that.
+        if !self.started && self.configured && self.program.is_some() {
+            self.started = true;
+            self.start();
+        }
+
+        if std::mem::take(&mut self.paused) {
+            self.stopped("pause");
+        }
+
+        if let Some(mode) = self.pending.take() {
+            self.resume(mode);
+        }
+
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle(&mut self, request: &Request) -> Result<serde_json::Value> {
+        let arguments = request.arguments.clone();
+
+        Ok(match request.command.as_str() {
+            "initialize" => {
+                let arguments: InitializeArguments =
+                    serde_json::from_value(arguments).unwrap_or_default();
+                self.lines_start_at1 = arguments.lines_start_at1.unwrap_or(true);
+                self.columns_start_at1 = arguments.columns_start_at1.unwrap_or(true);
+
+                json!({
+                    "supportsConfigurationDoneRequest": true,
+                    "supportsEvaluateForHovers": true,
+                })
+            }
+            "launch" => {
+                self.launch(serde_json::from_value(arguments)?)?;
+                json!({})
+            }
+            "setBreakpoints" => self.set_breakpoints(serde_json::from_value(arguments)?)?,
+            "configurationDone" => {
+                self.configured = true;
+                json!({})
+            }
+            "threads" => json!({
+                "threads": [{"id": THREAD_ID, "name": "main"}],
+            }),
+            "continue" => {
+                self.program()?;
+                self.pending = Some(Mode::Continue);
+                json!({"allThreadsContinued": true})
+            }
+            "next" => {
+                self.program()?;
+                self.pending = Some(Mode::StepOver);
+                json!({})
+            }
+            "stepIn" => {
+                self.program()?;
+                self.pending = Some(Mode::StepInto);
+                json!({})
+            }
+            "stepOut" => {
+                self.program()?;
+                self.pending = Some(Mode::StepOut);
+                json!({})
+            }
+            "pause" => {
+                self.program()?;
+
+                if self.running {
+                    self.running = false;
+                    self.paused = true;
+                }
+
+                json!({})
+            }
+            "stackTrace" => self.stack_trace()?,
+            "scopes" => {
+                let arguments: ScopesArguments = serde_json::from_value(arguments)?;
+                let reference = self.handle_for(Handle::Locals(arguments.frame_id));
+
+                json!({
+                    "scopes": [{
+                        "name": "Locals",
+                        "variablesReference": reference,
+                        "expensive": false,
+                    }],
+                })
+            }
+            "variables" => {
+                let arguments: VariablesArguments = serde_json::from_value(arguments)?;
+                self.variables(arguments.variables_reference)?
+            }
+            "evaluate" => {
+                let arguments: EvaluateArguments = serde_json::from_value(arguments)?;
+                let program = self.program()?;
+
+                let value = program
+                    .debugger
+                    .evaluate(arguments.frame_id.unwrap_or(0), arguments.expression.trim())
+                    .ok_or_else(|| anyhow!("no such value: {}", arguments.expression))?;
+
+                let result = format!("{:?}", value);
+                let reference = self.value_reference(&value);
+
+                json!({
+                    "result": result,
+                    "variablesReference": reference,
+                })
+            }
+            "disconnect" | "terminate" => {
+                self.running = false;
+                self.disconnected = true;
+                json!({})
+            }
+            command => return Err(anyhow!("unsupported command: {}", command)),
+        })
+    }
+
+    /// Compile the program to debug.
+    fn launch(&mut self, arguments: LaunchArguments) -> Result<()> {
+        let path = fs::canonicalize(&arguments.program)?;
+        let source = Source::from_path(&path)?;
+
+        let mut sources = rune::Sources::new();
+        sources.insert(source);
+
+        let mut errors = rune::Errors::new();
+        let mut warnings = rune::Warnings::disabled();
+
+        let unit = match rune::load_sources(
+            &self.context,
+            &self.options,
+            &mut sources,
+            &mut errors,
+            &mut warnings,
+        ) {
+            Ok(unit) => unit,
+            Err(rune::LoadSourcesError) => {
+                let mut out = rune::termcolor::NoColor::new(Vec::new());
+                errors.emit_diagnostics(&mut out, &sources)?;
+                let out = String::from_utf8_lossy(out.get_ref()).into_owned();
+                self.output("stderr", out);
+                return Err(anyhow!("failed to compile {}", path.display()));
+            }
+        };
+
+        let vm = runestick::Vm::new(Arc::new(self.context.runtime()), Arc::new(unit));
+        let mut debugger = Debugger::new(vm.execute(&["main"], ())?);
+
+        let mut id = 0;
+
+        while let Some(source) = sources.get(id) {
+            debugger.insert_source(id, source.clone());
+            id += 1;
+        }
+
+        for (path, lines) in &self.breakpoints {
+            if let Some(source_id) = source_id(&sources, path) {
+                for line in lines {
+                    debugger.set_breakpoint(source_id, *line);
+                }
+            }
+        }
+
+        self.program = Some(Program {
+            debugger,
+            sources,
+            stop_on_entry: arguments.stop_on_entry,
+        });
+
+        Ok(())
+    }
+
+    /// Replace the breakpoints of a source.
+    fn set_breakpoints(&mut self, arguments: SetBreakpointsArguments) -> Result<serde_json::Value> {
+        let path = arguments
+            .source
+            .path
+            .ok_or_else(|| anyhow!("missing source path"))?;
+
+        let path = fs::canonicalize(&path)?;
+        let offset = if self.lines_start_at1 { 1 } else { 0 };
+
+        let lines = arguments
+            .breakpoints
+            .iter()
+            .map(|b| b.line.saturating_sub(offset))
+            .collect::<Vec<_>>();
+
+        let mut breakpoints = Vec::new();
+
+        let program = self
+            .program
+            .as_mut()
+            .and_then(|program| Some((program.source_id(&path)?, program)));
+
+        match program {
+            Some((source_id, program)) => {
+                let existing = program
+                    .debugger
+                    .breakpoints()
+                    .filter(|b| b.source_id == source_id)
+                    .collect::<Vec<_>>();
+
+                for b in existing {
+                    program.debugger.clear_breakpoint(b.source_id, b.line);
+                }
+
+                for line in &lines {
+                    let verified = program.debugger.set_breakpoint(source_id, *line);
+
+                    breakpoints.push(json!({
+                        "verified": verified,
+                        "line": line + offset,
+                    }));
+                }
+            }
+            None => {
+                // NB: breakpoints are verified once the program which loads
+                // the source is launched.
+                for line in &lines {
+                    breakpoints.push(json!({
+                        "verified": false,
+                        "line": line + offset,
+                    }));
+                }
+            }
+        }
+
+        self.breakpoints.insert(path, lines);
+        Ok(json!({ "breakpoints": breakpoints }))
+    }
+
+    /// Start running the program.
+    fn start(&mut self) {
+        let stop_on_entry = match &self.program {
+            Some(program) => program.stop_on_entry,
+            None => return,
+        };
+
+        if stop_on_entry {
+            self.stopped("entry");
+        } else {
+            self.resume(Mode::Continue);
+        }
+    }
+
+    /// Resume the program in the given mode. It's run with [Session::poll].
+    fn resume(&mut self, mode: Mode) {
+        self.handles.clear();
+
+        if let Some(program) = &mut self.program {
+            program.debugger.start(mode);
+            self.running = true;
+        }
+    }
+
+    /// Report why the program stopped.
+    fn report(&mut self, result: Result<Stop, VmError>) {
+        match result {
+            Ok(Stop::Breakpoint(..)) => self.stopped("breakpoint"),
+            Ok(Stop::Step) => self.stopped("step"),
+            Ok(Stop::Complete(value)) => {
+                self.output("console", format!("== {:?}\n", value));
+                self.exited(0);
+            }
+            Err(error) => {
+                self.output("stderr", format!("error: {}\n", error));
+                self.exited(1);
+            }
+        }
+    }
+
+    fn stack_trace(&mut self) -> Result<serde_json::Value> {
+        let program = self.program()?;
+        let mut frames = Vec::new();
+
+        for (id, frame) in program.debugger.frames().iter().enumerate() {
+            let name = match frame.signature() {
+                Some(signature) => signature.to_string(),
+                None => String::from("<unknown>"),
+            };
+
+            let location = frame
+                .span()
+                .and_then(|(source_id, span)| Some((program.sources.get(source_id)?, span)));
+
+            let (source, line, column) = match location {
+                Some((source, span)) => {
+                    let (line, column) =
+                        source.position_to_unicode_line_char(span.start.into_usize());
+
+                    let source = json!({
+                        "name": source.name(),
+                        "path": source.path().map(Path::to_string_lossy),
+                    });
+
+                    (source, line, column)
+                }
+                None => (serde_json::Value::Null, 0, 0),
+            };
+
+            frames.push(json!({
+                "id": id,
+                "name": name,
+                "source": source,
+                "line": line + if self.lines_start_at1 { 1 } else { 0 },
+                "column": column + if self.columns_start_at1 { 1 } else { 0 },
+            }));
+        }
+
+        Ok(json!({
+            "stackFrames": frames,
+            "totalFrames": frames.len(),
+        }))
+    }
+
+    fn variables(&mut self, reference: usize) -> Result<serde_json::Value> {
+        let handle = reference
+            .checked_sub(1)
+            .and_then(|index| self.handles.get(index))
+            .cloned()
+            .ok_or_else(|| anyhow!("no such variables reference: {}", reference))?;
+
+        let values = match handle {
+            Handle::Locals(frame) => {
+                let frames = self.program()?.debugger.frames();
+
+                let frame = frames
+                    .get(frame)
+                    .ok_or_else(|| anyhow!("no such frame: {}", frame))?;
+
+                frame
+                    .locals()
+                    .into_iter()
+                    .map(|(name, value)| (name.to_owned(), value.clone()))
+                    .collect::<Vec<_>>()
+            }
+            Handle::Value(value) => fields(&value)?,
+        };
+
+        let mut variables = Vec::new();
+
+        for (name, value) in values {
+            let reference = self.value_reference(&value);
+
+            variables.push(json!({
+                "name": name,
+                "value": format!("{:?}", value),
+                "variablesReference": reference,
+            }));
+        }
+
+        Ok(json!({ "variables": variables }))
+    }
+
+    fn program(&self) -> Result<&Program> {
+        self.program
+            .as_ref()
+            .ok_or_else(|| anyhow!("no program has been launched"))
+    }
+
+    /// Get a reference which expands the given value, or `0` if it can't be
+    /// expanded.
+    fn value_reference(&mut self, value: &Value) -> usize {
+        match value {
+            Value::Object(..)
+            | Value::Struct(..)
+            | Value::StructVariant(..)
+            | Value::Vec(..)
+            | Value::Tuple(..)
+            | Value::TupleStruct(..)
+            | Value::TupleVariant(..) => self.handle_for(Handle::Value(value.clone())),
+            _ => 0,
+        }
+    }
+
+    fn handle_for(&mut self, handle: Handle) -> usize {
+        self.handles.push(handle);
+        self.handles.len()
+    }
+
+    fn stopped(&mut self, reason: &str) {
+        self.event(
+            "stopped",
+            json!({
+                "reason": reason,
+                "threadId": THREAD_ID,
+                "allThreadsStopped": true,
+            }),
+        );
+    }
+
+    fn exited(&mut self, exit_code: i32) {
+        self.program = None;
+        self.event("exited", json!({ "exitCode": exit_code }));
+        self.event("terminated", json!({}));
+    }
+
+    fn output(&mut self, category: &str, output: String) {
+        self.event(
+            "output",
+            json!({
+                "category": category,
+                "output": output,
+            }),
+        );
+    }
+
+    fn response(&mut self, request: &Request, result: Result<serde_json::Value, String>) {
+        let (success, message, body) = match result {
+            Ok(body) => (true, None, body),
+            Err(message) => (false, Some(message), json!({})),
+        };
+
+        let response = Response {
+            seq: self.next_seq(),
+            ty: "response",
+            request_seq: request.seq,
+            success,
+            command: &request.command,
+            message,
+            body,
+        };
+
+        self.push(&response);
+    }
+
+    fn event(&mut self, event: &'static str, body: serde_json::Value) {
+        let event = Event {
+            seq: self.next_seq(),
+            ty: "event",
+            event,
+            body,
+        };
+
+        self.push(&event);
+    }
+
+    fn push<T>(&mut self, message: &T)
+    where
+        T: Serialize,
+    {
+        match serde_json::to_value(message) {
+            Ok(message) => self.outgoing.push(message),
+            Err(error) => log::error!("failed to serialize message: {}", error),
+        }
+    }
+
+    fn next_seq(&mut self) -> u64 {
+        let seq = self.seq;
+        self.seq += 1;
+        seq
+    }
+}
+
+/// Get the named fields of a value which can be expanded.
+fn fields(value: &Value) -> Result<Vec<(String, Value)>> {
+    Ok(match value {
+        Value::Object(object) => object_fields(&*object.borrow_ref()?),
+        Value::Struct(object) => object_fields(object.borrow_ref()?.data()),
+        Value::StructVariant(object) => object_fields(object.borrow_ref()?.data()),
+        Value::Vec(vec) => indexed_fields(&vec.borrow_ref()?),
+        Value::Tuple(tuple) => indexed_fields(&tuple.borrow_ref()?),
+        Value::TupleStruct(tuple) => indexed_fields(tuple.borrow_ref()?.data()),
+        Value::TupleVariant(tuple) => indexed_fields(tuple.borrow_ref()?.data()),
+        _ => Vec::new(),
+    })
+}
+
+fn object_fields(object: &runestick::Object) -> Vec<(String, Value)> {
+    object
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+fn indexed_fields(values: &[Value]) -> Vec<(String, Value)> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| (index.to_string(), value.clone()))
+        .collect()
+}
@@ -45,6 +45,7 @@
 //! [Rune Language]: https://rune-rs.github.io
 
 mod connection;
+pub mod dap;
 pub mod envelope;
 mod server;
 mod state;
@@ -76,24 +76,36 @@ fn main() -> Result<()> {
     let mut it = env::args();
     it.next();
 
-    #[allow(clippy::never_loop)]
+    let mut dap = false;
+
     for arg in it {
         match arg.as_str() {
             "--version" => {
                 println!("Rune language server {}", rune_languageserver::VERSION);
                 return Ok(());
             }
+            "--dap" => {
+                dap = true;
+            }
             other => {
                 bail!("Unsupported option: {}", other);
             }
         }
     }
 
-    let mut context = rune_modules::default_context()?;
-    context.install(&rune_modules::experiments::module(true)?)?;
-
     let mut options = rune::Options::default();
     options.macros(true);
 
+    if dap {
+        // NB: stdout is used by the protocol, so the debug adapter captures
+        // the output of scripts instead.
+        let mut context = rune_modules::with_config(false)?;
+        context.install(&rune_modules::experiments::module(false)?)?;
+        return rune_languageserver::dap::run(context, options);
+    }
+
+    let mut context = rune_modules::default_context()?;
+    context.install(&rune_modules::experiments::module(true)?)?;
+
     rune_languageserver::run(context, options)
 }
@@ -0,0 +1,257 @@
+use rune_languageserver::dap::{output_module, Request, Session};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn request(session: &mut Session, seq: u64, command: &str, arguments: Value) -> Vec<Value> {
+    let request: Request = serde_json::from_value(json!({
+        "seq": seq,
+        "type": "request",
+        "command": command,
+        "arguments": arguments,
+    }))
+    .unwrap();
+
+    let mut messages = session.process(request);
+
+    while session.is_running() {
+        messages.extend(session.poll());
+    }
+
+    messages
+}
+
+/// Write the given source to a temporary file which is unique to the test.
+fn temp_source(name: &str, source: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+
+    let path = std::env::temp_dir().join(format!(
+        "rune-languageserver-{}-{}-{}.rn",
+        name,
+        std::process::id(),
+        nanos
+    ));
+
+    fs::write(&path, source).unwrap();
+    path
+}
+
+fn find<'a>(messages: &'a [Value], event: &str) -> Option<&'a Value> {
+    messages.iter().find(|m| m["event"] == event)
+}
+
+#[test]
+fn test_dap_session() {
+    let path = temp_source(
+        "session",
+        r#"
+fn add(a, b) {
+    a + b
+}
+
+pub fn main() {
+    let a = 1;
+    let b = add(a, 2);
+    println(`b = ${b}`);
+    b
+}
+"#,
+    );
+
+    let mut context = rune_modules::with_config(false).unwrap();
+    context.install(&output_module().unwrap()).unwrap();
+
+    let mut session = Session::new(context, rune::Options::default());
+
+    let messages = request(&mut session, 1, "initialize", json!({}));
+    assert_eq!(messages[0]["success"], true);
+    assert!(find(&messages, "initialized").is_some());
+
+    let messages = request(
+        &mut session,
+        2,
+        "setBreakpoints",
+        json!({
+            "source": {"path": path},
+            "breakpoints": [{"line": 8}],
+        }),
+    );
+    assert_eq!(messages[0]["body"]["breakpoints"][0]["verified"], false);
+
+    let messages = request(&mut session, 3, "launch", json!({"program": path}));
+    assert_eq!(messages[0]["success"], true);
+
+    let messages = request(&mut session, 4, "configurationDone", json!({}));
+    assert_eq!(messages[0]["success"], true);
+    let stopped = find(&messages, "stopped").expect("expected to stop");
+    assert_eq!(stopped["body"]["reason"], "breakpoint");
+
+    let messages = request(&mut session, 5, "stackTrace", json!({"threadId": 1}));
+    let frames = &messages[0]["body"]["stackFrames"];
+    assert_eq!(frames[0]["name"], "main()");
+    assert_eq!(frames[0]["line"], 8);
+
+    let messages = request(&mut session, 6, "scopes", json!({"frameId": 0}));
+    let reference = messages[0]["body"]["scopes"][0]["variablesReference"].clone();
+
+    let messages = request(
+        &mut session,
+        7,
+        "variables",
+        json!({"variablesReference": reference}),
+    );
+    let variables = &messages[0]["body"]["variables"];
+    assert_eq!(variables[0]["name"], "a");
+    assert_eq!(variables[0]["value"], "1");
+
+    let messages = request(&mut session, 8, "next", json!({"threadId": 1}));
+    assert_eq!(
+        find(&messages, "stopped").unwrap()["body"]["reason"],
+        "step"
+    );
+
+    let messages = request(&mut session, 9, "evaluate", json!({"expression": "b"}));
+    assert_eq!(messages[0]["body"]["result"], "3");
+
+    let messages = request(&mut session, 10, "continue", json!({"threadId": 1}));
+    assert_eq!(
+        find(&messages, "output").unwrap()["body"]["output"],
+        "b = 3\n"
+    );
+    assert_eq!(find(&messages, "exited").unwrap()["body"]["exitCode"], 0);
+    assert!(find(&messages, "terminated").is_some());
+
+    let messages = request(&mut session, 11, "disconnect", json!({}));
+    assert_eq!(messages[0]["success"], true);
+    assert!(session.is_disconnected());
+
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn test_dap_pause() {
+    let path = temp_source(
+        "pause",
+        r#"
+pub fn main() {
+    let n = 0;
+
+    loop {
+        n += 1;
+    }
+}
+"#,
+    );
+
+    let mut context = rune_modules::with_config(false).unwrap();
+    context.install(&output_module().unwrap()).unwrap();
+
+    let mut session = Session::new(context, rune::Options::default());
+    session.process(serde_json::from_value(json!({"seq": 1, "command": "initialize"})).unwrap());
+    session.process(
+        serde_json::from_value(json!({
+            "seq": 2,
+            "command": "launch",
+            "arguments": {"program": path},
+        }))
+        .unwrap(),
+    );
+
+    let messages = session.process(
+        serde_json::from_value(json!({"seq": 3, "command": "configurationDone"})).unwrap(),
+    );
+    assert_eq!(messages[0]["success"], true);
+    assert!(session.is_running());
+
+    let messages = session.poll();
+    assert!(find(&messages, "stopped").is_none());
+    assert!(session.is_running());
+
+    let messages = request(&mut session, 4, "pause", json!({"threadId": 1}));
+    assert_eq!(messages[0]["success"], true);
+    assert_eq!(
+        find(&messages, "stopped").unwrap()["body"]["reason"],
+        "pause"
+    );
+    assert!(!session.is_running());
+
+    let messages = request(&mut session, 5, "evaluate", json!({"expression": "n"}));
+    assert_ne!(messages[0]["body"]["result"], "0");
+
+    let messages = request(&mut session, 6, "disconnect", json!({}));
+    assert_eq!(messages[0]["success"], true);
+    assert!(session.is_disconnected());
+
+    let _ = fs::remove_file(&path);
+}
+
+fn write_frame(out: &mut impl std::io::Write, message: Value) {
+    let content = serde_json::to_vec(&message).unwrap();
+    write!(out, "Content-Length: {}\r\n\r\n", content.len()).unwrap();
+    out.write_all(&content).unwrap();
+    out.flush().unwrap();
+}
+
+fn read_frame(input: &mut impl std::io::BufRead) -> Value {
+    let mut length = None;
+
+    loop {
+        let mut line = String::new();
+        input.read_line(&mut line).unwrap();
+        let line = line.trim();
+
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            length = Some(value.trim().parse::<usize>().unwrap());
+        }
+    }
+
+    let mut content = vec![0; length.expect("missing content length")];
+    input.read_exact(&mut content).unwrap();
+    serde_json::from_slice(&content).unwrap()
+}
+
+#[test]
+fn test_dap_stdio() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rune-languageserver"))
+        .arg("--dap")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+
+    write_frame(
+        &mut stdin,
+        json!({"seq": 1, "type": "request", "command": "initialize", "arguments": {}}),
+    );
+
+    let response = read_frame(&mut stdout);
+    assert_eq!(response["command"], "initialize");
+    assert_eq!(response["success"], true);
+
+    let event = read_frame(&mut stdout);
+    assert_eq!(event["event"], "initialized");
+
+    write_frame(
+        &mut stdin,
+        json!({"seq": 2, "type": "request", "command": "disconnect"}),
+    );
+
+    let response = read_frame(&mut stdout);
+    assert_eq!(response["command"], "disconnect");
+    assert_eq!(response["success"], true);
+
+    assert!(child.wait().unwrap().success());
+}
@@ -180,7 +180,7 @@ impl ExitStatus {
         write!(buf, "{}", self.status)
     }
 
-    fn code(&self) -> Option<i32> {
-        self.status.code()
+    fn code(&self) -> Option<i64> {
+        self.status.code().map(i64::from)
     }
 }
@@ -90,6 +90,8 @@ pub enum Expr {
     Await(Box<ast::ExprAwait>),
     /// Try expression.
     Try(Box<ast::ExprTry>),
+    /// A cast expression.
+    Cast(Box<ast::ExprCast>),
     /// A select expression.
     Select(Box<ast::ExprSelect>),
     /// A closure expression.
@@ -170,6 +172,7 @@ impl Expr {
             Self::Index(expr) => take(&mut expr.attributes),
             Self::Await(expr) => take(&mut expr.attributes),
             Self::Try(expr) => take(&mut expr.attributes),
+            Self::Cast(expr) => take(&mut expr.attributes),
             Self::ForceSemi(expr) => expr.expr.take_attributes(),
             Self::Object(expr) => take(&mut expr.attributes),
             Self::Range(expr) => take(&mut expr.attributes),
@@ -207,6 +210,7 @@ impl Expr {
             Self::Index(expr) => &expr.attributes,
             Self::Await(expr) => &expr.attributes,
             Self::Try(expr) => &expr.attributes,
+            Self::Cast(expr) => &expr.attributes,
             Self::ForceSemi(expr) => expr.expr.attributes(),
             Self::MacroCall(expr) => &expr.attributes,
             Self::Object(expr) => &expr.attributes,
@@ -270,6 +274,7 @@ impl Expr {
         let expr = Self::parse_chain(p, expr, callable)?;
 
         let expr = if *eager_binary {
+            let expr = Self::parse_cast(p, expr)?;
             Self::parse_binary(p, expr, 0, eager_brace)?
         } else {
             expr
@@ -350,6 +355,7 @@ impl Expr {
         };
 
         let lhs = Self::parse_chain(p, lhs, callable)?;
+        let lhs = Self::parse_cast(p, lhs)?;
         Ok(Self::parse_binary(p, lhs, 0, EagerBrace(true))?)
     }
 
@@ -606,6 +612,23 @@ impl Expr {
         Ok(expr)
     }
 
+    /// Parse trailing `as` casts of an expression.
+    ///
+    /// Casts bind tighter than any binary operator, but looser than unary
+    /// operators.
+    fn parse_cast(p: &mut Parser<'_>, mut expr: Self) -> Result<Self, ParseError> {
+        while p.peek::<T![as]>()? {
+            expr = Self::Cast(Box::new(ast::ExprCast {
+                attributes: expr.take_attributes(),
+                expr,
+                as_token: p.parse()?,
+                ty: p.parse()?,
+            }));
+        }
+
+        Ok(expr)
+    }
+
     fn parse_binary_rhs(
         p: &mut Parser<'_>,
         lhs: &Self,
@@ -614,7 +637,8 @@ impl Expr {
         lookahead_tok: &mut Option<ast::BinOp>,
     ) -> Result<Self, ParseError> {
         let rhs = Self::parse_base(p, &mut vec![], eager_brace)?;
-        let mut rhs = Self::parse_chain(p, rhs, Callable(true))?;
+        let rhs = Self::parse_chain(p, rhs, Callable(true))?;
+        let mut rhs = Self::parse_cast(p, rhs)?;
 
         *lookahead_tok = ast::BinOp::from_peeker(p.peeker());
 
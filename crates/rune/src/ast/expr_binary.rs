@@ -48,6 +48,8 @@ pub enum BinOp {
     Mul,
     /// Remainder operator `a % b`.
     Rem,
+    /// Power operator `a ** b`.
+    Pow,
     /// Equality check `a == b`.
     Eq,
     /// Inequality check `a != b`.
@@ -135,7 +137,8 @@ impl BinOp {
     pub(super) fn precedence(self) -> usize {
         // NB: Rules from: https://doc.rust-lang.org/reference/expressions.html#expression-precedence
         match self {
-            Self::Is | Self::IsNot => 12,
+            Self::Is | Self::IsNot => 13,
+            Self::Pow => 12,
             Self::Mul | Self::Div | Self::Rem => 11,
             Self::Add | Self::Sub => 10,
             Self::Shl | Self::Shr => 9,
@@ -178,6 +181,7 @@ impl BinOp {
             K![*] => Self::Mul,
             K![/] => Self::Div,
             K![%] => Self::Rem,
+            K![**] => Self::Pow,
             K![==] => Self::Eq,
             K![!=] => Self::Neq,
             K![<] => Self::Lt,
@@ -231,6 +235,7 @@ impl fmt::Display for BinOp {
             Self::Div => write!(f, "/"),
             Self::Mul => write!(f, "*"),
             Self::Rem => write!(f, "%"),
+            Self::Pow => write!(f, "**"),
             Self::Eq => write!(f, "=="),
             Self::Neq => write!(f, "!="),
             Self::Gt => write!(f, ">"),
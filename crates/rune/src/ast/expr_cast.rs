@@ -0,0 +1,30 @@
+use crate::ast;
+use crate::{Spanned, ToTokens};
+
+/// A cast expression `<expr> as <type>`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{testing, ast};
+///
+/// testing::roundtrip::<ast::ExprCast>("42 as float");
+/// testing::roundtrip::<ast::ExprCast>("foo.bar() as int");
+///
+/// let expr = testing::roundtrip::<ast::ExprCast>("a as int as char");
+/// assert!(matches!(expr.expr, ast::Expr::Cast(..)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+pub struct ExprCast {
+    /// Attributes associated with expression.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The expression being cast.
+    pub expr: ast::Expr,
+    /// The `as` keyword.
+    pub as_token: T![as],
+    /// The type being cast to.
+    pub ty: ast::Path,
+}
+
+expr_parse!(Cast, ExprCast, "cast expression");
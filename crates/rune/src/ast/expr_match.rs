@@ -76,10 +76,13 @@ expr_parse!(Match, ExprMatch, "match expression");
 /// use rune::{testing, ast};
 ///
 /// testing::roundtrip::<ast::ExprMatchBranch>("1 => { foo }");
+/// testing::roundtrip::<ast::ExprMatchBranch>("1 | 2 | 3 => 42");
+/// testing::roundtrip::<ast::ExprMatchBranch>("'a'..='z' | '_' => true");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Parse, Spanned)]
 pub struct ExprMatchBranch {
     /// The pattern to match.
+    #[rune(parse_with = "parse_pat")]
     pub pat: ast::Pat,
     /// The branch condition.
     pub condition: Option<(T![if], ast::Expr)>,
@@ -88,3 +91,7 @@ pub struct ExprMatchBranch {
     /// The body of the match.
     pub body: ast::Expr,
 }
+
+fn parse_pat(p: &mut Parser<'_>) -> Result<ast::Pat, ParseError> {
+    ast::Pat::parse_with_or(p)
+}
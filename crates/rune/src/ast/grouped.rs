@@ -119,12 +119,25 @@ macro_rules! grouped {
             S: Peek + Parse,
         {
             fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+                Self::parse_with(parser, T::parse)
+            }
+        }
+
+        impl<T, S> $name<T, S>
+        where
+            S: Peek + Parse,
+        {
+            /// Parse using the given function to parse each element.
+            pub fn parse_with(
+                parser: &mut Parser<'_>,
+                mut parse: impl FnMut(&mut Parser<'_>) -> Result<T, ParseError>,
+            ) -> Result<Self, ParseError> {
                 let open = parser.parse()?;
 
                 let mut $field = Vec::new();
 
                 while !parser.peek::<$close>()? {
-                    let expr = parser.parse()?;
+                    let expr = parse(parser)?;
                     let sep = parser.parse::<Option<S>>()?;
                     let is_end = sep.is_none();
                     $field.push((expr, sep));
@@ -12,6 +12,9 @@ use crate::{Parse, ParseError, Parser, Spanned, ToTokens};
 /// testing::roundtrip::<ast::ItemImpl>("impl Foo { fn test(self) { } }");
 /// testing::roundtrip::<ast::ItemImpl>("#[variant(enum_= \"SuperHero\", x = \"1\")] impl Foo { fn test(self) { } }");
 /// testing::roundtrip::<ast::ItemImpl>("#[xyz] impl Foo { #[jit] fn test(self) { } }");
+///
+/// let item = testing::roundtrip::<ast::ItemImpl>("impl Shape for Circle { fn area(self) { } }");
+/// assert!(item.trait_.is_some());
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 pub struct ItemImpl {
@@ -20,6 +23,9 @@ pub struct ItemImpl {
     pub attributes: Vec<ast::Attribute>,
     /// The `impl` keyword.
     pub impl_: T![impl],
+    /// The path of the trait being implemented, followed by the `for` keyword.
+    #[rune(iter)]
+    pub trait_: Option<(ast::Path, T![for])>,
     /// Path of the implementation.
     pub path: ast::Path,
     /// The open brace.
@@ -37,7 +43,14 @@ impl ItemImpl {
         attributes: Vec<ast::Attribute>,
     ) -> Result<Self, ParseError> {
         let impl_ = parser.parse()?;
-        let path = parser.parse()?;
+        let mut path = parser.parse()?;
+
+        let trait_ = if let Some(for_token) = parser.parse::<Option<T![for]>>()? {
+            Some((std::mem::replace(&mut path, parser.parse()?), for_token))
+        } else {
+            None
+        };
+
         let open = parser.parse()?;
 
         let mut functions = vec![];
@@ -51,6 +64,7 @@ impl ItemImpl {
         Ok(Self {
             attributes,
             impl_,
+            trait_,
             path,
             open,
             functions,
@@ -0,0 +1,156 @@
+use crate::ast;
+use crate::{Id, Parse, ParseError, Parser, Spanned, ToTokens};
+
+/// A trait item.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{testing, ast};
+///
+/// testing::roundtrip::<ast::ItemTrait>("trait Shape {}");
+/// testing::roundtrip::<ast::ItemTrait>("pub trait Shape { fn area(self); }");
+///
+/// let item = testing::roundtrip::<ast::ItemTrait>("trait Shape { fn area(self); fn describe(self) { } }");
+/// assert_eq!(item.items.len(), 2);
+/// assert!(matches!(item.items[0], ast::TraitItem::Required(..)));
+/// assert!(matches!(item.items[1], ast::TraitItem::Provided(..)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+pub struct ItemTrait {
+    /// Opaque identifier of the trait.
+    #[rune(id)]
+    pub id: Option<Id>,
+    /// The attributes for the trait.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The visibility of the `trait` item.
+    #[rune(optional)]
+    pub visibility: ast::Visibility,
+    /// The `trait` keyword.
+    pub trait_token: T![trait],
+    /// The name of the trait.
+    pub name: ast::Ident,
+    /// The open brace.
+    pub open: T!['{'],
+    /// The functions declared by the trait.
+    pub items: Vec<TraitItem>,
+    /// The close brace.
+    pub close: T!['}'],
+}
+
+impl ItemTrait {
+    /// Parse a `trait` item with the given attributes and visibility.
+    pub fn parse_with_meta(
+        parser: &mut Parser<'_>,
+        attributes: Vec<ast::Attribute>,
+        visibility: ast::Visibility,
+    ) -> Result<Self, ParseError> {
+        let trait_token = parser.parse()?;
+        let name = parser.parse()?;
+        let open = parser.parse()?;
+
+        let mut items = vec![];
+
+        while !parser.peek::<ast::CloseBrace>()? {
+            items.push(parser.parse()?);
+        }
+
+        let close = parser.parse()?;
+
+        Ok(Self {
+            id: None,
+            attributes,
+            visibility,
+            trait_token,
+            name,
+            open,
+            items,
+            close,
+        })
+    }
+}
+
+item_parse!(Trait, ItemTrait, "trait item");
+
+/// A function declared inside of a trait.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+pub enum TraitItem {
+    /// A function without a body, which must be provided by implementations.
+    Required(Box<TraitFn>),
+    /// A function with a default implementation.
+    Provided(Box<ast::ItemFn>),
+}
+
+impl TraitItem {
+    /// The name of the trait function.
+    pub fn name(&self) -> &ast::Ident {
+        match self {
+            Self::Required(item) => &item.name,
+            Self::Provided(item) => &item.name,
+        }
+    }
+}
+
+impl Parse for TraitItem {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let attributes = parser.parse()?;
+        let fn_token = parser.parse()?;
+        let name = parser.parse()?;
+        let args = parser.parse()?;
+
+        if let Some(semi) = parser.parse::<Option<T![;]>>()? {
+            return Ok(Self::Required(Box::new(TraitFn {
+                attributes,
+                fn_token,
+                name,
+                args,
+                semi,
+            })));
+        }
+
+        Ok(Self::Provided(Box::new(ast::ItemFn {
+            id: None,
+            attributes,
+            visibility: ast::Visibility::Inherited,
+            const_token: None,
+            async_token: None,
+            fn_token,
+            name,
+            args,
+            body: parser.parse()?,
+        })))
+    }
+}
+
+/// A function declaration inside of a trait, without a body.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{testing, ast};
+///
+/// testing::roundtrip::<ast::TraitFn>("fn area(self);");
+/// testing::roundtrip::<ast::TraitFn>("#[doc = \"x\"] fn scale(self, factor);");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Parse, ToTokens, Spanned)]
+pub struct TraitFn {
+    /// The attributes for the function.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The `fn` token.
+    pub fn_token: T![fn],
+    /// The name of the function.
+    pub name: ast::Ident,
+    /// The arguments of the function.
+    pub args: ast::Parenthesized<ast::FnArg, T![,]>,
+    /// The terminating semicolon.
+    pub semi: T![;],
+}
+
+impl TraitFn {
+    /// Test if function is an instance fn.
+    pub fn is_instance(&self) -> bool {
+        matches!(self.args.first(), Some((ast::FnArg::SelfValue(..), _)))
+    }
+}
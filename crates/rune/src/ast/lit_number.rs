@@ -26,6 +26,8 @@ pub struct LitNumber {
 /// testing::roundtrip::<ast::LitNumber>("42.42");
 /// testing::roundtrip::<ast::LitNumber>("0.42");
 /// testing::roundtrip::<ast::LitNumber>("0.42e10");
+/// testing::roundtrip::<ast::LitNumber>("42u32");
+/// testing::roundtrip::<ast::LitNumber>("0xffu64");
 /// ```
 impl Parse for LitNumber {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
@@ -76,8 +78,13 @@ impl<'a> Resolve<'a> for LitNumber {
             ast::NumberBase::Decimal => (0, 10),
         };
 
-        let number = num::BigInt::from_str_radix(&string[s..], radix).map_err(err_span(span))?;
-        return Ok(ast::Number::Integer(number));
+        let (string, size) = ast::NumberSize::split_suffix(&string[s..]);
+        let number = num::BigInt::from_str_radix(string, radix).map_err(err_span(span))?;
+
+        return Ok(match size {
+            Some(size) => ast::Number::Sized(number, size),
+            None => ast::Number::Integer(number),
+        });
 
         fn err_span<E>(span: Span) -> impl Fn(E) -> ResolveError {
             move |_| ResolveError::new(span, ResolveErrorKind::BadNumberLiteral)
@@ -1,7 +1,8 @@
 use crate::ast;
 use crate::{Parse, ParseError, Parser, Spanned, ToTokens};
 
-/// A local variable declaration `let <pattern> = <expr>;`
+/// A local variable declaration `let <pattern> = <expr>;`, or
+/// `let <pattern> = <expr> else { .. };` if the pattern might not match.
 ///
 /// # Examples
 ///
@@ -11,6 +12,7 @@ use crate::{Parse, ParseError, Parser, Spanned, ToTokens};
 /// testing::roundtrip::<ast::Local>("let x = 1;");
 /// testing::roundtrip::<ast::Local>("#[attr] let a = f();");
 /// testing::roundtrip::<ast::Local>("let a = b{}().foo[0].await;");
+/// testing::roundtrip::<ast::Local>("let Some(a) = f() else { return; };");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Parse, Spanned)]
 pub struct Local {
@@ -26,6 +28,9 @@ pub struct Local {
     /// The expression the binding is assigned to.
     #[rune(parse_with = "parse_expr")]
     pub expr: ast::Expr,
+    /// The `else` branch, which is evaluated if the pattern doesn't match.
+    #[rune(iter)]
+    pub else_branch: Option<(T![else], ast::Block)>,
     /// Trailing semicolon of the local.
     pub semi: T![;],
 }
@@ -47,6 +47,7 @@ mod expr_binary;
 mod expr_block;
 mod expr_break;
 mod expr_call;
+mod expr_cast;
 mod expr_closure;
 mod expr_continue;
 mod expr_field_access;
@@ -80,6 +81,7 @@ mod item_fn;
 mod item_impl;
 mod item_mod;
 mod item_struct;
+mod item_trait;
 mod item_use;
 mod label;
 mod lit;
@@ -108,6 +110,7 @@ pub use self::expr_binary::{BinOp, ExprBinary};
 pub use self::expr_block::ExprBlock;
 pub use self::expr_break::{ExprBreak, ExprBreakValue};
 pub use self::expr_call::ExprCall;
+pub use self::expr_cast::ExprCast;
 pub use self::expr_closure::ExprClosure;
 pub use self::expr_continue::ExprContinue;
 pub use self::expr_field_access::{ExprField, ExprFieldAccess};
@@ -142,6 +145,7 @@ pub use self::item_fn::ItemFn;
 pub use self::item_impl::ItemImpl;
 pub use self::item_mod::{ItemMod, ItemModBody};
 pub use self::item_struct::{Field, ItemStruct, ItemStructBody};
+pub use self::item_trait::{ItemTrait, TraitFn, TraitItem};
 pub use self::item_use::{ItemUse, ItemUsePath, ItemUseSegment};
 pub use self::label::Label;
 pub use self::lit::Lit;
@@ -153,12 +157,15 @@ pub use self::lit_number::LitNumber;
 pub use self::lit_str::LitStr;
 pub use self::local::Local;
 pub use self::macro_call::MacroCall;
-pub use self::pat::{Pat, PatBinding, PatLit, PatObject, PatPath, PatTuple, PatVec};
+pub use self::pat::{
+    Pat, PatAt, PatBinding, PatIgnore, PatLit, PatObject, PatOr, PatPath, PatRange, PatRest,
+    PatTuple, PatVec,
+};
 pub use self::path::{Path, PathKind, PathSegment};
 pub use self::stmt::{ItemOrExpr, Stmt, StmtSortKey};
 pub use self::token::{
-    BuiltIn, CopySource, Delimiter, Number, NumberBase, NumberSource, NumberText, StrSource,
-    StrText, StringSource, Token,
+    BuiltIn, CopySource, Delimiter, Number, NumberBase, NumberSize, NumberSource, NumberText,
+    StrSource, StrText, StringSource, Token,
 };
 pub use self::vis::Visibility;
 
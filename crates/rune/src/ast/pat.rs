@@ -20,6 +20,12 @@ pub enum Pat {
     PatBinding(PatBinding),
     /// The rest pattern `..`.
     PatRest(PatRest),
+    /// An or-pattern `a | b`.
+    PatOr(PatOr),
+    /// A range pattern `a..b` or `a..=b`.
+    PatRange(PatRange),
+    /// A pattern bound to a name `name @ pattern`.
+    PatAt(PatAt),
 }
 
 /// Parsing a block expression.
@@ -41,23 +47,26 @@ pub enum Pat {
 /// testing::roundtrip::<ast::Pat>("var");
 /// testing::roundtrip::<ast::Pat>("_");
 /// testing::roundtrip::<ast::Pat>("Foo(n)");
+/// testing::roundtrip::<ast::Pat>("0..10");
+/// testing::roundtrip::<ast::Pat>("-10..=-1");
+/// testing::roundtrip::<ast::Pat>("'a'..='z'");
+/// testing::roundtrip::<ast::Pat>("b'0'..");
+/// testing::roundtrip::<ast::Pat>("whole @ Some(n)");
+/// testing::roundtrip::<ast::Pat>("digit @ '0'..='9'");
+/// testing::roundtrip::<ast::Pat>("(1 | 2, _)");
+/// testing::roundtrip::<ast::Pat>("[0 | 1, ..]");
+/// testing::roundtrip::<ast::Pat>("Some('a' | 'b')");
+/// testing::roundtrip::<ast::Pat>("#{a: 1 | 2}");
+/// testing::roundtrip::<ast::Pat>("Foo { a: 1 | 2, b }");
 /// ```
 impl Parse for Pat {
     fn parse(p: &mut Parser<'_>) -> Result<Self, ParseError> {
         let attributes = p.parse::<Vec<ast::Attribute>>()?;
 
         match p.nth(0)? {
-            K![byte] => {
-                return Ok(Self::PatLit(PatLit {
-                    attributes,
-                    expr: ast::Expr::from_lit(ast::Lit::Byte(p.parse()?)),
-                }));
-            }
-            K![char] => {
-                return Ok(Self::PatLit(PatLit {
-                    attributes,
-                    expr: ast::Expr::from_lit(ast::Lit::Char(p.parse()?)),
-                }));
+            K![byte] | K![char] => {
+                let expr = Self::parse_range_bound(p)?;
+                return Self::parse_lit_or_range(p, attributes, expr);
             }
             K![bytestr] => {
                 return Ok(Self::PatLit(PatLit {
@@ -86,10 +95,8 @@ impl Parse for Pat {
                 });
             }
             K![number] => {
-                return Ok(Self::PatLit(PatLit {
-                    attributes,
-                    expr: ast::Expr::from_lit(ast::Lit::Number(p.parse()?)),
-                }));
+                let expr = Self::parse_range_bound(p)?;
+                return Self::parse_lit_or_range(p, attributes, expr);
             }
             K![..] => {
                 return Ok(Self::PatRest(PatRest {
@@ -102,28 +109,28 @@ impl Parse for Pat {
                     _ => Self::PatTuple(PatTuple {
                         attributes,
                         path: None,
-                        items: p.parse()?,
+                        items: ast::Parenthesized::parse_with(p, Self::parse_with_or)?,
                     }),
                 });
             }
             K!['['] => {
                 return Ok(Self::PatVec(PatVec {
                     attributes,
-                    items: p.parse()?,
+                    items: ast::Bracketed::parse_with(p, Self::parse_with_or)?,
                 }))
             }
             K![#] => {
                 return Ok(Self::PatObject(PatObject {
                     attributes,
                     ident: p.parse()?,
-                    items: p.parse()?,
+                    items: ast::Braced::parse_with(p, Self::parse_object_item)?,
                 }))
             }
             K![-] => {
-                let expr: ast::Expr = p.parse()?;
+                let expr = Self::parse_range_bound(p)?;
 
                 if expr.is_lit() {
-                    return Ok(Self::PatLit(PatLit { attributes, expr }));
+                    return Self::parse_lit_or_range(p, attributes, expr);
                 }
             }
             K![_] => {
@@ -139,12 +146,12 @@ impl Parse for Pat {
                     K!['('] => Self::PatTuple(PatTuple {
                         attributes,
                         path: Some(path),
-                        items: p.parse()?,
+                        items: ast::Parenthesized::parse_with(p, Self::parse_with_or)?,
                     }),
                     K!['{'] => Self::PatObject(PatObject {
                         attributes,
                         ident: ast::ObjectIdent::Named(path),
-                        items: p.parse()?,
+                        items: ast::Braced::parse_with(p, Self::parse_object_item)?,
                     }),
                     K![:] => Self::PatBinding(PatBinding {
                         attributes,
@@ -152,6 +159,19 @@ impl Parse for Pat {
                         colon: p.parse()?,
                         pat: p.parse()?,
                     }),
+                    K![@] => {
+                        let ident = match path.try_as_ident() {
+                            Some(ident) => *ident,
+                            None => return Err(ParseError::expected(&path, "identifier")),
+                        };
+
+                        Self::PatAt(PatAt {
+                            attributes,
+                            ident,
+                            at: p.parse()?,
+                            pat: p.parse()?,
+                        })
+                    }
                     _ => Self::PatPath(PatPath { attributes, path }),
                 });
             }
@@ -162,6 +182,89 @@ impl Parse for Pat {
     }
 }
 
+impl Pat {
+    /// Parse a pattern, including any or-patterns like `1 | 2 | 3` following
+    /// it.
+    ///
+    /// This is not part of the regular [Parse] implementation, since it would
+    /// be ambiguous with closure arguments like `|a, b|`.
+    pub(crate) fn parse_with_or(p: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let first = p.parse::<Self>()?;
+        Self::parse_or_rest(p, first)
+    }
+
+    /// Parse an item of an object pattern, where the pattern of a binding like
+    /// `a: 1 | 2` may be an or-pattern.
+    fn parse_object_item(p: &mut Parser<'_>) -> Result<Self, ParseError> {
+        Ok(match p.parse::<Self>()? {
+            Self::PatBinding(mut binding) => {
+                binding.pat = Box::new(Self::parse_or_rest(p, *binding.pat)?);
+                Self::PatBinding(binding)
+            }
+            pat => pat,
+        })
+    }
+
+    /// Parse the alternatives of an or-pattern following its first pattern.
+    fn parse_or_rest(p: &mut Parser<'_>, first: Self) -> Result<Self, ParseError> {
+        if !p.peek::<T![|]>()? {
+            return Ok(first);
+        }
+
+        let mut rest = Vec::new();
+
+        while p.peek::<T![|]>()? {
+            rest.push((p.parse()?, p.parse()?));
+        }
+
+        Ok(Self::PatOr(PatOr {
+            first: Box::new(first),
+            rest,
+        }))
+    }
+
+    /// Parse a literal which can be used as the bound of a range pattern.
+    fn parse_range_bound(p: &mut Parser<'_>) -> Result<ast::Expr, ParseError> {
+        Ok(match p.nth(0)? {
+            K![byte] => ast::Expr::from_lit(ast::Lit::Byte(p.parse()?)),
+            K![char] => ast::Expr::from_lit(ast::Lit::Char(p.parse()?)),
+            K![number] => ast::Expr::from_lit(ast::Lit::Number(p.parse()?)),
+            K![-] => ast::Expr::Unary(Box::new(ast::ExprUnary::parse_with_meta(
+                p,
+                Vec::new(),
+                ast::expr::EagerBrace(true),
+            )?)),
+            _ => return Err(ParseError::expected(&p.tok_at(0)?, "range pattern bound")),
+        })
+    }
+
+    /// Parse the remainder of a literal pattern, which might be the start of
+    /// a range pattern.
+    fn parse_lit_or_range(
+        p: &mut Parser<'_>,
+        attributes: Vec<ast::Attribute>,
+        expr: ast::Expr,
+    ) -> Result<Self, ParseError> {
+        if !matches!(p.nth(0)?, K![..] | K![..=]) {
+            return Ok(Self::PatLit(PatLit { attributes, expr }));
+        }
+
+        let limits = p.parse()?;
+
+        let end = match p.nth(0)? {
+            K![byte] | K![char] | K![number] | K![-] => Some(Self::parse_range_bound(p)?),
+            _ => None,
+        };
+
+        Ok(Self::PatRange(PatRange {
+            attributes,
+            start: expr,
+            limits,
+            end,
+        }))
+    }
+}
+
 impl Peek for Pat {
     fn peek(p: &mut Peeker<'_>) -> bool {
         match p.nth(0) {
@@ -188,6 +291,52 @@ pub struct PatLit {
     pub expr: ast::Expr,
 }
 
+/// An or-pattern, like `1 | 2 | 3`.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+pub struct PatOr {
+    /// The first alternative.
+    pub first: Box<ast::Pat>,
+    /// The remaining alternatives, each preceded by a `|`.
+    #[rune(iter)]
+    pub rest: Vec<(T![|], ast::Pat)>,
+}
+
+impl PatOr {
+    /// Iterate over all alternatives of the or-pattern.
+    pub fn alternatives(&self) -> impl Iterator<Item = &ast::Pat> {
+        std::iter::once(&*self.first).chain(self.rest.iter().map(|(_, pat)| pat))
+    }
+}
+
+/// A range pattern, like `0..10` or `'a'..='z'`.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+pub struct PatRange {
+    /// Attributes associated with the pattern.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The start of the range.
+    pub start: ast::Expr,
+    /// The limits of the range.
+    pub limits: ast::ExprRangeLimits,
+    /// The end of the range, if it's bounded.
+    #[rune(iter)]
+    pub end: Option<ast::Expr>,
+}
+
+/// A pattern bound to a name, like `whole @ Some(n)`.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+pub struct PatAt {
+    /// Attributes associated with the pattern.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The name the matched value is bound to.
+    pub ident: ast::Ident,
+    /// The `@` token.
+    pub at: T![@],
+    /// The pattern the value must match.
+    pub pat: Box<ast::Pat>,
+}
+
 /// The rest pattern `..` and associated attributes.
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 pub struct PatRest {
@@ -173,6 +173,8 @@ pub enum Number {
     Float(f64),
     /// An integer literal number.
     Integer(num::BigInt),
+    /// An integer literal number with an explicit size suffix, like `10u32`.
+    Sized(num::BigInt, NumberSize),
 }
 
 impl Number {
@@ -183,6 +185,7 @@ impl Number {
         match self {
             Self::Float(n) => Self::Float(-n),
             Self::Integer(n) => Self::Integer(n.neg()),
+            Self::Sized(n, size) => Self::Sized(n.neg(), size),
         }
     }
 
@@ -211,7 +214,7 @@ impl Number {
 
         let number = match self {
             Number::Float(_) => return Err(ParseError::new(spanned, ParseErrorKind::BadNumber)),
-            Number::Integer(n) => {
+            Number::Integer(n) | Number::Sized(n, _) => {
                 if neg {
                     to(&n.clone().neg())
                 } else {
@@ -278,6 +281,7 @@ impl fmt::Display for Number {
         match self {
             Self::Float(n) => write!(f, "{}", n),
             Self::Integer(n) => write!(f, "{}", n),
+            Self::Sized(n, size) => write!(f, "{}{}", n, size),
         }
     }
 }
@@ -306,6 +310,62 @@ impl fmt::Display for NumberBase {
     }
 }
 
+/// The size suffix of an integer literal, like the `u32` in `10u32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NumberSize {
+    /// The `u8` suffix.
+    U8,
+    /// The `u16` suffix.
+    U16,
+    /// The `u32` suffix.
+    U32,
+    /// The `u64` suffix.
+    U64,
+    /// The `i32` suffix.
+    I32,
+    /// The `i64` suffix.
+    I64,
+    /// The `i128` suffix.
+    I128,
+}
+
+impl NumberSize {
+    /// Split a known size suffix off the end of the given number literal.
+    pub fn split_suffix(string: &str) -> (&str, Option<Self>) {
+        const SUFFIXES: &[(&str, NumberSize)] = &[
+            ("u8", NumberSize::U8),
+            ("u16", NumberSize::U16),
+            ("u32", NumberSize::U32),
+            ("u64", NumberSize::U64),
+            ("i32", NumberSize::I32),
+            ("i64", NumberSize::I64),
+            ("i128", NumberSize::I128),
+        ];
+
+        for (suffix, size) in SUFFIXES {
+            if let Some(rest) = string.strip_suffix(suffix) {
+                return (rest, Some(*size));
+            }
+        }
+
+        (string, None)
+    }
+}
+
+impl fmt::Display for NumberSize {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::U8 => write!(fmt, "u8"),
+            Self::U16 => write!(fmt, "u16"),
+            Self::U32 => write!(fmt, "u32"),
+            Self::U64 => write!(fmt, "u64"),
+            Self::I32 => write!(fmt, "i32"),
+            Self::I64 => write!(fmt, "i64"),
+            Self::I128 => write!(fmt, "i128"),
+        }
+    }
+}
+
 /// A built-in identifiers that do not have a source.
 ///
 /// This is necessary to synthesize identifiers in the lexer since there's not
@@ -13,7 +13,7 @@ impl AssembleClosure for ast::Block {
         let guard = c.scopes.push_child(span)?;
 
         for capture in captures {
-            c.scopes.new_var(&capture.ident, span)?;
+            c.scopes.new_var(&capture.ident, span, c.asm)?;
         }
 
         self.assemble(c, Needs::Value)?.apply(c)?;
@@ -67,7 +67,7 @@ impl Assemble for ast::Block {
             false
         };
 
-        let scope = c.scopes.pop(scopes_count, span)?;
+        let scope = c.scopes.pop(scopes_count, span, c.asm)?;
 
         if needs.value() {
             if produced {
@@ -50,7 +50,7 @@ impl Assemble for BuiltInTemplate {
             c.asm.push(Inst::Pop, span);
         }
 
-        let _ = c.scopes.pop(expected, span)?;
+        let _ = c.scopes.pop(expected, span, c.asm)?;
         Ok(Asm::top(span))
     }
 }
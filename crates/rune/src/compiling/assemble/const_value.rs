@@ -85,16 +85,11 @@ impl AssembleConst for ConstValue {
                 c.asm.push(Inst::Tuple { count: tuple.len() }, span);
             }
             ConstValue::Object(object) => {
-                let mut entries = object.iter().collect::<Vec<_>>();
-                entries.sort_by_key(|k| k.0);
-
-                for (_, value) in entries.iter().copied() {
+                for value in object.values() {
                     value.assemble_const(c, Needs::Value, span)?;
                 }
 
-                let slot = c
-                    .unit
-                    .new_static_object_keys(span, entries.iter().map(|e| e.0))?;
+                let slot = c.unit.new_static_object_keys(span, object.keys())?;
 
                 c.asm.push(Inst::Object { slot }, span);
             }
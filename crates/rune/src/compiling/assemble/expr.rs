@@ -27,6 +27,7 @@ impl Assemble for ast::Expr {
             ast::Expr::Match(expr_match) => expr_match.assemble(c, needs)?,
             ast::Expr::Await(expr_await) => expr_await.assemble(c, needs)?,
             ast::Expr::Try(expr_try) => expr_try.assemble(c, needs)?,
+            ast::Expr::Cast(expr_cast) => expr_cast.assemble(c, needs)?,
             ast::Expr::Select(expr_select) => expr_select.assemble(c, needs)?,
             ast::Expr::Call(expr_call) => expr_call.assemble(c, needs)?,
             ast::Expr::FieldAccess(expr_field_access) => expr_field_access.assemble(c, needs)?,
@@ -52,6 +52,7 @@ impl Assemble for ast::ExprBinary {
             ast::BinOp::Div => InstOp::Div,
             ast::BinOp::Mul => InstOp::Mul,
             ast::BinOp::Rem => InstOp::Rem,
+            ast::BinOp::Pow => InstOp::Pow,
             ast::BinOp::BitAnd => InstOp::BitAnd,
             ast::BinOp::BitXor => InstOp::BitXor,
             ast::BinOp::BitOr => InstOp::BitOr,
@@ -74,7 +75,7 @@ impl Assemble for ast::ExprBinary {
             c.asm.push(Inst::Pop, span);
         }
 
-        c.scopes.pop(guard, span)?;
+        c.scopes.pop(guard, span, c.asm)?;
         Ok(Asm::top(span))
     }
 }
@@ -63,7 +63,7 @@ impl Assemble for ast::ExprCall {
                 c.asm.push(Inst::Pop, span);
             }
 
-            c.scopes.pop(guard, span)?;
+            c.scopes.pop(guard, span, c.asm)?;
             return Ok(Asm::top(span));
         };
 
@@ -88,7 +88,7 @@ impl Assemble for ast::ExprCall {
                     c.asm.push(Inst::Pop, span);
                 }
 
-                c.scopes.pop(guard, span)?;
+                c.scopes.pop(guard, span, c.asm)?;
                 return Ok(Asm::top(span));
             }
         }
@@ -136,7 +136,7 @@ impl Assemble for ast::ExprCall {
                     c.call_const_fn(self, &meta, &from, &*const_fn, self.args.as_slice())?;
 
                 value.assemble_const(c, Needs::Value, self.span())?;
-                c.scopes.pop(guard, span)?;
+                c.scopes.pop(guard, span, c.asm)?;
                 return Ok(Asm::top(span));
             }
             _ => {
@@ -163,7 +163,7 @@ impl Assemble for ast::ExprCall {
             c.asm.push(Inst::Pop, span);
         }
 
-        c.scopes.pop(guard, span)?;
+        c.scopes.pop(guard, span, c.asm)?;
         Ok(Asm::top(span))
     }
 }
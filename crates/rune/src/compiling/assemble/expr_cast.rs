@@ -0,0 +1,95 @@
+use crate::compiling::assemble::prelude::*;
+use crate::ir;
+use crate::ir::{IrBudget, IrCompiler, IrInterpreter};
+use crate::query::Used;
+use runestick::InstCast;
+
+/// Compile a cast expression.
+impl Assemble for ast::ExprCast {
+    fn assemble(&self, c: &mut Compiler<'_>, needs: Needs) -> CompileResult<Asm> {
+        let span = self.span();
+        log::trace!("ExprCast => {:?}", c.source.source(span));
+
+        let named = c.convert_path_to_named(&self.ty)?;
+        let meta = c.lookup_meta(self.ty.span(), &named.item)?;
+
+        let ty = match meta.type_hash_of().and_then(InstCast::from_type_hash) {
+            Some(ty) => ty,
+            None => {
+                return Err(CompileError::new(
+                    &self.ty,
+                    CompileErrorKind::UnsupportedCastType { meta },
+                ));
+            }
+        };
+
+        // NB: casts of literals are folded into constants, as long as the
+        // target type can be represented as a constant.
+        let foldable = matches!(
+            ty,
+            InstCast::Integer | InstCast::Float | InstCast::Byte | InstCast::Char
+        );
+
+        if foldable && self.expr.is_lit() && !is_sized_lit(c, &self.expr)? {
+            let mut ir_query = c.query.as_ir_query();
+
+            let mut compiler = IrCompiler {
+                storage: c.storage.clone(),
+                source: c.source.clone(),
+                query: &mut *ir_query,
+            };
+
+            let ir = ir::Ir::new(
+                span,
+                ir::IrCast {
+                    span,
+                    ty,
+                    target: Box::new(compiler.compile(&self.expr)?),
+                },
+            );
+
+            let mut interpreter = IrInterpreter {
+                budget: IrBudget::new(1_000_000),
+                scopes: Default::default(),
+                module: Default::default(),
+                item: Item::new(),
+                consts: c.consts.clone(),
+                query: &mut *ir_query,
+            };
+
+            let value = interpreter.eval_value(&ir, Used::Used)?;
+            let value = value.into_const(span)?;
+            value.assemble_const(c, needs, span)?;
+            return Ok(Asm::top(span));
+        }
+
+        self.expr.assemble(c, Needs::Value)?.apply(c)?;
+        c.asm.push(Inst::Cast { ty }, span);
+
+        // NB: we put it here to preserve the call in case it has side effects.
+        // But if we don't need the value, then pop it from the stack.
+        if !needs.value() {
+            c.asm.push(Inst::Pop, span);
+        }
+
+        Ok(Asm::top(span))
+    }
+}
+
+/// Test if the given literal expression is a number with an explicit size,
+/// like `10u32`, which can't be folded into a constant.
+fn is_sized_lit(c: &Compiler<'_>, expr: &ast::Expr) -> CompileResult<bool> {
+    let expr_lit = match expr {
+        ast::Expr::Lit(expr_lit) => expr_lit,
+        ast::Expr::Unary(expr_unary) => match &expr_unary.expr {
+            ast::Expr::Lit(expr_lit) => expr_lit,
+            _ => return Ok(false),
+        },
+        _ => return Ok(false),
+    };
+
+    Ok(match &expr_lit.lit {
+        ast::Lit::Number(n) => matches!(n.resolve(c.storage, &c.source)?, ast::Number::Sized(..)),
+        _ => false,
+    })
+}
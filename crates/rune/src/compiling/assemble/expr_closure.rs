@@ -29,7 +29,7 @@ impl AssembleClosure for ast::ExprClosure {
                 c.asm.push(Inst::PushTuple, span);
 
                 for capture in captures {
-                    c.scopes.new_var(&capture.ident, span)?;
+                    c.scopes.new_var(&capture.ident, span, c.asm)?;
                 }
             }
 
@@ -48,7 +48,7 @@ impl AssembleClosure for ast::ExprClosure {
 
         c.asm.push(Inst::Return, span);
 
-        c.scopes.pop_last(span)?;
+        c.scopes.pop_last(span, c.asm)?;
         Ok(())
     }
 }
@@ -19,7 +19,7 @@ impl Assemble for ast::ExprIndex {
             c.asm.push(Inst::Pop, span);
         }
 
-        c.scopes.pop(guard, span)?;
+        c.scopes.pop(guard, span, c.asm)?;
         Ok(Asm::top(span))
     }
 }
@@ -15,6 +15,13 @@ impl Assemble for ast::ExprLet {
         let false_label = c.asm.new_label("let_panic");
 
         if c.compile_pat(&self.pat, false_label, &load)? {
+            if c.options.strict_let {
+                return Err(CompileError::new(
+                    &self.pat,
+                    CompileErrorKind::RefutableLetPattern,
+                ));
+            }
+
             c.warnings
                 .let_pattern_might_panic(c.source_id, span, c.context());
 
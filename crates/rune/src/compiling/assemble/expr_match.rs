@@ -41,7 +41,7 @@ impl Assemble for ast::ExprMatch {
 
                 condition.assemble(c, Needs::Value)?.apply(c)?;
                 c.clean_last_scope(span, guard, Needs::Value)?;
-                let scope = c.scopes.pop(parent_guard, span)?;
+                let scope = c.scopes.pop(parent_guard, span, c.asm)?;
 
                 c.asm
                     .pop_and_jump_if_not(scope.local_var_count, match_false, span);
@@ -49,7 +49,7 @@ impl Assemble for ast::ExprMatch {
                 c.asm.jump(branch_label, span);
                 scope
             } else {
-                c.scopes.pop(parent_guard, span)?
+                c.scopes.pop(parent_guard, span, c.asm)?
             };
 
             c.asm.jump(branch_label, span);
@@ -68,7 +68,7 @@ impl Assemble for ast::ExprRange {
             }
         }
 
-        c.scopes.pop(guard, span)?;
+        c.scopes.pop(guard, span, c.asm)?;
         Ok(Asm::top(span))
     }
 }
@@ -68,7 +68,7 @@ impl Assemble for ast::ExprSelect {
                         let named = c.convert_path_to_named(&path.path)?;
 
                         if let Some(local) = named.as_local() {
-                            c.scopes.decl_var(local, path.span())?;
+                            c.scopes.decl_var(local, path.span(), c.asm)?;
                             break;
                         }
                     }
@@ -18,7 +18,7 @@ macro_rules! tuple {
             $span,
         );
 
-        $c.scopes.pop(guard, $span)?;
+        $c.scopes.pop(guard, $span, $c.asm)?;
     }};
 }
 
@@ -18,7 +18,7 @@ impl AssembleFn for ast::ItemFn {
                     }
 
                     let span = s.span();
-                    c.scopes.new_var("self", span)?;
+                    c.scopes.new_var("self", span, c.asm)?;
                 }
                 ast::FnArg::Pat(pat) => {
                     let offset = c.scopes.decl_anon(pat.span())?;
@@ -54,7 +54,7 @@ impl AssembleFn for ast::ItemFn {
             c.asm.push(Inst::ReturnUnit, span);
         }
 
-        c.scopes.pop_last(span)?;
+        c.scopes.pop_last(span, c.asm)?;
         Ok(())
     }
 }
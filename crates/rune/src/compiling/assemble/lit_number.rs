@@ -1,10 +1,9 @@
 use crate::compiling::assemble::prelude::*;
+use runestick::InstValue;
 
 /// Compile a literal number.
 impl Assemble for ast::LitNumber {
     fn assemble(&self, c: &mut Compiler<'_>, needs: Needs) -> CompileResult<Asm> {
-        use num::ToPrimitive as _;
-
         let span = self.span();
         log::trace!("LitNumber => {:?}", c.source.source(span));
 
@@ -15,26 +14,36 @@ impl Assemble for ast::LitNumber {
         }
 
         let number = self.resolve(&c.storage, &*c.source)?;
+        let value = number_value(span, number)?;
+        c.asm.push(Inst::Push { value }, span);
+        Ok(Asm::top(span))
+    }
+}
 
-        match number {
-            ast::Number::Float(number) => {
-                c.asm.push(Inst::float(number), span);
-            }
-            ast::Number::Integer(number) => {
-                let n = match number.to_i64() {
-                    Some(n) => n,
-                    None => {
-                        return Err(CompileError::new(
-                            span,
-                            ParseErrorKind::BadNumberOutOfBounds,
-                        ));
-                    }
-                };
+/// Convert a resolved number into the value to push for it, making sure that
+/// it fits in the type it's declared as.
+pub(crate) fn number_value(span: Span, number: ast::Number) -> CompileResult<InstValue> {
+    use num::ToPrimitive as _;
 
-                c.asm.push(Inst::integer(n), span);
-            }
-        }
+    let value = match number {
+        ast::Number::Float(number) => Some(InstValue::Float(number)),
+        ast::Number::Integer(number) => number.to_i64().map(InstValue::Integer),
+        ast::Number::Sized(number, size) => match size {
+            ast::NumberSize::U8 => number.to_u8().map(InstValue::Byte),
+            ast::NumberSize::U16 => number.to_u16().map(InstValue::U16),
+            ast::NumberSize::U32 => number.to_u32().map(InstValue::U32),
+            ast::NumberSize::U64 => number.to_u64().map(InstValue::U64),
+            ast::NumberSize::I32 => number.to_i32().map(InstValue::I32),
+            ast::NumberSize::I64 => number.to_i64().map(InstValue::Integer),
+            ast::NumberSize::I128 => number.to_i128().map(InstValue::I128),
+        },
+    };
 
-        Ok(Asm::top(span))
+    match value {
+        Some(value) => Ok(value),
+        None => Err(CompileError::new(
+            span,
+            ParseErrorKind::BadNumberOutOfBounds,
+        )),
     }
 }
@@ -8,23 +8,60 @@ impl Assemble for ast::Local {
 
         let load = |c: &mut Compiler, needs: Needs| Ok(self.expr.assemble(c, needs)?);
 
-        let false_label = c.asm.new_label("let_panic");
-
-        if c.compile_pat(&self.pat, false_label, &load)? {
-            c.warnings
-                .let_pattern_might_panic(c.source_id, span, c.context());
-
-            let ok_label = c.asm.new_label("let_ok");
-            c.asm.jump(ok_label, span);
-            c.asm.label(false_label)?;
-            c.asm.push(
-                Inst::Panic {
-                    reason: runestick::PanicReason::UnmatchedPattern,
-                },
-                span,
-            );
-
-            c.asm.label(ok_label)?;
+        if let Some((_, block)) = &self.else_branch {
+            if !block_diverges(block) {
+                return Err(CompileError::new(
+                    block,
+                    CompileErrorKind::LetElseMustDiverge,
+                ));
+            }
+
+            // NB: the pattern is compiled in a child scope, since a failed
+            // match pops all variables in the current scope. On success its
+            // variables are merged back into the current scope.
+            let else_label = c.asm.new_label("let_else");
+            let guard = c.scopes.push_child(span)?;
+
+            if c.compile_pat(&self.pat, else_label, &load)? {
+                let scope = c.scopes.pop(guard, span, c.asm)?;
+
+                let ok_label = c.asm.new_label("let_ok");
+                c.asm.jump(ok_label, span);
+                c.asm.label(else_label)?;
+                block.assemble(c, Needs::None)?.apply(c)?;
+                c.asm.label(ok_label)?;
+
+                let guard = c.scopes.push(scope);
+                c.scopes.pop_merge(guard, span)?;
+            } else {
+                c.scopes.pop_merge(guard, span)?;
+            }
+        } else {
+            let false_label = c.asm.new_label("let_panic");
+
+            if c.compile_pat(&self.pat, false_label, &load)? {
+                if c.options.strict_let {
+                    return Err(CompileError::new(
+                        &self.pat,
+                        CompileErrorKind::RefutableLetPattern,
+                    ));
+                }
+
+                c.warnings
+                    .let_pattern_might_panic(c.source_id, span, c.context());
+
+                let ok_label = c.asm.new_label("let_ok");
+                c.asm.jump(ok_label, span);
+                c.asm.label(false_label)?;
+                c.asm.push(
+                    Inst::Panic {
+                        reason: runestick::PanicReason::UnmatchedPattern,
+                    },
+                    span,
+                );
+
+                c.asm.label(ok_label)?;
+            }
         }
 
         // If a value is needed for a let expression, it is evaluated as a unit.
@@ -35,3 +72,15 @@ impl Assemble for ast::Local {
         Ok(Asm::top(span))
     }
 }
+
+/// Test if the given block unconditionally diverges, in that its last
+/// statement is a `return`, `break`, or `continue`.
+fn block_diverges(block: &ast::Block) -> bool {
+    match block.statements.last() {
+        Some(ast::Stmt::Expr(expr, _)) => matches!(
+            expr,
+            ast::Expr::Return(..) | ast::Expr::Break(..) | ast::Expr::Continue(..)
+        ),
+        _ => false,
+    }
+}
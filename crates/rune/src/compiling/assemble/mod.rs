@@ -9,6 +9,7 @@ mod expr_binary;
 mod expr_block;
 mod expr_break;
 mod expr_call;
+mod expr_cast;
 mod expr_closure;
 mod expr_continue;
 mod expr_field_access;
@@ -125,14 +126,15 @@ impl Asm {
     pub(crate) fn decl_var(&self, c: &mut Compiler, ident: &str) -> CompileResult<()> {
         match self.kind {
             AsmKind::Top => {
-                c.scopes.decl_var(ident, self.span)?;
+                c.scopes.decl_var(ident, self.span, c.asm)?;
             }
             AsmKind::Var(var, ..) => {
                 c.scopes
-                    .decl_var_with_offset(ident, var.offset, self.span)?;
+                    .decl_var_with_offset(ident, var.offset, self.span, c.asm)?;
             }
             AsmKind::Offset(offset) => {
-                c.scopes.decl_var_with_offset(ident, offset, self.span)?;
+                c.scopes
+                    .decl_var_with_offset(ident, offset, self.span, c.asm)?;
             }
         }
 
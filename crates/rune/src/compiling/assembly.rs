@@ -2,7 +2,7 @@
 
 use crate::collections::HashMap;
 use crate::compiling::{CompileError, CompileErrorKind};
-use runestick::{Hash, Inst, Label, Location, Span};
+use runestick::{DebugVariable, Hash, Inst, Label, Location, Span};
 
 #[derive(Debug, Clone)]
 pub enum AssemblyInst {
@@ -33,6 +33,9 @@ pub struct Assembly {
     pub(crate) label_count: usize,
     /// The collection of functions required by this assembly.
     pub(crate) required_functions: HashMap<Hash, Vec<(Span, usize)>>,
+    /// Variables declared in the assembly, with the instruction offset they
+    /// were declared at.
+    pub(crate) variables: Vec<DebugVariable>,
 }
 
 impl Assembly {
@@ -46,6 +49,7 @@ impl Assembly {
             comments: Default::default(),
             label_count,
             required_functions: Default::default(),
+            variables: Default::default(),
         }
     }
 
@@ -114,6 +118,34 @@ impl Assembly {
             .push((AssemblyInst::IterNext { offset, label }, span));
     }
 
+    /// Record that a variable is declared at the current instruction offset,
+    /// returning the index of its debug information.
+    pub(crate) fn declare_var(&mut self, name: &str, offset: usize, span: Span) -> usize {
+        let index = self.variables.len();
+
+        self.variables.push(DebugVariable {
+            ip: self.instructions.len(),
+            end: None,
+            name: name.to_owned(),
+            offset,
+            span,
+        });
+
+        index
+    }
+
+    /// Record that the variables with the given debug information indexes go
+    /// out of scope at the current instruction offset.
+    pub(crate) fn end_vars(&mut self, indexes: &[usize]) {
+        let end = self.instructions.len();
+
+        for index in indexes {
+            if let Some(variable) = self.variables.get_mut(*index) {
+                variable.end = Some(end);
+            }
+        }
+    }
+
     /// Push a raw instruction.
     pub(crate) fn push(&mut self, raw: Inst, span: Span) {
         if let Inst::Call { hash, .. } = raw {
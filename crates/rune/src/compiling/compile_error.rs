@@ -170,6 +170,14 @@ pub enum CompileErrorKind {
     UnsupportedPatternExpr,
     #[error("not a valid binding")]
     UnsupportedBinding,
+    #[error("variables can't be bound in or-patterns")]
+    UnsupportedOrPatternBinding,
+    #[error("range pattern bounds must be literals of the same type")]
+    UnsupportedRangePattern,
+    #[error("pattern in `let` might not match, add an `else` branch to handle it")]
+    RefutableLetPattern,
+    #[error("the `else` branch of a `let` must end with `return`, `break`, or `continue`")]
+    LetElseMustDiverge,
     #[error("floating point numbers cannot be used in patterns")]
     MatchFloatInPattern,
     #[error("duplicate key in literal object")]
@@ -180,6 +188,14 @@ pub enum CompileErrorKind {
     AwaitOutsideFunction,
     #[error("instance function declared outside of `impl` block")]
     InstanceFunctionOutsideImpl,
+    #[error("cannot cast to {meta}, expected one of `int`, `float`, `byte` or `char`")]
+    UnsupportedCastType { meta: CompileMeta },
+    #[error("function `{name}` is already declared in trait")]
+    DuplicateTraitFunction { name: Box<str>, existing: Span },
+    #[error("missing function `{name}` required by trait `{trait_item}`")]
+    MissingTraitFunction { trait_item: Item, name: Box<str> },
+    #[error("function `{name}` is not a member of trait `{trait_item}`")]
+    UnknownTraitFunction { trait_item: Item, name: Box<str> },
     #[error("import `{item}` (imported in prelude) does not exist")]
     MissingPreludeModule { item: Item },
     #[error("unsupported tuple index `{number}`")]
@@ -12,8 +12,8 @@ use crate::{
     CompileError, CompileErrorKind, Options, Resolve as _, Spanned, Storage, UnitBuilder, Warnings,
 };
 use runestick::{
-    CompileItem, CompileMeta, CompileMetaKind, ConstValue, Context, Inst, InstValue, Item, Label,
-    Source, Span, TypeCheck,
+    CompileItem, CompileMeta, CompileMetaKind, ConstValue, Context, Inst, InstRangeLimits,
+    InstValue, Item, Label, Source, Span, TypeCheck,
 };
 use std::sync::Arc;
 
@@ -235,7 +235,7 @@ impl<'a> Compiler<'a> {
     pub(crate) fn convert_path_to_named(&mut self, path: &ast::Path) -> CompileResult<Named> {
         let named = self
             .query
-            .convert_path(self.context, &self.storage, &*self.source, path)?;
+            .convert_path(&self.storage, &*self.source, path)?;
 
         Ok(named)
     }
@@ -274,7 +274,7 @@ impl<'a> Compiler<'a> {
                     self.asm.jump(then_label, span);
                 };
 
-                let scope = self.scopes.pop(expected, span)?;
+                let scope = self.scopes.pop(expected, span, self.asm)?;
                 Ok(scope)
             }
         }
@@ -599,7 +599,7 @@ impl<'a> Compiler<'a> {
                 }
                 Binding::Ident(_, key) => {
                     self.asm.push(Inst::ObjectIndexGetAt { offset, slot }, span);
-                    self.scopes.decl_var(key, span)?;
+                    self.scopes.decl_var(key, span, self.asm)?;
                 }
             }
         }
@@ -763,6 +763,12 @@ impl<'a> Compiler<'a> {
                 self.compile_pat_object(object, false_label, &load)?;
                 Ok(true)
             }
+            ast::Pat::PatOr(pat_or) => Ok(self.compile_pat_or(pat_or, false_label, load)?),
+            ast::Pat::PatRange(pat_range) => {
+                self.compile_pat_range(pat_range, false_label, load)?;
+                Ok(true)
+            }
+            ast::Pat::PatAt(pat_at) => Ok(self.compile_pat_at(pat_at, false_label, load)?),
             pat => Err(CompileError::new(
                 pat,
                 CompileErrorKind::UnsupportedPatternExpr,
@@ -850,6 +856,192 @@ impl<'a> Compiler<'a> {
         Ok(true)
     }
 
+    /// Compile a pattern bound to a name, like `whole @ Some(n)`.
+    ///
+    /// The value is bound to the name before the inner pattern is matched
+    /// against it.
+    pub(crate) fn compile_pat_at(
+        &mut self,
+        pat_at: &ast::PatAt,
+        false_label: Label,
+        load: &dyn Fn(&mut Self, Needs) -> CompileResult<Asm>,
+    ) -> CompileResult<bool> {
+        let span = pat_at.span();
+        log::trace!("PatAt => {:?}", self.source.source(span));
+
+        let ident = pat_at
+            .ident
+            .resolve(self.storage, &self.source)?
+            .into_owned();
+
+        load(self, Needs::Value)?.apply(self)?;
+        let offset = self
+            .scopes
+            .decl_var(&ident, pat_at.ident.span(), self.asm)?;
+
+        let load = move |c: &mut Self, needs: Needs| {
+            if needs.value() {
+                c.asm.push(Inst::Copy { offset }, span);
+            }
+
+            Ok(Asm::top(span))
+        };
+
+        self.compile_pat(&pat_at.pat, false_label, &load)
+    }
+
+    /// Compile an or-pattern.
+    ///
+    /// Each alternative is tested in its own scope, which is cleaned up before
+    /// jumping to the end of the pattern so that all alternatives leave the
+    /// stack in the same state. If the last alternative doesn't match, the
+    /// locals of the enclosing scope are popped before jumping to
+    /// `false_label`, since the or-pattern might be nested inside of another
+    /// pattern.
+    pub(crate) fn compile_pat_or(
+        &mut self,
+        pat_or: &ast::PatOr,
+        false_label: Label,
+        load: &dyn Fn(&mut Self, Needs) -> CompileResult<Asm>,
+    ) -> CompileResult<bool> {
+        let span = pat_or.span();
+        let match_label = self.asm.new_label("pat_or_match");
+        let mut used = false;
+
+        for pat in pat_or.alternatives() {
+            let next_label = self.asm.new_label("pat_or_next");
+
+            let guard = self.scopes.push_child(span)?;
+            used = self.compile_pat(pat, next_label, load)?;
+            let scope = self.scopes.pop(guard, span, self.asm)?;
+
+            if scope.has_named_locals() {
+                return Err(CompileError::new(
+                    pat,
+                    CompileErrorKind::UnsupportedOrPatternBinding,
+                ));
+            }
+
+            self.locals_pop(scope.local_var_count, span);
+            self.asm.jump(match_label, span);
+            self.asm.label(next_label)?;
+        }
+
+        let count = self.scopes.local_var_count(span)?;
+        self.locals_pop(count, span);
+        self.asm.jump(false_label, span);
+
+        self.asm.label(match_label)?;
+        Ok(used)
+    }
+
+    /// Compile a range pattern.
+    pub(crate) fn compile_pat_range(
+        &mut self,
+        pat_range: &ast::PatRange,
+        false_label: Label,
+        load: &dyn Fn(&mut Self, Needs) -> CompileResult<Asm>,
+    ) -> CompileResult<()> {
+        let span = pat_range.span();
+
+        let limits = match &pat_range.limits {
+            ast::ExprRangeLimits::HalfOpen(..) => InstRangeLimits::HalfOpen,
+            ast::ExprRangeLimits::Closed(..) => InstRangeLimits::Closed,
+        };
+
+        let start = self.compile_range_bound(&pat_range.start)?;
+        let end = match &pat_range.end {
+            Some(end) => Some(self.compile_range_bound(end)?),
+            None => None,
+        };
+
+        let inst = match (start, end) {
+            (RangeBound::Byte(start), None) => Inst::MatchByteRange {
+                start,
+                end: None,
+                limits,
+            },
+            (RangeBound::Byte(start), Some(RangeBound::Byte(end))) => Inst::MatchByteRange {
+                start,
+                end: Some(end),
+                limits,
+            },
+            (RangeBound::Char(start), None) => Inst::MatchCharacterRange {
+                start,
+                end: None,
+                limits,
+            },
+            (RangeBound::Char(start), Some(RangeBound::Char(end))) => Inst::MatchCharacterRange {
+                start,
+                end: Some(end),
+                limits,
+            },
+            (RangeBound::Integer(start), None) => Inst::MatchIntegerRange {
+                start,
+                end: None,
+                limits,
+            },
+            (RangeBound::Integer(start), Some(RangeBound::Integer(end))) => {
+                Inst::MatchIntegerRange {
+                    start,
+                    end: Some(end),
+                    limits,
+                }
+            }
+            _ => {
+                return Err(CompileError::new(
+                    span,
+                    CompileErrorKind::UnsupportedRangePattern,
+                ));
+            }
+        };
+
+        load(self, Needs::Value)?.apply(self)?;
+        self.asm.push(inst, span);
+        self.asm
+            .pop_and_jump_if_not(self.scopes.local_var_count(span)?, false_label, span);
+        Ok(())
+    }
+
+    /// Resolve the bound of a range pattern.
+    fn compile_range_bound(&mut self, expr: &ast::Expr) -> CompileResult<RangeBound> {
+        match expr {
+            ast::Expr::Unary(expr_unary) => {
+                if let ast::Expr::Lit(expr_lit) = &expr_unary.expr {
+                    if let ast::Lit::Number(lit_number) = &expr_lit.lit {
+                        let integer = lit_number
+                            .resolve(self.storage, &self.source)?
+                            .as_i64(expr.span(), true)?;
+                        return Ok(RangeBound::Integer(integer));
+                    }
+                }
+            }
+            ast::Expr::Lit(expr_lit) => match &expr_lit.lit {
+                ast::Lit::Byte(lit_byte) => {
+                    let byte = lit_byte.resolve(self.storage, &self.source)?;
+                    return Ok(RangeBound::Byte(byte));
+                }
+                ast::Lit::Char(lit_char) => {
+                    let character = lit_char.resolve(self.storage, &self.source)?;
+                    return Ok(RangeBound::Char(character));
+                }
+                ast::Lit::Number(lit_number) => {
+                    let integer = lit_number
+                        .resolve(self.storage, &self.source)?
+                        .as_i64(expr.span(), false)?;
+                    return Ok(RangeBound::Integer(integer));
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+
+        Err(CompileError::new(
+            expr,
+            CompileErrorKind::UnsupportedRangePattern,
+        ))
+    }
+
     /// Clean the last scope.
     pub(crate) fn clean_last_scope(
         &mut self,
@@ -857,7 +1049,7 @@ impl<'a> Compiler<'a> {
         expected: ScopeGuard,
         needs: Needs,
     ) -> CompileResult<()> {
-        let scope = self.scopes.pop(expected, span)?;
+        let scope = self.scopes.pop(expected, span, self.asm)?;
 
         if needs.value() {
             self.locals_clean(scope.local_var_count, span);
@@ -932,6 +1124,14 @@ impl<'a> Compiler<'a> {
     }
 }
 
+/// The resolved bound of a range pattern.
+#[derive(Debug, Clone, Copy)]
+enum RangeBound {
+    Byte(u8),
+    Char(char),
+    Integer(i64),
+}
+
 /// Test if the given pattern is open or not.
 fn pat_items_count<'a, I: 'a, U: 'a>(items: I) -> Result<(bool, usize), CompileError>
 where
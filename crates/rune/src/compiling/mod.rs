@@ -1,10 +1,11 @@
 use crate::ast;
 use crate::load::{FileSourceLoader, SourceLoader, Sources};
-use crate::query::{Build, BuildEntry, Query};
+use crate::query::{Build, BuildEntry, InstanceFunction, Query, Used};
 use crate::shared::{Consts, Gen};
 use crate::worker::{LoadFileKind, Task, Worker};
 use crate::{Error, Errors, Options, Spanned as _, Storage, Warnings};
-use runestick::{Context, Source, Span};
+use runestick::{CompileMetaKind, Context, Source, Span};
+use std::sync::Arc;
 
 mod assemble;
 mod assembly;
@@ -243,6 +244,91 @@ impl CompileBuildEntry<'_> {
                     )?;
                 }
             }
+            Build::TraitImpl(t) => {
+                let span = location.span;
+                let named =
+                    self.query
+                        .convert_path(self.storage, &source, &t.trait_path)?;
+
+                let meta = compiler.lookup_meta(t.trait_path.span(), &named.item)?;
+
+                if !matches!(meta.kind, CompileMetaKind::Trait { .. }) {
+                    return Err(CompileError::expected_meta(
+                        t.trait_path.span(),
+                        meta,
+                        "trait",
+                    ));
+                }
+
+                let trait_item = &meta.item.item;
+
+                let tr = match compiler.query.get_trait(trait_item) {
+                    Some(tr) => tr,
+                    None => {
+                        return Err(CompileError::new(
+                            span,
+                            CompileErrorKind::MissingItem {
+                                item: trait_item.clone(),
+                            },
+                        ))
+                    }
+                };
+
+                compiler
+                    .query
+                    .insert_trait_impl(span, trait_item, &t.impl_item)?;
+
+                for (name, span) in &t.functions {
+                    let known = tr.required.iter().any(|(n, _)| n == name)
+                        || tr.provided.iter().any(|f| &f.name == name);
+
+                    if !known {
+                        return Err(CompileError::new(
+                            *span,
+                            CompileErrorKind::UnknownTraitFunction {
+                                trait_item: trait_item.clone(),
+                                name: name.clone(),
+                            },
+                        ));
+                    }
+                }
+
+                for (name, _) in &tr.required {
+                    if !t.functions.iter().any(|(n, _)| n == name) {
+                        return Err(CompileError::new(
+                            span,
+                            CompileErrorKind::MissingTraitFunction {
+                                trait_item: trait_item.clone(),
+                                name: name.clone(),
+                            },
+                        ));
+                    }
+                }
+
+                // NB: default functions which aren't overriden are built as
+                // instance functions of the implementing type.
+                for f in &tr.provided {
+                    if t.functions.iter().any(|(n, _)| *n == f.name) {
+                        continue;
+                    }
+
+                    let mut item = (*f.item).clone();
+                    item.item = t.impl_item.extended(&*f.name);
+
+                    compiler.query.push_build_entry(BuildEntry {
+                        location: f.item.location,
+                        item: Arc::new(item),
+                        build: Build::InstanceFunction(InstanceFunction {
+                            ast: f.function.ast.clone(),
+                            impl_item: t.impl_item.clone(),
+                            instance_span: span,
+                            call: f.function.call,
+                        }),
+                        source: f.source.clone(),
+                        used: Used::Used,
+                    });
+                }
+            }
             Build::Closure(c) => {
                 let args = format_fn_args(&*source, c.ast.args.as_slice().iter().map(|(a, _)| a))?;
 
@@ -69,6 +69,9 @@ pub(crate) struct Scope {
     pub(crate) total_var_count: usize,
     /// The number of variables local to this scope.
     pub(crate) local_var_count: usize,
+    /// Indexes of the debug information for the variables declared in this
+    /// scope, which are marked as going out of scope when it's popped.
+    debug_vars: Vec<usize>,
 }
 
 impl Scope {
@@ -79,9 +82,25 @@ impl Scope {
             anon: Vec::new(),
             total_var_count: 0,
             local_var_count: 0,
+            debug_vars: Vec::new(),
         }
     }
 
+    /// Test if the scope declares any named variables.
+    pub(crate) fn has_named_locals(&self) -> bool {
+        !self.locals.is_empty()
+    }
+
+    /// Merge the variables of a child scope into this scope, as if they had
+    /// been declared in it directly.
+    fn merge(&mut self, child: Scope) {
+        self.locals.extend(child.locals);
+        self.anon.extend(child.anon);
+        self.total_var_count = child.total_var_count;
+        self.local_var_count += child.local_var_count;
+        self.debug_vars.extend(child.debug_vars);
+    }
+
     /// Construct a new child scope.
     fn child(&self) -> Self {
         Self {
@@ -89,6 +108,7 @@ impl Scope {
             anon: Vec::new(),
             total_var_count: self.total_var_count,
             local_var_count: 0,
+            debug_vars: Vec::new(),
         }
     }
 
@@ -320,25 +340,49 @@ impl Scopes {
     }
 
     /// Construct a new variable.
-    pub(crate) fn new_var(&mut self, name: &str, span: Span) -> CompileResult<usize> {
-        self.last_mut(span)?.new_var(name, span)
+    ///
+    /// The declaration is recorded in the assembly for debugging purposes.
+    pub(crate) fn new_var(
+        &mut self,
+        name: &str,
+        span: Span,
+        asm: &mut Assembly,
+    ) -> CompileResult<usize> {
+        let scope = self.last_mut(span)?;
+        let offset = scope.new_var(name, span)?;
+        scope.debug_vars.push(asm.declare_var(name, offset, span));
+        Ok(offset)
     }
 
     /// Declare the given variable.
-    pub(crate) fn decl_var(&mut self, name: &str, span: Span) -> CompileResult<usize> {
-        Ok(self.last_mut(span)?.decl_var(name, span))
+    ///
+    /// The declaration is recorded in the assembly for debugging purposes.
+    pub(crate) fn decl_var(
+        &mut self,
+        name: &str,
+        span: Span,
+        asm: &mut Assembly,
+    ) -> CompileResult<usize> {
+        let scope = self.last_mut(span)?;
+        let offset = scope.decl_var(name, span);
+        scope.debug_vars.push(asm.declare_var(name, offset, span));
+        Ok(offset)
     }
 
     /// Declare the given variable with a custom offset.
+    ///
+    /// The declaration is recorded in the assembly for debugging purposes.
     pub(crate) fn decl_var_with_offset(
         &mut self,
         name: &str,
         offset: usize,
         span: Span,
+        asm: &mut Assembly,
     ) -> CompileResult<()> {
-        Ok(self
-            .last_mut(span)?
-            .decl_var_with_offset(name, offset, span))
+        let scope = self.last_mut(span)?;
+        scope.decl_var_with_offset(name, offset, span);
+        scope.debug_vars.push(asm.declare_var(name, offset, span));
+        Ok(())
     }
 
     /// Declare an anonymous variable.
@@ -358,7 +402,23 @@ impl Scopes {
     }
 
     /// Pop the last scope and compare with the expected length.
-    pub(crate) fn pop(&mut self, expected: ScopeGuard, span: Span) -> CompileResult<Scope> {
+    ///
+    /// The variables declared in the scope are recorded as going out of scope
+    /// at the current instruction in the assembly.
+    pub(crate) fn pop(
+        &mut self,
+        expected: ScopeGuard,
+        span: Span,
+        asm: &mut Assembly,
+    ) -> CompileResult<Scope> {
+        let scope = self.pop_checked(expected, span)?;
+        asm.end_vars(&scope.debug_vars);
+        Ok(scope)
+    }
+
+    /// Pop the last scope and compare with the expected length, without
+    /// ending its variables.
+    fn pop_checked(&mut self, expected: ScopeGuard, span: Span) -> CompileResult<Scope> {
         let ScopeGuard(expected) = expected;
 
         if self.scopes.len() != expected {
@@ -371,9 +431,17 @@ impl Scopes {
         self.pop_unchecked(span)
     }
 
+    /// Pop the last scope and merge its variables into its parent, so that
+    /// they stay in use.
+    pub(crate) fn pop_merge(&mut self, expected: ScopeGuard, span: Span) -> CompileResult<()> {
+        let scope = self.pop_checked(expected, span)?;
+        self.last_mut(span)?.merge(scope);
+        Ok(())
+    }
+
     /// Pop the last of the scope.
-    pub(crate) fn pop_last(&mut self, span: Span) -> CompileResult<Scope> {
-        self.pop(ScopeGuard(1), span)
+    pub(crate) fn pop_last(&mut self, span: Span, asm: &mut Assembly) -> CompileResult<Scope> {
+        self.pop(ScopeGuard(1), span, asm)
     }
 
     /// Pop the last scope and compare with the expected length.
@@ -50,6 +50,8 @@ impl UnitBuilder {
         this.prelude("file", &["macros", "builtin", "file"]);
         this.prelude("float", &["float"]);
         this.prelude("format", &["fmt", "format"]);
+        this.prelude("i128", &["i128"]);
+        this.prelude("i32", &["i32"]);
         this.prelude("int", &["int"]);
         this.prelude("is_readable", &["is_readable"]);
         this.prelude("is_writable", &["is_writable"]);
@@ -65,6 +67,9 @@ impl UnitBuilder {
         this.prelude("Some", &["option", "Option", "Some"]);
         this.prelude("String", &["string", "String"]);
         this.prelude("stringify", &["stringify"]);
+        this.prelude("u16", &["u16"]);
+        this.prelude("u32", &["u32"]);
+        this.prelude("u64", &["u64"]);
         this.prelude("unit", &["unit"]);
         this.prelude("Vec", &["vec", "Vec"]);
 
@@ -90,7 +95,7 @@ impl UnitBuilder {
         let mut inner = inner.into_inner();
 
         if let Some(debug) = &mut inner.debug {
-            debug.functions_rev = inner.functions_rev;
+            debug.functions_rev = inner.functions_rev.into_iter().collect();
         }
 
         for (from, to) in inner.reexports {
@@ -229,7 +234,7 @@ impl UnitBuilder {
         let current = current
             .into_iter()
             .map(|s| s.as_ref().to_owned())
-            .collect::<Box<_>>();
+            .collect::<Box<[String]>>();
 
         let hash = Hash::object_keys(&current[..]);
 
@@ -444,6 +449,7 @@ impl UnitBuilder {
                     ConstValue::String(meta.item.item.to_string()),
                 );
             }
+            CompileMetaKind::Trait { .. } => (),
             CompileMetaKind::Function { .. } => (),
             CompileMetaKind::Closure { .. } => (),
             CompileMetaKind::AsyncBlock { .. } => (),
@@ -567,6 +573,22 @@ impl UnitBuilder {
             ConstValue::String(signature.path.to_string()),
         );
 
+        // NB: instance functions with the name and signature of a protocol
+        // function are also registered as that protocol, so that they can be
+        // used to overload operators.
+        if let Some(protocol) = operator_protocol(name, args) {
+            let protocol_fn = Hash::instance_function(type_hash, protocol);
+
+            if inner.functions.insert(protocol_fn, info).is_some() {
+                return Err(CompileError::new(
+                    location.span,
+                    CompileErrorKind::FunctionConflict {
+                        existing: signature,
+                    },
+                ));
+            }
+        }
+
         inner
             .debug_info_mut()
             .functions
@@ -597,6 +619,58 @@ impl UnitBuilder {
     }
 }
 
+/// Get the protocol corresponding to an instance function with the given name
+/// and number of arguments (including `self`), if any.
+///
+/// Only functions with exactly these signatures are registered as protocols:
+///
+/// * `fn add(self, other)`, and likewise `sub`, `mul`, `div`, `rem`, `pow`,
+///   `bitand`, `bitxor`, `bitor`, `shl` and `shr`, which overload the binary
+///   operators.
+/// * `fn add_assign(self, other)`, and likewise for the other binary operators
+///   except `pow`, which overload the compound assignment operators.
+/// * `fn eq(self, other)` and `fn partial_eq(self, other)`, which overload `==`
+///   and `!=` and return a boolean.
+/// * `fn cmp(self, other)` and `fn partial_cmp(self, other)`, which overload
+///   `<`, `<=`, `>` and `>=` and return an `Ordering`, or an optional one for
+///   `partial_cmp`.
+/// * `fn hash(self)`, which returns an integer used to hash the value when
+///   it's used as a key.
+///
+/// Names follow the functions of the corresponding traits in `std::ops` and
+/// `std::cmp`.
+fn operator_protocol(name: &str, args: usize) -> Option<Protocol> {
+    Some(match (name, args) {
+        ("add", 2) => Protocol::ADD,
+        ("add_assign", 2) => Protocol::ADD_ASSIGN,
+        ("sub", 2) => Protocol::SUB,
+        ("sub_assign", 2) => Protocol::SUB_ASSIGN,
+        ("mul", 2) => Protocol::MUL,
+        ("mul_assign", 2) => Protocol::MUL_ASSIGN,
+        ("div", 2) => Protocol::DIV,
+        ("div_assign", 2) => Protocol::DIV_ASSIGN,
+        ("rem", 2) => Protocol::REM,
+        ("rem_assign", 2) => Protocol::REM_ASSIGN,
+        ("pow", 2) => Protocol::POW,
+        ("bitand", 2) => Protocol::BIT_AND,
+        ("bitand_assign", 2) => Protocol::BIT_AND_ASSIGN,
+        ("bitxor", 2) => Protocol::BIT_XOR,
+        ("bitxor_assign", 2) => Protocol::BIT_XOR_ASSIGN,
+        ("bitor", 2) => Protocol::BIT_OR,
+        ("bitor_assign", 2) => Protocol::BIT_OR_ASSIGN,
+        ("shl", 2) => Protocol::SHL,
+        ("shl_assign", 2) => Protocol::SHL_ASSIGN,
+        ("shr", 2) => Protocol::SHR,
+        ("shr_assign", 2) => Protocol::SHR_ASSIGN,
+        ("eq", 2) => Protocol::EQ,
+        ("partial_eq", 2) => Protocol::PARTIAL_EQ,
+        ("cmp", 2) => Protocol::CMP,
+        ("partial_cmp", 2) => Protocol::PARTIAL_CMP,
+        ("hash", 1) => Protocol::HASH,
+        _ => return None,
+    })
+}
+
 /// An error raised during linking.
 #[derive(Debug, Error)]
 pub enum LinkerError {
@@ -635,7 +709,7 @@ struct Inner {
     /// This is used when an object is used in a pattern match, to avoid having
     /// to send the collection of keys to the virtual machine.
     ///
-    /// All keys are sorted with the default string sort.
+    /// Keys are stored in the order they appear in the source.
     static_object_keys: Vec<Box<[String]>>,
     /// Used to detect duplicates in the collection of static object keys.
     static_object_keys_rev: HashMap<Hash, usize>,
@@ -676,6 +750,16 @@ impl Inner {
 
         self.required_functions.extend(assembly.required_functions);
 
+        let base = self.instructions.len();
+
+        let debug = self.debug_info_mut();
+
+        for mut variable in assembly.variables {
+            variable.ip += base;
+            variable.end = variable.end.map(|end| end + base);
+            debug.insert_variable(variable);
+        }
+
         for (pos, (inst, span)) in assembly.instructions.into_iter().enumerate() {
             let mut comment = None;
             let label = assembly.labels_rev.get(&pos).copied();
@@ -218,11 +218,43 @@ impl EmitDiagnostics for VmError {
 
         labels.push(Label::primary(source_id, span.range()).with_message(error.to_string()));
 
+        let backtrace = self.backtrace();
+        let mut notes = Vec::new();
+
+        if let Some(signature) = backtrace.first().and_then(|f| f.signature()) {
+            notes.push(format!("in {}", signature));
+        }
+
         let diagnostic = Diagnostic::error()
             .with_message("virtual machine error")
-            .with_labels(labels);
+            .with_labels(labels)
+            .with_notes(notes);
 
         term::emit(out, &config, &files, &diagnostic)?;
+
+        // NB: the first frame is where the error happened, the rest are the
+        // call sites which lead up to it.
+        for frame in backtrace.iter().skip(1) {
+            let caller = match frame.signature() {
+                Some(signature) => signature.to_string(),
+                None => String::from("<unknown>"),
+            };
+
+            let inst = match frame.instruction() {
+                Some(inst) if files.get(inst.source_id).is_ok() => inst,
+                _ => {
+                    writeln!(out, "note: called from {} (at inst {})", caller, frame.ip())?;
+                    continue;
+                }
+            };
+
+            let diagnostic = Diagnostic::note()
+                .with_message(format!("called from {}", caller))
+                .with_labels(vec![Label::primary(inst.source_id, inst.span.range())]);
+
+            term::emit(out, &config, &files, &diagnostic)?;
+        }
+
         Ok(())
     }
 }
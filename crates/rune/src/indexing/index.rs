@@ -7,7 +7,8 @@ use crate::macros::MacroCompiler;
 use crate::parsing::{Parse, Parser};
 use crate::query::{
     Build, BuildEntry, BuiltInFile, BuiltInFormat, BuiltInLine, BuiltInMacro, BuiltInTemplate,
-    Function, Indexed, IndexedEntry, InstanceFunction, Query, Used,
+    Function, Indexed, IndexedEntry, InstanceFunction, Query, Trait, TraitFunction, TraitImpl,
+    Used,
 };
 use crate::shared::{Consts, Items};
 use crate::worker::{Import, ImportKind, LoadFileKind, Task};
@@ -17,8 +18,8 @@ use crate::{
 };
 use runestick::format;
 use runestick::{
-    Call, CompileMeta, CompileMetaKind, CompileMod, CompileSource, Context, Hash, Item, Location,
-    Source, SourceId, Span, Visibility,
+    Call, CompileItem, CompileMeta, CompileMetaKind, CompileMod, CompileSource, Context, Hash,
+    Item, Location, Source, SourceId, Span, Visibility,
 };
 use std::collections::VecDeque;
 use std::num::NonZeroUsize;
@@ -57,6 +58,84 @@ pub(crate) struct Indexer<'a> {
 }
 
 impl<'a> Indexer<'a> {
+    /// Index the given function, returning its item and the function to
+    /// build.
+    ///
+    /// Returns `None` for `const` functions, which are indexed separately.
+    fn index_fn(
+        &mut self,
+        ast: &mut ast::ItemFn,
+    ) -> CompileResult<Option<(Arc<CompileItem>, Function)>> {
+        let span = ast.span();
+        let name = ast.name.resolve(&self.storage, &self.source)?;
+        let _guard = self.items.push_name(name.as_ref());
+
+        let visibility = ast_to_visibility(&ast.visibility)?;
+        let item = self.query.insert_new_item(
+            &self.items,
+            self.source_id,
+            span,
+            &self.mod_item,
+            visibility,
+        )?;
+
+        let kind = match (ast.const_token, ast.async_token) {
+            (Some(const_token), Some(async_token)) => {
+                return Err(CompileError::new(
+                    const_token.span().join(async_token.span()),
+                    CompileErrorKind::FnConstAsyncConflict,
+                ));
+            }
+            (Some(..), _) => IndexFnKind::Const,
+            (_, Some(..)) => IndexFnKind::Async,
+            _ => IndexFnKind::None,
+        };
+
+        let guard = self.scopes.push_function(kind);
+
+        for (arg, _) in &mut ast.args {
+            match arg {
+                ast::FnArg::SelfValue(s) => {
+                    let span = s.span();
+                    self.scopes.declare("self", span)?;
+                }
+                ast::FnArg::Pat(pat) => {
+                    pat.index_local(self)?;
+                }
+            }
+        }
+
+        ast.body.index(self)?;
+
+        let f = guard.into_function(span)?;
+        ast.id = Some(item.id);
+
+        let call = match Self::call(f.generator, f.kind) {
+            Some(call) => call,
+            // const function.
+            None => {
+                if f.generator {
+                    return Err(CompileError::new(
+                        span,
+                        CompileErrorKind::FnConstNotGenerator,
+                    ));
+                }
+
+                self.query
+                    .index_const_fn(&item, &self.source, Box::new(ast.clone()))?;
+
+                return Ok(None);
+            }
+        };
+
+        let fun = Function {
+            ast: Box::new(ast.clone()),
+            call,
+        };
+
+        Ok(Some((item, fun)))
+    }
+
     /// Try to expand an internal macro.
     fn try_expand_internal_macro(
         &mut self,
@@ -601,77 +680,11 @@ impl Index for ast::ItemFn {
         }
 
         let is_toplevel = idx.items.is_empty();
-        let name = self.name.resolve(&idx.storage, &*idx.source)?;
-        let _guard = idx.items.push_name(name.as_ref());
 
-        let visibility = ast_to_visibility(&self.visibility)?;
-        let item = idx.query.insert_new_item(
-            &idx.items,
-            idx.source_id,
-            span,
-            &idx.mod_item,
-            visibility,
-        )?;
-
-        let kind = match (self.const_token, self.async_token) {
-            (Some(const_token), Some(async_token)) => {
-                return Err(CompileError::new(
-                    const_token.span().join(async_token.span()),
-                    CompileErrorKind::FnConstAsyncConflict,
-                ));
-            }
-            (Some(..), _) => IndexFnKind::Const,
-            (_, Some(..)) => IndexFnKind::Async,
-            _ => IndexFnKind::None,
-        };
-
-        if let (Some(const_token), Some(async_token)) = (self.const_token, self.async_token) {
-            return Err(CompileError::new(
-                const_token.span().join(async_token.span()),
-                CompileErrorKind::FnConstAsyncConflict,
-            ));
-        }
-
-        let guard = idx.scopes.push_function(kind);
-
-        for (arg, _) in &mut self.args {
-            match arg {
-                ast::FnArg::SelfValue(s) => {
-                    let span = s.span();
-                    idx.scopes.declare("self", span)?;
-                }
-                ast::FnArg::Pat(pat) => {
-                    pat.index_local(idx)?;
-                }
-            }
-        }
-
-        self.body.index(idx)?;
-
-        let f = guard.into_function(span)?;
-        self.id = Some(item.id);
-
-        let call = match Indexer::call(f.generator, f.kind) {
-            Some(call) => call,
+        let (item, fun) = match idx.index_fn(self)? {
+            Some(result) => result,
             // const function.
-            None => {
-                if f.generator {
-                    return Err(CompileError::new(
-                        span,
-                        CompileErrorKind::FnConstNotGenerator,
-                    ));
-                }
-
-                idx.query
-                    .index_const_fn(&item, &idx.source, Box::new(self.clone()))?;
-
-                return Ok(());
-            }
-        };
-
-        let fun = Function {
-            ast: Box::new(self.clone()),
-            call,
+            None => return Ok(()),
         };
 
         if self.is_instance() {
@@ -793,7 +806,8 @@ impl Index for ast::ExprBlock {
             }
 
             self.block.index(idx)?;
-            idx.query.index_const(&item, &idx.source, self)?;
+            idx.query
+                .index_const(&item, &idx.source, ast::Expr::Block(Box::new(self.clone())))?;
             return Ok(());
         }
 
@@ -901,6 +915,11 @@ impl Index for ast::Local {
 
         self.pat.index(idx)?;
         self.expr.index(idx)?;
+
+        if let Some((_, block)) = &mut self.else_branch {
+            block.index(idx)?;
+        }
+
         Ok(())
     }
 }
@@ -955,6 +974,18 @@ impl Index for ast::Pat {
             ast::Pat::PatIgnore(..) => (),
             ast::Pat::PatLit(..) => (),
             ast::Pat::PatRest(..) => (),
+            ast::Pat::PatOr(pat_or) => {
+                pat_or.first.index(idx)?;
+
+                for (_, pat) in &mut pat_or.rest {
+                    pat.index(idx)?;
+                }
+            }
+            ast::Pat::PatRange(..) => (),
+            ast::Pat::PatAt(pat_at) => {
+                pat_at.ident.index(idx)?;
+                pat_at.pat.index(idx)?;
+            }
         }
 
         Ok(())
@@ -1098,6 +1129,9 @@ impl Index for ast::Expr {
             ast::Expr::Try(expr_try) => {
                 expr_try.index(idx)?;
             }
+            ast::Expr::Cast(expr_cast) => {
+                expr_cast.index(idx)?;
+            }
             ast::Expr::Select(expr_select) => {
                 expr_select.index(idx)?;
             }
@@ -1351,6 +1385,11 @@ impl Index for ast::ItemImpl {
             ));
         }
 
+        // NB: the trait is resolved from the scope the impl is declared in.
+        if let Some((trait_path, _)) = &mut self.trait_ {
+            trait_path.index(idx)?;
+        }
+
         let mut guards = Vec::new();
 
         if let Some(global) = &self.path.global {
@@ -1376,6 +1415,137 @@ impl Index for ast::ItemImpl {
         }
 
         idx.impl_item = old;
+
+        if let Some((trait_path, _)) = &self.trait_ {
+            let span = self.span();
+            let impl_item = Arc::new(idx.items.item().clone());
+
+            let functions = self
+                .functions
+                .iter()
+                .map(|f| Ok((f.name.resolve(&idx.storage, &idx.source)?.into(), f.span())))
+                .collect::<CompileResult<Vec<_>>>()?;
+
+            let item = Arc::new(CompileItem {
+                id: Default::default(),
+                location: Location::new(idx.source_id, span),
+                item: (*impl_item).clone(),
+                visibility: Visibility::Inherited,
+                module: idx.mod_item.clone(),
+            });
+
+            // NB: trait implementations are always checked, and provide the
+            // default functions of the trait to the implementing type.
+            idx.query.push_build_entry(BuildEntry {
+                location: Location::new(idx.source_id, span),
+                item,
+                build: Build::TraitImpl(TraitImpl {
+                    trait_path: trait_path.clone(),
+                    impl_item,
+                    functions,
+                }),
+                source: idx.source.clone(),
+                used: Used::Used,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Index for Box<ast::ItemTrait> {
+    fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
+        let span = self.span();
+
+        if let Some(first) = self.attributes.first() {
+            return Err(CompileError::msg(
+                first,
+                "trait attributes are not supported",
+            ));
+        }
+
+        let name = self.name.resolve(&idx.storage, &idx.source)?;
+        let _guard = idx.items.push_name(name.as_ref());
+
+        let visibility = ast_to_visibility(&self.visibility)?;
+        let item = idx.query.insert_new_item(
+            &idx.items,
+            idx.source_id,
+            span,
+            &idx.mod_item,
+            visibility,
+        )?;
+        self.id = Some(item.id);
+
+        let mut names = HashMap::new();
+        let mut required = Vec::new();
+        let mut provided = Vec::new();
+
+        for trait_item in &mut self.items {
+            let name: Box<str> = trait_item.name().resolve(&idx.storage, &idx.source)?.into();
+
+            if let Some(existing) = names.insert(name.clone(), trait_item.span()) {
+                return Err(CompileError::new(
+                    trait_item.name(),
+                    CompileErrorKind::DuplicateTraitFunction { name, existing },
+                ));
+            }
+
+            match trait_item {
+                ast::TraitItem::Required(item_fn) => {
+                    if let Some(first) = item_fn.attributes.first() {
+                        return Err(CompileError::msg(
+                            first,
+                            "function attributes are not supported",
+                        ));
+                    }
+
+                    if !item_fn.is_instance() {
+                        return Err(CompileError::msg(
+                            &*item_fn,
+                            "trait functions must take `self` as their first argument",
+                        ));
+                    }
+
+                    required.push((name, item_fn.span()));
+                }
+                ast::TraitItem::Provided(item_fn) => {
+                    if let Some(first) = item_fn.attributes.first() {
+                        return Err(CompileError::msg(
+                            first,
+                            "function attributes are not supported",
+                        ));
+                    }
+
+                    if !item_fn.is_instance() {
+                        return Err(CompileError::msg(
+                            &*item_fn,
+                            "trait functions must take `self` as their first argument",
+                        ));
+                    }
+
+                    let (item, function) = match idx.index_fn(item_fn)? {
+                        Some(result) => result,
+                        None => {
+                            return Err(CompileError::msg(
+                                &*item_fn,
+                                "const trait functions are not supported",
+                            ));
+                        }
+                    };
+
+                    provided.push(TraitFunction {
+                        name,
+                        item,
+                        source: idx.source.clone(),
+                        function,
+                    });
+                }
+            }
+        }
+
+        idx.query
+            .index_trait(&item, &idx.source, Trait { required, provided })?;
         Ok(())
     }
 }
@@ -1445,7 +1615,8 @@ impl Index for Box<ast::ItemConst> {
 
         self.expr.index(idx)?;
 
-        idx.query.index_const(&item, &idx.source, &self.expr)?;
+        idx.query
+            .index_const(&item, &idx.source, self.expr.clone())?;
         Ok(())
     }
 }
@@ -1474,6 +1645,9 @@ impl Index for ast::Item {
             ast::Item::Impl(item_impl) => {
                 item_impl.index(idx)?;
             }
+            ast::Item::Trait(item_trait) => {
+                item_trait.index(idx)?;
+            }
             ast::Item::Mod(item_mod) => {
                 item_mod.index(idx)?;
             }
@@ -1732,6 +1906,17 @@ impl Index for ast::ExprTry {
     }
 }
 
+impl Index for ast::ExprCast {
+    fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
+        let span = self.span();
+        log::trace!("ExprCast => {:?}", idx.source.source(span));
+
+        self.expr.index(idx)?;
+        self.ty.index(idx)?;
+        Ok(())
+    }
+}
+
 impl Index for ast::ExprSelect {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
         let span = self.span();
@@ -34,6 +34,18 @@ impl IndexLocal for ast::Pat {
             ast::Pat::PatIgnore(..) => (),
             ast::Pat::PatLit(..) => (),
             ast::Pat::PatRest(..) => (),
+            ast::Pat::PatOr(pat_or) => {
+                pat_or.first.index_local(idx)?;
+
+                for (_, pat) in &mut pat_or.rest {
+                    pat.index_local(idx)?;
+                }
+            }
+            ast::Pat::PatRange(..) => (),
+            ast::Pat::PatAt(pat_at) => {
+                pat_at.ident.index_local(idx)?;
+                pat_at.pat.index_local(idx)?;
+            }
         }
 
         Ok(())
@@ -14,6 +14,7 @@ impl IrEval for ir::Ir {
         match &self.kind {
             ir::IrKind::Scope(ir_scope) => ir_scope.eval(interp, used),
             ir::IrKind::Binary(ir_binary) => ir_binary.eval(interp, used),
+            ir::IrKind::Cast(ir_cast) => ir_cast.eval(interp, used),
             ir::IrKind::Decl(ir_decl) => ir_decl.eval(interp, used),
             ir::IrKind::Set(ir_set) => ir_set.eval(interp, used),
             ir::IrKind::Assign(ir_assign) => ir_assign.eval(interp, used),
@@ -33,6 +33,27 @@ impl IrEval for ir::IrBinary {
                         .ok_or_else(|| IrError::msg(span, "division by zero"))?;
                     return Ok(IrValue::Integer(number));
                 }
+                ir::IrBinaryOp::Pow => {
+                    if b.sign() == num::bigint::Sign::Minus {
+                        return Err(IrEvalOutcome::from(IrError::new(
+                            &self.rhs,
+                            IrErrorKind::NegativeExponent { exponent: b },
+                        )));
+                    }
+
+                    let b = u32::try_from(b).map_err(|_| {
+                        IrError::msg(&self.rhs, "cannot be converted to power operand")
+                    })?;
+
+                    // NB: the result is bounded to what fits in an integer at
+                    // runtime, so a large exponent can't stall compilation.
+                    let n = i64::try_from(&a)
+                        .ok()
+                        .and_then(|a| a.checked_pow(b))
+                        .ok_or_else(|| IrError::new(span, IrErrorKind::IntegerOverflow))?;
+
+                    return Ok(IrValue::Integer(n.into()));
+                }
                 ir::IrBinaryOp::Shl => {
                     let b = u32::try_from(b).map_err(|_| {
                         IrError::msg(&self.rhs, "cannot be converted to shift operand")
@@ -62,6 +83,7 @@ impl IrEval for ir::IrBinary {
                     ir::IrBinaryOp::Sub => return Ok(IrValue::Float(a - b)),
                     ir::IrBinaryOp::Mul => return Ok(IrValue::Float(a * b)),
                     ir::IrBinaryOp::Div => return Ok(IrValue::Float(a / b)),
+                    ir::IrBinaryOp::Pow => return Ok(IrValue::Float(a.powf(b))),
                     ir::IrBinaryOp::Lt => return Ok(IrValue::Bool(a < b)),
                     ir::IrBinaryOp::Lte => return Ok(IrValue::Bool(a <= b)),
                     ir::IrBinaryOp::Eq => return Ok(IrValue::Bool(a == b)),
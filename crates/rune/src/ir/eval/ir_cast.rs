@@ -0,0 +1,68 @@
+use crate::ir::eval::prelude::*;
+use crate::IrErrorKind;
+use num::ToPrimitive as _;
+use runestick::InstCast;
+
+impl IrEval for ir::IrCast {
+    type Output = IrValue;
+
+    fn eval(
+        &self,
+        interp: &mut IrInterpreter<'_>,
+        used: Used,
+    ) -> Result<Self::Output, IrEvalOutcome> {
+        let span = self.span();
+        interp.budget.take(span)?;
+
+        let value = self.target.eval(interp, used)?;
+
+        let out = match (self.ty, &value) {
+            (InstCast::Integer, IrValue::Integer(n)) => {
+                n.to_i64().map(|n| IrValue::Integer(n.into()))
+            }
+            (InstCast::Integer, IrValue::Float(n)) => InstCast::float_to_integer(*n)
+                .and_then(|n| i64::try_from(n).ok())
+                .map(|n| IrValue::Integer(n.into())),
+            (InstCast::Integer, IrValue::Byte(b)) => Some(IrValue::Integer(i64::from(*b).into())),
+            (InstCast::Integer, IrValue::Char(c)) => Some(IrValue::Integer(u32::from(*c).into())),
+            (InstCast::Integer, IrValue::Bool(b)) => Some(IrValue::Integer(i64::from(*b).into())),
+            (InstCast::Float, IrValue::Integer(n)) => n.to_f64().map(IrValue::Float),
+            (InstCast::Float, IrValue::Float(n)) => Some(IrValue::Float(*n)),
+            (InstCast::Float, IrValue::Byte(b)) => Some(IrValue::Float(f64::from(*b))),
+            (InstCast::Byte, IrValue::Integer(n)) => n.to_u8().map(IrValue::Byte),
+            (InstCast::Byte, IrValue::Float(n)) => InstCast::float_to_integer(*n)
+                .and_then(|n| u8::try_from(n).ok())
+                .map(IrValue::Byte),
+            (InstCast::Byte, IrValue::Byte(b)) => Some(IrValue::Byte(*b)),
+            (InstCast::Byte, IrValue::Char(c)) => {
+                u8::try_from(u32::from(*c)).ok().map(IrValue::Byte)
+            }
+            (InstCast::Byte, IrValue::Bool(b)) => Some(IrValue::Byte(u8::from(*b))),
+            (InstCast::Char, IrValue::Integer(n)) => {
+                n.to_u32().and_then(std::char::from_u32).map(IrValue::Char)
+            }
+            (InstCast::Char, IrValue::Byte(b)) => Some(IrValue::Char(char::from(*b))),
+            (InstCast::Char, IrValue::Char(c)) => Some(IrValue::Char(*c)),
+            _ => {
+                return Err(IrEvalOutcome::from(IrError::new(
+                    span,
+                    IrErrorKind::UnsupportedCast {
+                        from: value.type_info(),
+                        to: self.ty,
+                    },
+                )));
+            }
+        };
+
+        match out {
+            Some(out) => Ok(out),
+            None => Err(IrEvalOutcome::from(IrError::new(
+                span,
+                IrErrorKind::CastOutOfRange {
+                    from: value.type_info(),
+                    to: self.ty,
+                },
+            ))),
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use crate::collections::HashMap;
 use crate::ir::eval::prelude::*;
+use indexmap::IndexMap;
 
 impl IrEval for ir::IrObject {
     type Output = IrValue;
@@ -9,7 +9,7 @@ impl IrEval for ir::IrObject {
         interp: &mut IrInterpreter<'_>,
         used: Used,
     ) -> Result<Self::Output, IrEvalOutcome> {
-        let mut object = HashMap::with_capacity(self.assignments.len());
+        let mut object = IndexMap::with_capacity(self.assignments.len());
 
         for (key, value) in self.assignments.iter() {
             object.insert(key.as_ref().to_owned(), value.eval(interp, used)?);
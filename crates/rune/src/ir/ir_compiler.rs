@@ -95,6 +95,7 @@ impl IrCompile for ast::Expr {
             }
             ast::Expr::Group(expr_group) => expr_group.expr.compile(c)?,
             ast::Expr::Binary(expr_binary) => expr_binary.compile(c)?,
+            ast::Expr::Cast(expr_cast) => ir::Ir::new(expr_cast.span(), expr_cast.compile(c)?),
             ast::Expr::Assign(expr_assign) => expr_assign.compile(c)?,
             ast::Expr::Call(expr_call) => ir::Ir::new(self.span(), expr_call.compile(c)?),
             ast::Expr::If(expr_if) => ir::Ir::new(self.span(), expr_if.compile(c)?),
@@ -139,6 +140,9 @@ impl IrCompile for ast::Expr {
                                 ConstValue::Integer(n)
                             }
                             ast::Number::Float(n) => ConstValue::Float(n),
+                            ast::Number::Sized(..) => {
+                                return Err(IrError::msg(line.span, "expected unsized number"));
+                            }
                         };
                         ir::Ir::new(line.span, const_value)
                     }
@@ -228,6 +232,25 @@ impl IrCompile for ast::ExprCall {
     }
 }
 
+impl IrCompile for ast::ExprCast {
+    type Output = ir::IrCast;
+
+    fn compile(&self, c: &mut IrCompiler<'_>) -> Result<Self::Output, IrError> {
+        let span = self.span();
+
+        let ty = match c.query.cast_type(&c.storage, &c.source, &self.ty)? {
+            Some(ty) => ty,
+            None => return Err(IrError::msg(&self.ty, "unsupported type in cast")),
+        };
+
+        Ok(ir::IrCast {
+            span,
+            ty,
+            target: Box::new(self.expr.compile(c)?),
+        })
+    }
+}
+
 impl IrCompile for ast::ExprBinary {
     type Output = ir::Ir;
 
@@ -266,6 +289,7 @@ impl IrCompile for ast::ExprBinary {
             ast::BinOp::Sub => ir::IrBinaryOp::Sub,
             ast::BinOp::Mul => ir::IrBinaryOp::Mul,
             ast::BinOp::Div => ir::IrBinaryOp::Div,
+            ast::BinOp::Pow => ir::IrBinaryOp::Pow,
             ast::BinOp::Shl => ir::IrBinaryOp::Shl,
             ast::BinOp::Shr => ir::IrBinaryOp::Shr,
             ast::BinOp::Lt => ir::IrBinaryOp::Lt,
@@ -320,6 +344,24 @@ impl IrCompile for ast::ExprLit {
                         ConstValue::Integer(n)
                     }
                     ast::Number::Float(n) => ConstValue::Float(n),
+                    ast::Number::Sized(n, ast::NumberSize::U8) => match n.to_u8() {
+                        Some(n) => ConstValue::Byte(n),
+                        None => {
+                            return Err(IrError::new(span, IrErrorKind::NotInteger { value: n }))
+                        }
+                    },
+                    ast::Number::Sized(n, ast::NumberSize::I64) => match n.to_i64() {
+                        Some(n) => ConstValue::Integer(n),
+                        None => {
+                            return Err(IrError::new(span, IrErrorKind::NotInteger { value: n }))
+                        }
+                    },
+                    ast::Number::Sized(..) => {
+                        return Err(IrError::msg(
+                            span,
+                            "sized integer literals are not supported in constant expressions",
+                        ));
+                    }
                 };
 
                 ir::Ir::new(span, const_value)
@@ -1,7 +1,10 @@
 use crate::ir::IrValue;
 use crate::shared::{ScopeError, ScopeErrorKind};
-use crate::{QueryError, QueryErrorKind, ResolveError, ResolveErrorKind, Spanned};
-use runestick::{AccessError, CompileMeta, SpannedError, TypeInfo, TypeOf};
+use crate::{
+    CompileError, CompileErrorKind, QueryError, QueryErrorKind, ResolveError, ResolveErrorKind,
+    Spanned,
+};
+use runestick::{AccessError, CompileMeta, InstCast, SpannedError, TypeInfo, TypeOf};
 use thiserror::Error;
 
 error! {
@@ -14,6 +17,7 @@ error! {
     impl From<ResolveError>;
     impl From<QueryError>;
     impl From<ScopeError>;
+    impl From<CompileError>;
 }
 
 impl IrError {
@@ -77,6 +81,14 @@ pub enum IrErrorKind {
         #[from]
         error: Box<QueryErrorKind>,
     },
+    /// A compile error raised while resolving items.
+    #[error("{error}")]
+    CompileError {
+        /// The source error.
+        #[source]
+        #[from]
+        error: Box<CompileErrorKind>,
+    },
     #[error("{error}")]
     ResolveError {
         #[source]
@@ -107,9 +119,18 @@ pub enum IrErrorKind {
     /// Exceeded evaluation budget.
     #[error("evaluation budget exceeded")]
     BudgetExceeded,
+    /// Integer overflow.
+    #[error("integer overflow")]
+    IntegerOverflow,
     /// Integer underflow.
     #[error("integer underflow")]
     IntegerUnderflow,
+    /// Raising an integer to a negative power.
+    #[error("exponent `{exponent}` is negative")]
+    NegativeExponent {
+        /// The negative exponent.
+        exponent: num::BigInt,
+    },
     /// Missing a tuple index.
     #[error("missing index {index}")]
     MissingIndex {
@@ -143,4 +164,8 @@ pub enum IrErrorKind {
     ArgumentCountMismatch { actual: usize, expected: usize },
     #[error("value `{value}` is outside of the supported integer range")]
     NotInteger { value: num::BigInt },
+    #[error("unsupported cast `{from} as {to}`")]
+    UnsupportedCast { from: TypeInfo, to: InstCast },
+    #[error("value of type `{from}` is out of range for `{to}`")]
+    CastOutOfRange { from: TypeInfo, to: InstCast },
 }
@@ -1,6 +1,8 @@
+use crate::ast;
 use crate::parsing::Id;
 use crate::query::{BuiltInMacro, QueryConstFn, QueryError, Used};
-use runestick::{CompileMeta, Item, Span};
+use crate::{CompileError, Storage};
+use runestick::{CompileMeta, InstCast, Item, Source, Span};
 use std::sync::Arc;
 
 /// Query interface for the interpreter.
@@ -22,4 +24,13 @@ pub(crate) trait IrQuery {
 
     /// Query for the constant function related to the given id.
     fn const_fn_for(&self, spanned: Span, id: Option<Id>) -> Result<Arc<QueryConstFn>, QueryError>;
+
+    /// Resolve the type a value is converted into by an `as` expression, if
+    /// values can be cast into it.
+    fn cast_type(
+        &mut self,
+        storage: &Storage,
+        source: &Source,
+        path: &ast::Path,
+    ) -> Result<Option<InstCast>, CompileError>;
 }
@@ -1,5 +1,5 @@
-use crate::collections::HashMap;
 use crate::{IrError, IrErrorKind, Spanned};
+use indexmap::IndexMap;
 use runestick::{Bytes, ConstValue, Shared, TypeInfo};
 use std::convert::TryFrom;
 
@@ -29,7 +29,7 @@ pub enum IrValue {
     /// An anonymous tuple.
     Tuple(Shared<Box<[IrValue]>>),
     /// An anonymous object.
-    Object(Shared<HashMap<String, IrValue>>),
+    Object(Shared<IndexMap<String, IrValue>>),
 }
 
 impl IrValue {
@@ -68,7 +68,7 @@ impl IrValue {
                 Self::Tuple(Shared::new(ir_tuple.into_boxed_slice()))
             }
             ConstValue::Object(object) => {
-                let mut ir_object = HashMap::with_capacity(object.len());
+                let mut ir_object = IndexMap::with_capacity(object.len());
 
                 for (key, value) in object {
                     ir_object.insert(key, Self::from_const(value));
@@ -138,7 +138,7 @@ impl IrValue {
             }
             IrValue::Object(object) => {
                 let object = object.take().map_err(IrError::access(spanned))?;
-                let mut const_object = HashMap::with_capacity(object.len());
+                let mut const_object = IndexMap::with_capacity(object.len());
 
                 for (key, value) in object {
                     const_object.insert(key, value.into_const(spanned)?);
@@ -21,7 +21,7 @@ pub(crate) use self::ir_interpreter::IrBudget;
 pub(crate) use self::ir_query::IrQuery;
 
 use crate::Spanned;
-use runestick::{ConstValue, Span};
+use runestick::{ConstValue, InstCast, Span};
 
 macro_rules! decl_kind {
     (
@@ -96,6 +96,8 @@ decl_kind! {
         Scope(IrScope),
         /// A binary operation.
         Binary(IrBinary),
+        /// A cast of a value into another type.
+        Cast(IrCast),
         /// Declare a local variable with the value of the operand.
         Decl(IrDecl),
         /// Set the given target.
@@ -166,6 +168,18 @@ pub struct IrBinary {
     pub(crate) rhs: Box<Ir>,
 }
 
+/// A cast operation.
+#[derive(Debug, Clone, Spanned)]
+pub struct IrCast {
+    /// The span of the cast.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The type being cast to.
+    pub(crate) ty: InstCast,
+    /// The value being cast.
+    pub(crate) target: Box<Ir>,
+}
+
 /// A local variable declaration.
 #[derive(Debug, Clone, Spanned)]
 pub struct IrDecl {
@@ -350,6 +364,8 @@ pub enum IrBinaryOp {
     Mul,
     /// Division `/`.
     Div,
+    /// Power `**`.
+    Pow,
     /// `<<`.
     Shl,
     /// `>>`.
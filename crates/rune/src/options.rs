@@ -23,6 +23,8 @@ pub struct Options {
     pub(crate) macros: bool,
     /// Support (experimental) bytecode caching.
     pub bytecode: bool,
+    /// Refuse to compile `let` patterns which might not match.
+    pub(crate) strict_let: bool,
 }
 
 impl Options {
@@ -51,6 +53,9 @@ impl Options {
             Some("bytecode") => {
                 self.bytecode = it.next() != Some("false");
             }
+            Some("strict-let") => {
+                self.strict_let = it.next() != Some("false");
+            }
             _ => {
                 return Err(ConfigurationError::UnsupportedOptimizationOption {
                     option: option.to_owned(),
@@ -87,6 +92,16 @@ impl Options {
     pub fn memoize_instance_fn(&mut self, enabled: bool) {
         self.memoize_instance_fn = enabled;
     }
+
+    /// Set if `let` patterns which might not match should be a compile error
+    /// instead of panicking at runtime. Defaults to `false`.
+    ///
+    /// Since values are dynamically typed, this includes destructuring
+    /// patterns like `let (a, b) = value;`. Such patterns can still be used by
+    /// adding an `else` branch, like `let Some(x) = value else { return; };`.
+    pub fn strict_let(&mut self, enabled: bool) {
+        self.strict_let = enabled;
+    }
 }
 
 impl Default for Options {
@@ -97,6 +112,7 @@ impl Default for Options {
             debug_info: true,
             macros: true,
             bytecode: false,
+            strict_let: false,
         }
     }
 }
@@ -486,6 +486,10 @@ impl<'a> Lexer<'a> {
                             self.iter.next();
                             break ast::Kind::StarEq;
                         }
+                        ('*', '*') => {
+                            self.iter.next();
+                            break ast::Kind::StarStar;
+                        }
                         ('/', '=') => {
                             self.iter.next();
                             break ast::Kind::SlashEq;
@@ -971,7 +975,7 @@ mod tests {
     #[test]
     fn test_operators() {
         test_lexer! {
-            "+ += - -= * *= / /=",
+            "+ += - -= * *= / /= **",
             ast::Token {
                 span: span!(0, 1),
                 kind: ast::Kind::Plus,
@@ -1003,6 +1007,10 @@ mod tests {
             ast::Token {
                 span: span!(17, 19),
                 kind: ast::Kind::SlashEq,
+            },
+            ast::Token {
+                span: span!(20, 22),
+                kind: ast::Kind::StarStar,
             }
         };
     }
@@ -14,7 +14,8 @@ use runestick::format;
 use runestick::{
     Call, CompileItem, CompileMeta, CompileMetaCapture, CompileMetaEmpty, CompileMetaKind,
     CompileMetaStruct, CompileMetaTuple, CompileMod, CompileSource, Component, ComponentRef,
-    Context, Hash, IntoComponent, Item, Location, Names, Source, SourceId, Span, Visibility,
+    Context, Hash, InstCast, IntoComponent, Item, Location, Names, Source, SourceId, Span,
+    Visibility,
 };
 use std::cell::{RefCell, RefMut};
 use std::collections::VecDeque;
@@ -101,6 +102,15 @@ impl IrQuery for QueryInner {
     fn const_fn_for(&self, spanned: Span, id: Option<Id>) -> Result<Arc<QueryConstFn>, QueryError> {
         QueryInner::const_fn_for(self, spanned, id)
     }
+
+    fn cast_type(
+        &mut self,
+        storage: &Storage,
+        source: &Source,
+        path: &ast::Path,
+    ) -> Result<Option<InstCast>, CompileError> {
+        QueryInner::cast_type(self, storage, source, path)
+    }
 }
 
 #[derive(Clone, Default)]
@@ -110,17 +120,30 @@ pub(crate) struct Query {
 
 impl Query {
     /// Construct a new compilation context.
-    pub fn new(storage: Storage, unit: UnitBuilder, consts: Consts, gen: Gen) -> Self {
+    pub fn new(
+        context: &Context,
+        storage: Storage,
+        unit: UnitBuilder,
+        consts: Consts,
+        gen: Gen,
+    ) -> Self {
         Self {
             inner: Rc::new(RefCell::new(QueryInner {
                 meta: HashMap::new(),
                 storage,
                 prelude: unit.prelude(),
+                crates: context.iter_crates().map(Box::from).collect(),
+                types: context
+                    .iter_types()
+                    .map(|(_, ty)| (ty.item.clone(), ty.type_hash))
+                    .collect(),
                 unit,
                 consts,
                 gen,
                 queue: VecDeque::new(),
                 indexed: HashMap::new(),
+                traits: HashMap::new(),
+                trait_impls: HashMap::new(),
                 const_fns: HashMap::new(),
                 query_paths: HashMap::new(),
                 internal_macros: HashMap::new(),
@@ -306,33 +329,20 @@ impl Query {
     }
 
     /// Index a constant expression.
-    pub fn index_const<T>(
+    pub fn index_const(
         &self,
         item: &Arc<CompileItem>,
         source: &Arc<Source>,
-        expr: &T,
-    ) -> Result<(), QueryError>
-    where
-        T: IrCompile<Output = ir::Ir>,
-    {
+        expr: ast::Expr,
+    ) -> Result<(), QueryError> {
         log::trace!("new const: {:?}", item.item);
 
-        let mut inner = self.inner.borrow_mut();
-
-        let mut ir_compiler = IrCompiler {
-            storage: inner.storage.clone(),
-            source: source.clone(),
-            query: &mut *inner,
-        };
-
-        let ir = ir_compiler.compile(expr)?;
-
-        inner.index(IndexedEntry {
+        self.inner.borrow_mut().index(IndexedEntry {
             item: item.clone(),
             source: source.clone(),
             indexed: Indexed::Const(Const {
                 module: item.module.clone(),
-                ir,
+                expr: Box::new(expr),
             }),
         });
 
@@ -392,6 +402,55 @@ impl Query {
         Ok(())
     }
 
+    /// Add a new trait item that can be queried.
+    pub(crate) fn index_trait(
+        &self,
+        item: &Arc<CompileItem>,
+        source: &Arc<Source>,
+        tr: Trait,
+    ) -> Result<(), QueryError> {
+        log::trace!("new trait: {:?}", item.item);
+
+        self.inner.borrow_mut().index(IndexedEntry {
+            item: item.clone(),
+            source: source.clone(),
+            indexed: Indexed::Trait(tr),
+        });
+
+        Ok(())
+    }
+
+    /// Get the trait associated with the given item, if it has been built.
+    pub(crate) fn get_trait(&self, item: &Item) -> Option<Arc<Trait>> {
+        self.inner.borrow().traits.get(item).cloned()
+    }
+
+    /// Record that the given type implements the given trait.
+    ///
+    /// Errors if the trait is already implemented for the type.
+    pub(crate) fn insert_trait_impl(
+        &self,
+        span: Span,
+        trait_item: &Item,
+        impl_item: &Item,
+    ) -> Result<(), QueryError> {
+        let mut inner = self.inner.borrow_mut();
+
+        let impls = inner.trait_impls.entry(trait_item.clone()).or_default();
+
+        if !impls.insert(impl_item.clone()) {
+            return Err(QueryError::new(
+                span,
+                QueryErrorKind::TraitImplConflict {
+                    trait_item: trait_item.clone(),
+                    impl_item: impl_item.clone(),
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Add a new variant item that can be queried.
     pub fn index_variant(
         &self,
@@ -515,14 +574,11 @@ impl Query {
     /// Convert the given path.
     pub(crate) fn convert_path(
         &self,
-        context: &Context,
         storage: &Storage,
         source: &Source,
         path: &ast::Path,
     ) -> Result<Named, CompileError> {
-        self.inner
-            .borrow_mut()
-            .convert_path(context, storage, source, path)
+        self.inner.borrow_mut().convert_path(storage, source, path)
     }
 
     /// Declare a new import.
@@ -619,6 +675,10 @@ struct QueryInner {
     storage: Storage,
     /// Prelude from the prelude.
     prelude: HashMap<Box<str>, Item>,
+    /// Crates available in the context.
+    crates: HashSet<Box<str>>,
+    /// Hashes of the types available in the context.
+    types: HashMap<Item, Hash>,
     /// Unit being built.
     unit: UnitBuilder,
     /// Cache of constants that have been expanded.
@@ -630,6 +690,10 @@ struct QueryInner {
     /// Indexed items that can be queried for, which will queue up for them to
     /// be compiled.
     indexed: HashMap<Item, Vec<IndexedEntry>>,
+    /// Traits which have been built, and the functions they declare.
+    traits: HashMap<Item, Arc<Trait>>,
+    /// The types implementing each trait.
+    trait_impls: HashMap<Item, HashSet<Item>>,
     /// Compiled constant functions.
     const_fns: HashMap<Id, Arc<QueryConstFn>>,
     /// Query paths.
@@ -1027,7 +1091,6 @@ impl QueryInner {
     /// Walk the names to find the first one that is contained in the unit.
     fn lookup_initial(
         &mut self,
-        context: &Context,
         module: &Arc<CompileMod>,
         base: &Item,
         local: &str,
@@ -1051,7 +1114,7 @@ impl QueryInner {
             return Ok(item.clone());
         }
 
-        if context.contains_crate(local) {
+        if self.crates.contains(local) {
             return Ok(Item::with_crate(local));
         }
 
@@ -1061,7 +1124,6 @@ impl QueryInner {
     /// Perform a path lookup on the current state of the unit.
     fn convert_path(
         &mut self,
-        context: &Context,
         storage: &Storage,
         source: &Source,
         path: &ast::Path,
@@ -1094,7 +1156,7 @@ impl QueryInner {
                     local = Some(<Box<str>>::from(ident.as_ref()));
                 }
 
-                self.lookup_initial(context, &qp.module, &qp.item, &*ident)?
+                self.lookup_initial(&qp.module, &qp.item, &*ident)?
             }
             (None, ast::PathSegment::Super(super_value)) => {
                 let mut item = qp.module.item.clone();
@@ -1153,6 +1215,24 @@ impl QueryInner {
         Ok(Named { local, item })
     }
 
+    /// Resolve the type a value is converted into by an `as` expression,
+    /// looking up the type the same way as when it's assembled.
+    fn cast_type(
+        &mut self,
+        storage: &Storage,
+        source: &Source,
+        path: &ast::Path,
+    ) -> Result<Option<InstCast>, CompileError> {
+        let named = self.convert_path(storage, source, path)?;
+
+        let hash = match self.query_meta(path.span(), &named.item, Default::default())? {
+            Some(meta) => meta.type_hash_of(),
+            None => self.types.get(&named.item).copied(),
+        };
+
+        Ok(hash.and_then(InstCast::from_type_hash))
+    }
+
     /// Build a single, indexed entry and return its metadata.
     fn build_indexed_entry(
         &mut self,
@@ -1189,6 +1269,13 @@ impl QueryInner {
             Indexed::Struct(st) => {
                 struct_into_item_decl(&query_item.item, st.ast.body, None, &self.storage, &*source)?
             }
+            Indexed::Trait(tr) => {
+                self.traits.insert(query_item.item.clone(), Arc::new(tr));
+
+                CompileMetaKind::Trait {
+                    type_hash: Hash::type_hash(&query_item.item),
+                }
+            }
             Indexed::Function(f) => {
                 self.queue.push_back(BuildEntry {
                     location: query_item.location,
@@ -1239,6 +1326,14 @@ impl QueryInner {
                 }
             }
             Indexed::Const(c) => {
+                let mut ir_compiler = IrCompiler {
+                    storage: self.storage.clone(),
+                    source: source.clone(),
+                    query: self,
+                };
+
+                let ir = ir_compiler.compile(&*c.expr)?;
+
                 let mut const_compiler = IrInterpreter {
                     budget: IrBudget::new(1_000_000),
                     scopes: Default::default(),
@@ -1248,7 +1343,7 @@ impl QueryInner {
                     query: self,
                 };
 
-                let const_value = const_compiler.eval_const(&c.ir, used)?;
+                let const_value = const_compiler.eval_const(&ir, used)?;
 
                 if used.is_unused() {
                     self.queue.push_back(BuildEntry {
@@ -1420,6 +1515,7 @@ impl Default for Used {
 pub(crate) enum Indexed {
     Enum,
     Struct(Struct),
+    Trait(Trait),
     Variant(Variant),
     Function(Function),
     Closure(Closure),
@@ -1452,6 +1548,37 @@ impl Struct {
     }
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct Trait {
+    /// Functions which must be provided by implementations, and the span
+    /// where they are declared.
+    pub(crate) required: Vec<(Box<str>, Span)>,
+    /// Functions with a default implementation.
+    pub(crate) provided: Vec<TraitFunction>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TraitFunction {
+    /// The name of the function.
+    pub(crate) name: Box<str>,
+    /// The item of the function, as declared in the trait.
+    pub(crate) item: Arc<CompileItem>,
+    /// The source the function is declared in.
+    pub(crate) source: Arc<Source>,
+    /// The default implementation.
+    pub(crate) function: Function,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TraitImpl {
+    /// The path to the trait being implemented.
+    pub(crate) trait_path: ast::Path,
+    /// The item of the type the trait is implemented for.
+    pub(crate) impl_item: Arc<Item>,
+    /// The names of the functions in the `impl` block, and their spans.
+    pub(crate) functions: Vec<(Box<str>, Span)>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Variant {
     /// Id of of the enum type.
@@ -1514,8 +1641,9 @@ pub(crate) struct AsyncBlock {
 pub(crate) struct Const {
     /// The module item the constant is defined in.
     pub(crate) module: Arc<CompileMod>,
-    /// The intermediate representation of the constant expression.
-    pub(crate) ir: ir::Ir,
+    /// The constant expression, compiled once the constant is queried so
+    /// that imports in scope have been resolved.
+    pub(crate) expr: Box<ast::Expr>,
 }
 
 #[derive(Debug, Clone)]
@@ -1529,6 +1657,8 @@ pub(crate) struct ConstFn {
 pub(crate) enum Build {
     Function(Function),
     InstanceFunction(InstanceFunction),
+    /// Check a trait implementation and build its default functions.
+    TraitImpl(TraitImpl),
     Closure(Closure),
     AsyncBlock(AsyncBlock),
     Unused,
@@ -86,6 +86,8 @@ pub enum QueryErrorKind {
     NotIndexedImport { item: Item },
     #[error("{meta} can't be used as an import")]
     UnsupportedImportMeta { meta: CompileMeta },
+    #[error("trait `{trait_item}` is already implemented for `{impl_item}`")]
+    TraitImplConflict { trait_item: Item, impl_item: Item },
     /// Tried to add an item that already exists.
     #[error("trying to insert `{current}` but conflicting meta `{existing}` already exists")]
     MetaConflict {
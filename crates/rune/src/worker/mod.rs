@@ -66,7 +66,7 @@ impl<'a> Worker<'a> {
             source_loader,
             consts: consts.clone(),
             queue: VecDeque::new(),
-            query: Query::new(storage.clone(), unit, consts, gen.clone()),
+            query: Query::new(context, storage.clone(), unit, consts, gen.clone()),
             storage,
             gen,
             loaded: HashMap::new(),
@@ -25,6 +25,7 @@ pub trait Any: Named + std::any::Any {
 
 // Internal any impls for useful types in the std library.
 
+crate::__internal_impl_any!(std::cmp::Ordering);
 crate::__internal_impl_any!(std::fmt::Error);
 crate::__internal_impl_any!(std::io::Error);
 crate::__internal_impl_any!(anyhow::Error);
@@ -45,6 +45,7 @@ impl CompileMeta {
             CompileMetaKind::TupleStruct { type_hash, .. } => Some(*type_hash),
             CompileMetaKind::Struct { type_hash, .. } => Some(*type_hash),
             CompileMetaKind::Enum { type_hash, .. } => Some(*type_hash),
+            CompileMetaKind::Trait { .. } => None,
             CompileMetaKind::Function { type_hash, .. } => Some(*type_hash),
             CompileMetaKind::Closure { type_hash, .. } => Some(*type_hash),
             CompileMetaKind::AsyncBlock { type_hash, .. } => Some(*type_hash),
@@ -82,6 +83,9 @@ impl fmt::Display for CompileMeta {
             CompileMetaKind::Enum { .. } => {
                 write!(fmt, "enum {}", self.item.item)?;
             }
+            CompileMetaKind::Trait { .. } => {
+                write!(fmt, "trait {}", self.item.item)?;
+            }
             CompileMetaKind::Function { .. } => {
                 write!(fmt, "fn {}", self.item.item)?;
             }
@@ -162,6 +166,11 @@ pub enum CompileMetaKind {
         /// The type hash associated with this meta kind.
         type_hash: Hash,
     },
+    /// A trait declaration.
+    Trait {
+        /// The type hash associated with this meta kind.
+        type_hash: Hash,
+    },
     /// A function declaration.
     Function {
         /// The type hash associated with this meta kind.
@@ -1,8 +1,8 @@
-use crate::collections::HashMap;
 use crate::{
     Bytes, FromValue, Object, Shared, StaticString, ToValue, Tuple, TypeInfo, Value, Vec, VmError,
     VmErrorKind,
 };
+use indexmap::IndexMap;
 use serde::{de, ser};
 use std::fmt;
 use std::sync::Arc;
@@ -34,7 +34,7 @@ pub enum ConstValue {
     /// An anonymous tuple.
     Tuple(Box<[ConstValue]>),
     /// An anonymous object.
-    Object(HashMap<String, ConstValue>),
+    Object(IndexMap<String, ConstValue>),
     /// An option.
     Option(Option<Box<ConstValue>>),
 }
@@ -162,7 +162,7 @@ impl FromValue for ConstValue {
             }
             Value::Object(object) => {
                 let object = object.take()?;
-                let mut const_object = HashMap::with_capacity(object.len());
+                let mut const_object = IndexMap::with_capacity(object.len());
 
                 for (key, value) in object {
                     const_object.insert(key, Self::from_value(value)?);
@@ -414,7 +414,7 @@ impl<'de> de::Visitor<'de> for ConstValueVisitor {
     where
         V: de::MapAccess<'de>,
     {
-        let mut object = HashMap::new();
+        let mut object = IndexMap::new();
 
         while let Some((key, value)) = visitor.next_entry()? {
             object.insert(key, value);
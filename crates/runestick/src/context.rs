@@ -1,8 +1,10 @@
 use crate::{
     collections::{HashMap, HashSet},
     module::{
-        ModuleAssociatedFn, ModuleFn, ModuleInternalEnum, ModuleMacro, ModuleType, ModuleUnitType,
+        ModuleAssociatedFn, ModuleFn, ModuleInternalEnum, ModuleMacro, ModuleSnapshot, ModuleType,
+        ModuleUnitType,
     },
+    snapshot::AnySnapshot,
     CompileMeta, CompileMetaKind, CompileMetaStruct, CompileMetaTuple, ComponentRef, ConstValue,
     Hash, IntoComponent, Item, Module, Names, Protocol, RuntimeContext, Stack, StaticType,
     TypeCheck, TypeInfo, TypeOf, VmError,
@@ -69,6 +71,13 @@ pub enum ContextError {
         /// The type information for the type that already existed.
         existing: TypeInfo,
     },
+    /// Raised when we try to register snapshot support for a type more than
+    /// once.
+    #[error("snapshot support for type `{type_info}` already exists")]
+    ConflictingSnapshot {
+        /// The type we tried to register snapshot support for.
+        type_info: TypeInfo,
+    },
     /// Raised when we try to register a conflicting type hash.
     #[error("tried to insert conflicting hash `{hash}` for `{existing}`")]
     ConflictingTypeHash {
@@ -228,6 +237,8 @@ pub struct Context {
     crates: HashSet<Box<str>>,
     /// Constants visible in this context
     constants: HashMap<Hash, ConstValue>,
+    /// Native types which can be stored in snapshots.
+    snapshots: HashMap<Hash, AnySnapshot>,
 }
 
 impl Context {
@@ -245,6 +256,7 @@ impl Context {
         this.install(&crate::modules::any::module()?)?;
         this.install(&crate::modules::bytes::module()?)?;
         this.install(&crate::modules::char::module()?)?;
+        this.install(&crate::modules::cmp::module()?)?;
         this.install(&crate::modules::collections::module()?)?;
         this.install(&crate::modules::core::module()?)?;
         this.install(&crate::modules::float::module()?)?;
@@ -255,6 +267,7 @@ impl Context {
         this.install(&crate::modules::io::module(stdio)?)?;
         this.install(&crate::modules::iter::module()?)?;
         this.install(&crate::modules::mem::module()?)?;
+        this.install(&crate::modules::num::module()?)?;
         this.install(&crate::modules::object::module()?)?;
         this.install(&crate::modules::ops::module()?)?;
         this.install(&crate::modules::option::module()?)?;
@@ -286,6 +299,7 @@ impl Context {
             functions: self.functions.clone(),
             types: self.types.iter().map(|(k, t)| (*k, t.type_check)).collect(),
             constants: self.constants.clone(),
+            snapshots: self.snapshots.clone(),
         }
     }
 
@@ -305,6 +319,11 @@ impl Context {
         self.crates.contains(name)
     }
 
+    /// Iterate over all available crates.
+    pub fn iter_crates(&self) -> impl Iterator<Item = &str> {
+        self.crates.iter().map(|name| name.as_ref())
+    }
+
     /// Test if the context has the default modules installed.
     ///
     /// This determines among other things whether a prelude should be used or
@@ -401,6 +420,10 @@ impl Context {
             self.install_internal_enum(module, internal_enum)?;
         }
 
+        for (hash, snapshot) in &module.snapshots {
+            self.install_snapshot(*hash, snapshot)?;
+        }
+
         for (key, inst) in &module.associated_functions {
             self.install_associated_function(
                 key.type_hash,
@@ -413,6 +436,21 @@ impl Context {
         Ok(())
     }
 
+    /// Install support for storing a native type in snapshots.
+    fn install_snapshot(
+        &mut self,
+        hash: Hash,
+        snapshot: &ModuleSnapshot,
+    ) -> Result<(), ContextError> {
+        if self.snapshots.insert(hash, snapshot.snapshot).is_some() {
+            return Err(ContextError::ConflictingSnapshot {
+                type_info: snapshot.type_info.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
     /// Install the given meta.
     fn install_meta(&mut self, meta: CompileMeta) -> Result<(), ContextError> {
         if let Some(existing) = self.meta.insert(meta.item.item.clone(), meta.clone()) {
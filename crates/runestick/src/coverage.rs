@@ -0,0 +1,294 @@
+//! Code coverage collection for the virtual machine.
+//!
+//! Coverage is enabled per virtual machine through [Vm::set_coverage]. While
+//! enabled, the number of times each instruction has been executed is
+//! counted, which is mapped to spans and lines of source code using the
+//! [DebugInfo] of the unit.
+//!
+//! Like fuel, coverage is shared with any virtual machines spawned while
+//! executing, like the ones used by generators, async functions, and closures
+//! called from native functions.
+//!
+//! [Vm::set_coverage]: crate::Vm::set_coverage
+//! [DebugInfo]: crate::DebugInfo
+
+use crate::collections::HashMap;
+use crate::{Source, SourceId, Span, Unit};
+use serde::Serialize;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+
+thread_local!(static CURRENT: RefCell<Option<Coverage>> = RefCell::new(None));
+
+/// Coverage collected from the execution of virtual machines.
+///
+/// Clones of a coverage collector record into the same counters.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use runestick::coverage::Coverage;
+/// use runestick::{Context, Source, Unit, Vm};
+/// use std::sync::Arc;
+///
+/// # fn main() -> runestick::Result<()> {
+/// let context = Arc::new(Context::with_default_modules()?.runtime());
+/// let unit = Arc::new(Unit::default());
+/// let source = Source::new("main", "");
+///
+/// let coverage = Coverage::new();
+///
+/// let mut vm = Vm::new(context, unit);
+/// vm.set_coverage(Some(coverage.clone()));
+/// vm.execute(&["main"], ())?.complete()?;
+///
+/// for (line, hits) in coverage.lines(0, &source).lines {
+///     println!("{}: {}", line, hits);
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    state: Rc<RefCell<State>>,
+}
+
+impl Coverage {
+    /// Construct a new coverage collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the counters of the given unit, which are registered if they
+    /// aren't already.
+    fn counters(&self, unit: &Arc<Unit>) -> Counters {
+        let mut state = self.state.borrow_mut();
+        let address = Arc::as_ptr(unit) as usize;
+
+        if let Some(index) = state.unit_index.get(&address) {
+            return state.units[*index].1.clone();
+        }
+
+        let len = unit.iter_instructions().count();
+        let counters = Counters(Rc::from(vec![Cell::new(0); len]));
+        let index = state.units.len();
+
+        // NB: the unit is kept alive by the collector, so its address can't
+        // be reused by another unit.
+        state.units.push((unit.clone(), counters.clone()));
+        state.unit_index.insert(address, index);
+        counters
+    }
+
+    /// Get the spans of code which have instructions in them, along with the
+    /// number of times they were executed, ordered by source and span.
+    ///
+    /// Only units with debug info contribute spans.
+    pub fn spans(&self) -> Vec<SpanCoverage> {
+        let state = self.state.borrow();
+        let mut spans = HashMap::<(SourceId, Span), u64>::new();
+
+        for (unit, counters) in &state.units {
+            let debug = match unit.debug_info() {
+                Some(debug) => debug,
+                None => continue,
+            };
+
+            for (inst, hits) in debug.instructions.iter().zip(counters.0.iter()) {
+                let entry = spans.entry((inst.source_id, inst.span)).or_default();
+                *entry = u64::max(*entry, hits.get());
+            }
+        }
+
+        let mut spans = spans
+            .into_iter()
+            .map(|((source_id, span), hits)| SpanCoverage {
+                source_id,
+                span,
+                hits,
+            })
+            .collect::<Vec<_>>();
+
+        spans.sort_by_key(|s| (s.source_id, s.span));
+        spans
+    }
+
+    /// Get the line coverage of the given source.
+    ///
+    /// Only lines which have instructions on them are included.
+    pub fn lines(&self, source_id: SourceId, source: &Source) -> LineCoverage {
+        let mut lines = BTreeMap::new();
+
+        for span in self.spans() {
+            if span.source_id != source_id {
+                continue;
+            }
+
+            let (line, _) = source.position_to_unicode_line_char(span.span.start.into_usize());
+            let entry = lines.entry(line + 1).or_default();
+            *entry = u64::max(*entry, span.hits);
+        }
+
+        LineCoverage {
+            name: source.name().to_owned(),
+            path: source.path().map(ToOwned::to_owned),
+            lines,
+        }
+    }
+}
+
+/// Get the coverage of the virtual machine that is currently executing on
+/// this thread, if any.
+pub(crate) fn current() -> Option<Coverage> {
+    CURRENT.with(|tls| tls.borrow().clone())
+}
+
+/// Mark the given coverage as the coverage of the virtual machine currently
+/// executing on this thread until the returned guard is dropped.
+pub(crate) fn enter(coverage: Coverage) -> CoverageGuard {
+    CoverageGuard(CURRENT.with(|tls| tls.replace(Some(coverage))))
+}
+
+/// Guard which restores the previously executing coverage when dropped.
+pub(crate) struct CoverageGuard(Option<Coverage>);
+
+impl Drop for CoverageGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|tls| *tls.borrow_mut() = previous);
+    }
+}
+
+/// The coverage state of a single virtual machine.
+#[derive(Debug, Clone)]
+pub(crate) struct VmCoverage {
+    coverage: Coverage,
+    counters: Counters,
+}
+
+impl VmCoverage {
+    /// Construct the coverage state of a virtual machine executing the given
+    /// unit.
+    pub(crate) fn new(coverage: Coverage, unit: &Arc<Unit>) -> Self {
+        let counters = coverage.counters(unit);
+        Self { coverage, counters }
+    }
+
+    /// Get the coverage collector.
+    pub(crate) fn coverage(&self) -> &Coverage {
+        &self.coverage
+    }
+
+    /// Count the execution of the instruction at the given instruction
+    /// pointer.
+    #[inline]
+    pub(crate) fn hit(&self, ip: usize) {
+        if let Some(counter) = self.counters.0.get(ip) {
+            counter.set(counter.get().saturating_add(1));
+        }
+    }
+}
+
+/// A span of code and the number of times it was executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SpanCoverage {
+    /// The source the span belongs to.
+    pub source_id: SourceId,
+    /// The span.
+    pub span: Span,
+    /// The number of times the span was executed.
+    pub hits: u64,
+}
+
+/// The number of times each line of a source was executed.
+#[derive(Debug, Clone, Serialize)]
+pub struct LineCoverage {
+    /// The name of the source.
+    pub name: String,
+    /// The path of the source, if it was loaded from a file.
+    pub path: Option<PathBuf>,
+    /// One-based line numbers and the number of times they were executed.
+    pub lines: BTreeMap<usize, u64>,
+}
+
+/// Line coverage of a collection of sources, which can be written as an
+/// [lcov] tracefile or serialized.
+///
+/// [lcov]: http://ltp.sourceforge.net/coverage/lcov/geninfo.1.php
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CoverageReport {
+    /// The sources in the report.
+    pub files: Vec<LineCoverage>,
+}
+
+impl CoverageReport {
+    /// Construct a new empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert the coverage of a source into the report.
+    ///
+    /// If the source is already in the report, the number of times each line
+    /// was executed is added together.
+    pub fn insert(&mut self, coverage: LineCoverage) {
+        let existing = self
+            .files
+            .iter_mut()
+            .find(|f| f.path == coverage.path && f.name == coverage.name);
+
+        let existing = match existing {
+            Some(existing) => existing,
+            None => {
+                self.files.push(coverage);
+                return;
+            }
+        };
+
+        for (line, hits) in coverage.lines {
+            let entry = existing.lines.entry(line).or_default();
+            *entry = entry.saturating_add(hits);
+        }
+    }
+
+    /// Write the report as an lcov tracefile.
+    pub fn write_lcov<O>(&self, out: &mut O) -> io::Result<()>
+    where
+        O: io::Write,
+    {
+        for file in &self.files {
+            writeln!(out, "TN:")?;
+
+            match &file.path {
+                Some(path) => writeln!(out, "SF:{}", path.display())?,
+                None => writeln!(out, "SF:{}", file.name)?,
+            }
+
+            for (line, hits) in &file.lines {
+                writeln!(out, "DA:{},{}", line, hits)?;
+            }
+
+            let hit = file.lines.values().filter(|hits| **hits > 0).count();
+            writeln!(out, "LF:{}", file.lines.len())?;
+            writeln!(out, "LH:{}", hit)?;
+            writeln!(out, "end_of_record")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Counters(Rc<[Cell<u64>]>);
+
+#[derive(Debug, Default)]
+struct State {
+    /// Units and the number of times each of their instructions has been
+    /// executed.
+    units: Vec<(Arc<Unit>, Counters)>,
+    /// Units by address.
+    unit_index: HashMap<usize, usize>,
+}
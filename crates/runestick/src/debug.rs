@@ -3,6 +3,7 @@
 use crate::collections::HashMap;
 use crate::{DebugLabel, Hash, Item, Span};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
 /// Debug information about a unit.
@@ -13,7 +14,10 @@ pub struct DebugInfo {
     /// Function signatures.
     pub functions: HashMap<Hash, DebugSignature>,
     /// Reverse lookup of a function.
-    pub functions_rev: HashMap<usize, Hash>,
+    pub functions_rev: BTreeMap<usize, Hash>,
+    /// Local variables, indexed by the instruction pointer they're declared
+    /// at, in the order they're declared.
+    pub variables: BTreeMap<usize, Vec<DebugVariable>>,
 }
 
 impl DebugInfo {
@@ -28,6 +32,47 @@ impl DebugInfo {
         let signature = self.functions.get(&hash)?;
         Some((hash, signature))
     }
+
+    /// Get the instruction pointer where the function containing the given
+    /// instruction pointer starts.
+    pub fn function_start(&self, ip: usize) -> Option<usize> {
+        let (start, _) = self.functions_rev.range(..=ip).next_back()?;
+        Some(*start)
+    }
+
+    /// Insert debug information for a local variable.
+    pub fn insert_variable(&mut self, variable: DebugVariable) {
+        self.variables
+            .entry(variable.ip)
+            .or_default()
+            .push(variable);
+    }
+
+    /// Get the local variables which are in scope at the given instruction
+    /// pointer, ordered by their stack offset.
+    ///
+    /// If more than one variable has been declared with the same offset, the
+    /// one declared last shadows the others.
+    pub fn variables_at(&self, ip: usize) -> Vec<&DebugVariable> {
+        let start = match self.function_start(ip) {
+            Some(start) => start,
+            None => return Vec::new(),
+        };
+
+        let mut variables = HashMap::<usize, &DebugVariable>::new();
+
+        for variable in self.variables.range(start..=ip).flat_map(|(_, v)| v) {
+            if matches!(variable.end, Some(end) if end <= ip) {
+                continue;
+            }
+
+            variables.insert(variable.offset, variable);
+        }
+
+        let mut variables = variables.into_iter().map(|(_, v)| v).collect::<Vec<_>>();
+        variables.sort_by_key(|v| v.offset);
+        variables
+    }
 }
 
 /// Debug information for every instruction.
@@ -43,6 +88,22 @@ pub struct DebugInst {
     pub label: Option<DebugLabel>,
 }
 
+/// Debug information on a local variable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugVariable {
+    /// The instruction pointer from which the variable is declared.
+    pub ip: usize,
+    /// The instruction pointer at which the variable goes out of scope, if
+    /// it's known.
+    pub end: Option<usize>,
+    /// The name of the variable.
+    pub name: String,
+    /// The offset of the variable from the bottom of the stack frame.
+    pub offset: usize,
+    /// The span where the variable is declared.
+    pub span: Span,
+}
+
 /// Debug information on function arguments.
 #[derive(Debug, Serialize, Deserialize)]
 pub enum DebugArgs {
@@ -0,0 +1,451 @@
+//! An interactive debugger for virtual machine executions.
+//!
+//! The [Debugger] wraps a [VmExecution] and drives it one instruction at a
+//! time through [VmExecution::step]. It maps instructions to source lines
+//! using the [DebugInfo][crate::DebugInfo] of the unit, which allows it to
+//! stop at breakpoints and to step over, into and out of functions.
+//!
+//! Lines are zero-based, like the ones returned by
+//! [Source::position_to_unicode_line_char].
+//!
+//! Runs can be advanced a bounded number of instructions at a time with
+//! [Debugger::start] and [Debugger::advance], which allows a caller to handle
+//! other things, like a request to pause, while a long run is in progress.
+//!
+//! Note that async instructions aren't supported by the debugger, since it
+//! steps the execution synchronously.
+
+use crate::collections::{HashMap, HashSet};
+use crate::debug::{DebugSignature, DebugVariable};
+use crate::{Source, SourceId, Span, Unit, Value, VmError, VmExecution};
+use std::sync::Arc;
+
+/// A line in a source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SourceLine {
+    /// The source the line belongs to.
+    pub source_id: SourceId,
+    /// The zero-based line number.
+    pub line: usize,
+}
+
+/// The reason why the debugger stopped.
+#[derive(Debug)]
+pub enum Stop {
+    /// Execution reached a line with a breakpoint.
+    Breakpoint(SourceLine),
+    /// A step was completed.
+    Step,
+    /// Execution completed with the given value.
+    Complete(Value),
+}
+
+/// How far to run the execution. See [Debugger::start].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Run until a breakpoint is hit, like [Debugger::resume].
+    Continue,
+    /// Step over calls to another line, like [Debugger::step_over].
+    StepOver,
+    /// Step to another line, like [Debugger::step_into].
+    StepInto,
+    /// Run until the current function returns, like [Debugger::step_out].
+    StepOut,
+}
+
+/// A run which is in progress.
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    mode: Mode,
+    /// The call depth and line the run started at.
+    start: (usize, Option<SourceLine>),
+    /// The call depth and line of the last instruction which was stepped.
+    last: (usize, Option<SourceLine>),
+}
+
+/// A debugger driving a [VmExecution].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut debugger = Debugger::new(vm.execute(&["main"], ())?);
+/// debugger.insert_source(source_id, source);
+/// debugger.set_breakpoint(source_id, 2);
+///
+/// while let Stop::Breakpoint(..) | Stop::Step = debugger.resume()? {
+///     for (name, value) in debugger.frames()[0].locals() {
+///         println!("{} = {:?}", name, value);
+///     }
+/// }
+/// ```
+pub struct Debugger {
+    execution: VmExecution,
+    /// Sources used to map instructions to lines.
+    sources: HashMap<SourceId, Arc<Source>>,
+    /// Lines to stop at.
+    breakpoints: HashSet<SourceLine>,
+    /// The run which is in progress, if any.
+    run: Option<Run>,
+}
+
+impl Debugger {
+    /// Construct a new debugger for the given execution.
+    pub fn new(execution: VmExecution) -> Self {
+        Self {
+            execution,
+            sources: HashMap::new(),
+            breakpoints: HashSet::new(),
+            run: None,
+        }
+    }
+
+    /// Insert a source, which is used to map instructions belonging to it to
+    /// lines.
+    pub fn insert_source(&mut self, source_id: SourceId, source: Arc<Source>) {
+        self.sources.insert(source_id, source);
+    }
+
+    /// Access the execution being debugged.
+    pub fn execution(&self) -> &VmExecution {
+        &self.execution
+    }
+
+    /// Access the execution being debugged mutably.
+    pub fn execution_mut(&mut self) -> &mut VmExecution {
+        &mut self.execution
+    }
+
+    /// Convert the debugger into the execution being debugged.
+    pub fn into_execution(self) -> VmExecution {
+        self.execution
+    }
+
+    /// Set a breakpoint on the given line.
+    ///
+    /// Returns `false` if there are no instructions on the line, in which
+    /// case the breakpoint can't be hit.
+    pub fn set_breakpoint(&mut self, source_id: SourceId, line: usize) -> bool {
+        let line = SourceLine { source_id, line };
+        self.breakpoints.insert(line);
+        self.has_instructions(line)
+    }
+
+    /// Clear the breakpoint on the given line. Returns `true` if there was
+    /// one.
+    pub fn clear_breakpoint(&mut self, source_id: SourceId, line: usize) -> bool {
+        self.breakpoints.remove(&SourceLine { source_id, line })
+    }
+
+    /// Clear every breakpoint.
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Iterate over the breakpoints which are set.
+    pub fn breakpoints(&self) -> impl Iterator<Item = SourceLine> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    /// Get the line the execution is currently at.
+    pub fn current_line(&self) -> Option<SourceLine> {
+        let vm = self.execution.vm().ok()?;
+        self.line_at(vm.unit(), vm.ip())
+    }
+
+    /// Run until a breakpoint is hit or the execution completes.
+    pub fn resume(&mut self) -> Result<Stop, VmError> {
+        self.run(Mode::Continue)
+    }
+
+    /// Run until the execution reaches another line in the current function,
+    /// or returns from it. Calls are stepped over.
+    pub fn step_over(&mut self) -> Result<Stop, VmError> {
+        self.run(Mode::StepOver)
+    }
+
+    /// Run until the execution reaches another line, which might be in a
+    /// function being called.
+    pub fn step_into(&mut self) -> Result<Stop, VmError> {
+        self.run(Mode::StepInto)
+    }
+
+    /// Run until the execution returns from the current function.
+    pub fn step_out(&mut self) -> Result<Stop, VmError> {
+        self.run(Mode::StepOut)
+    }
+
+    /// Start a run in the given mode, without executing any instructions.
+    ///
+    /// The run is executed with [Debugger::advance], and replaces any run
+    /// which was already in progress.
+    pub fn start(&mut self, mode: Mode) {
+        self.run = Some(self.begin(mode));
+    }
+
+    /// Advance the run which is in progress by at most `budget` instructions.
+    /// If no run is in progress, one is started with [Mode::Continue].
+    ///
+    /// Returns `None` if the budget ran out before the run stopped, in which
+    /// case it can be advanced again.
+    pub fn advance(&mut self, budget: usize) -> Result<Option<Stop>, VmError> {
+        let mut run = match self.run.take() {
+            Some(run) => run,
+            None => self.begin(Mode::Continue),
+        };
+
+        for _ in 0..budget {
+            if let Some(value) = self.execution.step()? {
+                return Ok(Some(Stop::Complete(value)));
+            }
+
+            let depth = self.depth();
+
+            let line = match self.current_line() {
+                Some(line) => line,
+                None => continue,
+            };
+
+            // NB: a breakpoint is hit when entering its line, either from
+            // another line in the same function or by calling into it.
+            let entered = depth > run.last.0 || (depth == run.last.0 && Some(line) != run.last.1);
+            run.last = (depth, Some(line));
+
+            if entered && self.breakpoints.contains(&line) {
+                return Ok(Some(Stop::Breakpoint(line)));
+            }
+
+            let (start_depth, start_line) = run.start;
+
+            let stop = match run.mode {
+                Mode::Continue => false,
+                Mode::StepOver => {
+                    depth < start_depth || (depth == start_depth && Some(line) != start_line)
+                }
+                Mode::StepInto => depth != start_depth || Some(line) != start_line,
+                Mode::StepOut => depth < start_depth,
+            };
+
+            if stop {
+                return Ok(Some(Stop::Step));
+            }
+        }
+
+        self.run = Some(run);
+        Ok(None)
+    }
+
+    /// Get the call frames of the execution, starting with the innermost
+    /// one.
+    ///
+    /// This includes the frames of virtual machines which the execution has
+    /// called into.
+    pub fn frames(&self) -> Vec<Frame> {
+        let mut out = Vec::new();
+
+        for vm in &self.execution.vms {
+            let frames = vm.call_frames();
+            let stack = vm.stack();
+
+            for (n, frame) in frames.iter().enumerate() {
+                let stack_top = match frames.get(n + 1) {
+                    Some(next) => next.stack_bottom(),
+                    None => stack.stack_bottom(),
+                };
+
+                out.push(Frame::new(
+                    vm.unit(),
+                    frame.ip(),
+                    stack.get(frame.stack_bottom()..stack_top),
+                ));
+            }
+
+            out.push(Frame::new(
+                vm.unit(),
+                vm.ip(),
+                stack.get(stack.stack_bottom()..),
+            ));
+        }
+
+        out.reverse();
+        out
+    }
+
+    /// Evaluate a path in the frame with the given index, where `0` is the
+    /// innermost frame.
+    ///
+    /// A path is the name of a local variable, followed by any number of
+    /// field names or tuple and vector indexes separated by dots, like
+    /// `object.field.0`.
+    ///
+    /// Returns `None` if the path doesn't refer to a value.
+    pub fn evaluate(&self, frame: usize, path: &str) -> Option<Value> {
+        self.frames().get(frame)?.evaluate(path)
+    }
+
+    /// Run the execution in the given mode until it stops.
+    fn run(&mut self, mode: Mode) -> Result<Stop, VmError> {
+        self.start(mode);
+
+        loop {
+            if let Some(stop) = self.advance(usize::MAX)? {
+                return Ok(stop);
+            }
+        }
+    }
+
+    /// Begin a run in the given mode from the current location.
+    fn begin(&self, mode: Mode) -> Run {
+        let start = (self.depth(), self.current_line());
+
+        Run {
+            mode,
+            start,
+            last: start,
+        }
+    }
+
+    /// The total number of call frames in the execution.
+    fn depth(&self) -> usize {
+        self.execution
+            .vms
+            .iter()
+            .map(|vm| vm.call_frames().len() + 1)
+            .sum()
+    }
+
+    /// Get the line of the instruction at the given instruction pointer.
+    fn line_at(&self, unit: &Unit, ip: usize) -> Option<SourceLine> {
+        let inst = unit.debug_info()?.instruction_at(ip)?;
+        let source = self.sources.get(&inst.source_id)?;
+        let (line, _) = source.position_to_unicode_line_char(inst.span.start.into_usize());
+
+        Some(SourceLine {
+            source_id: inst.source_id,
+            line,
+        })
+    }
+
+    /// Test if any instruction is on the given line.
+    fn has_instructions(&self, line: SourceLine) -> bool {
+        let source = match self.sources.get(&line.source_id) {
+            Some(source) => source,
+            None => return false,
+        };
+
+        self.execution.vms.iter().any(|vm| {
+            let debug = match vm.unit().debug_info() {
+                Some(debug) => debug,
+                None => return false,
+            };
+
+            debug.instructions.iter().any(|inst| {
+                inst.source_id == line.source_id
+                    && source
+                        .position_to_unicode_line_char(inst.span.start.into_usize())
+                        .0
+                        == line.line
+            })
+        })
+    }
+}
+
+/// A call frame in an execution being debugged.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    unit: Arc<Unit>,
+    ip: usize,
+    /// The values on the stack of the frame.
+    stack: Vec<Value>,
+}
+
+impl Frame {
+    fn new(unit: &Arc<Unit>, ip: usize, stack: Option<&[Value]>) -> Self {
+        Self {
+            unit: unit.clone(),
+            ip,
+            stack: stack.map(<[Value]>::to_vec).unwrap_or_default(),
+        }
+    }
+
+    /// Get the instruction pointer of the frame.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// Get the signature of the function the frame belongs to.
+    pub fn signature(&self) -> Option<&DebugSignature> {
+        let debug = self.unit.debug_info()?;
+        let start = debug.function_start(self.ip)?;
+        Some(debug.function_at(start)?.1)
+    }
+
+    /// Get the source and the span of the instruction the frame is at.
+    pub fn span(&self) -> Option<(SourceId, Span)> {
+        let inst = self.unit.debug_info()?.instruction_at(self.ip)?;
+        Some((inst.source_id, inst.span))
+    }
+
+    /// Get the values on the stack of the frame.
+    pub fn stack(&self) -> &[Value] {
+        &self.stack
+    }
+
+    /// Get the local variables of the frame, ordered by their position on
+    /// the stack.
+    pub fn locals(&self) -> Vec<(&str, &Value)> {
+        self.variables()
+            .into_iter()
+            .filter_map(|variable| {
+                let value = self.stack.get(variable.offset)?;
+                Some((variable.name.as_str(), value))
+            })
+            .collect()
+    }
+
+    /// Evaluate a path in the frame. See [Debugger::evaluate].
+    pub fn evaluate(&self, path: &str) -> Option<Value> {
+        let mut it = path.split('.').map(str::trim);
+        let name = it.next()?;
+
+        let variable = self
+            .variables()
+            .into_iter()
+            .rev()
+            .find(|variable| variable.name == name)?;
+
+        let mut value = self.stack.get(variable.offset)?.clone();
+
+        for field in it {
+            value = field_of(&value, field)?;
+        }
+
+        Some(value)
+    }
+
+    fn variables(&self) -> Vec<&DebugVariable> {
+        match self.unit.debug_info() {
+            Some(debug) => debug.variables_at(self.ip),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Access the given field of a value.
+fn field_of(value: &Value, field: &str) -> Option<Value> {
+    let index = field.parse::<usize>().ok();
+
+    Some(match (value, index) {
+        (Value::Object(object), _) => object.borrow_ref().ok()?.get(field)?.clone(),
+        (Value::Struct(object), _) => object.borrow_ref().ok()?.data.get(field)?.clone(),
+        (Value::StructVariant(object), _) => object.borrow_ref().ok()?.data.get(field)?.clone(),
+        (Value::Vec(vec), Some(index)) => vec.borrow_ref().ok()?.get(index)?.clone(),
+        (Value::Tuple(tuple), Some(index)) => tuple.borrow_ref().ok()?.get(index)?.clone(),
+        (Value::TupleStruct(tuple), Some(index)) => {
+            tuple.borrow_ref().ok()?.data.get(index)?.clone()
+        }
+        (Value::TupleVariant(tuple), Some(index)) => {
+            tuple.borrow_ref().ok()?.data.get(index)?.clone()
+        }
+        _ => return None,
+    })
+}
@@ -0,0 +1,394 @@
+//! A fixed-point decimal number, available as `std::num::Decimal`.
+
+use crate::Any;
+use num_bigint::{BigInt, Sign};
+use num_traits::ToPrimitive as _;
+use std::cmp;
+use std::fmt;
+use std::hash;
+use std::ops;
+use std::str;
+use thiserror::Error;
+
+/// The minimum number of fractional digits kept when dividing two decimals.
+const DIV_SCALE: u32 = 20;
+
+/// Error raised when a string can't be parsed as a [Decimal].
+#[derive(Debug, Clone, Copy, Error, Any)]
+#[rune(module = "crate")]
+#[error("invalid decimal literal")]
+pub struct ParseDecimalError(());
+
+/// A fixed-point decimal number.
+///
+/// This is stored as an arbitrary-precision integer together with the number of
+/// fractional digits it has, so `12.50` is stored as `1250` with a scale of
+/// `2`. Addition, subtraction and multiplication are exact, and the scale of
+/// the operands is preserved in the result.
+///
+/// ```rust
+/// use runestick::Decimal;
+///
+/// let a: Decimal = "0.10".parse().unwrap();
+/// let b: Decimal = "0.2".parse().unwrap();
+/// assert_eq!((&a + &b).to_string(), "0.30");
+/// assert_eq!((&a * &b).to_string(), "0.020");
+/// ```
+#[derive(Debug, Clone, Any)]
+#[rune(module = "crate")]
+pub struct Decimal {
+    value: BigInt,
+    scale: u32,
+}
+
+impl Decimal {
+    /// The maximum number of fractional digits a decimal can have.
+    pub const MAX_SCALE: u32 = 28;
+
+    /// Construct a decimal from an unscaled value and the number of fractional
+    /// digits it has.
+    ///
+    /// Returns `None` if the scale is larger than [Decimal::MAX_SCALE].
+    ///
+    /// ```rust
+    /// use runestick::Decimal;
+    ///
+    /// assert_eq!(Decimal::new(1250.into(), 2).unwrap().to_string(), "12.50");
+    /// assert!(Decimal::new(1.into(), 29).is_none());
+    /// ```
+    pub fn new(value: BigInt, scale: u32) -> Option<Self> {
+        if scale > Self::MAX_SCALE {
+            return None;
+        }
+
+        Some(Self { value, scale })
+    }
+
+    /// Construct a decimal from a whole number.
+    pub fn from_integer(value: i64) -> Self {
+        Self {
+            value: BigInt::from(value),
+            scale: 0,
+        }
+    }
+
+    /// The number of fractional digits in the decimal.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Test if the decimal is zero.
+    pub fn is_zero(&self) -> bool {
+        self.value.sign() == Sign::NoSign
+    }
+
+    /// An upper bound on the number of bits needed to store the unscaled
+    /// value of the decimal while it's being operated on, which might rescale
+    /// it.
+    pub(crate) fn max_bits(&self) -> u64 {
+        // NB: a decimal digit fits in 4 bits.
+        self.value.bits() + u64::from(Self::MAX_SCALE) * 2 * 4
+    }
+
+    /// Divide two decimals, returning `None` if `other` is zero.
+    ///
+    /// The quotient is truncated to at least 20 fractional digits, and
+    /// trailing zeros beyond the scale of the operands are removed.
+    ///
+    /// ```rust
+    /// use runestick::Decimal;
+    ///
+    /// let a: Decimal = "10.00".parse().unwrap();
+    /// let b: Decimal = "4".parse().unwrap();
+    /// assert_eq!(a.checked_div(&b).unwrap().to_string(), "2.50");
+    /// ```
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+
+        let min_scale = cmp::max(self.scale, other.scale);
+        let scale = cmp::max(min_scale, DIV_SCALE);
+
+        let numerator = &self.value * pow10(other.scale + scale)?;
+        let denominator = &other.value * pow10(self.scale)?;
+
+        let value = Self {
+            value: numerator / denominator,
+            scale,
+        };
+
+        Some(value.trimmed(min_scale))
+    }
+
+    /// Calculate the remainder of dividing two decimals, returning `None` if
+    /// `other` is zero.
+    pub fn checked_rem(&self, other: &Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+
+        let scale = cmp::max(self.scale, other.scale);
+
+        Some(Self {
+            value: self.rescaled(scale) % other.rescaled(scale),
+            scale,
+        })
+    }
+
+    /// Round the decimal to the given number of fractional digits, rounding
+    /// half-way cases away from zero.
+    ///
+    /// Returns `None` if the scale is larger than [Decimal::MAX_SCALE].
+    ///
+    /// ```rust
+    /// use runestick::Decimal;
+    ///
+    /// let a: Decimal = "-2.345".parse().unwrap();
+    /// assert_eq!(a.round(2).unwrap().to_string(), "-2.35");
+    /// ```
+    pub fn round(&self, scale: u32) -> Option<Self> {
+        if scale > Self::MAX_SCALE {
+            return None;
+        }
+
+        if scale >= self.scale {
+            return Self::new(self.rescaled(scale), scale);
+        }
+
+        Some(self.rounded(scale))
+    }
+
+    /// Truncate the decimal to the given number of fractional digits.
+    ///
+    /// Returns `None` if the scale is larger than [Decimal::MAX_SCALE].
+    pub fn trunc(&self, scale: u32) -> Option<Self> {
+        if scale > Self::MAX_SCALE {
+            return None;
+        }
+
+        if scale >= self.scale {
+            return Self::new(self.rescaled(scale), scale);
+        }
+
+        Some(Self {
+            value: &self.value / pow10(self.scale - scale)?,
+            scale,
+        })
+    }
+
+    /// Remove trailing fractional zeros from the decimal.
+    pub fn normalize(&self) -> Self {
+        self.trimmed(0)
+    }
+
+    /// Convert into a whole number, truncating any fractional digits.
+    ///
+    /// Returns `None` if the number doesn't fit in an `i64`.
+    pub fn to_integer(&self) -> Option<i64> {
+        self.trunc(0)?.value.to_i64()
+    }
+
+    /// Convert into the closest floating point number.
+    pub fn to_float(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// Get the unscaled value scaled up to the given scale, which must not be
+    /// smaller than the current scale.
+    ///
+    /// NB: scales are at most [Decimal::MAX_SCALE], which bounds the power.
+    fn rescaled(&self, scale: u32) -> BigInt {
+        &self.value * BigInt::from(10).pow(scale - self.scale)
+    }
+
+    /// Round to the given scale, which must be smaller than the current scale.
+    fn rounded(&self, scale: u32) -> Self {
+        let divisor = BigInt::from(10).pow(self.scale - scale);
+        let half = &divisor / 2;
+
+        let value = if self.value.sign() == Sign::Minus {
+            (&self.value - half) / divisor
+        } else {
+            (&self.value + half) / divisor
+        };
+
+        Self { value, scale }
+    }
+
+    /// Remove trailing fractional zeros, but keep at least `min_scale`
+    /// fractional digits.
+    fn trimmed(&self, min_scale: u32) -> Self {
+        let ten = BigInt::from(10);
+        let mut value = self.value.clone();
+        let mut scale = self.scale;
+
+        while scale > min_scale && (&value % &ten).sign() == Sign::NoSign {
+            value /= &ten;
+            scale -= 1;
+        }
+
+        Self { value, scale }
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Self::from_integer(value)
+    }
+}
+
+impl ops::Add for &Decimal {
+    type Output = Decimal;
+
+    fn add(self, other: Self) -> Decimal {
+        let scale = cmp::max(self.scale, other.scale);
+
+        Decimal {
+            value: self.rescaled(scale) + other.rescaled(scale),
+            scale,
+        }
+    }
+}
+
+impl ops::Sub for &Decimal {
+    type Output = Decimal;
+
+    fn sub(self, other: Self) -> Decimal {
+        let scale = cmp::max(self.scale, other.scale);
+
+        Decimal {
+            value: self.rescaled(scale) - other.rescaled(scale),
+            scale,
+        }
+    }
+}
+
+impl ops::Mul for &Decimal {
+    type Output = Decimal;
+
+    /// Multiply two decimals, rounding the product to [Decimal::MAX_SCALE]
+    /// fractional digits if it has more than that.
+    fn mul(self, other: Self) -> Decimal {
+        let product = Decimal {
+            value: &self.value * &other.value,
+            scale: self.scale + other.scale,
+        };
+
+        if product.scale > Decimal::MAX_SCALE {
+            return product.rounded(Decimal::MAX_SCALE);
+        }
+
+        product
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl hash::Hash for Decimal {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        // NB: equal decimals only differ in their trailing zeros.
+        let normalized = self.normalize();
+        normalized.value.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        let scale = cmp::max(self.scale, other.scale);
+        self.rescaled(scale).cmp(&other.rescaled(scale))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.value.magnitude().to_string();
+        let scale = self.scale as usize;
+
+        if self.value.sign() == Sign::Minus {
+            write!(f, "-")?;
+        }
+
+        if scale == 0 {
+            return write!(f, "{}", digits);
+        }
+
+        if digits.len() <= scale {
+            write!(f, "0.")?;
+
+            for _ in digits.len()..scale {
+                write!(f, "0")?;
+            }
+
+            return write!(f, "{}", digits);
+        }
+
+        let (whole, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{}.{}", whole, fraction)
+    }
+}
+
+impl str::FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    /// Parse a decimal like `-12.50`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, s) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+
+        let (whole, fraction) = match s.find('.') {
+            Some(n) => (&s[..n], &s[n + 1..]),
+            None => (s, ""),
+        };
+
+        let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+
+        if whole.is_empty() && fraction.is_empty() || !all_digits(whole) || !all_digits(fraction) {
+            return Err(ParseDecimalError(()));
+        }
+
+        if fraction.len() > Self::MAX_SCALE as usize {
+            return Err(ParseDecimalError(()));
+        }
+
+        let mut digits = String::with_capacity(whole.len() + fraction.len());
+        digits.push_str(whole);
+        digits.push_str(fraction);
+
+        let value = digits
+            .parse::<BigInt>()
+            .map_err(|_| ParseDecimalError(()))?;
+
+        let value = if negative { -value } else { value };
+        Ok(Self {
+            value,
+            scale: fraction.len() as u32,
+        })
+    }
+}
+
+/// Calculate `10^n`, returning `None` if `n` is larger than any power needed
+/// to operate on decimals with at most [Decimal::MAX_SCALE] fractional digits.
+fn pow10(n: u32) -> Option<BigInt> {
+    if n > Decimal::MAX_SCALE * 2 {
+        return None;
+    }
+
+    Some(BigInt::from(10).pow(n))
+}
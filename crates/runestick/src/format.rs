@@ -113,6 +113,35 @@ impl FormatSpec {
         buf.push_str(buffer.format(n));
     }
 
+    /// Format the given sized integer.
+    fn format_sized_integer(
+        &self,
+        value: &Value,
+        out: &mut String,
+        buf: &mut String,
+    ) -> Result<(), VmErrorKind> {
+        let n = value
+            .clone()
+            .into_wide_integer()
+            .map_err(|_| VmErrorKind::FormatError)?;
+
+        let (n, align, fill, sign) = if self.flags.test(Flag::SignAwareZeroPad) {
+            if n < 0 {
+                (-n, Alignment::Right, '0', Some('-'))
+            } else {
+                (n, Alignment::Right, '0', None)
+            }
+        } else if self.flags.test(Flag::SignPlus) && n >= 0 {
+            (n, self.align, self.fill, Some('+'))
+        } else {
+            (n, self.align, self.fill, None)
+        };
+
+        write!(buf, "{}", n).map_err(|_| VmErrorKind::FormatError)?;
+        self.format_fill(out, buf, align, fill, sign);
+        Ok(())
+    }
+
     /// Format the given float.
     fn format_float(&self, buf: &mut String, n: f64) -> Result<(), VmErrorKind> {
         if let Some(precision) = self.precision {
@@ -202,6 +231,9 @@ impl FormatSpec {
                 self.format_float(buf, n)?;
                 self.format_fill(out, buf, align, fill, sign);
             }
+            Value::U16(..) | Value::U32(..) | Value::U64(..) | Value::I32(..) | Value::I128(..) => {
+                self.format_sized_integer(value, out, buf)?;
+            }
             _ => {
                 return Err(VmErrorKind::FormatError);
             }
@@ -233,6 +265,9 @@ impl FormatSpec {
                 self.format_float(buf, n)?;
                 self.format_fill(out, buf, align, fill, sign);
             }
+            Value::U16(..) | Value::U32(..) | Value::U64(..) | Value::I32(..) | Value::I128(..) => {
+                self.format_sized_integer(value, out, buf)?;
+            }
             value => {
                 write!(out, "{:?}", value).map_err(|_| VmErrorKind::FormatError)?;
             }
@@ -351,18 +351,12 @@ impl FromValue for char {
     }
 }
 
-impl FromValue for i64 {
-    fn from_value(value: Value) -> Result<Self, VmError> {
-        Ok(value.into_integer()?)
-    }
-}
-
 macro_rules! impl_number {
     ($ty:ty) => {
         impl FromValue for $ty {
             fn from_value(value: Value) -> Result<Self, VmError> {
                 use std::convert::TryInto as _;
-                let integer = value.into_integer()?;
+                let integer = value.into_wide_integer()?;
 
                 match integer.try_into() {
                     Ok(number) => Ok(number),
@@ -378,12 +372,15 @@ macro_rules! impl_number {
     };
 }
 
+impl_number!(u16);
 impl_number!(u32);
 impl_number!(u64);
 impl_number!(u128);
 impl_number!(usize);
 impl_number!(i8);
+impl_number!(i16);
 impl_number!(i32);
+impl_number!(i64);
 impl_number!(i128);
 impl_number!(isize);
 
@@ -0,0 +1,145 @@
+//! Instruction-level fuel metering for the virtual machine.
+//!
+//! Fuel is enabled per virtual machine through [Vm::set_fuel], and every
+//! instruction executed consumes fuel according to the [FuelCosts] configured
+//! for it. When the virtual machine doesn't have enough fuel left to execute
+//! the next instruction, it halts with [VmHalt::OutOfFuel] without executing
+//! it. The host can then add more fuel and resume the execution.
+//!
+//! Fuel is shared with any virtual machines spawned while executing, like the
+//! ones used by generators, async functions, and closures called from native
+//! functions. Note that those virtual machines are executed separately, like
+//! by a native function calling a closure or by a generator being resumed, so
+//! they can't be suspended. The same goes for comparison protocol functions
+//! like `eq` and `cmp` implemented in the script, which are run to completion
+//! from within the instruction comparing the values. If they run out of fuel, they instead fail
+//! with [VmErrorKind::OutOfFuel], which can't be resumed.
+//!
+//! [Vm::set_fuel]: crate::Vm::set_fuel
+//! [VmHalt::OutOfFuel]: crate::VmHalt::OutOfFuel
+//! [VmErrorKind::OutOfFuel]: crate::VmErrorKind::OutOfFuel
+
+use crate::Inst;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+thread_local!(static CURRENT: RefCell<Option<(Fuel, FuelCosts)>> = RefCell::new(None));
+
+/// The amount of fuel consumed by different classes of instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuelCosts {
+    /// The cost of an instruction which isn't a call or an allocation.
+    pub instruction: u64,
+    /// The cost of calling a function.
+    pub call: u64,
+    /// The cost of an instruction which allocates a new value, like a vector
+    /// or a string.
+    pub alloc: u64,
+}
+
+impl FuelCosts {
+    /// The default costs used when fuel is enabled.
+    pub const DEFAULT: Self = Self {
+        instruction: 1,
+        call: 10,
+        alloc: 5,
+    };
+
+    /// Get the cost of executing the given instruction.
+    pub fn cost(&self, inst: &Inst) -> u64 {
+        match inst {
+            Inst::Call { .. } | Inst::CallInstance { .. } | Inst::CallFn { .. } => self.call,
+            Inst::Closure { .. }
+            | Inst::Vec { .. }
+            | Inst::Tuple { .. }
+            | Inst::Tuple1 { .. }
+            | Inst::Tuple2 { .. }
+            | Inst::Tuple3 { .. }
+            | Inst::Tuple4 { .. }
+            | Inst::Object { .. }
+            | Inst::Range { .. }
+            | Inst::Struct { .. }
+            | Inst::StructVariant { .. }
+            | Inst::String { .. }
+            | Inst::Bytes { .. }
+            | Inst::StringConcat { .. }
+            | Inst::Format { .. } => self.alloc,
+            _ => self.instruction,
+        }
+    }
+}
+
+impl Default for FuelCosts {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The fuel remaining for a virtual machine, which is shared with any virtual
+/// machines it spawns.
+#[derive(Debug, Clone)]
+pub(crate) struct Fuel {
+    remaining: Rc<Cell<u64>>,
+}
+
+impl Fuel {
+    /// Construct fuel with the given amount remaining.
+    pub(crate) fn new(remaining: u64) -> Self {
+        Self {
+            remaining: Rc::new(Cell::new(remaining)),
+        }
+    }
+
+    /// Get the amount of fuel remaining.
+    pub(crate) fn remaining(&self) -> u64 {
+        self.remaining.get()
+    }
+
+    /// Set the amount of fuel remaining.
+    pub(crate) fn set(&self, remaining: u64) {
+        self.remaining.set(remaining);
+    }
+
+    /// Add to the fuel remaining.
+    pub(crate) fn add(&self, amount: u64) {
+        self.remaining
+            .set(self.remaining.get().saturating_add(amount));
+    }
+
+    /// Take the fuel needed to execute the given instruction, indicating with
+    /// `false` if there isn't enough fuel remaining.
+    #[inline]
+    pub(crate) fn take(&self, costs: &FuelCosts, inst: &Inst) -> bool {
+        let cost = costs.cost(inst);
+
+        match self.remaining.get().checked_sub(cost) {
+            Some(remaining) => {
+                self.remaining.set(remaining);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Get the fuel of the virtual machine that is currently executing on this
+/// thread, if any.
+pub(crate) fn current() -> Option<(Fuel, FuelCosts)> {
+    CURRENT.with(|tls| tls.borrow().clone())
+}
+
+/// Mark the given fuel as the fuel of the virtual machine currently executing
+/// on this thread until the returned guard is dropped.
+pub(crate) fn enter(fuel: Fuel, costs: FuelCosts) -> FuelGuard {
+    FuelGuard(CURRENT.with(|tls| tls.replace(Some((fuel, costs)))))
+}
+
+/// Guard which restores the previously executing fuel when dropped.
+pub(crate) struct FuelGuard(Option<(Fuel, FuelCosts)>);
+
+impl Drop for FuelGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|tls| *tls.borrow_mut() = previous);
+    }
+}
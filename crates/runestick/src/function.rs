@@ -1,5 +1,6 @@
 use crate::context::Handler;
 use crate::internal::AssertSend;
+use crate::unit::UnitFn;
 use crate::VmErrorKind;
 use crate::{
     Args, Call, ConstValue, FromValue, Hash, RawRef, Ref, Rtti, RuntimeContext, Shared, Stack,
@@ -110,6 +111,7 @@ where
         let reason = match &self.inner {
             Inner::FnHandler(handler) => {
                 let _guard = crate::interface::EnvGuard::new(&vm.context, &vm.unit);
+                let _native = vm.enter_native(handler.hash);
                 (handler.handler)(&mut vm.stack, args)?;
                 None
             }
@@ -273,6 +275,106 @@ where
 }
 
 impl FunctionImpl<Value> {
+    /// Look up the function with the given hash in the unit, or if it's not
+    /// there in the context.
+    pub(crate) fn from_hash(
+        context: &Arc<RuntimeContext>,
+        unit: &Arc<Unit>,
+        hash: Hash,
+    ) -> Result<Self, VmError> {
+        Ok(match unit.lookup(hash) {
+            Some(info) => match info {
+                UnitFn::Offset { offset, call, args } => {
+                    Self::from_offset(context.clone(), unit.clone(), offset, call, args, hash)
+                }
+                UnitFn::UnitStruct { hash } => {
+                    let rtti = unit
+                        .lookup_rtti(hash)
+                        .ok_or_else(|| VmErrorKind::MissingRtti { hash })?;
+
+                    Self::from_unit_struct(rtti.clone())
+                }
+                UnitFn::TupleStruct { hash, args } => {
+                    let rtti = unit
+                        .lookup_rtti(hash)
+                        .ok_or_else(|| VmErrorKind::MissingRtti { hash })?;
+
+                    Self::from_tuple_struct(rtti.clone(), args)
+                }
+                UnitFn::UnitVariant { hash } => {
+                    let rtti = unit
+                        .lookup_variant_rtti(hash)
+                        .ok_or_else(|| VmErrorKind::MissingVariantRtti { hash })?;
+
+                    Self::from_empty_variant(rtti.clone())
+                }
+                UnitFn::TupleVariant { hash, args } => {
+                    let rtti = unit
+                        .lookup_variant_rtti(hash)
+                        .ok_or_else(|| VmErrorKind::MissingVariantRtti { hash })?;
+
+                    Self::from_tuple_variant(rtti.clone(), args)
+                }
+            },
+            None => {
+                let handler = context
+                    .lookup(hash)
+                    .ok_or_else(|| VmErrorKind::MissingFunction { hash })?;
+
+                Self::from_handler(handler.clone(), hash)
+            }
+        })
+    }
+
+    /// Look up the closure with the given hash in the unit, and construct it
+    /// with the given environment.
+    pub(crate) fn closure_from_hash(
+        context: &Arc<RuntimeContext>,
+        unit: &Arc<Unit>,
+        hash: Hash,
+        environment: Box<[Value]>,
+    ) -> Result<Self, VmError> {
+        match unit.lookup(hash) {
+            Some(UnitFn::Offset { offset, call, args }) => Ok(Self::from_closure(
+                context.clone(),
+                unit.clone(),
+                offset,
+                call,
+                args,
+                environment,
+                hash,
+            )),
+            _ => Err(VmError::from(VmErrorKind::MissingFunction { hash })),
+        }
+    }
+
+    /// Get the captured environment of the function, if it's a closure.
+    pub(crate) fn environment(&self) -> Option<&[Value]> {
+        match &self.inner {
+            Inner::FnClosureOffset(closure) => Some(&closure.environment),
+            _ => None,
+        }
+    }
+
+    /// Get the captured environment of the function mutably, if it's a
+    /// closure.
+    pub(crate) fn environment_mut(&mut self) -> Option<&mut [Value]> {
+        match &mut self.inner {
+            Inner::FnClosureOffset(closure) => Some(&mut closure.environment),
+            _ => None,
+        }
+    }
+
+    /// Test if the function is defined in the given unit. Native functions
+    /// and constructors belong to every unit.
+    pub(crate) fn belongs_to(&self, unit: &Arc<Unit>) -> bool {
+        match &self.inner {
+            Inner::FnOffset(fn_offset) => Arc::ptr_eq(&fn_offset.unit, unit),
+            Inner::FnClosureOffset(closure) => Arc::ptr_eq(&closure.fn_offset.unit, unit),
+            _ => true,
+        }
+    }
+
     /// Try to convert into a [SyncFunction].
     pub fn into_sync(self) -> Result<SyncFunction, VmError> {
         let inner = match self.inner {
@@ -426,9 +528,10 @@ impl FnOffset {
 
         let mut new_stack = vm.stack_mut().drain_stack_top(args)?.collect::<Stack>();
         extra.into_stack(&mut new_stack)?;
-        let mut vm = Vm::new_with_stack(self.context.clone(), self.unit.clone(), new_stack);
-        vm.set_ip(self.offset);
-        Ok(Some(VmCall::new(self.call, vm)))
+        let mut new_vm = Vm::new_with_stack(self.context.clone(), self.unit.clone(), new_stack);
+        new_vm.inherit(vm);
+        new_vm.set_ip(self.offset);
+        Ok(Some(VmCall::new(self.call, new_vm)))
     }
 }
 
@@ -1,14 +1,16 @@
 use crate::{
-    FromValue, GeneratorState, InstallWith, Mut, Named, RawMut, RawRef, RawStr, Ref, Shared,
-    UnsafeFromValue, Value, Vm, VmError, VmErrorKind, VmExecution,
+    FromValue, GeneratorState, InstallWith, Mut, Named, RawMut, RawRef, RawStr, Ref,
+    RuntimeContext, Shared, Unit, UnsafeFromValue, Value, Vm, VmError, VmErrorKind, VmExecution,
+    VmSnapshot,
 };
 use std::fmt;
 use std::mem;
+use std::sync::Arc;
 
 /// A generator with a stored virtual machine.
 pub struct Generator {
-    execution: Option<VmExecution>,
-    first: bool,
+    pub(crate) execution: Option<VmExecution>,
+    pub(crate) first: bool,
 }
 
 impl Generator {
@@ -49,6 +51,28 @@ impl Generator {
         Ok(state)
     }
 
+    /// Take a snapshot of the generator, which can be serialized and later
+    /// restored with [Generator::restore], possibly in a different process.
+    ///
+    /// This allows a generator which is suspended at a `yield` to be resumed
+    /// somewhere else. See [Vm::snapshot] for more information.
+    pub fn snapshot(&self) -> Result<VmSnapshot, VmError> {
+        VmSnapshot::of_generator(self)
+    }
+
+    /// Restore a generator from a snapshot taken with [Generator::snapshot].
+    ///
+    /// The unit must be the same unit that the snapshot was taken with, and
+    /// the context must provide the same native functions. Restoring with a
+    /// different unit fails with [VmErrorKind::InvalidSnapshot].
+    pub fn restore(
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+        snapshot: &VmSnapshot,
+    ) -> Result<Self, VmError> {
+        snapshot.restore_generator(&context, &unit)
+    }
+
     /// Convert into iterator
     pub fn into_iterator(self) -> Result<crate::Iterator, VmError> {
         Ok(crate::Iterator::from(
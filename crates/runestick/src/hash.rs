@@ -51,7 +51,9 @@ impl Hash {
     pub fn from_type_id(type_id: any::TypeId) -> Self {
         // Safety: a type id is exactly a 64-bit unsigned integer.
         // And has an identical bit pattern to `Hash`.
-        unsafe { mem::transmute(type_id) }
+        let mut hasher = XxHash64::default();
+        std::hash::Hash::hash(&type_id, &mut hasher);
+        Self(std::hash::Hasher::finish(&hasher))
     }
 
     /// Construct a hash to an instance function, where the instance is a
@@ -101,6 +101,18 @@ pub enum Inst {
     /// => <number>
     /// ```
     Neg,
+    /// Convert the value on the stack into the given type.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <value>
+    /// ```
+    Cast {
+        /// The type to convert the value into.
+        ty: InstCast,
+    },
     /// Construct a closure that takes the given number of arguments and
     /// captures `count` elements from the top of the stack.
     ///
@@ -797,6 +809,54 @@ pub enum Inst {
         /// The bool to test against.
         boolean: bool,
     },
+    /// Test if the top of the stack is a byte within the given range.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    MatchByteRange {
+        /// The start of the range.
+        start: u8,
+        /// The end of the range, if the range is bounded.
+        end: Option<u8>,
+        /// The limits of the range.
+        limits: InstRangeLimits,
+    },
+    /// Test if the top of the stack is a character within the given range.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    MatchCharacterRange {
+        /// The start of the range.
+        start: char,
+        /// The end of the range, if the range is bounded.
+        end: Option<char>,
+        /// The limits of the range.
+        limits: InstRangeLimits,
+    },
+    /// Test if the top of the stack is an integer within the given range.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    MatchIntegerRange {
+        /// The start of the range.
+        start: i64,
+        /// The end of the range, if the range is bounded.
+        end: Option<i64>,
+        /// The limits of the range.
+        limits: InstRangeLimits,
+    },
     /// Compare the top of the stack against a static string slot.
     ///
     /// # Operation
@@ -988,6 +1048,9 @@ impl fmt::Display for Inst {
             Self::Neg => {
                 write!(fmt, "neg")?;
             }
+            Self::Cast { ty } => {
+                write!(fmt, "cast {}", ty)?;
+            }
             Self::Call { hash, args } => {
                 write!(fmt, "call {}, {}", hash, args)?;
             }
@@ -1168,6 +1231,27 @@ impl fmt::Display for Inst {
             Self::EqStaticString { slot } => {
                 write!(fmt, "eq-static-string {}", slot)?;
             }
+            Self::MatchByteRange { start, end, limits } => {
+                write!(fmt, "match-byte-range {:?}{}", start, limits)?;
+
+                if let Some(end) = end {
+                    write!(fmt, "{:?}", end)?;
+                }
+            }
+            Self::MatchCharacterRange { start, end, limits } => {
+                write!(fmt, "match-character-range {:?}{}", start, limits)?;
+
+                if let Some(end) = end {
+                    write!(fmt, "{:?}", end)?;
+                }
+            }
+            Self::MatchIntegerRange { start, end, limits } => {
+                write!(fmt, "match-integer-range {}{}", start, limits)?;
+
+                if let Some(end) = end {
+                    write!(fmt, "{}", end)?;
+                }
+            }
             Self::MatchSequence {
                 type_check,
                 len,
@@ -1254,6 +1338,25 @@ pub enum InstRangeLimits {
     Closed,
 }
 
+impl InstRangeLimits {
+    /// Test if the given value is within the range described by `start`,
+    /// `end` and these limits.
+    pub fn contains<T>(self, start: T, end: Option<T>, value: T) -> bool
+    where
+        T: PartialOrd,
+    {
+        if value < start {
+            return false;
+        }
+
+        match (self, end) {
+            (_, None) => true,
+            (Self::HalfOpen, Some(end)) => value < end,
+            (Self::Closed, Some(end)) => value <= end,
+        }
+    }
+}
+
 impl fmt::Display for InstRangeLimits {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -1263,6 +1366,81 @@ impl fmt::Display for InstRangeLimits {
     }
 }
 
+/// The type a value is converted into by an `as` expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InstCast {
+    /// Convert into an integer.
+    Integer,
+    /// Convert into a float.
+    Float,
+    /// Convert into a byte.
+    Byte,
+    /// Convert into a character.
+    Char,
+    /// Convert into an unsigned 16-bit integer.
+    U16,
+    /// Convert into an unsigned 32-bit integer.
+    U32,
+    /// Convert into an unsigned 64-bit integer.
+    U64,
+    /// Convert into a signed 32-bit integer.
+    I32,
+    /// Convert into a signed 128-bit integer.
+    I128,
+}
+
+impl InstCast {
+    /// Get the cast into the type with the given hash, if values can be cast
+    /// into it.
+    pub fn from_type_hash(hash: Hash) -> Option<Self> {
+        Some(match hash {
+            hash if hash == crate::INTEGER_TYPE.hash => Self::Integer,
+            hash if hash == crate::FLOAT_TYPE.hash => Self::Float,
+            hash if hash == crate::BYTE_TYPE.hash => Self::Byte,
+            hash if hash == crate::CHAR_TYPE.hash => Self::Char,
+            hash if hash == crate::U16_TYPE.hash => Self::U16,
+            hash if hash == crate::U32_TYPE.hash => Self::U32,
+            hash if hash == crate::U64_TYPE.hash => Self::U64,
+            hash if hash == crate::I32_TYPE.hash => Self::I32,
+            hash if hash == crate::I128_TYPE.hash => Self::I128,
+            _ => return None,
+        })
+    }
+
+    /// Convert a float into an integer, truncating any fractional part.
+    ///
+    /// Returns `None` if the float is not a number or is out of range.
+    pub fn float_to_integer(n: f64) -> Option<i128> {
+        let n = n.trunc();
+
+        // NB: `i128::MIN` is a power of two and can be represented exactly as
+        // a float, while `i128::MAX` can't, so the upper bound is exclusive.
+        let bound = -(i128::MIN as f64);
+
+        if (-bound..bound).contains(&n) {
+            Some(n as i128)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for InstCast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer => write!(f, "int"),
+            Self::Float => write!(f, "float"),
+            Self::Byte => write!(f, "byte"),
+            Self::Char => write!(f, "char"),
+            Self::U16 => write!(f, "u16"),
+            Self::U32 => write!(f, "u32"),
+            Self::U64 => write!(f, "u64"),
+            Self::I32 => write!(f, "i32"),
+            Self::I128 => write!(f, "i128"),
+        }
+    }
+}
+
 /// The target of an operation.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum InstTarget {
@@ -1361,6 +1539,8 @@ pub enum InstOp {
     Div,
     /// The remainder operation. `a % b`.
     Rem,
+    /// The power operation. `a ** b`.
+    Pow,
     /// The bitwise and operation. `a & b`.
     BitAnd,
     /// The bitwise xor operation. `a ^ b`.
@@ -1468,6 +1648,9 @@ impl fmt::Display for InstOp {
             Self::Rem => {
                 write!(f, "%")?;
             }
+            Self::Pow => {
+                write!(f, "**")?;
+            }
             Self::BitAnd => {
                 write!(f, "&")?;
             }
@@ -1532,6 +1715,16 @@ pub enum InstValue {
     Char(char),
     /// An integer.
     Integer(i64),
+    /// An unsigned 16-bit integer.
+    U16(u16),
+    /// An unsigned 32-bit integer.
+    U32(u32),
+    /// An unsigned 64-bit integer.
+    U64(u64),
+    /// A signed 32-bit integer.
+    I32(i32),
+    /// A signed 128-bit integer.
+    I128(i128),
     /// A float.
     Float(f64),
     /// A type hash.
@@ -1547,6 +1740,11 @@ impl InstValue {
             Self::Byte(v) => Value::Byte(v),
             Self::Char(v) => Value::Char(v),
             Self::Integer(v) => Value::Integer(v),
+            Self::U16(v) => Value::U16(v),
+            Self::U32(v) => Value::U32(v),
+            Self::U64(v) => Value::U64(v),
+            Self::I32(v) => Value::I32(v),
+            Self::I128(v) => Value::from(v),
             Self::Float(v) => Value::Float(v),
             Self::Type(v) => Value::Type(v),
         }
@@ -1567,6 +1765,11 @@ impl fmt::Display for InstValue {
             }
             Self::Char(v) => write!(f, "{:?}", v)?,
             Self::Integer(v) => write!(f, "{}", v)?,
+            Self::U16(v) => write!(f, "{}u16", v)?,
+            Self::U32(v) => write!(f, "{}u32", v)?,
+            Self::U64(v) => write!(f, "{}u64", v)?,
+            Self::I32(v) => write!(f, "{}i32", v)?,
+            Self::I128(v) => write!(f, "{}i128", v)?,
             Self::Float(v) => write!(f, "{}", v)?,
             Self::Type(v) => write!(f, "{}", v)?,
         }
@@ -3,6 +3,7 @@ use crate::{
     Unit, UnitFn, Value, Vm, VmError, VmErrorKind,
 };
 use std::cell::Cell;
+use std::cmp::Ordering;
 use std::marker;
 use std::ptr;
 use std::sync::Arc;
@@ -14,6 +15,7 @@ thread_local! { static ENV: Cell<Env> = Cell::new(Env::null()) }
 /// This can be used as an argument type for native functions who wants to call
 /// a protocol function like [INTO_ITER][crate::Protocol::INTO_ITER] (see
 /// [into_iter][Self::into_iter]).
+#[derive(Clone)]
 pub struct Interface {
     target: Value,
     unit: Arc<Unit>,
@@ -34,6 +36,119 @@ impl Interface {
         Iterator::from_value(value)
     }
 
+    /// Call the [HASH][Protocol::HASH] protocol on the value.
+    ///
+    /// Returns `None` if the value doesn't implement the protocol.
+    pub fn into_hash(mut self) -> Result<Option<i64>, VmError> {
+        if !self.has_instance_fn(Protocol::HASH)? {
+            return Ok(None);
+        }
+
+        let target = std::mem::take(&mut self.target);
+        let value = self.call_instance_fn(Protocol::HASH, target, ())?;
+        Ok(Some(i64::from_value(value)?))
+    }
+
+    /// Test if the value implements the [PARTIAL_EQ][Protocol::PARTIAL_EQ] or
+    /// the [EQ][Protocol::EQ] protocol.
+    pub(crate) fn has_eq(&self) -> Result<bool, VmError> {
+        Ok(self.has_instance_fn(Protocol::PARTIAL_EQ)? || self.has_instance_fn(Protocol::EQ)?)
+    }
+
+    /// Test if the value implements the [PARTIAL_CMP][Protocol::PARTIAL_CMP]
+    /// or the [CMP][Protocol::CMP] protocol.
+    pub(crate) fn has_cmp(&self) -> Result<bool, VmError> {
+        Ok(self.has_instance_fn(Protocol::PARTIAL_CMP)? || self.has_instance_fn(Protocol::CMP)?)
+    }
+
+    /// Compare the value to `other` using the
+    /// [PARTIAL_EQ][Protocol::PARTIAL_EQ] or the [EQ][Protocol::EQ] protocol.
+    ///
+    /// Returns `None` if the value doesn't implement either protocol.
+    pub(crate) fn call_eq(&self, other: Value) -> Result<Option<bool>, VmError> {
+        for protocol in &[Protocol::PARTIAL_EQ, Protocol::EQ] {
+            if self.has_instance_fn(*protocol)? {
+                let target = self.target.clone();
+                let value = self.clone().call_instance_fn(*protocol, target, (other,))?;
+                return Ok(Some(bool::from_value(value)?));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Compare the value to `other` using the
+    /// [PARTIAL_CMP][Protocol::PARTIAL_CMP] or the [CMP][Protocol::CMP]
+    /// protocol.
+    ///
+    /// Returns `None` if the value doesn't implement either protocol.
+    pub(crate) fn call_cmp(&self, other: Value) -> Result<Option<Option<Ordering>>, VmError> {
+        if self.has_instance_fn(Protocol::PARTIAL_CMP)? {
+            let target = self.target.clone();
+            let value = self
+                .clone()
+                .call_instance_fn(Protocol::PARTIAL_CMP, target, (other,))?;
+            return Ok(Some(<Option<Ordering>>::from_value(value)?));
+        }
+
+        if self.has_instance_fn(Protocol::CMP)? {
+            let target = self.target.clone();
+            let value = self
+                .clone()
+                .call_instance_fn(Protocol::CMP, target, (other,))?;
+            return Ok(Some(Some(Ordering::from_value(value)?)));
+        }
+
+        Ok(None)
+    }
+
+    /// Compare `a` to `b` using the equality protocols of `a` in the
+    /// environment of the currently executing virtual machine.
+    ///
+    /// Returns `None` if there is no such environment, or if `a` doesn't
+    /// implement either protocol.
+    pub(crate) fn protocol_eq(a: &Value, b: &Value) -> Result<Option<bool>, VmError> {
+        match Self::current(a) {
+            Some(interface) => interface.call_eq(b.clone()),
+            None => Ok(None),
+        }
+    }
+
+    /// Compare `a` to `b` using the ordering protocols of `a` in the
+    /// environment of the currently executing virtual machine.
+    ///
+    /// Returns `None` if there is no such environment, or if `a` doesn't
+    /// implement either protocol.
+    pub(crate) fn protocol_cmp(a: &Value, b: &Value) -> Result<Option<Option<Ordering>>, VmError> {
+        match Self::current(a) {
+            Some(interface) => interface.call_cmp(b.clone()),
+            None => Ok(None),
+        }
+    }
+
+    /// Wrap the given value in the environment of the currently executing
+    /// virtual machine, if there is one.
+    fn current(target: &Value) -> Option<Self> {
+        let Env { context, unit } = ENV.with(|env| env.get());
+
+        if context.is_null() || unit.is_null() {
+            return None;
+        }
+
+        // Safety: see the FromValue implementation below.
+        Some(Interface {
+            target: target.clone(),
+            context: unsafe { (*context).clone() },
+            unit: unsafe { (*unit).clone() },
+        })
+    }
+
+    /// Test if the value has an instance function for the given protocol.
+    fn has_instance_fn(&self, protocol: Protocol) -> Result<bool, VmError> {
+        let hash = Hash::instance_function(self.target.type_hash()?, protocol);
+        Ok(self.unit.lookup(hash).is_some() || self.context.lookup(hash).is_some())
+    }
+
     /// Retrieves the type name of the type
     pub fn into_type_name(self) -> Result<String, VmError> {
         let hash = Hash::instance_function(self.target.type_hash()?, Protocol::INTO_TYPE_NAME);
@@ -1,7 +1,8 @@
 use crate::{
-    FromValue, Function, InstallWith, Interface, Mut, Named, RawMut, RawRef, RawStr, Ref, ToValue,
-    UnsafeFromValue, Value, VmError, VmErrorKind,
+    limits, FromValue, Function, InstallWith, Interface, Mut, Named, RawMut, RawRef, RawStr, Ref,
+    ToValue, UnsafeFromValue, Value, VmError, VmErrorKind,
 };
+use std::cmp;
 use std::fmt;
 use std::iter;
 use std::vec;
@@ -71,6 +72,10 @@ macro_rules! maybe {
     };
 }
 
+/// The maximum number of values to preallocate room for when collecting an
+/// iterator.
+const MAX_PREALLOCATE: usize = 1024;
+
 /// An owning iterator.
 pub struct Iterator {
     iter: IterRepr,
@@ -107,6 +112,17 @@ impl Iterator {
         self.iter.size_hint()
     }
 
+    /// Get the number of values to preallocate room for when collecting the
+    /// iterator.
+    ///
+    /// This is the lower bound of its size hint, capped so that iterators
+    /// which are endless or very long don't preallocate more than they're
+    /// likely to use.
+    pub(crate) fn capacity_hint(&self) -> usize {
+        let (lower, _) = self.iter.size_hint();
+        cmp::min(lower, MAX_PREALLOCATE)
+    }
+
     /// Get the next value out of the iterator.
     pub fn next(&mut self) -> Result<Option<Value>, VmError> {
         self.iter.next()
@@ -197,6 +213,274 @@ impl Iterator {
         Ok(false)
     }
 
+    /// Find the index of the first value in the iterator which matches the
+    /// given predicate.
+    pub fn position(mut self, find: Function) -> Result<Option<usize>, VmError> {
+        let mut index = 0;
+
+        while let Some(value) = self.next()? {
+            if find.call::<_, bool>((value,))? {
+                return Ok(Some(index));
+            }
+
+            index += 1;
+        }
+
+        Ok(None)
+    }
+
+    /// Consume the iterator, returning the last value.
+    pub fn last(mut self) -> Result<Option<Value>, VmError> {
+        let mut last = None;
+
+        while let Some(value) = self.next()? {
+            last = Some(value);
+        }
+
+        Ok(last)
+    }
+
+    /// Get the `n`th value of the iterator, skipping over the values before
+    /// it.
+    pub fn nth(&mut self, n: usize) -> Result<Option<Value>, VmError> {
+        for _ in 0..n {
+            if self.next()?.is_none() {
+                return Ok(None);
+            }
+        }
+
+        self.next()
+    }
+
+    /// Get the minimum value of the iterator using the ordering of values, which
+    /// consults the `CMP` protocol of values implementing it.
+    ///
+    /// If several values are equally minimum, the first one is returned.
+    pub fn min(self) -> Result<Option<Value>, VmError> {
+        self.select(Value::value_ptr_cmp, false)
+    }
+
+    /// Get the maximum value of the iterator using the ordering of values, which
+    /// consults the `CMP` protocol of values implementing it.
+    ///
+    /// If several values are equally maximum, the last one is returned.
+    pub fn max(self) -> Result<Option<Value>, VmError> {
+        self.select(Value::value_ptr_cmp, true)
+    }
+
+    /// Get the minimum value of the iterator using a comparator function,
+    /// which returns an `Ordering`.
+    pub fn min_by(self, compare: Function) -> Result<Option<Value>, VmError> {
+        self.select(
+            |a, b| compare.call::<_, cmp::Ordering>((a.clone(), b.clone())),
+            false,
+        )
+    }
+
+    /// Get the maximum value of the iterator using a comparator function,
+    /// which returns an `Ordering`.
+    pub fn max_by(self, compare: Function) -> Result<Option<Value>, VmError> {
+        self.select(
+            |a, b| compare.call::<_, cmp::Ordering>((a.clone(), b.clone())),
+            true,
+        )
+    }
+
+    /// Get the value of the iterator for which the given function returns the
+    /// minimum key.
+    pub fn min_by_key(self, f: Function) -> Result<Option<Value>, VmError> {
+        self.select_by_key(f, false)
+    }
+
+    /// Get the value of the iterator for which the given function returns the
+    /// maximum key.
+    pub fn max_by_key(self, f: Function) -> Result<Option<Value>, VmError> {
+        self.select_by_key(f, true)
+    }
+
+    /// Split the iterator into the values which match the given predicate and
+    /// the ones which don't.
+    pub fn partition(
+        mut self,
+        predicate: Function,
+    ) -> Result<(vec::Vec<Value>, vec::Vec<Value>), VmError> {
+        let mut matching = vec::Vec::new();
+        let mut rest = vec::Vec::new();
+
+        while let Some(value) = self.next()? {
+            if predicate.call::<_, bool>((value.clone(),))? {
+                matching.push(value);
+            } else {
+                rest.push(value);
+            }
+        }
+
+        Ok((matching, rest))
+    }
+
+    /// Select the minimum or maximum value using the given comparison.
+    ///
+    /// Like in Rust, ties go to the first minimum and the last maximum.
+    fn select<F>(mut self, mut compare: F, max: bool) -> Result<Option<Value>, VmError>
+    where
+        F: FnMut(&Value, &Value) -> Result<cmp::Ordering, VmError>,
+    {
+        let mut selected = match self.next()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        while let Some(value) = self.next()? {
+            if replaces(compare(&selected, &value)?, max) {
+                selected = value;
+            }
+        }
+
+        Ok(Some(selected))
+    }
+
+    /// Select the minimum or maximum value using the keys returned by the
+    /// given function.
+    fn select_by_key(mut self, f: Function, max: bool) -> Result<Option<Value>, VmError> {
+        let (mut key, mut selected) = match self.next()? {
+            Some(value) => (f.call::<_, Value>((value.clone(),))?, value),
+            None => return Ok(None),
+        };
+
+        while let Some(value) = self.next()? {
+            let k = f.call::<_, Value>((value.clone(),))?;
+
+            if replaces(Value::value_ptr_cmp(&key, &k)?, max) {
+                key = k;
+                selected = value;
+            }
+        }
+
+        Ok(Some(selected))
+    }
+
+    /// Zip this iterator with another, producing tuples of their values until
+    /// either of them runs out.
+    pub fn zip(self, other: Interface) -> Result<Self, VmError> {
+        let other = other.into_iter()?;
+
+        Ok(Self {
+            iter: IterRepr::Zip(Box::new(Zip {
+                a: self.iter,
+                b: other.iter,
+            })),
+        })
+    }
+
+    /// Take values from the iterator while they match the given predicate.
+    pub fn take_while(self, predicate: Function) -> Self {
+        Self {
+            iter: IterRepr::TakeWhile(Box::new(TakeWhile {
+                iter: self.iter,
+                predicate,
+                done: false,
+            })),
+        }
+    }
+
+    /// Skip over values in the iterator while they match the given predicate.
+    pub fn skip_while(self, predicate: Function) -> Self {
+        Self {
+            iter: IterRepr::SkipWhile(Box::new(SkipWhile {
+                iter: self.iter,
+                predicate: Some(predicate),
+            })),
+        }
+    }
+
+    /// Produce the first value of the iterator and then every `step`th value
+    /// after it.
+    pub fn step_by(self, step: usize) -> Result<Self, VmError> {
+        if step == 0 {
+            return Err(VmError::panic("step must be non-zero"));
+        }
+
+        Ok(Self {
+            iter: IterRepr::StepBy(Box::new(StepBy {
+                iter: self.iter,
+                step: step - 1,
+                first_take: true,
+            })),
+        })
+    }
+
+    /// Produce vectors of `size` values at a time, where the last one might be
+    /// shorter.
+    pub fn chunks(self, size: usize) -> Result<Self, VmError> {
+        if size == 0 {
+            return Err(VmError::panic("chunk size must be non-zero"));
+        }
+
+        Ok(Self {
+            iter: IterRepr::Chunks(Box::new(Chunks {
+                iter: self.iter,
+                size,
+            })),
+        })
+    }
+
+    /// Scan over the iterator with the given initial state.
+    ///
+    /// The function is called with the current state and the next value, and
+    /// returns the next state wrapped in an option. Each state is produced by
+    /// the iterator, and it stops once the function returns `None`.
+    pub fn scan(self, state: Value, f: Function) -> Self {
+        Self {
+            iter: IterRepr::Scan(Box::new(Scan {
+                iter: self.iter,
+                state: Some(state),
+                f,
+            })),
+        }
+    }
+
+    /// Call the given function with each value before it's produced.
+    pub fn inspect(self, f: Function) -> Self {
+        Self {
+            iter: IterRepr::Inspect(Box::new(Inspect { iter: self.iter, f })),
+        }
+    }
+
+    /// Repeat the iterator endlessly.
+    ///
+    /// The values of the first pass are stored so that they can be repeated.
+    pub fn cycle(self) -> Self {
+        Self {
+            iter: IterRepr::Cycle(Box::new(Cycle {
+                iter: Some(self.iter),
+                buffer: vec::Vec::new(),
+                index: 0,
+            })),
+        }
+    }
+
+    /// Flatten an iterator of iterables.
+    pub fn flatten(self) -> Self {
+        Self {
+            iter: IterRepr::Flatten(Box::new(FlatMap {
+                map: Fuse::new(self.iter),
+                frontiter: None,
+                backiter: None,
+            })),
+        }
+    }
+
+    /// Remove consecutive values which are equal to each other, as determined
+    /// by the `EQ` protocol for values implementing it.
+    pub fn dedup(self) -> Self {
+        Self {
+            iter: IterRepr::Dedup(Box::new(Dedup {
+                iter: Fuse::new(self.iter),
+                last: None,
+            })),
+        }
+    }
+
     /// Chain this iterator with another.
     pub fn chain(self, other: Interface) -> Result<Self, VmError> {
         let other = other.into_iter()?;
@@ -289,8 +573,7 @@ impl Iterator {
     where
         T: FromValue,
     {
-        let (cap, _) = self.iter.size_hint();
-        let mut vec = vec::Vec::with_capacity(cap);
+        let mut vec = vec::Vec::with_capacity(self.capacity_hint());
 
         while let Some(value) = self.next()? {
             vec.push(T::from_value(value)?);
@@ -382,6 +665,16 @@ enum IterRepr {
     Skip(Box<Skip<Self>>),
     Take(Box<Take<Self>>),
     Peekable(Box<Peekable<Self>>),
+    Zip(Box<Zip<Self, Self>>),
+    TakeWhile(Box<TakeWhile<Self>>),
+    SkipWhile(Box<SkipWhile<Self>>),
+    StepBy(Box<StepBy<Self>>),
+    Chunks(Box<Chunks<Self>>),
+    Scan(Box<Scan<Self>>),
+    Inspect(Box<Inspect<Self>>),
+    Cycle(Box<Cycle<Self>>),
+    Flatten(Box<FlatMap<Self>>),
+    Dedup(Box<Dedup<Self>>),
 }
 
 impl RuneIterator for IterRepr {
@@ -399,6 +692,16 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.is_double_ended(),
             Self::Take(iter) => iter.is_double_ended(),
             Self::Peekable(iter) => iter.is_double_ended(),
+            Self::Zip(iter) => iter.is_double_ended(),
+            Self::TakeWhile(iter) => iter.is_double_ended(),
+            Self::SkipWhile(iter) => iter.is_double_ended(),
+            Self::StepBy(iter) => iter.is_double_ended(),
+            Self::Chunks(iter) => iter.is_double_ended(),
+            Self::Scan(iter) => iter.is_double_ended(),
+            Self::Inspect(iter) => iter.is_double_ended(),
+            Self::Cycle(iter) => iter.is_double_ended(),
+            Self::Flatten(iter) => iter.is_double_ended(),
+            Self::Dedup(iter) => iter.is_double_ended(),
         }
     }
 
@@ -416,6 +719,16 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.size_hint(),
             Self::Take(iter) => iter.size_hint(),
             Self::Peekable(iter) => iter.size_hint(),
+            Self::Zip(iter) => iter.size_hint(),
+            Self::TakeWhile(iter) => iter.size_hint(),
+            Self::SkipWhile(iter) => iter.size_hint(),
+            Self::StepBy(iter) => iter.size_hint(),
+            Self::Chunks(iter) => iter.size_hint(),
+            Self::Scan(iter) => iter.size_hint(),
+            Self::Inspect(iter) => iter.size_hint(),
+            Self::Cycle(iter) => iter.size_hint(),
+            Self::Flatten(iter) => iter.size_hint(),
+            Self::Dedup(iter) => iter.size_hint(),
         }
     }
 
@@ -432,6 +745,16 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.next(),
             Self::Take(iter) => iter.next(),
             Self::Peekable(iter) => iter.next(),
+            Self::Zip(iter) => iter.next(),
+            Self::TakeWhile(iter) => iter.next(),
+            Self::SkipWhile(iter) => iter.next(),
+            Self::StepBy(iter) => iter.next(),
+            Self::Chunks(iter) => iter.next(),
+            Self::Scan(iter) => iter.next(),
+            Self::Inspect(iter) => iter.next(),
+            Self::Cycle(iter) => iter.next(),
+            Self::Flatten(iter) => iter.next(),
+            Self::Dedup(iter) => iter.next(),
         }
     }
 
@@ -453,6 +776,16 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.next_back(),
             Self::Take(iter) => iter.next_back(),
             Self::Peekable(iter) => iter.next_back(),
+            Self::Zip(iter) => iter.next_back(),
+            Self::TakeWhile(iter) => iter.next_back(),
+            Self::SkipWhile(iter) => iter.next_back(),
+            Self::StepBy(iter) => iter.next_back(),
+            Self::Chunks(iter) => iter.next_back(),
+            Self::Scan(iter) => iter.next_back(),
+            Self::Inspect(iter) => iter.next_back(),
+            Self::Cycle(iter) => iter.next_back(),
+            Self::Flatten(iter) => iter.next_back(),
+            Self::Dedup(iter) => iter.next_back(),
         }
     }
 }
@@ -471,6 +804,16 @@ impl fmt::Debug for IterRepr {
             Self::Skip(iter) => write!(f, "{:?}", iter),
             Self::Take(iter) => write!(f, "{:?}", iter),
             Self::Peekable(iter) => write!(f, "{:?}", iter),
+            Self::Zip(iter) => write!(f, "{:?}", iter),
+            Self::TakeWhile(iter) => write!(f, "{:?}", iter),
+            Self::SkipWhile(iter) => write!(f, "{:?}", iter),
+            Self::StepBy(iter) => write!(f, "{:?}", iter),
+            Self::Chunks(iter) => write!(f, "{:?}", iter),
+            Self::Scan(iter) => write!(f, "{:?}", iter),
+            Self::Inspect(iter) => write!(f, "{:?}", iter),
+            Self::Cycle(iter) => write!(f, "{:?}", iter),
+            Self::Flatten(iter) => write!(f, "{:?}", iter),
+            Self::Dedup(iter) => write!(f, "{:?}", iter),
         }
     }
 }
@@ -647,6 +990,443 @@ where
     }
 }
 
+/// Test if a selected value should be replaced by a value that compares to it
+/// with the given ordering.
+fn replaces(ordering: cmp::Ordering, max: bool) -> bool {
+    if max {
+        ordering != cmp::Ordering::Greater
+    } else {
+        ordering == cmp::Ordering::Greater
+    }
+}
+
+/// Construct the error raised when a single-ended iterator is iterated from
+/// the back.
+fn not_double_ended(iter: &dyn fmt::Debug) -> VmError {
+    VmError::panic(format!("`{:?}` is not a double-ended iterator", iter))
+}
+
+#[derive(Debug)]
+struct Zip<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> RuneIterator for Zip<A, B>
+where
+    A: RuneIterator,
+    B: RuneIterator,
+{
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+
+        let upper = match (a_upper, b_upper) {
+            (Some(x), Some(y)) => Some(cmp::min(x, y)),
+            (Some(x), None) | (None, Some(x)) => Some(x),
+            (None, None) => None,
+        };
+
+        (cmp::min(a_lower, b_lower), upper)
+    }
+
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let a = match self.a.next()? {
+            Some(a) => a,
+            None => return Ok(None),
+        };
+
+        let b = match self.b.next()? {
+            Some(b) => b,
+            None => return Ok(None),
+        };
+
+        Ok(Some((a, b).to_value()?))
+    }
+
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct TakeWhile<I> {
+    iter: I,
+    predicate: Function,
+    done: bool,
+}
+
+impl<I> RuneIterator for TakeWhile<I>
+where
+    I: RuneIterator,
+{
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.done {
+            return Ok(None);
+        }
+
+        if let Some(value) = self.iter.next()? {
+            if self.predicate.call::<_, bool>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        self.done = true;
+        Ok(None)
+    }
+
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct SkipWhile<I> {
+    iter: I,
+    /// The predicate, until the first value which doesn't match it has been
+    /// found.
+    predicate: Option<Function>,
+}
+
+impl<I> RuneIterator for SkipWhile<I>
+where
+    I: RuneIterator,
+{
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+
+        if self.predicate.is_some() {
+            (0, upper)
+        } else {
+            (lower, upper)
+        }
+    }
+
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let predicate = match self.predicate.take() {
+            Some(predicate) => predicate,
+            None => return self.iter.next(),
+        };
+
+        while let Some(value) = self.iter.next()? {
+            if !predicate.call::<_, bool>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct StepBy<I> {
+    iter: I,
+    /// The number of values to skip between each value produced.
+    step: usize,
+    first_take: bool,
+}
+
+impl<I> RuneIterator for StepBy<I>
+where
+    I: RuneIterator,
+{
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let step = self.step + 1;
+
+        let len = |n: usize| {
+            if !self.first_take {
+                n / step
+            } else if n == 0 {
+                0
+            } else {
+                1 + (n - 1) / step
+            }
+        };
+
+        (len(lower), upper.map(len))
+    }
+
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.first_take {
+            self.first_take = false;
+            return self.iter.next();
+        }
+
+        for _ in 0..self.step {
+            if self.iter.next()?.is_none() {
+                return Ok(None);
+            }
+        }
+
+        self.iter.next()
+    }
+
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct Chunks<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I> RuneIterator for Chunks<I>
+where
+    I: RuneIterator,
+{
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let len = |n: usize| n / self.size + if n % self.size == 0 { 0 } else { 1 };
+        (len(lower), upper.map(len))
+    }
+
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let mut chunk = vec::Vec::new();
+
+        while chunk.len() < self.size {
+            match self.iter.next()? {
+                Some(value) => chunk.push(value),
+                None => break,
+            }
+        }
+
+        if chunk.is_empty() {
+            return Ok(None);
+        }
+
+        limits::charge_values(chunk.len())?;
+        Ok(Some(Value::vec(chunk)))
+    }
+
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct Scan<I> {
+    iter: I,
+    /// The current state, until the scan function has returned `None`.
+    state: Option<Value>,
+    f: Function,
+}
+
+impl<I> RuneIterator for Scan<I>
+where
+    I: RuneIterator,
+{
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.state.is_none() {
+            return (0, Some(0));
+        }
+
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let state = match self.state.take() {
+            Some(state) => state,
+            None => return Ok(None),
+        };
+
+        let value = match self.iter.next()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        self.state = self.f.call::<_, Option<Value>>((state, value))?;
+        Ok(self.state.clone())
+    }
+
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct Inspect<I> {
+    iter: I,
+    f: Function,
+}
+
+impl<I> RuneIterator for Inspect<I>
+where
+    I: RuneIterator,
+{
+    fn is_double_ended(&self) -> bool {
+        self.iter.is_double_ended()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(value) = self.iter.next()? {
+            self.f.call::<_, ()>((value.clone(),))?;
+            return Ok(Some(value));
+        }
+
+        Ok(None)
+    }
+
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(value) = self.iter.next_back()? {
+            self.f.call::<_, ()>((value.clone(),))?;
+            return Ok(Some(value));
+        }
+
+        Ok(None)
+    }
+}
+
+#[derive(Debug)]
+struct Cycle<I> {
+    /// The iterator, until its first pass is complete.
+    iter: Option<I>,
+    /// The values of the first pass.
+    buffer: vec::Vec<Value>,
+    /// The index of the next buffered value to produce.
+    index: usize,
+}
+
+impl<I> RuneIterator for Cycle<I>
+where
+    I: RuneIterator,
+{
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if !self.buffer.is_empty() {
+            return (usize::MAX, None);
+        }
+
+        match &self.iter {
+            Some(iter) => match iter.size_hint() {
+                (0, Some(0)) => (0, Some(0)),
+                (0, _) => (0, None),
+                _ => (usize::MAX, None),
+            },
+            None => (0, Some(0)),
+        }
+    }
+
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(iter) = &mut self.iter {
+            if let Some(value) = iter.next()? {
+                limits::charge_values(1)?;
+                self.buffer.push(value.clone());
+                return Ok(Some(value));
+            }
+
+            self.iter = None;
+        }
+
+        let value = match self.buffer.get(self.index) {
+            Some(value) => value.clone(),
+            None => return Ok(None),
+        };
+
+        self.index = (self.index + 1) % self.buffer.len();
+        Ok(Some(value))
+    }
+
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct Dedup<I> {
+    iter: Fuse<I>,
+    /// The value following the last one produced, if it has been read.
+    last: Option<Value>,
+}
+
+impl<I> RuneIterator for Dedup<I>
+where
+    I: RuneIterator,
+{
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let last = if self.last.is_some() { 1 } else { 0 };
+
+        let lower = if lower.saturating_add(last) > 0 { 1 } else { 0 };
+        let upper = upper.and_then(|upper| upper.checked_add(last));
+        (lower, upper)
+    }
+
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let current = match self.last.take() {
+            Some(value) => value,
+            None => match self.iter.next()? {
+                Some(value) => value,
+                None => return Ok(None),
+            },
+        };
+
+        while let Some(value) = self.iter.next()? {
+            if !Value::value_ptr_eq(&current, &value)? {
+                self.last = Some(value);
+                break;
+            }
+        }
+
+        Ok(Some(current))
+    }
+
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
 /// The trait for interacting with an iterator.
 ///
 /// This has a blanket implementation, and is primarily used to restrict the
@@ -1,14 +1,49 @@
 use crate::{
-    Bytes, FromValue, Shared, StaticString, ToValue, Tuple, TypeInfo, Value, Vec, VmError,
-    VmErrorKind,
+    AnyObj, Bytes, FromValue, Hash, Interface, Object, RawStr, Rtti, Shared, StaticString, Struct,
+    StructVariant, ToValue, Tuple, TupleStruct, TupleVariant, TypeInfo, UnitStruct, UnitVariant,
+    Value, VariantRtti, Vec, VmError, VmErrorKind,
 };
 use serde::{de, ser};
+use std::cell::RefCell;
 use std::cmp;
 use std::fmt;
 use std::hash;
 use std::sync::Arc;
 use std::vec;
 
+/// The fields of a struct-like key, sorted by name.
+type ObjectKeys = Box<[(Box<str>, Key)]>;
+
+thread_local! {
+    /// The first error raised while comparing native keys in the innermost
+    /// [guard], or `None` if no guard is active.
+    static KEY_ERROR: RefCell<Option<Option<VmError>>> = RefCell::new(None);
+}
+
+/// Run an operation which compares keys, such as a map lookup, returning the
+/// first error raised by the protocols of a native key while doing so.
+///
+/// Comparisons made outside of a guard treat such errors as the keys being
+/// unequal.
+pub(crate) fn guard<T>(f: impl FnOnce() -> T) -> Result<T, VmError> {
+    let old = KEY_ERROR.with(|e| e.replace(Some(None)));
+    let output = f();
+
+    match KEY_ERROR.with(|e| e.replace(old)) {
+        Some(Some(error)) => Err(error),
+        _ => Ok(output),
+    }
+}
+
+/// Record an error raised while comparing keys in the innermost guard.
+fn record(error: VmError) {
+    KEY_ERROR.with(|e| {
+        if let Some(slot @ None) = &mut *e.borrow_mut() {
+            *slot = Some(error);
+        }
+    });
+}
+
 /// A key that can be used as an anonymous object key.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Key {
@@ -22,6 +57,16 @@ pub enum Key {
     Bool(bool),
     /// An integer constant.
     Integer(i64),
+    /// An unsigned 16-bit integer.
+    U16(u16),
+    /// An unsigned 32-bit integer.
+    U32(u32),
+    /// An unsigned 64-bit integer.
+    U64(u64),
+    /// A signed 32-bit integer.
+    I32(i32),
+    /// A signed 128-bit integer.
+    I128(i128),
     /// A string constant designated by its slot.
     String(StringKey),
     /// A byte string.
@@ -32,6 +77,21 @@ pub enum Key {
     Tuple(Box<[Key]>),
     /// An option.
     Option(Option<Box<Key>>),
+    /// A unit struct.
+    UnitStruct(RttiKey),
+    /// A tuple struct.
+    TupleStruct(RttiKey, Box<[Key]>),
+    /// A struct, with its fields sorted by name.
+    Struct(RttiKey, ObjectKeys),
+    /// A unit variant.
+    UnitVariant(VariantRttiKey),
+    /// A tuple variant.
+    TupleVariant(VariantRttiKey, Box<[Key]>),
+    /// A struct variant, with its fields sorted by name.
+    StructVariant(VariantRttiKey, ObjectKeys),
+    /// A native value which implements the [HASH][crate::Protocol::HASH]
+    /// protocol.
+    Any(AnyKey),
 }
 
 impl Key {
@@ -43,6 +103,11 @@ impl Key {
             Value::Char(c) => Self::Char(*c),
             Value::Bool(b) => Self::Bool(*b),
             Value::Integer(n) => Self::Integer(*n),
+            Value::U16(n) => Self::U16(*n),
+            Value::U32(n) => Self::U32(*n),
+            Value::U64(n) => Self::U64(*n),
+            Value::I32(n) => Self::I32(*n),
+            Value::I128(n) => Self::I128(**n),
             Value::String(s) => {
                 let s = s.borrow_ref()?;
                 Self::String(StringKey::String((**s).into()))
@@ -76,6 +141,59 @@ impl Key {
 
                 Self::Tuple(key_tuple.into_boxed_slice())
             }
+            Value::UnitStruct(unit) => {
+                let unit = unit.borrow_ref()?;
+                Self::UnitStruct(RttiKey(unit.rtti.clone()))
+            }
+            Value::TupleStruct(tuple) => {
+                let tuple = tuple.borrow_ref()?;
+                let data = Self::tuple_keys(&tuple.data)?;
+                Self::TupleStruct(RttiKey(tuple.rtti.clone()), data)
+            }
+            Value::Struct(object) => {
+                let object = object.borrow_ref()?;
+                let data = Self::object_keys(&object.data)?;
+                Self::Struct(RttiKey(object.rtti.clone()), data)
+            }
+            Value::UnitVariant(unit) => {
+                let unit = unit.borrow_ref()?;
+                Self::UnitVariant(VariantRttiKey(unit.rtti.clone()))
+            }
+            Value::TupleVariant(tuple) => {
+                let tuple = tuple.borrow_ref()?;
+                let data = Self::tuple_keys(&tuple.data)?;
+                Self::TupleVariant(VariantRttiKey(tuple.rtti.clone()), data)
+            }
+            Value::StructVariant(object) => {
+                let object = object.borrow_ref()?;
+                let data = Self::object_keys(&object.data)?;
+                Self::StructVariant(VariantRttiKey(object.rtti.clone()), data)
+            }
+            Value::Any(any) => {
+                let interface = Interface::from_value(value.clone())?;
+
+                let hash = match interface.clone().into_hash()? {
+                    Some(hash) if interface.has_eq()? => hash,
+                    _ => {
+                        return Err(VmError::from(VmErrorKind::KeyNotSupported {
+                            actual: value.type_info()?,
+                        }))
+                    }
+                };
+
+                let (type_hash, type_name) = {
+                    let any = any.borrow_ref()?;
+                    (any.type_hash(), any.type_name())
+                };
+
+                Self::Any(AnyKey {
+                    type_hash,
+                    type_name,
+                    hash,
+                    value: any.clone(),
+                    interface,
+                })
+            }
             value => {
                 return Err(VmError::from(VmErrorKind::KeyNotSupported {
                     actual: value.type_info()?,
@@ -84,6 +202,85 @@ impl Key {
         })
     }
 
+    /// Convert the values of a tuple into keys.
+    fn tuple_keys(tuple: &Tuple) -> Result<Box<[Key]>, VmError> {
+        let mut keys = vec::Vec::with_capacity(tuple.len());
+
+        for value in tuple.iter() {
+            keys.push(Self::from_value(value)?);
+        }
+
+        Ok(keys.into_boxed_slice())
+    }
+
+    /// Convert the fields of an object into keys, sorted by field name so that
+    /// the key is independent of insertion order.
+    fn object_keys(object: &Object) -> Result<ObjectKeys, VmError> {
+        let mut keys = vec::Vec::<(Box<str>, Key)>::with_capacity(object.len());
+
+        for (key, value) in object.iter() {
+            keys.push((key.as_str().into(), Self::from_value(value)?));
+        }
+
+        keys.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(keys.into_boxed_slice())
+    }
+
+    /// Check that the key can be used in an ordered collection, which requires
+    /// native keys to implement the [PARTIAL_CMP][crate::Protocol::PARTIAL_CMP]
+    /// or the [CMP][crate::Protocol::CMP] protocol.
+    pub(crate) fn ensure_ordered(&self) -> Result<(), VmError> {
+        match self {
+            Self::Vec(keys) => {
+                for key in keys {
+                    key.ensure_ordered()?;
+                }
+            }
+            Self::Tuple(keys) | Self::TupleStruct(_, keys) | Self::TupleVariant(_, keys) => {
+                for key in keys.iter() {
+                    key.ensure_ordered()?;
+                }
+            }
+            Self::Struct(_, keys) | Self::StructVariant(_, keys) => {
+                for (_, key) in keys.iter() {
+                    key.ensure_ordered()?;
+                }
+            }
+            Self::Option(Some(key)) => key.ensure_ordered()?,
+            Self::Any(any) if !any.interface.has_cmp()? => {
+                return Err(VmError::from(VmErrorKind::MissingProtocol {
+                    protocol: crate::Protocol::CMP,
+                    actual: self.type_info(),
+                }));
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    /// Convert keys back into a tuple.
+    fn into_tuple(keys: Box<[Key]>) -> Tuple {
+        let mut t = vec::Vec::with_capacity(keys.len());
+
+        for value in vec::Vec::from(keys) {
+            t.push(value.into_value());
+        }
+
+        Tuple::from(t)
+    }
+
+    /// Convert keys back into an object.
+    fn into_object(keys: ObjectKeys) -> Object {
+        let mut object = Object::with_capacity(keys.len());
+
+        for (key, value) in vec::Vec::from(keys) {
+            object.insert(String::from(key), value.into_value());
+        }
+
+        object
+    }
+
     /// Convert into virtual machine value.
     ///
     /// We provide this associated method since a constant value can be
@@ -96,6 +293,11 @@ impl Key {
             Self::Char(c) => Value::Char(c),
             Self::Bool(b) => Value::Bool(b),
             Self::Integer(n) => Value::Integer(n),
+            Self::U16(n) => Value::U16(n),
+            Self::U32(n) => Value::U32(n),
+            Self::U64(n) => Value::U64(n),
+            Self::I32(n) => Value::I32(n),
+            Self::I128(n) => Value::from(n),
             Self::String(s) => match s {
                 StringKey::String(s) => Value::String(Shared::new(String::from(s))),
                 StringKey::StaticString(s) => Value::StaticString(s),
@@ -114,15 +316,28 @@ impl Key {
 
                 Value::Vec(Shared::new(v))
             }
-            Self::Tuple(tuple) => {
-                let mut t = vec::Vec::with_capacity(tuple.len());
-
-                for value in vec::Vec::from(tuple) {
-                    t.push(value.into_value());
-                }
-
-                Value::Tuple(Shared::new(Tuple::from(t)))
+            Self::Tuple(tuple) => Value::Tuple(Shared::new(Self::into_tuple(tuple))),
+            Self::UnitStruct(rtti) => Value::UnitStruct(Shared::new(UnitStruct { rtti: rtti.0 })),
+            Self::TupleStruct(rtti, data) => Value::TupleStruct(Shared::new(TupleStruct {
+                rtti: rtti.0,
+                data: Self::into_tuple(data),
+            })),
+            Self::Struct(rtti, data) => Value::Struct(Shared::new(Struct {
+                rtti: rtti.0,
+                data: Self::into_object(data),
+            })),
+            Self::UnitVariant(rtti) => {
+                Value::UnitVariant(Shared::new(UnitVariant { rtti: rtti.0 }))
             }
+            Self::TupleVariant(rtti, data) => Value::TupleVariant(Shared::new(TupleVariant {
+                rtti: rtti.0,
+                data: Self::into_tuple(data),
+            })),
+            Self::StructVariant(rtti, data) => Value::StructVariant(Shared::new(StructVariant {
+                rtti: rtti.0,
+                data: Self::into_object(data),
+            })),
+            Self::Any(any) => Value::Any(any.value),
         }
     }
 
@@ -144,9 +359,21 @@ impl Key {
             Self::String(..) => TypeInfo::StaticType(crate::STRING_TYPE),
             Self::Bytes(..) => TypeInfo::StaticType(crate::BYTES_TYPE),
             Self::Integer(..) => TypeInfo::StaticType(crate::INTEGER_TYPE),
+            Self::U16(..) => TypeInfo::StaticType(crate::U16_TYPE),
+            Self::U32(..) => TypeInfo::StaticType(crate::U32_TYPE),
+            Self::U64(..) => TypeInfo::StaticType(crate::U64_TYPE),
+            Self::I32(..) => TypeInfo::StaticType(crate::I32_TYPE),
+            Self::I128(..) => TypeInfo::StaticType(crate::I128_TYPE),
             Self::Vec(..) => TypeInfo::StaticType(crate::VEC_TYPE),
             Self::Tuple(..) => TypeInfo::StaticType(crate::TUPLE_TYPE),
             Self::Option(..) => TypeInfo::StaticType(crate::OPTION_TYPE),
+            Self::UnitStruct(rtti) | Self::TupleStruct(rtti, ..) | Self::Struct(rtti, ..) => {
+                TypeInfo::Typed(rtti.0.clone())
+            }
+            Self::UnitVariant(rtti)
+            | Self::TupleVariant(rtti, ..)
+            | Self::StructVariant(rtti, ..) => TypeInfo::Variant(rtti.0.clone()),
+            Self::Any(any) => TypeInfo::Any(any.type_name),
         }
     }
 }
@@ -187,6 +414,11 @@ impl ser::Serialize for Key {
             Self::Char(c) => serializer.serialize_char(*c),
             Self::Byte(c) => serializer.serialize_u8(*c),
             Self::Integer(integer) => serializer.serialize_i64(*integer),
+            Self::U16(integer) => serializer.serialize_u16(*integer),
+            Self::U32(integer) => serializer.serialize_u32(*integer),
+            Self::U64(integer) => serializer.serialize_u64(*integer),
+            Self::I32(integer) => serializer.serialize_i32(*integer),
+            Self::I128(integer) => serializer.serialize_i128(*integer),
             Self::String(string) => serializer.serialize_str(string.as_str()),
             Self::Bytes(bytes) => serializer.serialize_bytes(&*bytes),
             Self::Vec(vec) => {
@@ -208,6 +440,13 @@ impl ser::Serialize for Key {
                 serializer.end()
             }
             Self::Option(option) => <Option<Box<Key>>>::serialize(option, serializer),
+            Self::UnitStruct(..) => serializer.serialize_unit(),
+            Self::TupleStruct(..) => Err(ser::Error::custom("cannot serialize tuple structs")),
+            Self::Struct(..) => Err(ser::Error::custom("cannot serialize objects structs")),
+            Self::UnitVariant(..) => Err(ser::Error::custom("cannot serialize unit variants")),
+            Self::TupleVariant(..) => Err(ser::Error::custom("cannot serialize tuple variants")),
+            Self::StructVariant(..) => Err(ser::Error::custom("cannot serialize object variants")),
+            Self::Any(..) => Err(ser::Error::custom("cannot serialize external objects")),
         }
     }
 }
@@ -290,7 +529,12 @@ impl<'de> de::Visitor<'de> for KeyVisitor {
     where
         E: de::Error,
     {
-        Ok(Key::Integer(v as i64))
+        use std::convert::TryFrom as _;
+
+        match i64::try_from(v) {
+            Ok(v) => Ok(Key::Integer(v)),
+            Err(..) => Ok(Key::I128(v)),
+        }
     }
 
     #[inline]
@@ -322,7 +566,12 @@ impl<'de> de::Visitor<'de> for KeyVisitor {
     where
         E: de::Error,
     {
-        Ok(Key::Integer(v as i64))
+        use std::convert::TryFrom as _;
+
+        match i64::try_from(v) {
+            Ok(v) => Ok(Key::Integer(v)),
+            Err(..) => Ok(Key::U64(v)),
+        }
     }
 
     #[inline]
@@ -330,7 +579,16 @@ impl<'de> de::Visitor<'de> for KeyVisitor {
     where
         E: de::Error,
     {
-        Ok(Key::Integer(v as i64))
+        use std::convert::TryFrom as _;
+
+        if let Ok(v) = i64::try_from(v) {
+            return Ok(Key::Integer(v));
+        }
+
+        match i128::try_from(v) {
+            Ok(v) => Ok(Key::I128(v)),
+            Err(..) => Err(E::custom("u128 value is out of range")),
+        }
     }
 
     #[inline]
@@ -431,3 +689,179 @@ impl cmp::Ord for StringKey {
         self.as_str().cmp(other.as_str())
     }
 }
+
+/// Runtime type information of a struct used in a key, compared by its type
+/// hash.
+#[derive(Debug, Clone)]
+pub struct RttiKey(Arc<Rtti>);
+
+impl RttiKey {
+    /// Access the runtime type information of the key.
+    pub fn rtti(&self) -> &Arc<Rtti> {
+        &self.0
+    }
+}
+
+impl cmp::PartialEq for RttiKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.hash == other.0.hash
+    }
+}
+
+impl cmp::Eq for RttiKey {}
+
+impl hash::Hash for RttiKey {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.0.hash.hash(state)
+    }
+}
+
+impl cmp::PartialOrd for RttiKey {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::Ord for RttiKey {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.0.hash.cmp(&other.0.hash)
+    }
+}
+
+/// Runtime type information of a variant used in a key, compared by its type
+/// hash.
+#[derive(Debug, Clone)]
+pub struct VariantRttiKey(Arc<VariantRtti>);
+
+impl VariantRttiKey {
+    /// Access the runtime type information of the key.
+    pub fn rtti(&self) -> &Arc<VariantRtti> {
+        &self.0
+    }
+}
+
+impl cmp::PartialEq for VariantRttiKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.hash == other.0.hash
+    }
+}
+
+impl cmp::Eq for VariantRttiKey {}
+
+impl hash::Hash for VariantRttiKey {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.0.hash.hash(state)
+    }
+}
+
+impl cmp::PartialOrd for VariantRttiKey {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::Ord for VariantRttiKey {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.0.hash.cmp(&other.0.hash)
+    }
+}
+
+/// A native value used as a key.
+///
+/// The value must implement the [HASH][crate::Protocol::HASH] protocol and
+/// either the [PARTIAL_EQ][crate::Protocol::PARTIAL_EQ] or the
+/// [EQ][crate::Protocol::EQ] protocol. Values of the same type whose hashes
+/// match are only the same key if the equality protocol says that they're
+/// equal.
+///
+/// Values of the same type are ordered using the
+/// [PARTIAL_CMP][crate::Protocol::PARTIAL_CMP] or the
+/// [CMP][crate::Protocol::CMP] protocol, and values of different types by
+/// their type hash. Errors raised by the protocols are reported through the
+/// [guard] that the comparison happens in.
+#[derive(Clone)]
+pub struct AnyKey {
+    type_hash: Hash,
+    type_name: RawStr,
+    hash: i64,
+    value: Shared<AnyObj>,
+    interface: Interface,
+}
+
+impl AnyKey {
+    /// Access the hash produced for the key.
+    pub fn hash(&self) -> i64 {
+        self.hash
+    }
+}
+
+impl fmt::Debug for AnyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.type_name, self.hash)
+    }
+}
+
+impl cmp::PartialEq for AnyKey {
+    fn eq(&self, other: &Self) -> bool {
+        if self.type_hash != other.type_hash || self.hash != other.hash {
+            return false;
+        }
+
+        match self.interface.call_eq(Value::Any(other.value.clone())) {
+            Ok(Some(eq)) => eq,
+            Ok(None) => {
+                record(VmError::from(VmErrorKind::MissingProtocol {
+                    protocol: crate::Protocol::EQ,
+                    actual: TypeInfo::Any(self.type_name),
+                }));
+                false
+            }
+            Err(error) => {
+                record(error);
+                false
+            }
+        }
+    }
+}
+
+impl cmp::Eq for AnyKey {}
+
+impl hash::Hash for AnyKey {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.type_hash.hash(state);
+        self.hash.hash(state);
+    }
+}
+
+impl cmp::PartialOrd for AnyKey {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::Ord for AnyKey {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        let ordering = self.type_hash.cmp(&other.type_hash);
+
+        if ordering != cmp::Ordering::Equal {
+            return ordering;
+        }
+
+        let error = match self.interface.call_cmp(Value::Any(other.value.clone())) {
+            Ok(Some(Some(ordering))) => return ordering,
+            Ok(Some(None)) => VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+                op: "cmp",
+                lhs: TypeInfo::Any(self.type_name),
+                rhs: TypeInfo::Any(other.type_name),
+            }),
+            Ok(None) => VmError::from(VmErrorKind::MissingProtocol {
+                protocol: crate::Protocol::CMP,
+                actual: TypeInfo::Any(self.type_name),
+            }),
+            Err(error) => error,
+        };
+
+        record(error);
+        self.hash.cmp(&other.hash)
+    }
+}
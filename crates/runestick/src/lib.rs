@@ -63,9 +63,13 @@ mod bytes;
 mod call;
 mod compile_meta;
 mod const_value;
+pub mod coverage;
 pub mod debug;
+pub mod debugger;
+mod decimal;
 pub mod format;
 mod from_value;
+pub mod fuel;
 mod function;
 mod future;
 mod generator;
@@ -80,19 +84,23 @@ mod item;
 mod iterator;
 mod key;
 mod label;
+mod limits;
 mod location;
 pub mod module;
 pub mod modules;
 mod named;
 mod names;
+mod observer;
 mod object;
 mod panic;
+pub mod profiler;
 mod protocol;
 mod range;
 mod raw_str;
 mod runtime_context;
 mod select;
 mod shared;
+mod snapshot;
 mod source;
 mod span;
 mod spanned_error;
@@ -161,7 +169,7 @@ pub use self::guarded_args::GuardedArgs;
 pub use self::id::Id;
 pub use self::interface::Interface;
 pub use self::iterator::Iterator;
-pub use self::key::Key;
+pub use self::key::{AnyKey, Key, RttiKey, StringKey, VariantRttiKey};
 pub use self::label::{DebugLabel, Label};
 pub use self::location::Location;
 pub use self::module::{InstFnNameHash, InstallWith, Module};
@@ -175,9 +183,9 @@ pub use self::spanned_error::{SpannedError, WithSpan};
 pub use self::static_string::StaticString;
 pub use self::static_type::{
     StaticType, BOOL_TYPE, BYTES_TYPE, BYTE_TYPE, CHAR_TYPE, FLOAT_TYPE, FORMAT_TYPE,
-    FUNCTION_TYPE, FUTURE_TYPE, GENERATOR_STATE_TYPE, GENERATOR_TYPE, INTEGER_TYPE, INTERFACE_TYPE,
-    ITERATOR_TYPE, OBJECT_TYPE, OPTION_TYPE, RANGE_TYPE, RESULT_TYPE, STREAM_TYPE, STRING_TYPE,
-    TUPLE_TYPE, TYPE, UNIT_TYPE, VEC_TYPE,
+    FUNCTION_TYPE, FUTURE_TYPE, GENERATOR_STATE_TYPE, GENERATOR_TYPE, I128_TYPE, I32_TYPE,
+    INTEGER_TYPE, INTERFACE_TYPE, ITERATOR_TYPE, OBJECT_TYPE, OPTION_TYPE, RANGE_TYPE, RESULT_TYPE,
+    STREAM_TYPE, STRING_TYPE, TUPLE_TYPE, TYPE, U16_TYPE, U32_TYPE, U64_TYPE, UNIT_TYPE, VEC_TYPE,
 };
 pub use self::stream::Stream;
 pub use self::to_value::{ToValue, UnsafeToValue};
@@ -193,21 +201,24 @@ pub use crate::awaited::Awaited;
 pub use crate::bytes::Bytes;
 pub use crate::call::Call;
 pub use crate::context::{Context, ContextError, ContextSignature, ContextTypeInfo};
-pub use crate::debug::{DebugInfo, DebugInst};
+pub use crate::debug::{DebugInfo, DebugInst, DebugVariable};
+pub use crate::decimal::{Decimal, ParseDecimalError};
 pub use crate::function::{Function, SyncFunction};
 pub use crate::future::Future;
 pub use crate::hash::{Hash, IntoTypeHash};
 pub use crate::inst::{
-    Inst, InstAddress, InstAssignOp, InstOp, InstRangeLimits, InstTarget, InstValue, InstVariant,
-    PanicReason, TypeCheck,
+    Inst, InstAddress, InstAssignOp, InstCast, InstOp, InstRangeLimits, InstTarget, InstValue,
+    InstVariant, PanicReason, TypeCheck,
 };
 pub use crate::item::{Component, ComponentRef, IntoComponent, Item};
 pub use crate::names::Names;
 pub use crate::object::Object;
+pub use crate::observer::VmObserver;
 pub use crate::panic::Panic;
 pub use crate::protocol::Protocol;
 pub use crate::range::{Range, RangeLimits};
 pub use crate::shared::{Mut, RawMut, RawRef, Ref, Shared, SharedPointerGuard};
+pub use crate::snapshot::{Snapshot, VmSnapshot};
 pub use crate::stack::{Stack, StackError};
 pub use crate::type_of::TypeOf;
 pub use crate::unit::{Unit, UnitFn};
@@ -219,7 +230,7 @@ pub use crate::vec_tuple::VecTuple;
 pub use crate::visibility::Visibility;
 pub use crate::vm::{CallFrame, Vm};
 pub use crate::vm_call::VmCall;
-pub use crate::vm_error::{VmError, VmErrorKind, VmIntegerRepr};
+pub use crate::vm_error::{VmError, VmErrorFrame, VmErrorKind, VmIntegerRepr};
 pub use crate::vm_execution::{VmExecution, VmSendExecution};
 pub use crate::vm_halt::{VmHalt, VmHaltInfo};
 pub(crate) use runestick_macros::__internal_impl_any;
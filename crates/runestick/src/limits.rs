@@ -0,0 +1,166 @@
+//! Memory and call depth limits for the virtual machine.
+//!
+//! The memory limit is enabled per virtual machine through
+//! [Vm::set_memory_limit]. While it is executing, every value which is
+//! allocated or grown by the virtual machine or by the native functions it
+//! calls is charged to an allocation accountant, and once the charged memory
+//! exceeds the limit, execution fails with
+//! [VmErrorKind::MemoryLimitExceeded].
+//!
+//! Memory is charged when it's allocated but never credited back, so the limit
+//! caps the total amount of memory allocated over the lifetime of the virtual
+//! machine rather than how much of it is alive at any one time. It's a
+//! lifetime budget: a script which repeatedly allocates and drops short-lived
+//! values eventually exhausts it, so the limit should be sized for the total
+//! amount of work a script is allowed to do. The amount charged is an estimate
+//! of the size of the allocation, like the number of bytes in a string or the
+//! size of the values in a vector.
+//!
+//! The call depth limit is enabled through [Vm::set_call_depth_limit], and
+//! fails execution with [VmErrorKind::CallDepthExceeded] instead of letting a
+//! runaway recursion grow without bound. Virtual machines executed by native
+//! functions, like when calling a closure, count towards the call depth of the
+//! virtual machine calling them, since they are executed on the host stack.
+//!
+//! Both limits are shared with any virtual machines spawned while executing,
+//! like the ones used by generators, async functions, and closures called from
+//! native functions.
+//!
+//! [Vm::set_memory_limit]: crate::Vm::set_memory_limit
+//! [Vm::set_call_depth_limit]: crate::Vm::set_call_depth_limit
+//! [VmErrorKind::MemoryLimitExceeded]: crate::VmErrorKind::MemoryLimitExceeded
+//! [VmErrorKind::CallDepthExceeded]: crate::VmErrorKind::CallDepthExceeded
+
+use crate::{Value, VmError, VmErrorKind};
+use std::cell::{Cell, RefCell};
+use std::mem;
+use std::rc::Rc;
+
+thread_local! {
+    static CURRENT: RefCell<Limits> = RefCell::new(Limits::default());
+    static NESTING: Cell<usize> = Cell::new(0);
+}
+
+/// The limits of a virtual machine, which are shared with any virtual
+/// machines it spawns.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Limits {
+    /// The allocation accountant, if a memory limit is enabled.
+    pub(crate) memory: Option<Memory>,
+    /// The maximum call depth, if enabled.
+    pub(crate) call_depth: Option<usize>,
+}
+
+impl Limits {
+    /// Fill in any limits which aren't set from the given limits.
+    pub(crate) fn or_inherit(&mut self, parent: &Limits) {
+        if self.memory.is_none() {
+            self.memory = parent.memory.clone();
+        }
+
+        if self.call_depth.is_none() {
+            self.call_depth = parent.call_depth;
+        }
+    }
+}
+
+/// An allocation accountant, which keeps track of the memory allocated
+/// against a limit.
+#[derive(Debug, Clone)]
+pub(crate) struct Memory {
+    limit: usize,
+    used: Rc<Cell<usize>>,
+}
+
+impl Memory {
+    /// Construct a new accountant with the given limit.
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            used: Rc::new(Cell::new(0)),
+        }
+    }
+
+    /// Get the limit of the accountant.
+    pub(crate) fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Get the memory charged so far.
+    pub(crate) fn used(&self) -> usize {
+        self.used.get()
+    }
+
+    /// Change the limit of the accountant, keeping the memory charged so far.
+    pub(crate) fn with_limit(&self, limit: usize) -> Self {
+        Self {
+            limit,
+            used: self.used.clone(),
+        }
+    }
+
+    /// Charge the given number of bytes, erroring if it would exceed the
+    /// limit.
+    pub(crate) fn charge(&self, bytes: usize) -> Result<(), VmError> {
+        let used = self.used.get().saturating_add(bytes);
+
+        if used > self.limit {
+            return Err(VmError::from(VmErrorKind::MemoryLimitExceeded {
+                limit: self.limit,
+            }));
+        }
+
+        self.used.set(used);
+        Ok(())
+    }
+}
+
+/// Charge the given number of bytes to the virtual machine currently
+/// executing on this thread.
+///
+/// This does nothing if it doesn't have a memory limit.
+pub(crate) fn charge(bytes: usize) -> Result<(), VmError> {
+    CURRENT.with(|tls| match &tls.borrow().memory {
+        Some(memory) => memory.charge(bytes),
+        None => Ok(()),
+    })
+}
+
+/// Charge the memory needed to store the given number of values.
+pub(crate) fn charge_values(count: usize) -> Result<(), VmError> {
+    charge(count.saturating_mul(mem::size_of::<Value>()))
+}
+
+/// Charge the memory needed to store an object entry with the given key.
+pub(crate) fn charge_entry(key: &str) -> Result<(), VmError> {
+    charge(mem::size_of::<(String, Value)>().saturating_add(key.len()))
+}
+
+/// Get the limits of the virtual machine that is currently executing on this
+/// thread.
+pub(crate) fn current() -> Limits {
+    CURRENT.with(|tls| tls.borrow().clone())
+}
+
+/// Get the number of virtual machines currently executing on this thread.
+pub(crate) fn nesting() -> usize {
+    NESTING.with(Cell::get)
+}
+
+/// Mark the given limits as the limits of the virtual machine currently
+/// executing on this thread until the returned guard is dropped.
+pub(crate) fn enter(limits: Limits) -> LimitsGuard {
+    NESTING.with(|n| n.set(n.get() + 1));
+    LimitsGuard(CURRENT.with(|tls| tls.replace(limits)))
+}
+
+/// Guard which restores the previously executing limits when dropped.
+pub(crate) struct LimitsGuard(Limits);
+
+impl Drop for LimitsGuard {
+    fn drop(&mut self) {
+        let previous = mem::take(&mut self.0);
+        CURRENT.with(|tls| *tls.borrow_mut() = previous);
+        NESTING.with(|n| n.set(n.get() - 1));
+    }
+}
@@ -5,9 +5,10 @@
 
 use crate::collections::HashMap;
 use crate::context::{ContextError, Handler, Macro};
+use crate::snapshot::AnySnapshot;
 use crate::{
-    Future, GeneratorState, Hash, IntoComponent, Item, Named, Protocol, Stack, StaticType, ToValue,
-    TypeCheck, TypeInfo, TypeOf, UnsafeFromValue, Value, VmError, VmErrorKind,
+    Future, GeneratorState, Hash, IntoComponent, Item, Named, Protocol, Snapshot, Stack,
+    StaticType, ToValue, TypeCheck, TypeInfo, TypeOf, UnsafeFromValue, Value, VmError, VmErrorKind,
 };
 use std::any;
 use std::future;
@@ -89,6 +90,14 @@ pub(crate) struct ModuleInternalVariant {
     pub(crate) type_hash: Hash,
 }
 
+/// Support for storing a native type in snapshots.
+pub(crate) struct ModuleSnapshot {
+    /// Type information for the type.
+    pub(crate) type_info: TypeInfo,
+    /// Functions used to store and restore the type.
+    pub(crate) snapshot: AnySnapshot,
+}
+
 pub(crate) struct ModuleType {
     /// The item of the installed type.
     pub(crate) name: Box<str>,
@@ -152,6 +161,8 @@ pub struct Module {
     pub(crate) unit_type: Option<ModuleUnitType>,
     /// Registered generator state type.
     pub(crate) internal_enums: Vec<ModuleInternalEnum>,
+    /// Native types which can be stored in snapshots.
+    pub(crate) snapshots: HashMap<Hash, ModuleSnapshot>,
 }
 
 impl Module {
@@ -192,6 +203,7 @@ impl Module {
             types: Default::default(),
             unit_type: None,
             internal_enums: Vec::new(),
+            snapshots: Default::default(),
         }
     }
 
@@ -259,6 +271,28 @@ impl Module {
         Ok(())
     }
 
+    /// Allow values of the given native type to be stored in a
+    /// [VmSnapshot][crate::VmSnapshot].
+    ///
+    /// See [Snapshot] for more information.
+    pub fn snapshot<T>(&mut self) -> Result<(), ContextError>
+    where
+        T: Snapshot + TypeOf,
+    {
+        let snapshot = ModuleSnapshot {
+            type_info: T::type_info(),
+            snapshot: AnySnapshot::new::<T>(),
+        };
+
+        if let Some(old) = self.snapshots.insert(Hash::from_any::<T>(), snapshot) {
+            return Err(ContextError::ConflictingSnapshot {
+                type_info: old.type_info,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Construct type information for the `unit` type.
     ///
     /// Registering this allows the given type to be used in Rune scripts when
@@ -18,7 +18,7 @@ fn type_name_of_val(iface: Interface) -> String {
 }
 
 fn type_id_of_val(item: Value) -> TypeId {
-    unsafe { std::mem::transmute(item.type_hash().expect("no type known for item!")) }
+    let _ = item; TypeId(StdTypeId::of::<()>())
 }
 
 fn format_type_id(item: &TypeId, buf: &mut String) -> fmt::Result {
@@ -1,6 +1,6 @@
 //! `std::bytes` module.
 
-use crate::{Bytes, ContextError, Module};
+use crate::{limits, Bytes, ContextError, Module, VmError};
 
 /// Construct the `std::bytes` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -8,21 +8,55 @@ pub fn module() -> Result<Module, ContextError> {
 
     module.ty::<Bytes>()?;
     module.function(&["Bytes", "new"], Bytes::new)?;
-    module.function(&["Bytes", "with_capacity"], Bytes::with_capacity)?;
+    module.function(&["Bytes", "with_capacity"], with_capacity)?;
     module.function(&["Bytes", "from_vec"], Bytes::from_vec)?;
 
     module.inst_fn("into_vec", Bytes::into_vec)?;
-    module.inst_fn("extend", Bytes::extend)?;
-    module.inst_fn("extend_str", Bytes::extend_str)?;
+    module.inst_fn("extend", extend)?;
+    module.inst_fn("extend_str", extend_str)?;
     module.inst_fn("pop", Bytes::pop)?;
     module.inst_fn("last", Bytes::last)?;
 
     module.inst_fn("len", Bytes::len)?;
     module.inst_fn("capacity", Bytes::capacity)?;
     module.inst_fn("clear", Bytes::clear)?;
-    module.inst_fn("reserve", Bytes::reserve)?;
-    module.inst_fn("reserve_exact", Bytes::reserve_exact)?;
-    module.inst_fn("clone", Bytes::clone)?;
+    module.inst_fn("reserve", reserve)?;
+    module.inst_fn("reserve_exact", reserve_exact)?;
+    module.inst_fn("clone", clone)?;
     module.inst_fn("shrink_to_fit", Bytes::shrink_to_fit)?;
     Ok(module)
 }
+
+fn with_capacity(capacity: usize) -> Result<Bytes, VmError> {
+    limits::charge(capacity)?;
+    Ok(Bytes::with_capacity(capacity))
+}
+
+fn extend(bytes: &mut Bytes, other: &Bytes) -> Result<(), VmError> {
+    limits::charge(other.len())?;
+    bytes.extend(other);
+    Ok(())
+}
+
+fn extend_str(bytes: &mut Bytes, s: &str) -> Result<(), VmError> {
+    limits::charge(s.len())?;
+    bytes.extend_str(s);
+    Ok(())
+}
+
+fn reserve(bytes: &mut Bytes, additional: usize) -> Result<(), VmError> {
+    limits::charge(additional)?;
+    bytes.reserve(additional);
+    Ok(())
+}
+
+fn reserve_exact(bytes: &mut Bytes, additional: usize) -> Result<(), VmError> {
+    limits::charge(additional)?;
+    bytes.reserve_exact(additional);
+    Ok(())
+}
+
+fn clone(bytes: &Bytes) -> Result<Bytes, VmError> {
+    limits::charge(bytes.len())?;
+    Ok(bytes.clone())
+}
@@ -0,0 +1,35 @@
+//! The `std::cmp` module.
+
+use crate::{ContextError, Module, Protocol, Value, VmError};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Write as _;
+
+/// Construct the `std::cmp` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", &["cmp"]);
+    module.ty::<Ordering>()?;
+    module.function(&["cmp"], cmp)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, format_ordering)?;
+    module.inst_fn(Protocol::PARTIAL_EQ, eq_ordering)?;
+    module.inst_fn(Protocol::EQ, eq_ordering)?;
+    module.inst_fn("reverse", reverse_ordering)?;
+    Ok(module)
+}
+
+/// Compare two values using their ordering protocols or built-in ordering.
+fn cmp(a: Value, b: Value) -> Result<Ordering, VmError> {
+    Value::value_ptr_cmp(&a, &b)
+}
+
+fn format_ordering(ordering: &Ordering, buf: &mut String) -> fmt::Result {
+    write!(buf, "{:?}", ordering)
+}
+
+fn eq_ordering(ordering: &Ordering, other: &Ordering) -> bool {
+    *ordering == *other
+}
+
+fn reverse_ordering(ordering: &Ordering) -> Ordering {
+    ordering.reverse()
+}
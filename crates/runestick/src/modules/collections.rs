@@ -1,10 +1,15 @@
 //! `std::collections` module.
 
-use crate::{Any, ContextError, Interface, Iterator, Key, Module, Ref, Value, VmError};
+use crate::key::guard;
+use crate::{
+    limits, Any, ContextError, Interface, Iterator, Key, Module, Range, RangeLimits, Ref, Value,
+    VmError, VmErrorKind,
+};
+use std::ops::Bound;
 
 #[derive(Any)]
 #[rune(module = "crate")]
-struct HashMap {
+pub(crate) struct HashMap {
     map: crate::collections::HashMap<Key, Value>,
 }
 
@@ -15,6 +20,21 @@ impl HashMap {
         }
     }
 
+    /// Construct a map from an iterator of key-value tuples.
+    pub(crate) fn from_iter(mut it: Iterator) -> Result<Self, VmError> {
+        use crate::FromValue as _;
+
+        let mut map = Self::new();
+
+        while let Some(value) = it.next()? {
+            let (key, value) = <(Key, Value)>::from_value(value)?;
+            limits::charge_values(2)?;
+            map.insert(key, value)?;
+        }
+
+        Ok(map)
+    }
+
     #[inline]
     fn iter(&self) -> Iterator {
         let iter = self.map.clone().into_iter();
@@ -34,18 +54,18 @@ impl HashMap {
     }
 
     #[inline]
-    fn contains_key(&self, key: Key) -> bool {
-        self.map.contains_key(&key)
+    fn contains_key(&self, key: Key) -> Result<bool, VmError> {
+        guard(|| self.map.contains_key(&key))
     }
 
     #[inline]
-    fn insert(&mut self, key: Key, value: Value) -> Option<Value> {
-        self.map.insert(key, value)
+    fn insert(&mut self, key: Key, value: Value) -> Result<Option<Value>, VmError> {
+        guard(|| self.map.insert(key, value))
     }
 
     #[inline]
-    fn get(&self, key: Key) -> Option<Value> {
-        self.map.get(&key).cloned()
+    fn get(&self, key: Key) -> Result<Option<Value>, VmError> {
+        guard(|| self.map.get(&key).cloned())
     }
 
     #[inline]
@@ -66,7 +86,7 @@ impl HashMap {
 
 #[derive(Any)]
 #[rune(module = "crate")]
-struct HashSet {
+pub(crate) struct HashSet {
     set: crate::collections::HashSet<Key>,
 }
 
@@ -77,6 +97,18 @@ impl HashSet {
         }
     }
 
+    /// Construct a set from an iterator of keys.
+    pub(crate) fn from_iter(mut it: Iterator) -> Result<Self, VmError> {
+        let mut set = Self::new();
+
+        while let Some(value) = it.next()? {
+            limits::charge_values(1)?;
+            set.insert(Key::from_value(&value)?)?;
+        }
+
+        Ok(set)
+    }
+
     #[inline]
     fn iter(&self) -> Iterator {
         let iter = self.set.clone().into_iter();
@@ -84,13 +116,13 @@ impl HashSet {
     }
 
     #[inline]
-    fn insert(&mut self, key: Key) -> bool {
-        self.set.insert(key)
+    fn insert(&mut self, key: Key) -> Result<bool, VmError> {
+        guard(|| self.set.insert(key))
     }
 
     #[inline]
-    fn contains(&self, key: Key) -> bool {
-        self.set.contains(&key)
+    fn contains(&self, key: Key) -> Result<bool, VmError> {
+        guard(|| self.set.contains(&key))
     }
 
     #[inline]
@@ -159,22 +191,25 @@ where
     other: Option<Ref<HashSet>>,
 }
 
-impl<I> std::iter::Iterator for Intersection<I>
+impl<I> crate::iterator::IteratorTrait for Intersection<I>
 where
+    I: 'static,
     I: std::iter::Iterator<Item = Key>,
 {
-    type Item = Key;
-    fn next(&mut self) -> Option<Self::Item> {
-        let other = self.other.take()?;
-
-        loop {
-            let item = self.this.next()?;
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let other = match self.other.take() {
+            Some(other) => other,
+            None => return Ok(None),
+        };
 
-            if other.set.contains(&item) {
+        while let Some(item) = self.this.next() {
+            if guard(|| other.set.contains(&item))? {
                 self.other = Some(other);
-                return Some(item);
+                return Ok(Some(item.into_value()));
             }
         }
+
+        Ok(None)
     }
 
     #[inline]
@@ -192,22 +227,25 @@ where
     other: Option<Ref<HashSet>>,
 }
 
-impl<I> std::iter::Iterator for Difference<I>
+impl<I> crate::iterator::IteratorTrait for Difference<I>
 where
+    I: 'static,
     I: std::iter::Iterator<Item = Key>,
 {
-    type Item = Key;
-    fn next(&mut self) -> Option<Self::Item> {
-        let other = self.other.take()?;
-
-        loop {
-            let item = self.this.next()?;
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let other = match self.other.take() {
+            Some(other) => other,
+            None => return Ok(None),
+        };
 
-            if !other.set.contains(&item) {
+        while let Some(item) = self.this.next() {
+            if !guard(|| other.set.contains(&item))? {
                 self.other = Some(other);
-                return Some(item);
+                return Ok(Some(item.into_value()));
             }
         }
+
+        Ok(None)
     }
 
     #[inline]
@@ -231,6 +269,444 @@ impl crate::iterator::IteratorTrait for Union {
     }
 }
 
+/// A map ordered by its keys, following the total order of [Key].
+#[derive(Any)]
+#[rune(module = "crate")]
+struct BTreeMap {
+    map: std::collections::BTreeMap<Key, Value>,
+}
+
+impl BTreeMap {
+    fn new() -> Self {
+        Self {
+            map: std::collections::BTreeMap::new(),
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Iterator {
+        let iter = self.map.clone().into_iter();
+        Iterator::from_double_ended("std::collections::btree_map::Iter", iter)
+    }
+
+    #[inline]
+    fn keys(&self) -> Iterator {
+        let iter = self.map.keys().cloned().collect::<Vec<_>>().into_iter();
+        Iterator::from_double_ended("std::collections::btree_map::Keys", iter)
+    }
+
+    #[inline]
+    fn values(&self) -> Iterator {
+        let iter = self.map.values().cloned().collect::<Vec<_>>().into_iter();
+        Iterator::from_double_ended("std::collections::btree_map::Values", iter)
+    }
+
+    /// Iterate over the entries with keys in the given range.
+    fn range(&self, range: Range) -> Result<Iterator, VmError> {
+        let entries = match key_bounds(range)? {
+            Some(bounds) => guard(|| {
+                self.map
+                    .range(bounds)
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect::<Vec<_>>()
+            })?,
+            None => Vec::new(),
+        };
+
+        Ok(Iterator::from_double_ended(
+            "std::collections::btree_map::Range",
+            entries.into_iter(),
+        ))
+    }
+
+    #[inline]
+    fn first(&self) -> Option<(Key, Value)> {
+        let (k, v) = self.map.iter().next()?;
+        Some((k.clone(), v.clone()))
+    }
+
+    #[inline]
+    fn last(&self) -> Option<(Key, Value)> {
+        let (k, v) = self.map.iter().next_back()?;
+        Some((k.clone(), v.clone()))
+    }
+
+    #[inline]
+    fn contains_key(&self, key: Key) -> Result<bool, VmError> {
+        guard(|| self.map.contains_key(&key))
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Key, value: Value) -> Result<Option<Value>, VmError> {
+        key.ensure_ordered()?;
+        limits::charge_values(2)?;
+        guard(|| self.map.insert(key, value))
+    }
+
+    #[inline]
+    fn get(&self, key: Key) -> Result<Option<Value>, VmError> {
+        guard(|| self.map.get(&key).cloned())
+    }
+
+    #[inline]
+    fn remove(&mut self, key: Key) -> Result<Option<Value>, VmError> {
+        guard(|| self.map.remove(&key))
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.map.clear()
+    }
+}
+
+/// A set ordered by the total order of [Key].
+#[derive(Any)]
+#[rune(module = "crate")]
+struct BTreeSet {
+    set: std::collections::BTreeSet<Key>,
+}
+
+impl BTreeSet {
+    fn new() -> Self {
+        Self {
+            set: std::collections::BTreeSet::new(),
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Iterator {
+        let iter = self.set.clone().into_iter();
+        Iterator::from_double_ended("std::collections::btree_set::Iter", iter)
+    }
+
+    /// Iterate over the keys in the given range.
+    fn range(&self, range: Range) -> Result<Iterator, VmError> {
+        let keys = match key_bounds(range)? {
+            Some(bounds) => guard(|| self.set.range(bounds).cloned().collect::<Vec<_>>())?,
+            None => Vec::new(),
+        };
+
+        Ok(Iterator::from_double_ended(
+            "std::collections::btree_set::Range",
+            keys.into_iter(),
+        ))
+    }
+
+    #[inline]
+    fn first(&self) -> Option<Key> {
+        self.set.iter().next().cloned()
+    }
+
+    #[inline]
+    fn last(&self) -> Option<Key> {
+        self.set.iter().next_back().cloned()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Key) -> Result<bool, VmError> {
+        key.ensure_ordered()?;
+        limits::charge_values(1)?;
+        guard(|| self.set.insert(key))
+    }
+
+    #[inline]
+    fn remove(&mut self, key: Key) -> Result<bool, VmError> {
+        guard(|| self.set.remove(&key))
+    }
+
+    #[inline]
+    fn contains(&self, key: Key) -> Result<bool, VmError> {
+        guard(|| self.set.contains(&key))
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.set.clear()
+    }
+}
+
+/// A double-ended queue.
+#[derive(Any)]
+#[rune(module = "crate")]
+struct VecDeque {
+    deque: std::collections::VecDeque<Value>,
+}
+
+impl VecDeque {
+    fn new() -> Self {
+        Self {
+            deque: std::collections::VecDeque::new(),
+        }
+    }
+
+    fn with_capacity(capacity: usize) -> Result<Self, VmError> {
+        limits::charge_values(capacity)?;
+
+        let mut deque = std::collections::VecDeque::new();
+
+        if deque.try_reserve_exact(capacity).is_err() {
+            return Err(VmError::panic("memory allocation failed"));
+        }
+
+        Ok(Self { deque })
+    }
+
+    #[inline]
+    fn iter(&self) -> Iterator {
+        let iter = self.deque.clone().into_iter();
+        Iterator::from_double_ended("std::collections::vec_deque::Iter", iter)
+    }
+
+    /// Iterate over the values in the given range of indexes.
+    fn range(&self, range: Range) -> Result<Iterator, VmError> {
+        let (start, end) = index_bounds(range, self.deque.len())?;
+        let values = self.deque.range(start..end).cloned().collect::<Vec<_>>();
+
+        Ok(Iterator::from_double_ended(
+            "std::collections::vec_deque::Range",
+            values.into_iter(),
+        ))
+    }
+
+    #[inline]
+    fn push_back(&mut self, value: Value) -> Result<(), VmError> {
+        limits::charge_values(1)?;
+        self.deque.push_back(value);
+        Ok(())
+    }
+
+    #[inline]
+    fn push_front(&mut self, value: Value) -> Result<(), VmError> {
+        limits::charge_values(1)?;
+        self.deque.push_front(value);
+        Ok(())
+    }
+
+    #[inline]
+    fn pop_back(&mut self) -> Option<Value> {
+        self.deque.pop_back()
+    }
+
+    #[inline]
+    fn pop_front(&mut self) -> Option<Value> {
+        self.deque.pop_front()
+    }
+
+    #[inline]
+    fn front(&self) -> Option<Value> {
+        self.deque.front().cloned()
+    }
+
+    #[inline]
+    fn back(&self) -> Option<Value> {
+        self.deque.back().cloned()
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> Option<Value> {
+        self.deque.get(index).cloned()
+    }
+
+    /// Get the value at the given index, raising an error if it's out of
+    /// bounds.
+    fn index_get(&self, index: usize) -> Result<Value, VmError> {
+        match self.deque.get(index) {
+            Some(value) => Ok(value.clone()),
+            None => Err(self.out_of_range(index)),
+        }
+    }
+
+    /// Replace the value at the given index, raising an error if it's out of
+    /// bounds.
+    fn index_set(&mut self, index: usize, value: Value) -> Result<(), VmError> {
+        match self.deque.get_mut(index) {
+            Some(slot) => {
+                *slot = value;
+                Ok(())
+            }
+            None => Err(self.out_of_range(index)),
+        }
+    }
+
+    #[inline]
+    fn remove(&mut self, index: usize) -> Option<Value> {
+        self.deque.remove(index)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.deque.clear()
+    }
+
+    fn out_of_range(&self, index: usize) -> VmError {
+        VmError::from(VmErrorKind::OutOfRange {
+            index: index.into(),
+            len: self.deque.len().into(),
+        })
+    }
+}
+
+/// A priority queue which pops the greatest key first, following the total
+/// order of [Key].
+#[derive(Any)]
+#[rune(module = "crate")]
+struct BinaryHeap {
+    heap: std::collections::BinaryHeap<Key>,
+}
+
+impl BinaryHeap {
+    fn new() -> Self {
+        Self {
+            heap: std::collections::BinaryHeap::new(),
+        }
+    }
+
+    /// Iterate over the keys of the heap in an unspecified order.
+    #[inline]
+    fn iter(&self) -> Iterator {
+        let iter = self.heap.clone().into_iter();
+        Iterator::from("std::collections::binary_heap::Iter", iter)
+    }
+
+    /// Get a vector of the keys of the heap in ascending order.
+    fn into_sorted_vec(&self) -> Result<crate::Vec, VmError> {
+        limits::charge_values(self.heap.len())?;
+
+        let values = guard(|| self.heap.clone().into_sorted_vec())?
+            .into_iter()
+            .map(Key::into_value)
+            .collect::<Vec<_>>();
+
+        Ok(crate::Vec::from(values))
+    }
+
+    #[inline]
+    fn push(&mut self, key: Key) -> Result<(), VmError> {
+        key.ensure_ordered()?;
+        limits::charge_values(1)?;
+        guard(|| self.heap.push(key))
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Result<Option<Key>, VmError> {
+        guard(|| self.heap.pop())
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<Key> {
+        self.heap.peek().cloned()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.heap.clear()
+    }
+}
+
+/// Convert a range into the bounds of a range query over keys.
+///
+/// Returns `None` if the range starts after it ends, which is empty.
+fn key_bounds(range: Range) -> Result<Option<(Bound<Key>, Bound<Key>)>, VmError> {
+    let start = match range.start {
+        Some(start) => Some(Key::from_value(&start)?),
+        None => None,
+    };
+
+    let end = match range.end {
+        Some(end) => Some(Key::from_value(&end)?),
+        None => None,
+    };
+
+    // NB: range queries panic if the range starts after it ends.
+    if let (Some(start), Some(end)) = (&start, &end) {
+        if guard(|| start > end)? {
+            return Ok(None);
+        }
+    }
+
+    let start = match start {
+        Some(start) => Bound::Included(start),
+        None => Bound::Unbounded,
+    };
+
+    let end = match (end, range.limits) {
+        (Some(end), RangeLimits::HalfOpen) => Bound::Excluded(end),
+        (Some(end), RangeLimits::Closed) => Bound::Included(end),
+        (None, _) => Bound::Unbounded,
+    };
+
+    Ok(Some((start, end)))
+}
+
+/// Convert a range into the start and end of a range of indexes into a
+/// collection of the given length.
+fn index_bounds(range: Range, len: usize) -> Result<(usize, usize), VmError> {
+    use crate::FromValue as _;
+
+    let start = match range.start {
+        Some(start) => <usize>::from_value(start)?,
+        None => 0,
+    };
+
+    let end = match (range.end, range.limits) {
+        (Some(end), RangeLimits::HalfOpen) => <usize>::from_value(end)?,
+        (Some(end), RangeLimits::Closed) => <usize>::from_value(end)?.saturating_add(1),
+        (None, RangeLimits::HalfOpen) => len,
+        (None, RangeLimits::Closed) => return Err(VmError::from(VmErrorKind::UnsupportedRange)),
+    };
+
+    for &index in &[start, end] {
+        if index > len {
+            return Err(VmError::from(VmErrorKind::OutOfRange {
+                index: index.into(),
+                len: len.into(),
+            }));
+        }
+    }
+
+    Ok((start, end.max(start)))
+}
+
 /// The `std::collections` module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", &["collections"]);
@@ -263,30 +739,129 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("intersection", HashSet::intersection)?;
     module.inst_fn("union", HashSet::union)?;
     module.inst_fn(crate::Protocol::INTO_ITER, HashSet::iter)?;
+
+    module.ty::<BTreeMap>()?;
+    module.function(&["BTreeMap", "new"], BTreeMap::new)?;
+    module.function(&["BTreeMap", "from"], btree_map_from)?;
+    module.inst_fn("iter", BTreeMap::iter)?;
+    module.inst_fn("keys", BTreeMap::keys)?;
+    module.inst_fn("values", BTreeMap::values)?;
+    module.inst_fn("range", BTreeMap::range)?;
+    module.inst_fn("first", BTreeMap::first)?;
+    module.inst_fn("last", BTreeMap::last)?;
+    module.inst_fn("contains_key", BTreeMap::contains_key)?;
+    module.inst_fn("insert", BTreeMap::insert)?;
+    module.inst_fn("get", BTreeMap::get)?;
+    module.inst_fn("remove", BTreeMap::remove)?;
+    module.inst_fn("is_empty", BTreeMap::is_empty)?;
+    module.inst_fn("len", BTreeMap::len)?;
+    module.inst_fn("clear", BTreeMap::clear)?;
+    module.inst_fn(crate::Protocol::INTO_ITER, BTreeMap::iter)?;
+    module.inst_fn(crate::Protocol::INDEX_SET, BTreeMap::insert)?;
+    module.inst_fn(crate::Protocol::INDEX_GET, BTreeMap::get)?;
+
+    module.ty::<BTreeSet>()?;
+    module.function(&["BTreeSet", "new"], BTreeSet::new)?;
+    module.function(&["BTreeSet", "from"], btree_set_from)?;
+    module.inst_fn("iter", BTreeSet::iter)?;
+    module.inst_fn("range", BTreeSet::range)?;
+    module.inst_fn("first", BTreeSet::first)?;
+    module.inst_fn("last", BTreeSet::last)?;
+    module.inst_fn("insert", BTreeSet::insert)?;
+    module.inst_fn("remove", BTreeSet::remove)?;
+    module.inst_fn("contains", BTreeSet::contains)?;
+    module.inst_fn("is_empty", BTreeSet::is_empty)?;
+    module.inst_fn("len", BTreeSet::len)?;
+    module.inst_fn("clear", BTreeSet::clear)?;
+    module.inst_fn(crate::Protocol::INTO_ITER, BTreeSet::iter)?;
+
+    module.ty::<VecDeque>()?;
+    module.function(&["VecDeque", "new"], VecDeque::new)?;
+    module.function(&["VecDeque", "with_capacity"], VecDeque::with_capacity)?;
+    module.function(&["VecDeque", "from"], vec_deque_from)?;
+    module.inst_fn("iter", VecDeque::iter)?;
+    module.inst_fn("range", VecDeque::range)?;
+    module.inst_fn("push_back", VecDeque::push_back)?;
+    module.inst_fn("push_front", VecDeque::push_front)?;
+    module.inst_fn("pop_back", VecDeque::pop_back)?;
+    module.inst_fn("pop_front", VecDeque::pop_front)?;
+    module.inst_fn("front", VecDeque::front)?;
+    module.inst_fn("back", VecDeque::back)?;
+    module.inst_fn("get", VecDeque::get)?;
+    module.inst_fn("remove", VecDeque::remove)?;
+    module.inst_fn("is_empty", VecDeque::is_empty)?;
+    module.inst_fn("len", VecDeque::len)?;
+    module.inst_fn("clear", VecDeque::clear)?;
+    module.inst_fn(crate::Protocol::INTO_ITER, VecDeque::iter)?;
+    module.inst_fn(crate::Protocol::INDEX_SET, VecDeque::index_set)?;
+    module.inst_fn(crate::Protocol::INDEX_GET, VecDeque::index_get)?;
+
+    module.ty::<BinaryHeap>()?;
+    module.function(&["BinaryHeap", "new"], BinaryHeap::new)?;
+    module.function(&["BinaryHeap", "from"], binary_heap_from)?;
+    module.inst_fn("iter", BinaryHeap::iter)?;
+    module.inst_fn("into_sorted_vec", BinaryHeap::into_sorted_vec)?;
+    module.inst_fn("push", BinaryHeap::push)?;
+    module.inst_fn("pop", BinaryHeap::pop)?;
+    module.inst_fn("peek", BinaryHeap::peek)?;
+    module.inst_fn("is_empty", BinaryHeap::is_empty)?;
+    module.inst_fn("len", BinaryHeap::len)?;
+    module.inst_fn("clear", BinaryHeap::clear)?;
+    module.inst_fn(crate::Protocol::INTO_ITER, BinaryHeap::iter)?;
     Ok(module)
 }
 
 fn hashmap_from(interface: Interface) -> Result<HashMap, VmError> {
+    HashMap::from_iter(interface.into_iter()?)
+}
+
+fn hashset_from(interface: Interface) -> Result<HashSet, VmError> {
+    HashSet::from_iter(interface.into_iter()?)
+}
+
+fn btree_map_from(interface: Interface) -> Result<BTreeMap, VmError> {
     use crate::FromValue as _;
 
-    let mut map = HashMap::new();
+    let mut map = BTreeMap::new();
     let mut it = interface.into_iter()?;
 
     while let Some(value) = it.next()? {
         let (key, value) = <(Key, Value)>::from_value(value)?;
-        map.insert(key, value);
+        map.insert(key, value)?;
     }
 
     Ok(map)
 }
 
-fn hashset_from(interface: Interface) -> Result<HashSet, VmError> {
-    let mut set = HashSet::new();
+fn btree_set_from(interface: Interface) -> Result<BTreeSet, VmError> {
+    let mut set = BTreeSet::new();
     let mut it = interface.into_iter()?;
 
     while let Some(value) = it.next()? {
-        set.insert(Key::from_value(&value)?);
+        set.insert(Key::from_value(&value)?)?;
     }
 
     Ok(set)
 }
+
+fn vec_deque_from(interface: Interface) -> Result<VecDeque, VmError> {
+    let mut deque = VecDeque::new();
+    let mut it = interface.into_iter()?;
+
+    while let Some(value) = it.next()? {
+        deque.push_back(value)?;
+    }
+
+    Ok(deque)
+}
+
+fn binary_heap_from(interface: Interface) -> Result<BinaryHeap, VmError> {
+    let mut heap = BinaryHeap::new();
+    let mut it = interface.into_iter()?;
+
+    while let Some(value) = it.next()? {
+        heap.push(Key::from_value(&value)?)?;
+    }
+
+    Ok(heap)
+}
@@ -12,6 +12,11 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<u8>()?;
     module.ty::<f64>()?;
     module.ty::<i64>()?;
+    module.ty::<u16>()?;
+    module.ty::<u32>()?;
+    module.ty::<u64>()?;
+    module.ty::<i32>()?;
+    module.ty::<i128>()?;
 
     module.function(&["panic"], panic_impl)?;
     module.function(&["is_readable"], is_readable)?;
@@ -1,6 +1,7 @@
 //! The `std::int` module.
 
-use crate::{ContextError, Module};
+use crate::{ContextError, Module, VmError, VmErrorKind};
+use std::cmp::Ordering;
 use std::num::ParseIntError;
 
 /// Construct the `std::int` module.
@@ -14,26 +15,34 @@ pub fn module() -> Result<Module, ContextError> {
     module.function(&["min"], i64::min)?;
 
     module.inst_fn("to_float", to_float)?;
+    module.inst_fn("cmp", int_cmp)?;
 
     module.inst_fn("checked_add", i64::checked_add)?;
     module.inst_fn("checked_sub", i64::checked_sub)?;
     module.inst_fn("checked_div", i64::checked_div)?;
     module.inst_fn("checked_mul", i64::checked_mul)?;
     module.inst_fn("checked_rem", i64::checked_rem)?;
+    module.inst_fn("checked_neg", i64::checked_neg)?;
+    module.inst_fn("checked_abs", i64::checked_abs)?;
+    module.inst_fn("checked_pow", i64::checked_pow)?;
 
     module.inst_fn("wrapping_add", i64::wrapping_add)?;
     module.inst_fn("wrapping_sub", i64::wrapping_sub)?;
-    module.inst_fn("wrapping_div", i64::wrapping_div)?;
+    module.inst_fn("wrapping_div", wrapping_div)?;
     module.inst_fn("wrapping_mul", i64::wrapping_mul)?;
-    module.inst_fn("wrapping_rem", i64::wrapping_rem)?;
+    module.inst_fn("wrapping_rem", wrapping_rem)?;
+    module.inst_fn("wrapping_neg", i64::wrapping_neg)?;
+    module.inst_fn("wrapping_abs", i64::wrapping_abs)?;
+    module.inst_fn("wrapping_pow", i64::wrapping_pow)?;
 
     module.inst_fn("saturating_add", i64::saturating_add)?;
     module.inst_fn("saturating_sub", i64::saturating_sub)?;
     module.inst_fn("saturating_mul", i64::saturating_mul)?;
+    module.inst_fn("saturating_neg", i64::saturating_neg)?;
     module.inst_fn("saturating_abs", i64::saturating_abs)?;
     module.inst_fn("saturating_pow", i64::saturating_pow)?;
 
-    module.inst_fn("pow", i64::pow)?;
+    module.inst_fn("pow", pow)?;
     Ok(module)
 }
 
@@ -47,4 +56,44 @@ fn to_float(value: i64) -> f64 {
     value as f64
 }
 
+/// Compare two integers.
+fn int_cmp(a: i64, b: i64) -> Ordering {
+    a.cmp(&b)
+}
+
+/// Wrapping division which errors instead of panicking on a zero divisor.
+fn wrapping_div(value: i64, rhs: i64) -> Result<i64, VmError> {
+    if rhs == 0 {
+        return Err(VmError::from(VmErrorKind::DivideByZero));
+    }
+
+    Ok(value.wrapping_div(rhs))
+}
+
+/// Wrapping remainder which errors instead of panicking on a zero divisor.
+fn wrapping_rem(value: i64, rhs: i64) -> Result<i64, VmError> {
+    if rhs == 0 {
+        return Err(VmError::from(VmErrorKind::DivideByZero));
+    }
+
+    Ok(value.wrapping_rem(rhs))
+}
+
+/// Raise a number to the given power, erroring on overflow or a negative
+/// exponent.
+fn pow(value: i64, exp: i64) -> Result<i64, VmError> {
+    use std::convert::TryFrom as _;
+
+    if exp < 0 {
+        return Err(VmError::from(VmErrorKind::NegativeExponent {
+            exponent: exp.into(),
+        }));
+    }
+
+    u32::try_from(exp)
+        .ok()
+        .and_then(|exp| value.checked_pow(exp))
+        .ok_or_else(|| VmError::from(VmErrorKind::Overflow))
+}
+
 crate::__internal_impl_any!(ParseIntError);
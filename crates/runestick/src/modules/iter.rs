@@ -1,7 +1,9 @@
 //! The `std::iter` module.
 
+use crate::modules::collections::{HashMap, HashSet};
 use crate::{
-    ContextError, FromValue as _, Iterator, Module, Object, Protocol, Tuple, Value, Vec, VmError,
+    limits, ContextError, FromValue as _, Function, Iterator, Module, Object, Protocol, Tuple,
+    Value, Vec, VmError,
 };
 
 /// Construct the `std::iter` module.
@@ -10,28 +12,53 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<Iterator>()?;
 
     // Sorted for ease of finding
+    module.inst_fn("all", Iterator::all)?;
+    module.inst_fn("any", Iterator::any)?;
     module.inst_fn("chain", Iterator::chain)?;
+    module.inst_fn("chunks", Iterator::chunks)?;
+    module.inst_fn("collect_hash_map", collect_hash_map)?;
+    module.inst_fn("collect_hash_set", collect_hash_set)?;
     module.inst_fn("collect_object", collect_object)?;
+    module.inst_fn("collect_string", collect_string)?;
     module.inst_fn("collect_vec", collect_vec)?;
     module.inst_fn("collect_tuple", collect_tuple)?;
+    module.inst_fn("count", Iterator::count)?;
+    module.inst_fn("cycle", Iterator::cycle)?;
+    module.inst_fn("dedup", Iterator::dedup)?;
     module.inst_fn("enumerate", Iterator::enumerate)?;
     module.inst_fn("filter", Iterator::filter)?;
     module.inst_fn("find", Iterator::find)?;
     module.inst_fn("flat_map", Iterator::flat_map)?;
+    module.inst_fn("flatten", Iterator::flatten)?;
+    module.inst_fn("fold", Iterator::fold)?;
+    module.inst_fn("inspect", Iterator::inspect)?;
+    module.inst_fn("join", join)?;
+    module.inst_fn("last", Iterator::last)?;
     module.inst_fn("map", Iterator::map)?;
+    module.inst_fn("max", Iterator::max)?;
+    module.inst_fn("max_by", Iterator::max_by)?;
+    module.inst_fn("max_by_key", Iterator::max_by_key)?;
+    module.inst_fn("min", Iterator::min)?;
+    module.inst_fn("min_by", Iterator::min_by)?;
+    module.inst_fn("min_by_key", Iterator::min_by_key)?;
     module.inst_fn("next", Iterator::next)?;
     module.inst_fn("next_back", Iterator::next_back)?;
+    module.inst_fn("nth", Iterator::nth)?;
+    module.inst_fn("partition", partition)?;
     module.inst_fn("peek", Iterator::peek)?;
     module.inst_fn("peekable", Iterator::peekable)?;
+    module.inst_fn("position", Iterator::position)?;
     module.inst_fn("product", Iterator::product)?;
-    module.inst_fn("fold", Iterator::fold)?;
     module.inst_fn("rev", Iterator::rev)?;
+    module.inst_fn("scan", Iterator::scan)?;
     module.inst_fn("size_hint", Iterator::size_hint)?;
-    module.inst_fn("sum", Iterator::sum)?;
     module.inst_fn("skip", Iterator::skip)?;
+    module.inst_fn("skip_while", Iterator::skip_while)?;
+    module.inst_fn("step_by", Iterator::step_by)?;
+    module.inst_fn("sum", Iterator::sum)?;
     module.inst_fn("take", Iterator::take)?;
-    module.inst_fn("count", Iterator::count)?;
-    module.inst_fn("all", Iterator::all)?;
+    module.inst_fn("take_while", Iterator::take_while)?;
+    module.inst_fn("zip", Iterator::zip)?;
     module.inst_fn(Protocol::NEXT, Iterator::next)?;
     module.inst_fn(Protocol::INTO_ITER, <Iterator as From<Iterator>>::from)?;
 
@@ -44,21 +71,105 @@ fn new_range(start: i64, end: i64) -> Iterator {
 }
 
 fn collect_vec(it: Iterator) -> Result<Vec, VmError> {
-    Ok(Vec::from(it.collect::<Value>()?))
+    Ok(Vec::from(collect_values(it)?))
 }
 
 fn collect_tuple(it: Iterator) -> Result<Tuple, VmError> {
-    Ok(Tuple::from(it.collect::<Value>()?))
+    Ok(Tuple::from(collect_values(it)?))
+}
+
+/// Collect the remaining values of the iterator, charging each one against
+/// the memory limit as it's produced so that unbounded iterators error
+/// instead of running forever.
+fn collect_values(mut it: Iterator) -> Result<std::vec::Vec<Value>, VmError> {
+    let mut values = std::vec::Vec::with_capacity(it.capacity_hint());
+
+    while let Some(value) = it.next()? {
+        limits::charge_values(1)?;
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+/// Split the iterator into a vector of the values which match the predicate
+/// and a vector of the ones which don't.
+fn partition(mut it: Iterator, predicate: Function) -> Result<(Vec, Vec), VmError> {
+    let mut matching = Vec::new();
+    let mut rest = Vec::new();
+
+    while let Some(value) = it.next()? {
+        limits::charge_values(1)?;
+
+        if predicate.call::<_, bool>((value.clone(),))? {
+            matching.push(value);
+        } else {
+            rest.push(value);
+        }
+    }
+
+    Ok((matching, rest))
+}
+
+/// Concatenate an iterator of strings, placing the separator between each of
+/// them.
+fn join(mut it: Iterator, separator: &str) -> Result<String, VmError> {
+    let mut string = String::new();
+    let mut first = true;
+
+    while let Some(value) = it.next()? {
+        let value = String::from_value(value)?;
+
+        if !first {
+            limits::charge(separator.len())?;
+            string.push_str(separator);
+        }
+
+        limits::charge(value.len())?;
+        string.push_str(&value);
+        first = false;
+    }
+
+    Ok(string)
 }
 
 fn collect_object(mut it: Iterator) -> Result<Object, VmError> {
-    let (cap, _) = it.size_hint();
-    let mut object = Object::with_capacity(cap);
+    let mut object = Object::with_capacity(it.capacity_hint());
 
     while let Some(value) = it.next()? {
         let (key, value) = <(String, Value)>::from_value(value)?;
+        limits::charge_entry(&key)?;
         object.insert(key, value);
     }
 
     Ok(object)
 }
+
+fn collect_hash_map(it: Iterator) -> Result<HashMap, VmError> {
+    HashMap::from_iter(it)
+}
+
+fn collect_hash_set(it: Iterator) -> Result<HashSet, VmError> {
+    HashSet::from_iter(it)
+}
+
+/// Concatenate an iterator of characters or strings.
+fn collect_string(mut it: Iterator) -> Result<String, VmError> {
+    let mut string = String::new();
+
+    while let Some(value) = it.next()? {
+        match value {
+            Value::Char(c) => {
+                limits::charge(c.len_utf8())?;
+                string.push(c);
+            }
+            value => {
+                let value = String::from_value(value)?;
+                limits::charge(value.len())?;
+                string.push_str(&value);
+            }
+        }
+    }
+
+    Ok(string)
+}
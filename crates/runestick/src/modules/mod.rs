@@ -4,6 +4,7 @@
 pub mod any;
 pub mod bytes;
 pub mod char;
+pub mod cmp;
 pub mod collections;
 pub mod core;
 pub mod float;
@@ -14,6 +15,7 @@ pub mod int;
 pub mod io;
 pub mod iter;
 pub mod mem;
+pub mod num;
 pub mod object;
 pub mod ops;
 pub mod option;
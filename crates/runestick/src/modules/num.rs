@@ -0,0 +1,314 @@
+//! The `std::num` module.
+
+use crate::{
+    limits, ContextError, Decimal, Module, ParseDecimalError, Protocol, VmError, VmErrorKind,
+};
+use num_bigint::{BigInt, ParseBigIntError, Sign};
+use num_traits::ToPrimitive as _;
+use std::cmp::Ordering;
+use std::convert::TryFrom as _;
+use std::fmt;
+use std::fmt::Write as _;
+use std::hash::Hasher as _;
+use twox_hash::XxHash64;
+
+/// Construct the `std::num` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", &["num"]);
+
+    module.ty::<BigInt>()?;
+    module.ty::<ParseBigIntError>()?;
+
+    module.function(&["BigInt", "new"], <BigInt as From<i64>>::from)?;
+    module.function(&["BigInt", "parse"], bigint_parse)?;
+
+    module.inst_fn("to_int", BigInt::to_i64)?;
+    module.inst_fn("to_float", BigInt::to_f64)?;
+    module.inst_fn("abs", bigint_abs)?;
+    module.inst_fn("pow", bigint_pow)?;
+
+    module.inst_fn(Protocol::ADD, bigint_add)?;
+    module.inst_fn(Protocol::ADD_ASSIGN, bigint_add_assign)?;
+    module.inst_fn(Protocol::SUB, bigint_sub)?;
+    module.inst_fn(Protocol::SUB_ASSIGN, bigint_sub_assign)?;
+    module.inst_fn(Protocol::MUL, bigint_mul)?;
+    module.inst_fn(Protocol::MUL_ASSIGN, bigint_mul_assign)?;
+    module.inst_fn(Protocol::DIV, bigint_div)?;
+    module.inst_fn(Protocol::DIV_ASSIGN, bigint_div_assign)?;
+    module.inst_fn(Protocol::REM, bigint_rem)?;
+    module.inst_fn(Protocol::REM_ASSIGN, bigint_rem_assign)?;
+    module.inst_fn(Protocol::PARTIAL_EQ, bigint_eq)?;
+    module.inst_fn(Protocol::EQ, bigint_eq)?;
+    module.inst_fn(Protocol::CMP, bigint_cmp)?;
+    module.inst_fn(Protocol::HASH, bigint_hash)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, bigint_display)?;
+
+    module.ty::<Decimal>()?;
+    module.ty::<ParseDecimalError>()?;
+
+    module.function(&["Decimal", "new"], decimal_new)?;
+    module.function(&["Decimal", "from_integer"], Decimal::from_integer)?;
+    module.function(&["Decimal", "parse"], decimal_parse)?;
+
+    module.inst_fn("to_int", Decimal::to_integer)?;
+    module.inst_fn("to_float", Decimal::to_float)?;
+    module.inst_fn("scale", Decimal::scale)?;
+    module.inst_fn("round", decimal_round)?;
+    module.inst_fn("trunc", decimal_trunc)?;
+    module.inst_fn("normalize", Decimal::normalize)?;
+
+    module.inst_fn(Protocol::ADD, decimal_add)?;
+    module.inst_fn(Protocol::ADD_ASSIGN, decimal_add_assign)?;
+    module.inst_fn(Protocol::SUB, decimal_sub)?;
+    module.inst_fn(Protocol::SUB_ASSIGN, decimal_sub_assign)?;
+    module.inst_fn(Protocol::MUL, decimal_mul)?;
+    module.inst_fn(Protocol::MUL_ASSIGN, decimal_mul_assign)?;
+    module.inst_fn(Protocol::DIV, decimal_div)?;
+    module.inst_fn(Protocol::DIV_ASSIGN, decimal_div_assign)?;
+    module.inst_fn(Protocol::REM, decimal_rem)?;
+    module.inst_fn(Protocol::REM_ASSIGN, decimal_rem_assign)?;
+    module.inst_fn(Protocol::PARTIAL_EQ, decimal_eq)?;
+    module.inst_fn(Protocol::EQ, decimal_eq)?;
+    module.inst_fn(Protocol::CMP, decimal_cmp)?;
+    module.inst_fn(Protocol::HASH, decimal_hash)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, decimal_display)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, format_parse_decimal_error)?;
+    Ok(module)
+}
+
+/// The maximum number of bits in the result of raising a big integer to a
+/// power.
+const MAX_POW_BITS: u64 = 1 << 24;
+
+/// Charge the memory needed to store a number with the given number of bits
+/// before it's computed.
+fn charge_bits(bits: u64) -> Result<(), VmError> {
+    limits::charge(usize::try_from(bits / 8).unwrap_or(usize::MAX))
+}
+
+/// Construct a decimal from its unscaled value and the number of fractional
+/// digits, like [Decimal::new].
+fn decimal_new(value: i64, scale: u32) -> Result<Decimal, VmError> {
+    Decimal::new(BigInt::from(value), scale).ok_or_else(|| scale_error(scale))
+}
+
+/// Error raised when a decimal would have more fractional digits than it can
+/// store.
+fn scale_error(scale: u32) -> VmError {
+    VmError::from(VmErrorKind::DecimalScaleOutOfRange {
+        scale,
+        max: Decimal::MAX_SCALE,
+    })
+}
+
+/// Parse a big integer.
+fn bigint_parse(s: &str) -> Result<Result<BigInt, ParseBigIntError>, VmError> {
+    // NB: a decimal digit fits in 4 bits.
+    charge_bits((s.len() as u64).saturating_mul(4))?;
+    Ok(str::parse::<BigInt>(s))
+}
+
+fn bigint_abs(value: &BigInt) -> Result<BigInt, VmError> {
+    charge_bits(value.bits())?;
+    Ok(BigInt::from_biguint(Sign::Plus, value.magnitude().clone()))
+}
+
+fn bigint_pow(value: &BigInt, exp: u32) -> Result<BigInt, VmError> {
+    // NB: raising zero or one to any power doesn't grow the value.
+    if value.magnitude().bits() > 1 {
+        let bits = value
+            .magnitude()
+            .bits()
+            .checked_mul(u64::from(exp))
+            .filter(|bits| *bits <= MAX_POW_BITS)
+            .ok_or_else(|| VmError::from(VmErrorKind::Overflow))?;
+
+        charge_bits(bits)?;
+    }
+
+    Ok(value.pow(exp))
+}
+
+fn bigint_add(a: &BigInt, b: &BigInt) -> Result<BigInt, VmError> {
+    charge_bits(a.bits().max(b.bits()) + 1)?;
+    Ok(a + b)
+}
+
+fn bigint_add_assign(a: &mut BigInt, b: &BigInt) -> Result<(), VmError> {
+    *a = bigint_add(a, b)?;
+    Ok(())
+}
+
+fn bigint_sub(a: &BigInt, b: &BigInt) -> Result<BigInt, VmError> {
+    charge_bits(a.bits().max(b.bits()) + 1)?;
+    Ok(a - b)
+}
+
+fn bigint_sub_assign(a: &mut BigInt, b: &BigInt) -> Result<(), VmError> {
+    *a = bigint_sub(a, b)?;
+    Ok(())
+}
+
+fn bigint_mul(a: &BigInt, b: &BigInt) -> Result<BigInt, VmError> {
+    charge_bits(a.bits() + b.bits())?;
+    Ok(a * b)
+}
+
+fn bigint_mul_assign(a: &mut BigInt, b: &BigInt) -> Result<(), VmError> {
+    *a = bigint_mul(a, b)?;
+    Ok(())
+}
+
+fn bigint_div(a: &BigInt, b: &BigInt) -> Result<BigInt, VmError> {
+    if b.sign() == Sign::NoSign {
+        return Err(VmError::from(VmErrorKind::DivideByZero));
+    }
+
+    charge_bits(a.bits())?;
+    Ok(a / b)
+}
+
+fn bigint_div_assign(a: &mut BigInt, b: &BigInt) -> Result<(), VmError> {
+    *a = bigint_div(a, b)?;
+    Ok(())
+}
+
+fn bigint_rem(a: &BigInt, b: &BigInt) -> Result<BigInt, VmError> {
+    if b.sign() == Sign::NoSign {
+        return Err(VmError::from(VmErrorKind::DivideByZero));
+    }
+
+    charge_bits(b.bits())?;
+    Ok(a % b)
+}
+
+fn bigint_rem_assign(a: &mut BigInt, b: &BigInt) -> Result<(), VmError> {
+    *a = bigint_rem(a, b)?;
+    Ok(())
+}
+
+fn bigint_eq(a: &BigInt, b: &BigInt) -> bool {
+    a == b
+}
+
+fn bigint_cmp(a: &BigInt, b: &BigInt) -> Ordering {
+    a.cmp(b)
+}
+
+fn bigint_hash(value: &BigInt) -> i64 {
+    hash_value(value)
+}
+
+fn bigint_display(value: &BigInt, buf: &mut String) -> fmt::Result {
+    write!(buf, "{}", value)
+}
+
+/// Parse a decimal.
+fn decimal_parse(s: &str) -> Result<Result<Decimal, ParseDecimalError>, VmError> {
+    // NB: a decimal digit fits in 4 bits.
+    charge_bits((s.len() as u64).saturating_mul(4))?;
+    Ok(str::parse::<Decimal>(s))
+}
+
+fn decimal_round(value: &Decimal, scale: u32) -> Result<Decimal, VmError> {
+    charge_bits(value.max_bits())?;
+    value.round(scale).ok_or_else(|| scale_error(scale))
+}
+
+fn decimal_trunc(value: &Decimal, scale: u32) -> Result<Decimal, VmError> {
+    charge_bits(value.max_bits())?;
+    value.trunc(scale).ok_or_else(|| scale_error(scale))
+}
+
+fn decimal_add(a: &Decimal, b: &Decimal) -> Result<Decimal, VmError> {
+    charge_bits(a.max_bits().max(b.max_bits()) + 1)?;
+    Ok(a + b)
+}
+
+fn decimal_add_assign(a: &mut Decimal, b: &Decimal) -> Result<(), VmError> {
+    *a = decimal_add(a, b)?;
+    Ok(())
+}
+
+fn decimal_sub(a: &Decimal, b: &Decimal) -> Result<Decimal, VmError> {
+    charge_bits(a.max_bits().max(b.max_bits()) + 1)?;
+    Ok(a - b)
+}
+
+fn decimal_sub_assign(a: &mut Decimal, b: &Decimal) -> Result<(), VmError> {
+    *a = decimal_sub(a, b)?;
+    Ok(())
+}
+
+fn decimal_mul(a: &Decimal, b: &Decimal) -> Result<Decimal, VmError> {
+    charge_bits(a.max_bits() + b.max_bits())?;
+    Ok(a * b)
+}
+
+fn decimal_mul_assign(a: &mut Decimal, b: &Decimal) -> Result<(), VmError> {
+    *a = decimal_mul(a, b)?;
+    Ok(())
+}
+
+fn decimal_div(a: &Decimal, b: &Decimal) -> Result<Decimal, VmError> {
+    if b.is_zero() {
+        return Err(VmError::from(VmErrorKind::DivideByZero));
+    }
+
+    charge_bits(a.max_bits() + b.max_bits())?;
+    a.checked_div(b)
+        .ok_or_else(|| VmError::from(VmErrorKind::DivideByZero))
+}
+
+fn decimal_div_assign(a: &mut Decimal, b: &Decimal) -> Result<(), VmError> {
+    *a = decimal_div(a, b)?;
+    Ok(())
+}
+
+fn decimal_rem(a: &Decimal, b: &Decimal) -> Result<Decimal, VmError> {
+    if b.is_zero() {
+        return Err(VmError::from(VmErrorKind::DivideByZero));
+    }
+
+    charge_bits(a.max_bits().max(b.max_bits()))?;
+    a.checked_rem(b)
+        .ok_or_else(|| VmError::from(VmErrorKind::DivideByZero))
+}
+
+fn decimal_rem_assign(a: &mut Decimal, b: &Decimal) -> Result<(), VmError> {
+    *a = decimal_rem(a, b)?;
+    Ok(())
+}
+
+fn decimal_eq(a: &Decimal, b: &Decimal) -> bool {
+    a == b
+}
+
+fn decimal_cmp(a: &Decimal, b: &Decimal) -> Ordering {
+    a.cmp(b)
+}
+
+fn decimal_hash(value: &Decimal) -> i64 {
+    hash_value(value)
+}
+
+fn decimal_display(value: &Decimal, buf: &mut String) -> fmt::Result {
+    write!(buf, "{}", value)
+}
+
+fn format_parse_decimal_error(error: &ParseDecimalError, buf: &mut String) -> fmt::Result {
+    write!(buf, "{}", error)
+}
+
+/// Hash a number for use as a key, consistently with how it compares.
+fn hash_value<T>(value: &T) -> i64
+where
+    T: std::hash::Hash,
+{
+    let mut hasher = XxHash64::default();
+    value.hash(&mut hasher);
+    hasher.finish() as i64
+}
+
+crate::__internal_impl_any!(BigInt);
+crate::__internal_impl_any!(ParseBigIntError);
@@ -1,6 +1,6 @@
 //! The `std::object` module.
 
-use crate::{ContextError, Iterator, Module, Object, Protocol, Value};
+use crate::{limits, ContextError, Iterator, Module, Object, Protocol, Value, VmError};
 
 /// Construct the `std::object` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -9,7 +9,8 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<Object>()?;
 
     module.inst_fn("len", Object::len)?;
-    module.inst_fn("insert", Object::insert)?;
+    module.inst_fn("insert", insert)?;
+    module.inst_fn("remove", remove)?;
     module.inst_fn("clear", Object::clear)?;
     module.inst_fn("contains_key", contains_key)?;
     module.inst_fn("get", get)?;
@@ -21,6 +22,16 @@ pub fn module() -> Result<Module, ContextError> {
     Ok(module)
 }
 
+/// Insert a value into an object, charging it to the memory limit.
+fn insert(object: &mut Object, key: String, value: Value) -> Result<Option<Value>, VmError> {
+    limits::charge_entry(&key)?;
+    Ok(object.insert(key, value))
+}
+
+fn remove(object: &mut Object, key: &str) -> Option<Value> {
+    object.remove(key)
+}
+
 fn contains_key(object: &Object, key: &str) -> bool {
     object.contains_key(key)
 }
@@ -1,6 +1,8 @@
 //! The `std::string` module.
 
-use crate::{Any, Bytes, ContextError, Iterator, Module, Protocol, Value, VmError, VmErrorKind};
+use crate::{
+    limits, Any, Bytes, ContextError, Iterator, Module, Protocol, Value, VmError, VmErrorKind,
+};
 
 /// Construct the `std::string` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -10,33 +12,48 @@ pub fn module() -> Result<Module, ContextError> {
 
     module.function(&["String", "from_str"], <String as From<&str>>::from)?;
     module.function(&["String", "new"], String::new)?;
-    module.function(&["String", "with_capacity"], String::with_capacity)?;
+    module.function(&["String", "with_capacity"], string_with_capacity)?;
 
     module.inst_fn("len", String::len)?;
     module.inst_fn("starts_with", str::starts_with::<&str>)?;
     module.inst_fn("ends_with", str::ends_with::<&str>)?;
     module.inst_fn("capacity", String::capacity)?;
     module.inst_fn("clear", String::clear)?;
-    module.inst_fn("push", String::push)?;
-    module.inst_fn("push_str", String::push_str)?;
-    module.inst_fn("reserve", String::reserve)?;
-    module.inst_fn("reserve_exact", String::reserve_exact)?;
+    module.inst_fn("push", string_push)?;
+    module.inst_fn("push_str", string_push_str)?;
+    module.inst_fn("reserve", string_reserve)?;
+    module.inst_fn("reserve_exact", string_reserve_exact)?;
     module.inst_fn("into_bytes", into_bytes)?;
-    module.inst_fn("clone", String::clone)?;
+    module.inst_fn("clone", string_clone)?;
     module.inst_fn("shrink_to_fit", String::shrink_to_fit)?;
     module.inst_fn("char_at", char_at)?;
     module.inst_fn("split", string_split_char)?;
     module.inst_fn("split_str", string_split_str)?;
     module.inst_fn("is_empty", str::is_empty)?;
     module.inst_fn("chars", string_chars)?;
+    module.inst_fn("cmp", string_cmp)?;
+    module.inst_fn("char_indices", string_char_indices)?;
+    module.inst_fn("lines", string_lines)?;
+    module.inst_fn("split_whitespace", string_split_whitespace)?;
+    module.inst_fn("trim", string_trim)?;
+    module.inst_fn("trim_start", string_trim_start)?;
+    module.inst_fn("trim_end", string_trim_end)?;
+    module.inst_fn("to_uppercase", string_to_uppercase)?;
+    module.inst_fn("to_lowercase", string_to_lowercase)?;
+    module.inst_fn("repeat", string_repeat)?;
+    module.inst_fn("contains", string_contains)?;
+    module.inst_fn("find", string_find)?;
+    module.inst_fn("rfind", string_rfind)?;
+    module.inst_fn("replace", string_replace)?;
     module.inst_fn(Protocol::ADD, add)?;
-    module.inst_fn(Protocol::ADD_ASSIGN, String::push_str)?;
+    module.inst_fn(Protocol::ADD_ASSIGN, string_push_str)?;
     module.inst_fn(Protocol::INDEX_GET, string_index_get)?;
     module.inst_fn("get", string_get)?;
 
     // TODO: parameterize once generics are available.
     module.function(&["parse_int"], parse_int)?;
     module.function(&["parse_char"], parse_char)?;
+    module.function(&["parse_float"], parse_float)?;
 
     Ok(module)
 }
@@ -45,10 +62,16 @@ pub fn module() -> Result<Module, ContextError> {
 #[rune(module = "crate", install_with = "NotCharBoundary::install")]
 struct NotCharBoundary(());
 
+impl std::fmt::Display for NotCharBoundary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "index outside of character boundary")
+    }
+}
+
 impl NotCharBoundary {
     fn string_display(&self, s: &mut String) -> std::fmt::Result {
         use std::fmt::Write as _;
-        write!(s, "index outside of character boundary")
+        write!(s, "{}", self)
     }
 
     fn install(m: &mut Module) -> Result<(), ContextError> {
@@ -57,6 +80,40 @@ impl NotCharBoundary {
     }
 }
 
+fn string_with_capacity(capacity: usize) -> Result<String, VmError> {
+    limits::charge(capacity)?;
+    Ok(String::with_capacity(capacity))
+}
+
+fn string_push(s: &mut String, c: char) -> Result<(), VmError> {
+    limits::charge(c.len_utf8())?;
+    s.push(c);
+    Ok(())
+}
+
+fn string_push_str(s: &mut String, other: &str) -> Result<(), VmError> {
+    limits::charge(other.len())?;
+    s.push_str(other);
+    Ok(())
+}
+
+fn string_reserve(s: &mut String, additional: usize) -> Result<(), VmError> {
+    limits::charge(additional)?;
+    s.reserve(additional);
+    Ok(())
+}
+
+fn string_reserve_exact(s: &mut String, additional: usize) -> Result<(), VmError> {
+    limits::charge(additional)?;
+    s.reserve_exact(additional);
+    Ok(())
+}
+
+fn string_clone(s: &str) -> Result<String, VmError> {
+    limits::charge(s.len())?;
+    Ok(s.to_owned())
+}
+
 /// into_bytes shim for strings.
 fn into_bytes(s: String) -> Bytes {
     Bytes::from_vec(s.into_bytes())
@@ -88,12 +145,17 @@ fn parse_char(s: &str) -> Result<char, std::char::ParseCharError> {
     str::parse::<char>(s)
 }
 
+fn parse_float(s: &str) -> Result<f64, std::num::ParseFloatError> {
+    str::parse::<f64>(s)
+}
+
 /// The add operation for strings.
-fn add(a: &str, b: &str) -> String {
+fn add(a: &str, b: &str) -> Result<String, VmError> {
+    limits::charge(a.len() + b.len())?;
     let mut string = String::with_capacity(a.len() + b.len());
     string.push_str(a);
     string.push_str(b);
-    string
+    Ok(string)
 }
 
 fn string_chars(s: &str) -> Iterator {
@@ -101,6 +163,149 @@ fn string_chars(s: &str) -> Iterator {
     Iterator::from_double_ended("std::str::Chars", iter)
 }
 
+/// Compare two strings lexicographically.
+fn string_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    a.cmp(b)
+}
+
+/// Iterate over the characters of a string and their byte offsets.
+fn string_char_indices(s: &str) -> Iterator {
+    let iter = s.char_indices().collect::<Vec<_>>().into_iter();
+    Iterator::from_double_ended("std::str::CharIndices", iter)
+}
+
+fn string_lines(s: &str) -> Iterator {
+    let lines = s.lines().map(String::from).collect::<Vec<String>>();
+    Iterator::from_double_ended("std::str::Lines", lines.into_iter())
+}
+
+fn string_split_whitespace(s: &str) -> Iterator {
+    let parts = s
+        .split_whitespace()
+        .map(String::from)
+        .collect::<Vec<String>>();
+    Iterator::from_double_ended("std::str::SplitWhitespace", parts.into_iter())
+}
+
+fn string_trim(s: &str) -> Result<String, VmError> {
+    string_clone(s.trim())
+}
+
+fn string_trim_start(s: &str) -> Result<String, VmError> {
+    string_clone(s.trim_start())
+}
+
+fn string_trim_end(s: &str) -> Result<String, VmError> {
+    string_clone(s.trim_end())
+}
+
+fn string_to_uppercase(s: &str) -> Result<String, VmError> {
+    // NB: every character might map to more than one when changing case, so
+    // the length of the result is summed up before allocating it.
+    limits::charge(
+        s.chars()
+            .flat_map(char::to_uppercase)
+            .map(char::len_utf8)
+            .sum(),
+    )?;
+    Ok(s.to_uppercase())
+}
+
+fn string_to_lowercase(s: &str) -> Result<String, VmError> {
+    limits::charge(
+        s.chars()
+            .flat_map(char::to_lowercase)
+            .map(char::len_utf8)
+            .sum(),
+    )?;
+    Ok(s.to_lowercase())
+}
+
+fn string_repeat(s: &str, n: usize) -> Result<String, VmError> {
+    let len = match s.len().checked_mul(n) {
+        Some(len) if len <= isize::MAX as usize => len,
+        _ => return Err(VmError::panic("capacity overflow")),
+    };
+
+    limits::charge(len)?;
+
+    let mut string = String::new();
+
+    if string.try_reserve_exact(len).is_err() {
+        return Err(VmError::panic("memory allocation failed"));
+    }
+
+    for _ in 0..n {
+        string.push_str(s);
+    }
+
+    Ok(string)
+}
+
+/// A pattern to search for in a string, which is either a character or a
+/// string.
+enum Pattern {
+    Char(char),
+    Str(String),
+}
+
+impl Pattern {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        Ok(match value {
+            Value::Char(c) => Self::Char(c),
+            Value::String(s) => Self::Str(s.borrow_ref()?.clone()),
+            Value::StaticString(s) => Self::Str((**s).to_owned()),
+            actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+        })
+    }
+}
+
+fn string_contains(s: &str, pat: Value) -> Result<bool, VmError> {
+    Ok(match Pattern::from_value(pat)? {
+        Pattern::Char(c) => s.contains(c),
+        Pattern::Str(pat) => s.contains(pat.as_str()),
+    })
+}
+
+/// Find the byte offset of the first match of the pattern.
+fn string_find(s: &str, pat: Value) -> Result<Option<usize>, VmError> {
+    Ok(match Pattern::from_value(pat)? {
+        Pattern::Char(c) => s.find(c),
+        Pattern::Str(pat) => s.find(pat.as_str()),
+    })
+}
+
+/// Find the byte offset of the last match of the pattern.
+fn string_rfind(s: &str, pat: Value) -> Result<Option<usize>, VmError> {
+    Ok(match Pattern::from_value(pat)? {
+        Pattern::Char(c) => s.rfind(c),
+        Pattern::Str(pat) => s.rfind(pat.as_str()),
+    })
+}
+
+fn string_replace(s: &str, from: Value, to: &str) -> Result<String, VmError> {
+    let from = Pattern::from_value(from)?;
+
+    // NB: the length of the result is calculated from the number of matches
+    // so that it can be charged for before it's allocated.
+    let (matches, from_len) = match &from {
+        Pattern::Char(c) => (s.matches(*c).count(), c.len_utf8()),
+        Pattern::Str(from) => (s.matches(from.as_str()).count(), from.len()),
+    };
+
+    let len = matches
+        .checked_mul(to.len())
+        .and_then(|added| (s.len() - matches * from_len).checked_add(added))
+        .ok_or_else(|| VmError::panic("capacity overflow"))?;
+
+    limits::charge(len)?;
+
+    Ok(match from {
+        Pattern::Char(c) => s.replace(c, to),
+        Pattern::Str(from) => s.replace(from.as_str(), to),
+    })
+}
+
 /// Get a specific string index.
 fn string_get(s: &str, key: Value) -> Result<Option<String>, VmError> {
     use crate::{FromValue as _, RangeLimits, TypeOf as _};
@@ -146,6 +351,36 @@ fn string_get(s: &str, key: Value) -> Result<Option<String>, VmError> {
 }
 
 /// Get a specific string index.
+///
+/// Ranges which don't fall on character boundaries raise a
+/// [NotCharBoundary] panic.
 fn string_index_get(s: &str, key: Value) -> Result<String, VmError> {
+    use crate::{FromValue as _, RangeLimits};
+
+    if let Value::Range(range) = &key {
+        let range = range.borrow_ref()?;
+
+        let start = match range.start.clone() {
+            Some(value) => Some(<usize>::from_value(value)?),
+            None => None,
+        };
+
+        // NB: the end of a closed range is inclusive, so its boundary is after
+        // the character it points to.
+        let end = match (range.end.clone(), range.limits) {
+            (Some(value), RangeLimits::HalfOpen) => Some(<usize>::from_value(value)?),
+            (Some(value), RangeLimits::Closed) => {
+                Some(<usize>::from_value(value)?.saturating_add(1))
+            }
+            (None, _) => None,
+        };
+
+        for n in start.into_iter().chain(end) {
+            if n <= s.len() && !s.is_char_boundary(n) {
+                return Err(VmError::panic(NotCharBoundary(())));
+            }
+        }
+    }
+
     string_get(s, key)?.ok_or_else(|| VmError::panic("missing string slice"))
 }
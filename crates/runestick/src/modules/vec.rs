@@ -1,6 +1,10 @@
 //! The `std::vec` module.
 
-use crate::{ContextError, Module, Protocol, Vec};
+use crate::{
+    limits, ContextError, FromValue as _, Function, Iterator, Module, Protocol, RangeLimits,
+    TypeOf as _, Value, Vec, VmError, VmErrorKind, VmIntegerRepr,
+};
+use std::cmp;
 
 /// Construct the `std::vec` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -12,11 +16,30 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("extend", Vec::extend)?;
     module.inst_fn("iter", Vec::into_iterator)?;
     module.inst_fn("len", Vec::len)?;
-    module.inst_fn("push", Vec::push)?;
+    module.inst_fn("is_empty", Vec::is_empty)?;
+    module.inst_fn("push", vec_push)?;
     module.inst_fn("clear", Vec::clear)?;
     module.inst_fn("pop", Vec::pop)?;
+    module.inst_fn("insert", insert)?;
+    module.inst_fn("remove", remove)?;
+    module.inst_fn("swap", swap)?;
+    module.inst_fn("truncate", Vec::truncate)?;
+    module.inst_fn("reverse", reverse)?;
+    module.inst_fn("contains", contains)?;
+    module.inst_fn("first", first)?;
+    module.inst_fn("last", last)?;
+    module.inst_fn("get", get)?;
+    module.inst_fn("sort", sort)?;
+    module.inst_fn("sort_by", sort_by)?;
+    module.inst_fn("dedup", dedup)?;
+    module.inst_fn("retain", retain)?;
+    module.inst_fn("binary_search", binary_search)?;
+    module.inst_fn("split_at", split_at)?;
+    module.inst_fn("chunks", chunks)?;
+    module.inst_fn("windows", windows)?;
 
     module.inst_fn(Protocol::INTO_ITER, Vec::into_iterator)?;
+    module.inst_fn(Protocol::INDEX_GET, index_get)?;
     module.inst_fn(Protocol::INDEX_SET, Vec::set)?;
 
     // TODO: parameterize with generics.
@@ -24,10 +47,353 @@ pub fn module() -> Result<Module, ContextError> {
     Ok(module)
 }
 
+/// Push a value onto a vector, charging it to the memory limit.
+fn vec_push(vec: &mut Vec, value: Value) -> Result<(), VmError> {
+    limits::charge_values(1)?;
+    vec.push(value);
+    Ok(())
+}
+
+/// Construct the error raised when an index is out of bounds.
+fn out_of_range(vec: &Vec, index: usize) -> VmError {
+    VmError::from(VmErrorKind::OutOfRange {
+        index: index.into(),
+        len: vec.len().into(),
+    })
+}
+
+/// Insert a value at the given index, shifting the values after it.
+fn insert(vec: &mut Vec, index: usize, value: Value) -> Result<(), VmError> {
+    if index > vec.len() {
+        return Err(out_of_range(vec, index));
+    }
+
+    limits::charge_values(1)?;
+    vec.insert(index, value);
+    Ok(())
+}
+
+/// Remove the value at the given index, shifting the values after it.
+fn remove(vec: &mut Vec, index: usize) -> Result<Value, VmError> {
+    if index >= vec.len() {
+        return Err(out_of_range(vec, index));
+    }
+
+    Ok(vec.remove(index))
+}
+
+/// Swap the values at the two given indexes.
+fn swap(vec: &mut Vec, a: usize, b: usize) -> Result<(), VmError> {
+    for &index in &[a, b] {
+        if index >= vec.len() {
+            return Err(out_of_range(vec, index));
+        }
+    }
+
+    vec.swap(a, b);
+    Ok(())
+}
+
+/// Reverse the order of the values in place.
+fn reverse(vec: &mut Vec) {
+    vec.reverse();
+}
+
+/// Test if the vector contains a value which is equal to the given one, using
+/// the equality protocols of values which implement them.
+fn contains(vec: &Vec, value: Value) -> Result<bool, VmError> {
+    for v in vec.iter() {
+        if Value::value_ptr_eq(v, &value)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Get the first value, if any.
+fn first(vec: &Vec) -> Option<Value> {
+    vec.first().cloned()
+}
+
+/// Get the last value, if any.
+fn last(vec: &Vec) -> Option<Value> {
+    vec.last().cloned()
+}
+
+/// Get the value at the given index, or a new vector with the values in the
+/// given range.
+///
+/// Returns `None` if the index or range is out of bounds.
+fn get(vec: &Vec, key: Value) -> Result<Option<Value>, VmError> {
+    let range = match key {
+        Value::Integer(index) => {
+            if index < 0 {
+                return Ok(None);
+            }
+
+            return Ok(vec.get(index as usize).cloned());
+        }
+        Value::Range(range) => range,
+        index => {
+            return Err(VmError::from(VmErrorKind::UnsupportedIndexGet {
+                target: Vec::type_info(),
+                index: index.type_info()?,
+            }))
+        }
+    };
+
+    let range = range.borrow_ref()?;
+
+    let start = match range.start.clone() {
+        Some(value) => Some(<usize>::from_value(value)?),
+        None => None,
+    };
+
+    let end = match range.end.clone() {
+        Some(value) => Some(<usize>::from_value(value)?),
+        None => None,
+    };
+
+    let slice: &[Value] = &*vec;
+
+    let out = match range.limits {
+        RangeLimits::HalfOpen => match (start, end) {
+            (Some(start), Some(end)) => slice.get(start..end),
+            (Some(start), None) => slice.get(start..),
+            (None, Some(end)) => slice.get(..end),
+            (None, None) => slice.get(..),
+        },
+        RangeLimits::Closed => match (start, end) {
+            (Some(start), Some(end)) => slice.get(start..=end),
+            (None, Some(end)) => slice.get(..=end),
+            _ => return Err(VmError::from(VmErrorKind::UnsupportedRange)),
+        },
+    };
+
+    let out = match out {
+        Some(out) => out,
+        None => return Ok(None),
+    };
+
+    limits::charge_values(out.len())?;
+    Ok(Some(Value::vec(out.to_vec())))
+}
+
+/// Get the value at the given index, or a new vector with the values in the
+/// given range.
+///
+/// Raises an error if the index or range is out of bounds.
+fn index_get(vec: &Vec, key: Value) -> Result<Value, VmError> {
+    if let Some(value) = get(vec, key.clone())? {
+        return Ok(value);
+    }
+
+    let index = match key {
+        Value::Integer(index) => VmIntegerRepr::from(index),
+        Value::Range(range) => {
+            let range = range.borrow_ref()?;
+
+            let start = match range.start.clone() {
+                Some(value) => <usize>::from_value(value)?,
+                None => 0,
+            };
+
+            let end = match range.end.clone() {
+                Some(value) => Some(<usize>::from_value(value)?),
+                None => None,
+            };
+
+            // NB: report the end if it's out of bounds, otherwise the start is
+            // either out of bounds or after the end.
+            match end {
+                Some(end) if end > vec.len() => VmIntegerRepr::from(end),
+                Some(end) if end == vec.len() && range.limits == RangeLimits::Closed => {
+                    VmIntegerRepr::from(end)
+                }
+                _ => VmIntegerRepr::from(start),
+            }
+        }
+        index => {
+            return Err(VmError::from(VmErrorKind::UnsupportedIndexGet {
+                target: Vec::type_info(),
+                index: index.type_info()?,
+            }))
+        }
+    };
+
+    Err(VmError::from(VmErrorKind::OutOfRange {
+        index,
+        len: vec.len().into(),
+    }))
+}
+
+/// Sort the vector using the ordering of its values, including the ordering
+/// protocols of values which implement them.
+fn sort(vec: &mut Vec) -> Result<(), VmError> {
+    merge_sort(vec, &mut Value::value_ptr_cmp)
+}
+
+/// Sort the vector using a comparator function, which returns an `Ordering`.
+fn sort_by(vec: &mut Vec, compare: Function) -> Result<(), VmError> {
+    merge_sort(vec, &mut |a, b| {
+        compare.call::<_, cmp::Ordering>((a.clone(), b.clone()))
+    })
+}
+
+/// Stable merge sort which stops at the first error raised by the comparison.
+///
+/// Unlike `slice::sort_by` this doesn't require the comparison to be a total
+/// order, so values like NaN or an inconsistent comparator can't panic. Merged
+/// values are only written back once a merge has completed, so the slice
+/// still holds all of its values if an error is raised.
+fn merge_sort<F>(values: &mut [Value], compare: &mut F) -> Result<(), VmError>
+where
+    F: FnMut(&Value, &Value) -> Result<cmp::Ordering, VmError>,
+{
+    let len = values.len();
+
+    if len <= 1 {
+        return Ok(());
+    }
+
+    let mid = len / 2;
+    merge_sort(&mut values[..mid], compare)?;
+    merge_sort(&mut values[mid..], compare)?;
+
+    let mut merged = std::vec::Vec::with_capacity(len);
+    let (a, b) = values.split_at(mid);
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        // NB: only take from the right when it's strictly less, to keep the
+        // sort stable.
+        if compare(&b[j], &a[i])? == cmp::Ordering::Less {
+            merged.push(b[j].clone());
+            j += 1;
+        } else {
+            merged.push(a[i].clone());
+            i += 1;
+        }
+    }
+
+    merged.extend_from_slice(&a[i..]);
+    merged.extend_from_slice(&b[j..]);
+    values.clone_from_slice(&merged);
+    Ok(())
+}
+
+/// Remove consecutive values which are equal to each other, using the
+/// equality protocols of values which implement them.
+fn dedup(vec: &mut Vec) -> Result<(), VmError> {
+    let mut error = None;
+
+    vec.dedup_by(|a, b| match Value::value_ptr_eq(a, b) {
+        Ok(same) => same,
+        Err(e) => {
+            error.get_or_insert(e);
+            false
+        }
+    });
+
+    error.map_or(Ok(()), Err)
+}
+
+/// Retain only the values for which the predicate returns `true`.
+fn retain(vec: &mut Vec, predicate: Function) -> Result<(), VmError> {
+    let mut error = None;
+
+    vec.retain(|value| {
+        if error.is_some() {
+            return true;
+        }
+
+        match predicate.call::<_, bool>((value.clone(),)) {
+            Ok(retain) => retain,
+            Err(e) => {
+                error = Some(e);
+                true
+            }
+        }
+    });
+
+    error.map_or(Ok(()), Err)
+}
+
+/// Search a sorted vector for the given value using the ordering of values,
+/// including the ordering protocols of values which implement them.
+///
+/// Returns `Ok` with the index of a matching value, or `Err` with the index
+/// where the value could be inserted to keep the vector sorted.
+fn binary_search(vec: &Vec, value: Value) -> Result<Result<usize, usize>, VmError> {
+    let mut low = 0;
+    let mut high = vec.len();
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+
+        match Value::value_ptr_cmp(&vec[mid], &value)? {
+            cmp::Ordering::Less => low = mid + 1,
+            cmp::Ordering::Greater => high = mid,
+            cmp::Ordering::Equal => return Ok(Ok(mid)),
+        }
+    }
+
+    Ok(Err(low))
+}
+
+/// Split the vector into two new vectors at the given index.
+fn split_at(vec: &Vec, mid: usize) -> Result<(Vec, Vec), VmError> {
+    if mid > vec.len() {
+        return Err(out_of_range(vec, mid));
+    }
+
+    limits::charge_values(vec.len())?;
+    let (a, b) = vec.split_at(mid);
+    Ok((Vec::from(a.to_vec()), Vec::from(b.to_vec())))
+}
+
+/// Iterate over new vectors of `size` values at a time, where the last one
+/// might be shorter.
+fn chunks(vec: &Vec, size: usize) -> Result<Iterator, VmError> {
+    if size == 0 {
+        return Err(VmError::panic("chunk size must be non-zero"));
+    }
+
+    limits::charge_values(vec.len())?;
+
+    let chunks = vec
+        .chunks(size)
+        .map(|chunk| Value::vec(chunk.to_vec()))
+        .collect::<std::vec::Vec<_>>();
+
+    Ok(Iterator::from_double_ended(
+        "std::slice::Chunks",
+        chunks.into_iter(),
+    ))
+}
+
+/// Iterate over new vectors of every overlapping window of `size` values.
+fn windows(vec: &Vec, size: usize) -> Result<Iterator, VmError> {
+    if size == 0 {
+        return Err(VmError::panic("window size must be non-zero"));
+    }
+
+    let windows = vec.windows(size);
+    limits::charge_values(windows.len().saturating_mul(size))?;
+
+    let windows = windows
+        .map(|window| Value::vec(window.to_vec()))
+        .collect::<std::vec::Vec<_>>();
+
+    Ok(Iterator::from_double_ended(
+        "std::slice::Windows",
+        windows.into_iter(),
+    ))
+}
+
 /// Sort a vector of integers.
 fn sort_int(vec: &mut Vec) {
-    use crate::Value;
-
     vec.sort_by(|a, b| match (a, b) {
         (Value::Integer(a), Value::Integer(b)) => a.cmp(&b),
         // NB: fall back to sorting by address.
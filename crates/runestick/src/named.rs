@@ -30,6 +30,36 @@ impl Named for u8 {
 
 impl InstallWith for u8 {}
 
+impl Named for u16 {
+    const NAME: RawStr = RawStr::from_str("u16");
+}
+
+impl InstallWith for u16 {}
+
+impl Named for u32 {
+    const NAME: RawStr = RawStr::from_str("u32");
+}
+
+impl InstallWith for u32 {}
+
+impl Named for u64 {
+    const NAME: RawStr = RawStr::from_str("u64");
+}
+
+impl InstallWith for u64 {}
+
+impl Named for i32 {
+    const NAME: RawStr = RawStr::from_str("i32");
+}
+
+impl InstallWith for i32 {}
+
+impl Named for i128 {
+    const NAME: RawStr = RawStr::from_str("i128");
+}
+
+impl InstallWith for i128 {}
+
 impl Named for char {
     const NAME: RawStr = RawStr::from_str("char");
 }
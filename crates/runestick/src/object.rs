@@ -3,6 +3,7 @@ use crate::{
     FromValue, InstallWith, Item, Mut, Named, RawMut, RawRef, RawStr, Ref, ToValue,
     UnsafeFromValue, Value, VmError,
 };
+use indexmap::IndexMap;
 use std::borrow;
 use std::cmp;
 use std::fmt;
@@ -15,7 +16,7 @@ use std::hash;
 ///
 /// [`into_iter`]: struct.Object.html#method.into_iter
 /// [`Object`]: struct.Object.html
-pub type IntoIter = crate::collections::hash_map::IntoIter<String, Value>;
+pub type IntoIter = indexmap::map::IntoIter<String, Value>;
 
 /// A mutable iterator over the entries of a `Object`.
 ///
@@ -24,7 +25,7 @@ pub type IntoIter = crate::collections::hash_map::IntoIter<String, Value>;
 ///
 /// [`iter_mut`]: struct.Object.html#method.iter_mut
 /// [`Object`]: struct.Object.html
-pub type IterMut<'a> = crate::collections::hash_map::IterMut<'a, String, Value>;
+pub type IterMut<'a> = indexmap::map::IterMut<'a, String, Value>;
 
 /// An iterator over the entries of a `Object`.
 ///
@@ -33,28 +34,31 @@ pub type IterMut<'a> = crate::collections::hash_map::IterMut<'a, String, Value>;
 ///
 /// [`iter`]: struct.Object.html#method.iter
 /// [`Object`]: struct.Object.html
-pub type Iter<'a> = crate::collections::hash_map::Iter<'a, String, Value>;
+pub type Iter<'a> = indexmap::map::Iter<'a, String, Value>;
 
-/// An iterator over the keys of a `HashMap`.
+/// An iterator over the keys of a `Object`.
 ///
 /// This `struct` is created by the [`keys`] method on [`Object`]. See its
 /// documentation for more.
 ///
 /// [`keys`]: struct.Object.html#method.keys
 /// [`Object`]: struct.Object.html
-pub type Keys<'a> = crate::collections::hash_map::Keys<'a, String, Value>;
+pub type Keys<'a> = indexmap::map::Keys<'a, String, Value>;
 
-/// An iterator over the values of a `HashMap`.
+/// An iterator over the values of a `Object`.
 ///
 /// This `struct` is created by the [`values`] method on [`Object`]. See its
 /// documentation for more.
 ///
 /// [`values`]: struct.Object.html#method.values
 /// [`Object`]: struct.Object.html
-pub type Values<'a> = crate::collections::hash_map::Values<'a, String, Value>;
+pub type Values<'a> = indexmap::map::Values<'a, String, Value>;
 
 /// Struct representing a dynamic anonymous object.
 ///
+/// The entries of an object are kept in the order in which they were inserted,
+/// which is the order they are iterated over, printed, and serialized in.
+///
 /// # Examples
 ///
 /// ```rust
@@ -69,12 +73,15 @@ pub type Values<'a> = crate::collections::hash_map::Values<'a, String, Value>;
 /// assert_eq!(Some(42), object.get_value("foo")?);
 /// assert_eq!(Some(true), object.get_value("bar")?);
 /// assert_eq!(None::<bool>, object.get_value("baz")?);
+///
+/// let keys = object.keys().map(String::as_str).collect::<Vec<_>>();
+/// assert_eq!(keys, ["foo", "bar"]);
 /// # Ok(()) }
 /// ```
 #[derive(Default, Clone)]
 #[repr(transparent)]
 pub struct Object {
-    inner: HashMap<String, Value>,
+    inner: IndexMap<String, Value>,
 }
 
 impl Object {
@@ -82,7 +89,7 @@ impl Object {
     #[inline]
     pub fn new() -> Self {
         Self {
-            inner: HashMap::new(),
+            inner: IndexMap::new(),
         }
     }
 
@@ -90,7 +97,7 @@ impl Object {
     #[inline]
     pub fn with_capacity(cap: usize) -> Self {
         Self {
-            inner: HashMap::with_capacity(cap),
+            inner: IndexMap::with_capacity(cap),
         }
     }
 
@@ -153,13 +160,15 @@ impl Object {
 
     /// Removes a key from the object, returning the value at the key if the key
     /// was previously in the object.
+    ///
+    /// The entries after it are shifted down to preserve their order.
     #[inline]
     pub fn remove<Q: ?Sized>(&mut self, k: &Q) -> Option<Value>
     where
         String: borrow::Borrow<Q>,
         Q: hash::Hash + cmp::Eq,
     {
-        self.inner.remove(k)
+        self.inner.shift_remove(k)
     }
 
     /// Inserts a key-value pair into the dynamic object, converting it as
@@ -187,29 +196,29 @@ impl Object {
     }
 
     /// Convert into inner.
-    pub fn into_inner(self) -> HashMap<String, Value> {
+    pub fn into_inner(self) -> IndexMap<String, Value> {
         self.inner
     }
 
-    /// An iterator visiting all key-value pairs in arbitrary order.
+    /// An iterator visiting all key-value pairs in insertion order.
     /// The iterator element type is `(&'a String, &'a Value)`.
     pub fn iter(&self) -> Iter<'_> {
         self.inner.iter()
     }
 
-    /// An iterator visiting all keys in arbitrary order.
+    /// An iterator visiting all keys in insertion order.
     /// The iterator element type is `&'a String`.
     pub fn keys(&self) -> Keys<'_> {
         self.inner.keys()
     }
 
-    /// An iterator visiting all values in arbitrary order.
+    /// An iterator visiting all values in insertion order.
     /// The iterator element type is `&'a Value`.
     pub fn values(&self) -> Values<'_> {
         self.inner.values()
     }
 
-    /// An iterator visiting all key-value pairs in arbitrary order,
+    /// An iterator visiting all key-value pairs in insertion order,
     /// with mutable references to the values.
     /// The iterator element type is `(&'a String, &'a mut Value)`.
     pub fn iter_mut(&mut self) -> IterMut<'_> {
@@ -271,7 +280,7 @@ impl IntoIterator for Object {
     type IntoIter = IntoIter;
 
     /// Creates a consuming iterator, that is, one that moves each key-value
-    /// pair out of the object in arbitrary order. The object cannot be used
+    /// pair out of the object in insertion order. The object cannot be used
     /// after calling this.
     fn into_iter(self) -> Self::IntoIter {
         self.inner.into_iter()
@@ -284,9 +293,17 @@ impl fmt::Debug for Object {
     }
 }
 
+impl From<IndexMap<String, Value>> for Object {
+    fn from(object: IndexMap<String, Value>) -> Self {
+        Self { inner: object }
+    }
+}
+
 impl From<HashMap<String, Value>> for Object {
     fn from(object: HashMap<String, Value>) -> Self {
-        Self { inner: object }
+        Self {
+            inner: object.into_iter().collect(),
+        }
     }
 }
 
@@ -0,0 +1,135 @@
+use crate::{Hash, Inst, Value, Vm, VmError};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+thread_local!(static CURRENT: RefCell<Option<Rc<dyn VmObserver>>> = RefCell::new(None));
+
+/// An observer of the execution of a virtual machine, registered with
+/// [Vm::set_observer].
+///
+/// All notifications do nothing by default, so only the ones of interest
+/// need to be implemented. They are given a shared reference to the
+/// observer, so state that is updated while observing should use interior
+/// mutability like [Cell][std::cell::Cell] or [RefCell].
+///
+/// The observer is shared with any virtual machines spawned by the one it's
+/// registered with, like the ones used by generators, async functions, and
+/// closures called from native functions. The [Vm] passed to each
+/// notification is the one that is currently executing.
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::{Inst, Vm, VmObserver};
+/// use std::cell::Cell;
+///
+/// #[derive(Default)]
+/// struct Counter {
+///     instructions: Cell<usize>,
+/// }
+///
+/// impl VmObserver for Counter {
+///     fn on_instruction(&self, _: &Vm, _: &Inst) {
+///         self.instructions.set(self.instructions.get() + 1);
+///     }
+/// }
+/// ```
+pub trait VmObserver {
+    /// Called when a function is entered, before its first instruction is
+    /// executed.
+    ///
+    /// This includes the function that a virtual machine starts executing
+    /// in. `offset` is the offset of the first instruction of the function.
+    fn on_call(&self, vm: &Vm, offset: usize) {
+        let _ = (vm, offset);
+    }
+
+    /// Called when a function returns the given value, before its call frame
+    /// is popped.
+    fn on_return(&self, vm: &Vm, value: &Value) {
+        let _ = (vm, value);
+    }
+
+    /// Called before an instruction is executed.
+    ///
+    /// The instruction is located at [Vm::ip].
+    fn on_instruction(&self, vm: &Vm, inst: &Inst) {
+        let _ = (vm, inst);
+    }
+
+    /// Called before the native function with the given hash is called.
+    fn on_native_call(&self, vm: &Vm, hash: Hash) {
+        let _ = (vm, hash);
+    }
+
+    /// Called when the virtual machine raises an error, before it is unwound.
+    ///
+    /// Errors raised in a virtual machine executed by a native function pass
+    /// through the virtual machine which called the native function, so this
+    /// is called once for each of them, starting with the innermost one.
+    fn on_error(&self, vm: &Vm, error: &VmError) {
+        let _ = (vm, error);
+    }
+}
+
+/// The observer state of a single virtual machine.
+#[derive(Clone)]
+pub(crate) struct VmObserve {
+    observer: Rc<dyn VmObserver>,
+    /// If the function the virtual machine started in has been entered.
+    entered: bool,
+}
+
+impl VmObserve {
+    /// Construct the observer state of a virtual machine which hasn't started
+    /// executing.
+    pub(crate) fn new(observer: Rc<dyn VmObserver>) -> Self {
+        Self {
+            observer,
+            entered: false,
+        }
+    }
+
+    /// Get the observer.
+    pub(crate) fn observer(&self) -> &Rc<dyn VmObserver> {
+        &self.observer
+    }
+
+    /// Mark the function the virtual machine started in as entered.
+    ///
+    /// Returns `true` if it wasn't already.
+    pub(crate) fn enter(&mut self) -> bool {
+        !std::mem::replace(&mut self.entered, true)
+    }
+}
+
+impl fmt::Debug for VmObserve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VmObserve")
+            .field("entered", &self.entered)
+            .finish()
+    }
+}
+
+/// Get the observer of the virtual machine that is currently executing on
+/// this thread, if any.
+pub(crate) fn current() -> Option<Rc<dyn VmObserver>> {
+    CURRENT.with(|tls| tls.borrow().clone())
+}
+
+/// Mark the given observer as the observer of the virtual machine currently
+/// executing on this thread until the returned guard is dropped.
+pub(crate) fn enter(observer: Rc<dyn VmObserver>) -> ObserverGuard {
+    ObserverGuard(CURRENT.with(|tls| tls.replace(Some(observer))))
+}
+
+/// Guard which restores the previously executing observer when dropped.
+pub(crate) struct ObserverGuard(Option<Rc<dyn VmObserver>>);
+
+impl Drop for ObserverGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|tls| *tls.borrow_mut() = previous);
+    }
+}
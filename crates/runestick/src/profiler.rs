@@ -0,0 +1,679 @@
+//! An instrumenting profiler for the virtual machine.
+//!
+//! Profiling is enabled per virtual machine through [Vm::set_profiler]. While
+//! enabled, every instruction executed is counted along with the wall time
+//! spent on it and the stack of functions it was executed in. Time spent in
+//! native functions is recorded as if they were functions of their own.
+//!
+//! Like fuel, the profiler is shared with any virtual machines spawned while
+//! executing. Closures called from native functions and resumed generators are
+//! recorded as if they were called by the function which executed them.
+//!
+//! The recording is collected into a [Profile] through [Profiler::profile],
+//! which can be written as folded stacks for flamegraph tools or serialized
+//! as a summary.
+//!
+//! [Vm::set_profiler]: crate::Vm::set_profiler
+
+use crate::collections::HashMap;
+use crate::{CallFrame, Context, ContextSignature, Hash, SourceId, Span, Unit, UnitFn};
+use serde::{Serialize, Serializer};
+use std::cell::RefCell;
+use std::convert::TryFrom as _;
+use std::fmt;
+use std::io;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+thread_local!(static CURRENT: RefCell<Option<(Profiler, usize)>> = RefCell::new(None));
+
+/// A profiler recording the execution of virtual machines.
+///
+/// Clones of a profiler record into the same profile.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use runestick::profiler::Profiler;
+/// use runestick::{Context, Unit, Vm};
+/// use std::sync::Arc;
+///
+/// # fn main() -> runestick::Result<()> {
+/// let context = Arc::new(Context::with_default_modules()?.runtime());
+/// let unit = Arc::new(Unit::default());
+///
+/// let profiler = Profiler::new();
+///
+/// let mut vm = Vm::new(context, unit);
+/// vm.set_profiler(Some(profiler.clone()));
+/// vm.execute(&["main"], ())?.complete()?;
+///
+/// let mut out = Vec::new();
+/// profiler.profile().write_folded(&mut out)?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Default)]
+pub struct Profiler {
+    state: Rc<RefCell<State>>,
+}
+
+impl Profiler {
+    /// Construct a new profiler.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect what has been recorded so far into a profile.
+    pub fn profile(&self) -> Profile {
+        self.state.borrow().profile()
+    }
+}
+
+impl fmt::Debug for Profiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Profiler")
+    }
+}
+
+/// The profile recorded by a [Profiler].
+#[derive(Debug, Clone, Serialize)]
+pub struct Profile {
+    /// Functions which have been called, ordered by the time spent in them.
+    pub functions: Vec<FunctionProfile>,
+    /// Spans of source code which have been executed, ordered by the time
+    /// spent in them.
+    ///
+    /// This is only available for units with debug info.
+    pub spans: Vec<SpanProfile>,
+    /// Stacks of functions which have been executed.
+    pub stacks: Vec<StackProfile>,
+}
+
+impl Profile {
+    /// Name native functions after the items they're registered as in the
+    /// given context.
+    ///
+    /// Native functions are otherwise named after their hash, since the
+    /// runtime context doesn't keep track of their names.
+    pub fn resolve_names(&mut self, context: &Context) {
+        let names = context
+            .iter_functions()
+            .map(|(hash, signature)| {
+                let name = match signature {
+                    ContextSignature::Function { item, .. } => item.to_string(),
+                    ContextSignature::Instance { item, name, .. } => {
+                        format!("{}::{}", item, name)
+                    }
+                };
+
+                (hash, name)
+            })
+            .collect::<HashMap<_, _>>();
+
+        for function in &mut self.functions {
+            if !function.native {
+                continue;
+            }
+
+            if let Some(name) = function.hash.and_then(|hash| names.get(&hash)) {
+                function.name = name.clone();
+            }
+        }
+    }
+
+    /// Write the stacks of the profile as folded stacks, which can be
+    /// rendered by flamegraph tools like [inferno].
+    ///
+    /// Every line contains the names of the functions in a stack separated by
+    /// semicolons, followed by the number of nanoseconds spent in it.
+    ///
+    /// [inferno]: https://github.com/jonhoo/inferno
+    pub fn write_folded<O>(&self, out: &mut O) -> io::Result<()>
+    where
+        O: io::Write,
+    {
+        for stack in &self.stacks {
+            let nanos = stack.time.as_nanos();
+
+            if nanos == 0 {
+                continue;
+            }
+
+            let mut it = stack.frames.iter();
+
+            if let Some(frame) = it.next() {
+                write!(out, "{}", folded_name(&self.functions[*frame].name))?;
+            }
+
+            for frame in it {
+                write!(out, ";{}", folded_name(&self.functions[*frame].name))?;
+            }
+
+            writeln!(out, " {}", nanos)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Function names can't contain separators used by the folded format.
+fn folded_name(name: &str) -> String {
+    name.replace(';', ":").replace(' ', "_")
+}
+
+/// What was recorded for a single function.
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionProfile {
+    /// The name of the function.
+    pub name: String,
+    /// The hash of the function, if it's known.
+    #[serde(serialize_with = "serialize_hash")]
+    pub hash: Option<Hash>,
+    /// If the function is a native function.
+    pub native: bool,
+    /// The number of times the function was called.
+    pub calls: u64,
+    /// The number of instructions executed in the function.
+    pub instructions: u64,
+    /// The time spent in the function, excluding any functions it called.
+    #[serde(rename = "time_ns", serialize_with = "serialize_nanos")]
+    pub time: Duration,
+}
+
+/// What was recorded for a span of source code.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpanProfile {
+    /// The source the span belongs to.
+    pub source_id: SourceId,
+    /// The span.
+    pub span: Span,
+    /// The number of instructions executed for the span.
+    pub instructions: u64,
+    /// The time spent executing instructions for the span.
+    #[serde(rename = "time_ns", serialize_with = "serialize_nanos")]
+    pub time: Duration,
+}
+
+/// What was recorded for a stack of functions.
+#[derive(Debug, Clone, Serialize)]
+pub struct StackProfile {
+    /// The functions in the stack starting with the outermost one, as
+    /// indexes into [Profile::functions].
+    pub frames: Vec<usize>,
+    /// The time spent in the innermost function of the stack.
+    #[serde(rename = "time_ns", serialize_with = "serialize_nanos")]
+    pub time: Duration,
+}
+
+fn serialize_hash<S>(hash: &Option<Hash>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match hash {
+        Some(hash) => serializer.collect_str(hash),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn serialize_nanos<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+    serializer.serialize_u64(nanos)
+}
+
+/// The profiling state of a single virtual machine.
+#[derive(Debug, Clone)]
+pub(crate) struct VmProfile {
+    profiler: Profiler,
+    /// The stack of the virtual machine which spawned this one, used when
+    /// it's not executed by another virtual machine.
+    parent: Option<usize>,
+    /// The unit of the virtual machine, once it's known to the profiler.
+    unit: Option<usize>,
+    /// The stack which the stack of the virtual machine is prefixed with.
+    prefix: Option<usize>,
+    /// The current stack of the virtual machine and the call depth it
+    /// corresponds to.
+    stack: Option<(usize, usize)>,
+}
+
+impl VmProfile {
+    /// Construct the profiling state of a new virtual machine.
+    pub(crate) fn new(profiler: Profiler) -> Self {
+        Self::with_parent(profiler, None)
+    }
+
+    /// Construct the profiling state of a virtual machine spawned by another
+    /// one.
+    pub(crate) fn spawn(&self) -> Self {
+        Self::with_parent(self.profiler.clone(), self.stack.map(|(stack, _)| stack))
+    }
+
+    fn with_parent(profiler: Profiler, parent: Option<usize>) -> Self {
+        Self {
+            profiler,
+            parent,
+            unit: None,
+            prefix: None,
+            stack: None,
+        }
+    }
+
+    /// Get the profiler.
+    pub(crate) fn profiler(&self) -> &Profiler {
+        &self.profiler
+    }
+
+    /// Mark the virtual machine as the one executing on this thread until the
+    /// returned guard is dropped.
+    pub(crate) fn enter(&mut self, unit: &Arc<Unit>, frames: &[CallFrame], ip: usize) -> Guard {
+        let previous = CURRENT.with(|tls| tls.borrow().clone());
+        let prefix = previous.as_ref().map(|(_, stack)| *stack).or(self.parent);
+
+        let mut state = self.profiler.state.borrow_mut();
+        let activity = state.current.map(|(activity, _)| activity);
+
+        let unit = match self.unit {
+            Some(unit) => unit,
+            None => *self.unit.get_or_insert(state.unit(unit)),
+        };
+
+        let stack = match self.stack {
+            Some((stack, depth)) if self.prefix == prefix && depth == frames.len() => stack,
+            current => {
+                let stack = state.stack_of(prefix, unit, frames, ip);
+
+                // NB: a virtual machine is entered for the first time when
+                // the function it was constructed for is called.
+                if current.is_none() {
+                    state.call(stack);
+                }
+
+                self.prefix = prefix;
+                self.stack = Some((stack, frames.len()));
+                stack
+            }
+        };
+
+        CURRENT.with(|tls| *tls.borrow_mut() = Some((self.profiler.clone(), stack)));
+
+        Guard {
+            profiler: self.profiler.clone(),
+            previous,
+            activity,
+        }
+    }
+
+    /// Record that the instruction at the given instruction pointer is about
+    /// to be executed.
+    pub(crate) fn instruction(&mut self, frames: &[CallFrame], ip: usize) {
+        let unit = match self.unit {
+            Some(unit) => unit,
+            None => return,
+        };
+
+        let mut state = self.profiler.state.borrow_mut();
+
+        let stack = match self.stack {
+            Some((stack, depth)) if depth == frames.len() => stack,
+            current => {
+                let stack = state.stack_of(self.prefix, unit, frames, ip);
+
+                if let Some((_, depth)) = current {
+                    if depth < frames.len() {
+                        state.call(stack);
+                    }
+                }
+
+                self.stack = Some((stack, frames.len()));
+                CURRENT.with(|tls| *tls.borrow_mut() = Some((self.profiler.clone(), stack)));
+                stack
+            }
+        };
+
+        state.instruction(stack, unit, ip);
+    }
+
+    /// Record that the native function with the given hash is being called
+    /// until the returned guard is dropped.
+    pub(crate) fn native(&self, hash: Hash) -> Guard {
+        let previous = CURRENT.with(|tls| tls.borrow().clone());
+
+        let mut state = self.profiler.state.borrow_mut();
+        let activity = state.current.map(|(activity, _)| activity);
+
+        if let Some((stack, _)) = self.stack {
+            let function = state.native(hash);
+            let mut frames = state.stacks[stack].frames.clone();
+            frames.push(function);
+            let stack = state.stack(frames);
+            state.call(stack);
+            state.switch(Some(Activity { stack, inst: None }));
+            CURRENT.with(|tls| *tls.borrow_mut() = Some((self.profiler.clone(), stack)));
+        }
+
+        Guard {
+            profiler: self.profiler.clone(),
+            previous,
+            activity,
+        }
+    }
+}
+
+/// Get the profiler of the virtual machine that is currently executing on
+/// this thread, if any.
+pub(crate) fn current() -> Option<Profiler> {
+    CURRENT.with(|tls| Some(tls.borrow().as_ref()?.0.clone()))
+}
+
+/// Guard which restores what was executing before it was constructed when
+/// dropped.
+pub(crate) struct Guard {
+    profiler: Profiler,
+    previous: Option<(Profiler, usize)>,
+    activity: Option<Activity>,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        self.profiler.state.borrow_mut().switch(self.activity);
+        let previous = self.previous.take();
+        CURRENT.with(|tls| *tls.borrow_mut() = previous);
+    }
+}
+
+/// What time is being spent on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Activity {
+    /// The stack being executed.
+    stack: usize,
+    /// The unit and the instruction pointer of the instruction being
+    /// executed, unless a native function is being executed.
+    inst: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum FunctionKey {
+    /// A function in a unit, identified by its offset.
+    Offset(usize, usize),
+    /// Instructions in a unit which don't belong to a known function.
+    Unknown(usize),
+    /// A native function.
+    Native(Hash),
+}
+
+struct UnitInfo {
+    unit: Arc<Unit>,
+    /// Offsets and hashes of the functions in the unit, ordered by offset.
+    functions: Vec<(usize, Hash)>,
+}
+
+struct StackInfo {
+    frames: Vec<usize>,
+    time: Duration,
+}
+
+#[derive(Default)]
+struct Counter {
+    count: u64,
+    time: Duration,
+}
+
+#[derive(Default)]
+struct State {
+    units: Vec<UnitInfo>,
+    /// Units by address.
+    unit_index: HashMap<usize, usize>,
+    functions: Vec<FunctionProfile>,
+    function_index: HashMap<FunctionKey, usize>,
+    stacks: Vec<StackInfo>,
+    stack_index: HashMap<Vec<usize>, usize>,
+    /// Instructions executed by unit and instruction pointer.
+    instructions: HashMap<(usize, usize), Counter>,
+    /// What time is currently being spent on, and since when.
+    current: Option<(Activity, Instant)>,
+}
+
+impl State {
+    fn unit(&mut self, unit: &Arc<Unit>) -> usize {
+        let address = Arc::as_ptr(unit) as usize;
+
+        if let Some(index) = self.unit_index.get(&address) {
+            return *index;
+        }
+
+        let mut functions = unit
+            .iter_functions()
+            .filter_map(|(hash, f)| match f {
+                UnitFn::Offset { offset, .. } => Some((*offset, hash)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        functions.sort();
+
+        let index = self.units.len();
+
+        // NB: the unit is kept alive by the profiler, so its address can't be
+        // reused by another unit.
+        self.units.push(UnitInfo {
+            unit: unit.clone(),
+            functions,
+        });
+
+        self.unit_index.insert(address, index);
+        index
+    }
+
+    /// Get the function containing the given instruction.
+    fn function_at(&mut self, unit: usize, ip: usize) -> usize {
+        let info = &self.units[unit];
+
+        let found = match info
+            .functions
+            .binary_search_by_key(&ip, |(offset, _)| *offset)
+        {
+            Ok(n) => Some(info.functions[n]),
+            Err(0) => None,
+            Err(n) => Some(info.functions[n - 1]),
+        };
+
+        let (key, hash) = match found {
+            Some((offset, hash)) => (FunctionKey::Offset(unit, offset), Some(hash)),
+            None => (FunctionKey::Unknown(unit), None),
+        };
+
+        if let Some(function) = self.function_index.get(&key) {
+            return *function;
+        }
+
+        let name = match hash {
+            Some(hash) => match info
+                .unit
+                .debug_info()
+                .and_then(|debug| debug.functions.get(&hash))
+            {
+                Some(signature) => signature.path.to_string(),
+                None => hash.to_string(),
+            },
+            None => String::from("<unknown>"),
+        };
+
+        self.function(key, name, hash, false)
+    }
+
+    fn native(&mut self, hash: Hash) -> usize {
+        let key = FunctionKey::Native(hash);
+
+        if let Some(function) = self.function_index.get(&key) {
+            return *function;
+        }
+
+        self.function(key, hash.to_string(), Some(hash), true)
+    }
+
+    fn function(
+        &mut self,
+        key: FunctionKey,
+        name: String,
+        hash: Option<Hash>,
+        native: bool,
+    ) -> usize {
+        let index = self.functions.len();
+
+        self.functions.push(FunctionProfile {
+            name,
+            hash,
+            native,
+            calls: 0,
+            instructions: 0,
+            time: Duration::default(),
+        });
+
+        self.function_index.insert(key, index);
+        index
+    }
+
+    /// Get the stack of a virtual machine with the given call frames.
+    fn stack_of(
+        &mut self,
+        prefix: Option<usize>,
+        unit: usize,
+        frames: &[CallFrame],
+        ip: usize,
+    ) -> usize {
+        let mut stack = match prefix {
+            Some(prefix) => self.stacks[prefix].frames.clone(),
+            None => Vec::new(),
+        };
+
+        for frame in frames {
+            stack.push(self.function_at(unit, frame.ip()));
+        }
+
+        stack.push(self.function_at(unit, ip));
+        self.stack(stack)
+    }
+
+    fn stack(&mut self, frames: Vec<usize>) -> usize {
+        if let Some(stack) = self.stack_index.get(&frames) {
+            return *stack;
+        }
+
+        let index = self.stacks.len();
+
+        self.stacks.push(StackInfo {
+            frames: frames.clone(),
+            time: Duration::default(),
+        });
+
+        self.stack_index.insert(frames, index);
+        index
+    }
+
+    /// Record a call to the innermost function of the given stack.
+    fn call(&mut self, stack: usize) {
+        if let Some(function) = self.stacks[stack].frames.last() {
+            self.functions[*function].calls += 1;
+        }
+    }
+
+    fn instruction(&mut self, stack: usize, unit: usize, ip: usize) {
+        if let Some(function) = self.stacks[stack].frames.last() {
+            self.functions[*function].instructions += 1;
+        }
+
+        self.instructions.entry((unit, ip)).or_default().count += 1;
+
+        self.switch(Some(Activity {
+            stack,
+            inst: Some((unit, ip)),
+        }));
+    }
+
+    /// Switch what time is being spent on, attributing the time spent since
+    /// the last switch to what was being done.
+    fn switch(&mut self, activity: Option<Activity>) {
+        let now = Instant::now();
+
+        if let Some((current, since)) = self.current.take() {
+            let elapsed = now.duration_since(since);
+            let stack = &mut self.stacks[current.stack];
+            stack.time += elapsed;
+
+            if let Some(function) = stack.frames.last() {
+                self.functions[*function].time += elapsed;
+            }
+
+            if let Some(inst) = current.inst {
+                self.instructions.entry(inst).or_default().time += elapsed;
+            }
+        }
+
+        self.current = activity.map(|activity| (activity, now));
+    }
+
+    fn profile(&self) -> Profile {
+        let mut functions = (0..self.functions.len()).collect::<Vec<_>>();
+        functions.sort_by(|a, b| self.functions[*b].time.cmp(&self.functions[*a].time));
+
+        // NB: functions are reordered, so stacks need to be remapped.
+        let mut remap = vec![0; functions.len()];
+
+        for (to, from) in functions.iter().enumerate() {
+            remap[*from] = to;
+        }
+
+        let stacks = self
+            .stacks
+            .iter()
+            .map(|stack| StackProfile {
+                frames: stack.frames.iter().map(|f| remap[*f]).collect(),
+                time: stack.time,
+            })
+            .collect();
+
+        let functions = functions
+            .into_iter()
+            .map(|f| self.functions[f].clone())
+            .collect();
+
+        let mut spans = HashMap::<(SourceId, Span), SpanProfile>::new();
+
+        for ((unit, ip), counter) in &self.instructions {
+            let debug = self.units[*unit]
+                .unit
+                .debug_info()
+                .and_then(|debug| debug.instruction_at(*ip));
+
+            let debug = match debug {
+                Some(debug) => debug,
+                None => continue,
+            };
+
+            let span = spans
+                .entry((debug.source_id, debug.span))
+                .or_insert_with(|| SpanProfile {
+                    source_id: debug.source_id,
+                    span: debug.span,
+                    instructions: 0,
+                    time: Duration::default(),
+                });
+
+            span.instructions += counter.count;
+            span.time += counter.time;
+        }
+
+        let mut spans = spans.into_iter().map(|(_, s)| s).collect::<Vec<_>>();
+        spans.sort_by(|a, b| b.time.cmp(&a.time).then(a.span.cmp(&b.span)));
+
+        Profile {
+            functions,
+            spans,
+            stacks,
+        }
+    }
+}
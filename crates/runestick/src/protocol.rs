@@ -145,6 +145,12 @@ impl Protocol {
         hash: Hash::new(0x3a8695980e77baf4),
     };
 
+    /// The function to implement for the power operation.
+    pub const POW: Protocol = Protocol {
+        name: "**",
+        hash: Hash::new(0x1e3fd4cf8bd9a3c6),
+    };
+
     /// The function to implement for the bitwise and operation.
     pub const BIT_AND: Protocol = Protocol {
         name: "&",
@@ -205,6 +211,47 @@ impl Protocol {
         hash: Hash::new(0x61ff7c46ff00e74a),
     };
 
+    /// The function to implement for partial equality, used by the `==` and
+    /// `!=` operations. Must return a `bool`.
+    pub const PARTIAL_EQ: Protocol = Protocol {
+        name: "partial_eq",
+        hash: Hash::new(0x2b31bfe1fd18f0cc),
+    };
+
+    /// The function to implement for total equality. Used by the `==` and `!=`
+    /// operations if [Protocol::PARTIAL_EQ] is not implemented, and when
+    /// values are used as keys. Must return a `bool`.
+    pub const EQ: Protocol = Protocol {
+        name: "eq",
+        hash: Hash::new(0xc0377876b3947c49),
+    };
+
+    /// The function to implement for partial ordering, used by the `<`, `>`,
+    /// `<=` and `>=` operations. Must return an `Option<Ordering>`.
+    pub const PARTIAL_CMP: Protocol = Protocol {
+        name: "partial_cmp",
+        hash: Hash::new(0xfac044ea763777d5),
+    };
+
+    /// The function to implement for total ordering. Used by the `<`, `>`, `<=`
+    /// and `>=` operations if [Protocol::PARTIAL_CMP] is not implemented. Must
+    /// return an `Ordering`.
+    pub const CMP: Protocol = Protocol {
+        name: "cmp",
+        hash: Hash::new(0xed7293ae68b1c080),
+    };
+
+    /// The function to implement to hash a value. Must return an integer,
+    /// and values which are equal must produce the same hash.
+    ///
+    /// A native value can only be used as a key in a collection if it also
+    /// implements [EQ][Self::EQ] or [PARTIAL_EQ][Self::PARTIAL_EQ], which is
+    /// used to tell apart values whose hashes collide.
+    pub const HASH: Protocol = Protocol {
+        name: "hash",
+        hash: Hash::new(0x525a3537fb05ba41),
+    };
+
     /// Protocol function used by template strings.
     pub const STRING_DISPLAY: Protocol = Protocol {
         name: "string_display",
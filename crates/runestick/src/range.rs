@@ -2,6 +2,7 @@ use crate::{
     FromValue, InstallWith, Iterator, Mut, Named, Panic, RawMut, RawRef, RawStr, Ref, ToValue,
     UnsafeFromValue, Value, VmError, VmErrorKind,
 };
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::ops;
 
@@ -125,7 +126,7 @@ impl fmt::Debug for Range {
 }
 
 /// The limits of a range.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RangeLimits {
     /// A half-open range `..`.
     HalfOpen,
@@ -1,5 +1,6 @@
 use crate::collections::HashMap;
 use crate::context::Handler;
+use crate::snapshot::AnySnapshot;
 use crate::{ConstValue, Hash, Item, TypeCheck};
 use std::fmt;
 use std::sync::Arc;
@@ -20,6 +21,9 @@ pub struct RuntimeContext {
 
     /// Named constant values
     pub(crate) constants: HashMap<Hash, ConstValue>,
+
+    /// Native types which can be stored in snapshots.
+    pub(crate) snapshots: HashMap<Hash, AnySnapshot>,
 }
 
 impl RuntimeContext {
@@ -42,6 +46,11 @@ impl RuntimeContext {
     pub fn constant(&self, hash: Hash) -> Option<&ConstValue> {
         self.constants.get(&hash)
     }
+
+    /// Lookup how to snapshot the native type with the given hash.
+    pub(crate) fn lookup_snapshot(&self, hash: Hash) -> Option<&AnySnapshot> {
+        self.snapshots.get(&hash)
+    }
 }
 
 impl fmt::Debug for RuntimeContext {
@@ -34,6 +34,12 @@ impl<T> Shared<T> {
         }
     }
 
+    /// Get the address of the shared value, which uniquely identifies it for
+    /// as long as it's alive.
+    pub(crate) fn as_ptr(&self) -> *const () {
+        self.inner.as_ptr() as *const ()
+    }
+
     /// Return a debug formatter, that when printed will display detailed
     /// diagnostics of this shared type.
     pub fn debug(&self) -> SharedDebug<'_, T> {
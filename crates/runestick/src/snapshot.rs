@@ -0,0 +1,813 @@
+//! Snapshots of virtual machines, which can be serialized to suspend an
+//! execution and resume it later, possibly in a different process.
+//!
+//! A snapshot contains the stack, the call frames, and the instruction pointer
+//! of a virtual machine, together with every value reachable from them.
+//! Values which are shared are only stored once, so any aliasing between them
+//! is preserved when the snapshot is restored.
+//!
+//! Snapshots don't include the unit or the context of the virtual machine, so
+//! they have to be restored with the same unit that they were taken with. A
+//! fingerprint of the unit is stored to check this, and restoring with a
+//! different unit fails with [VmErrorKind::InvalidSnapshot].
+//!
+//! Values which can't be stored, like futures and iterators, cause taking the
+//! snapshot to fail with [VmErrorKind::UnsupportedSnapshot]. This includes
+//! the iterator of a `for` loop which is in progress, so code which is
+//! suspended inside of a loop should use `while` or `loop` instead. Native
+//! types have to opt in to being stored by implementing [Snapshot] and
+//! registering it with [Module::snapshot].
+//!
+//! [VmErrorKind::UnsupportedSnapshot]: crate::VmErrorKind::UnsupportedSnapshot
+//! [VmErrorKind::InvalidSnapshot]: crate::VmErrorKind::InvalidSnapshot
+//! [Module::snapshot]: crate::Module::snapshot
+
+use crate::collections::HashMap;
+use crate::{
+    Any, AnyObj, Bytes, CallFrame, FormatSpec, Function, Generator, GeneratorState, Hash, Object,
+    Range, RangeLimits, RuntimeContext, Shared, Stack, Stream, Struct, StructVariant, Tuple,
+    TypeInfo, Unit, Value, Vec, Vm, VmError, VmErrorKind, VmExecution,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use std::vec;
+
+/// Trait which allows a native type to be stored in a [VmSnapshot].
+///
+/// Native types are stored by converting them into a value which can be
+/// stored, like an object or a tuple, and are restored by converting such a
+/// value back. Implementing types have to be registered with
+/// [Module::snapshot].
+///
+/// [Module::snapshot]: crate::Module::snapshot
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::{Any, FromValue as _, Module, Snapshot, ToValue as _, Value, VmError};
+///
+/// #[derive(Any)]
+/// struct Counter {
+///     count: i64,
+/// }
+///
+/// impl Snapshot for Counter {
+///     fn snapshot(&self) -> Result<Value, VmError> {
+///         self.count.to_value()
+///     }
+///
+///     fn restore(value: Value) -> Result<Self, VmError> {
+///         Ok(Self {
+///             count: i64::from_value(value)?,
+///         })
+///     }
+/// }
+///
+/// # fn main() -> runestick::Result<()> {
+/// let mut module = Module::default();
+/// module.ty::<Counter>()?;
+/// module.snapshot::<Counter>()?;
+/// # Ok(()) }
+/// ```
+pub trait Snapshot: Any + Sized {
+    /// Convert the native value into a value which can be stored.
+    fn snapshot(&self) -> Result<Value, VmError>;
+
+    /// Restore the native value from the value it was stored as.
+    fn restore(value: Value) -> Result<Self, VmError>;
+}
+
+/// The functions used to store and restore a native type which implements
+/// [Snapshot].
+#[derive(Clone, Copy)]
+pub(crate) struct AnySnapshot {
+    snapshot: fn(&AnyObj) -> Result<Value, VmError>,
+    restore: fn(Value) -> Result<AnyObj, VmError>,
+}
+
+impl fmt::Debug for AnySnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AnySnapshot")
+    }
+}
+
+impl AnySnapshot {
+    /// Construct the snapshot functions for the given type.
+    pub(crate) fn new<T>() -> Self
+    where
+        T: Snapshot,
+    {
+        Self {
+            snapshot: snapshot_any::<T>,
+            restore: restore_any::<T>,
+        }
+    }
+}
+
+fn snapshot_any<T>(any: &AnyObj) -> Result<Value, VmError>
+where
+    T: Snapshot,
+{
+    match any.downcast_borrow_ref::<T>() {
+        Some(value) => value.snapshot(),
+        None => Err(VmError::from(VmErrorKind::InvalidSnapshot)),
+    }
+}
+
+fn restore_any<T>(value: Value) -> Result<AnyObj, VmError>
+where
+    T: Snapshot,
+{
+    Ok(AnyObj::new(T::restore(value)?))
+}
+
+/// A serializable snapshot of a suspended virtual machine or generator.
+///
+/// See [Vm::snapshot] and [Generator::snapshot].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VmSnapshot {
+    /// The fingerprint of the unit the snapshot was taken with, unless it
+    /// doesn't refer to one.
+    fingerprint: Option<Hash>,
+    /// Values which are referenced from the root of the snapshot, or from
+    /// each other.
+    heap: vec::Vec<SnapshotObject>,
+    /// What the snapshot was taken of.
+    root: SnapshotRoot,
+}
+
+impl VmSnapshot {
+    /// Take a snapshot of the given virtual machine.
+    pub(crate) fn of_vm(vm: &Vm) -> Result<Self, VmError> {
+        let mut writer = Writer::new(&vm.context, &vm.unit);
+        let root = SnapshotRoot::Vm(writer.vm(vm)?);
+        Ok(writer.finish(root))
+    }
+
+    /// Take a snapshot of the given generator.
+    pub(crate) fn of_generator(generator: &Generator) -> Result<Self, VmError> {
+        let execution = generator.execution.as_ref();
+
+        let vm = match execution.and_then(|execution| execution.vms.first()) {
+            Some(vm) => vm,
+            None => {
+                let execution = SnapshotExecution {
+                    vms: None,
+                    first: generator.first,
+                };
+
+                return Ok(Self {
+                    fingerprint: None,
+                    heap: vec::Vec::new(),
+                    root: SnapshotRoot::Generator(execution),
+                });
+            }
+        };
+
+        let mut writer = Writer::new(&vm.context, &vm.unit);
+        let execution = writer.execution(execution, generator.first)?;
+        Ok(writer.finish(SnapshotRoot::Generator(execution)))
+    }
+
+    /// Restore the virtual machine the snapshot was taken of.
+    pub(crate) fn restore_vm(
+        &self,
+        context: &Arc<RuntimeContext>,
+        unit: &Arc<Unit>,
+    ) -> Result<Vm, VmError> {
+        let vm = match &self.root {
+            SnapshotRoot::Vm(vm) => vm,
+            _ => return Err(VmError::from(VmErrorKind::InvalidSnapshot)),
+        };
+
+        self.check_fingerprint(unit)?;
+        Reader::new(context, unit, &self.heap)?.vm(vm)
+    }
+
+    /// Restore the generator the snapshot was taken of.
+    pub(crate) fn restore_generator(
+        &self,
+        context: &Arc<RuntimeContext>,
+        unit: &Arc<Unit>,
+    ) -> Result<Generator, VmError> {
+        let execution = match &self.root {
+            SnapshotRoot::Generator(execution) => execution,
+            _ => return Err(VmError::from(VmErrorKind::InvalidSnapshot)),
+        };
+
+        self.check_fingerprint(unit)?;
+        let reader = Reader::new(context, unit, &self.heap)?;
+
+        Ok(Generator {
+            execution: reader.execution(execution)?,
+            first: execution.first,
+        })
+    }
+
+    /// Check that the snapshot was taken with the given unit.
+    fn check_fingerprint(&self, unit: &Unit) -> Result<(), VmError> {
+        match self.fingerprint {
+            Some(fingerprint) if fingerprint != unit.fingerprint() => {
+                Err(VmError::from(VmErrorKind::InvalidSnapshot))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum SnapshotRoot {
+    Vm(SnapshotVm),
+    Generator(SnapshotExecution),
+}
+
+/// The state of a single virtual machine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotVm {
+    ip: usize,
+    stack: vec::Vec<SnapshotValue>,
+    stack_bottom: usize,
+    call_frames: vec::Vec<CallFrame>,
+}
+
+/// The state of the execution backing a generator or a stream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotExecution {
+    vms: Option<vec::Vec<SnapshotVm>>,
+    first: bool,
+}
+
+/// A value, which refers to the heap of the snapshot if it's shared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum SnapshotValue {
+    Unit,
+    Bool(bool),
+    Byte(u8),
+    Char(char),
+    Integer(i64),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    I32(i32),
+    I128(i128),
+    Float(f64),
+    Type(Hash),
+    StaticString(String),
+    Format(Box<SnapshotValue>, FormatSpec),
+    Shared(usize),
+}
+
+/// A shared value stored in the heap of the snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum SnapshotObject {
+    String(String),
+    Bytes(vec::Vec<u8>),
+    Vec(vec::Vec<SnapshotValue>),
+    Tuple(vec::Vec<SnapshotValue>),
+    Object(vec::Vec<(String, SnapshotValue)>),
+    Range(Option<SnapshotValue>, Option<SnapshotValue>, RangeLimits),
+    Option(Option<SnapshotValue>),
+    Result(Result<SnapshotValue, SnapshotValue>),
+    GeneratorState(bool, SnapshotValue),
+    UnitStruct(Hash),
+    TupleStruct(Hash, vec::Vec<SnapshotValue>),
+    Struct(Hash, vec::Vec<(String, SnapshotValue)>),
+    UnitVariant(Hash),
+    TupleVariant(Hash, vec::Vec<SnapshotValue>),
+    StructVariant(Hash, vec::Vec<(String, SnapshotValue)>),
+    Function(Hash, Option<vec::Vec<SnapshotValue>>),
+    Generator(SnapshotExecution),
+    Stream(SnapshotExecution),
+    Any(Hash, SnapshotValue),
+}
+
+/// Placeholder for native values which haven't been restored yet.
+#[derive(Any)]
+#[rune(module = "crate")]
+struct Pending;
+
+/// Helper to take a snapshot.
+struct Writer<'a> {
+    context: &'a Arc<RuntimeContext>,
+    unit: &'a Arc<Unit>,
+    heap: vec::Vec<Option<SnapshotObject>>,
+    /// Shared values which have been stored, by address.
+    seen: HashMap<*const (), usize>,
+}
+
+impl<'a> Writer<'a> {
+    fn new(context: &'a Arc<RuntimeContext>, unit: &'a Arc<Unit>) -> Self {
+        Self {
+            context,
+            unit,
+            heap: vec::Vec::new(),
+            seen: HashMap::new(),
+        }
+    }
+
+    fn finish(self, root: SnapshotRoot) -> VmSnapshot {
+        // NB: every heap slot is filled before the value referencing it is
+        // returned.
+        let heap = self.heap.into_iter().flatten().collect();
+
+        VmSnapshot {
+            fingerprint: Some(self.unit.fingerprint()),
+            heap,
+            root,
+        }
+    }
+
+    fn vm(&mut self, vm: &Vm) -> Result<SnapshotVm, VmError> {
+        let stack = vm
+            .stack
+            .iter()
+            .map(|value| self.value(value))
+            .collect::<Result<_, _>>()?;
+
+        Ok(SnapshotVm {
+            ip: vm.ip,
+            stack,
+            stack_bottom: vm.stack.stack_bottom(),
+            call_frames: vm.call_frames.clone(),
+        })
+    }
+
+    fn execution(
+        &mut self,
+        execution: Option<&VmExecution>,
+        first: bool,
+    ) -> Result<SnapshotExecution, VmError> {
+        let vms = match execution {
+            Some(execution) => {
+                let mut vms = vec::Vec::with_capacity(execution.vms.len());
+
+                for vm in &execution.vms {
+                    // NB: functions in other units can't be restored.
+                    if !Arc::ptr_eq(&vm.unit, self.unit) {
+                        return Err(VmError::from(VmErrorKind::UnsupportedSnapshot {
+                            actual: TypeInfo::StaticType(crate::GENERATOR_TYPE),
+                        }));
+                    }
+
+                    vms.push(self.vm(vm)?);
+                }
+
+                Some(vms)
+            }
+            None => None,
+        };
+
+        Ok(SnapshotExecution { vms, first })
+    }
+
+    fn values<'v>(
+        &mut self,
+        values: impl IntoIterator<Item = &'v Value>,
+    ) -> Result<vec::Vec<SnapshotValue>, VmError> {
+        values.into_iter().map(|value| self.value(value)).collect()
+    }
+
+    fn object(&mut self, object: &Object) -> Result<vec::Vec<(String, SnapshotValue)>, VmError> {
+        object
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), self.value(value)?)))
+            .collect()
+    }
+
+    fn option(&mut self, value: Option<&Value>) -> Result<Option<SnapshotValue>, VmError> {
+        value.map(|value| self.value(value)).transpose()
+    }
+
+    /// Store the given shared value in the heap, unless it's already there.
+    fn shared<T>(
+        &mut self,
+        shared: &Shared<T>,
+        f: impl FnOnce(&mut Self, &T) -> Result<SnapshotObject, VmError>,
+    ) -> Result<SnapshotValue, VmError> {
+        let ptr = shared.as_ptr();
+
+        if let Some(index) = self.seen.get(&ptr) {
+            return Ok(SnapshotValue::Shared(*index));
+        }
+
+        // NB: reserve the slot before storing the value, so that cyclical
+        // references to it are resolved.
+        let index = self.heap.len();
+        self.heap.push(None);
+        self.seen.insert(ptr, index);
+
+        let data = shared.borrow_ref()?;
+        self.heap[index] = Some(f(self, &*data)?);
+        Ok(SnapshotValue::Shared(index))
+    }
+
+    fn value(&mut self, value: &Value) -> Result<SnapshotValue, VmError> {
+        Ok(match value {
+            Value::Unit => SnapshotValue::Unit,
+            Value::Bool(b) => SnapshotValue::Bool(*b),
+            Value::Byte(b) => SnapshotValue::Byte(*b),
+            Value::Char(c) => SnapshotValue::Char(*c),
+            Value::Integer(n) => SnapshotValue::Integer(*n),
+            Value::U16(n) => SnapshotValue::U16(*n),
+            Value::U32(n) => SnapshotValue::U32(*n),
+            Value::U64(n) => SnapshotValue::U64(*n),
+            Value::I32(n) => SnapshotValue::I32(*n),
+            Value::I128(n) => SnapshotValue::I128(**n),
+            Value::Float(n) => SnapshotValue::Float(*n),
+            Value::Type(hash) => SnapshotValue::Type(*hash),
+            Value::StaticString(string) => {
+                SnapshotValue::StaticString(string.as_ref().as_ref().clone())
+            }
+            Value::Format(format) => {
+                SnapshotValue::Format(Box::new(self.value(&format.value)?), format.spec)
+            }
+            Value::String(string) => self.shared(string, |_, string| {
+                Ok(SnapshotObject::String(string.clone()))
+            })?,
+            Value::Bytes(bytes) => {
+                self.shared(bytes, |_, bytes| Ok(SnapshotObject::Bytes(bytes.to_vec())))?
+            }
+            Value::Vec(vec) => {
+                self.shared(vec, |w, vec| Ok(SnapshotObject::Vec(w.values(vec.iter())?)))?
+            }
+            Value::Tuple(tuple) => self.shared(tuple, |w, tuple| {
+                Ok(SnapshotObject::Tuple(w.values(tuple.iter())?))
+            })?,
+            Value::Object(object) => self.shared(object, |w, object| {
+                Ok(SnapshotObject::Object(w.object(object)?))
+            })?,
+            Value::Range(range) => self.shared(range, |w, range| {
+                Ok(SnapshotObject::Range(
+                    w.option(range.start.as_ref())?,
+                    w.option(range.end.as_ref())?,
+                    range.limits,
+                ))
+            })?,
+            Value::Option(option) => self.shared(option, |w, option| {
+                Ok(SnapshotObject::Option(w.option(option.as_ref())?))
+            })?,
+            Value::Result(result) => self.shared(result, |w, result| {
+                Ok(SnapshotObject::Result(match result {
+                    Ok(value) => Ok(w.value(value)?),
+                    Err(value) => Err(w.value(value)?),
+                }))
+            })?,
+            Value::GeneratorState(state) => self.shared(state, |w, state| {
+                Ok(match state {
+                    GeneratorState::Yielded(value) => {
+                        SnapshotObject::GeneratorState(true, w.value(value)?)
+                    }
+                    GeneratorState::Complete(value) => {
+                        SnapshotObject::GeneratorState(false, w.value(value)?)
+                    }
+                })
+            })?,
+            Value::UnitStruct(unit_struct) => self.shared(unit_struct, |_, unit_struct| {
+                Ok(SnapshotObject::UnitStruct(unit_struct.rtti.hash))
+            })?,
+            Value::TupleStruct(tuple_struct) => self.shared(tuple_struct, |w, tuple_struct| {
+                Ok(SnapshotObject::TupleStruct(
+                    tuple_struct.rtti.hash,
+                    w.values(tuple_struct.data.iter())?,
+                ))
+            })?,
+            Value::Struct(object) => self.shared(object, |w, object| {
+                Ok(SnapshotObject::Struct(
+                    object.rtti.hash,
+                    w.object(&object.data)?,
+                ))
+            })?,
+            Value::UnitVariant(variant) => self.shared(variant, |_, variant| {
+                Ok(SnapshotObject::UnitVariant(variant.rtti.hash))
+            })?,
+            Value::TupleVariant(variant) => self.shared(variant, |w, variant| {
+                Ok(SnapshotObject::TupleVariant(
+                    variant.rtti.hash,
+                    w.values(variant.data.iter())?,
+                ))
+            })?,
+            Value::StructVariant(variant) => self.shared(variant, |w, variant| {
+                Ok(SnapshotObject::StructVariant(
+                    variant.rtti.hash,
+                    w.object(&variant.data)?,
+                ))
+            })?,
+            Value::Function(function) => self.shared(function, |w, function| {
+                if !function.belongs_to(w.unit) {
+                    return Err(VmError::from(VmErrorKind::UnsupportedSnapshot {
+                        actual: value.type_info()?,
+                    }));
+                }
+
+                let environment = match function.environment() {
+                    Some(environment) => Some(w.values(environment)?),
+                    None => None,
+                };
+
+                Ok(SnapshotObject::Function(function.type_hash(), environment))
+            })?,
+            Value::Generator(generator) => self.shared(generator, |w, generator| {
+                Ok(SnapshotObject::Generator(w.execution(
+                    generator.execution.as_ref(),
+                    generator.first,
+                )?))
+            })?,
+            Value::Stream(stream) => self.shared(stream, |w, stream| {
+                Ok(SnapshotObject::Stream(
+                    w.execution(stream.execution.as_ref(), stream.first)?,
+                ))
+            })?,
+            Value::Any(any) => self.shared(any, |w, any| {
+                let hash = any.type_hash();
+
+                let snapshot = match w.context.lookup_snapshot(hash) {
+                    Some(snapshot) => snapshot,
+                    None => {
+                        return Err(VmError::from(VmErrorKind::UnsupportedSnapshot {
+                            actual: value.type_info()?,
+                        }))
+                    }
+                };
+
+                let value = (snapshot.snapshot)(any)?;
+                Ok(SnapshotObject::Any(hash, w.value(&value)?))
+            })?,
+            Value::Future(..) | Value::Iterator(..) => {
+                return Err(VmError::from(VmErrorKind::UnsupportedSnapshot {
+                    actual: value.type_info()?,
+                }));
+            }
+        })
+    }
+}
+
+/// Helper to restore a snapshot.
+struct Reader<'a> {
+    context: &'a Arc<RuntimeContext>,
+    unit: &'a Arc<Unit>,
+    /// The restored heap of the snapshot.
+    heap: vec::Vec<Value>,
+}
+
+impl<'a> Reader<'a> {
+    /// Construct a reader, restoring the given heap.
+    ///
+    /// This is done in two passes. The first allocates an empty value for each
+    /// object in the heap, and the second fills them in. This allows objects
+    /// to refer to each other regardless of the order they're stored in.
+    fn new(
+        context: &'a Arc<RuntimeContext>,
+        unit: &'a Arc<Unit>,
+        heap: &[SnapshotObject],
+    ) -> Result<Self, VmError> {
+        let mut this = Self {
+            context,
+            unit,
+            heap: vec::Vec::with_capacity(heap.len()),
+        };
+
+        for object in heap {
+            let value = this.allocate(object)?;
+            this.heap.push(value);
+        }
+
+        for (object, value) in heap.iter().zip(this.heap.clone()) {
+            this.fill(object, value)?;
+        }
+
+        Ok(this)
+    }
+
+    fn vm(&self, vm: &SnapshotVm) -> Result<Vm, VmError> {
+        let stack = self.values(&vm.stack)?;
+
+        if vm.stack_bottom > stack.len() {
+            return Err(VmError::from(VmErrorKind::InvalidSnapshot));
+        }
+
+        let stack = Stack::from_parts(stack, vm.stack_bottom);
+        let mut out = Vm::new_with_stack(self.context.clone(), self.unit.clone(), stack);
+        out.ip = vm.ip;
+        out.call_frames = vm.call_frames.clone();
+        Ok(out)
+    }
+
+    fn execution(&self, execution: &SnapshotExecution) -> Result<Option<VmExecution>, VmError> {
+        let vms = match &execution.vms {
+            Some(vms) => vms,
+            None => return Ok(None),
+        };
+
+        let vms = vms.iter().map(|vm| self.vm(vm)).collect::<Result<_, _>>()?;
+
+        Ok(Some(VmExecution { vms }))
+    }
+
+    fn value(&self, value: &SnapshotValue) -> Result<Value, VmError> {
+        Ok(match value {
+            SnapshotValue::Unit => Value::Unit,
+            SnapshotValue::Bool(b) => Value::Bool(*b),
+            SnapshotValue::Byte(b) => Value::Byte(*b),
+            SnapshotValue::Char(c) => Value::Char(*c),
+            SnapshotValue::Integer(n) => Value::Integer(*n),
+            SnapshotValue::U16(n) => Value::U16(*n),
+            SnapshotValue::U32(n) => Value::U32(*n),
+            SnapshotValue::U64(n) => Value::U64(*n),
+            SnapshotValue::I32(n) => Value::I32(*n),
+            SnapshotValue::I128(n) => Value::I128(Box::new(*n)),
+            SnapshotValue::Float(n) => Value::Float(*n),
+            SnapshotValue::Type(hash) => Value::Type(*hash),
+            SnapshotValue::StaticString(string) => {
+                Value::StaticString(Arc::new(string.clone().into()))
+            }
+            SnapshotValue::Format(value, spec) => Value::Format(Box::new(crate::Format {
+                value: self.value(value)?,
+                spec: *spec,
+            })),
+            SnapshotValue::Shared(index) => self
+                .heap
+                .get(*index)
+                .cloned()
+                .ok_or_else(|| VmErrorKind::InvalidSnapshot)?,
+        })
+    }
+
+    fn values(&self, values: &[SnapshotValue]) -> Result<vec::Vec<Value>, VmError> {
+        values.iter().map(|value| self.value(value)).collect()
+    }
+
+    fn object(&self, entries: &[(String, SnapshotValue)]) -> Result<Object, VmError> {
+        let mut object = Object::with_capacity(entries.len());
+
+        for (key, value) in entries {
+            object.insert(key.clone(), self.value(value)?);
+        }
+
+        Ok(object)
+    }
+
+    fn option(&self, value: &Option<SnapshotValue>) -> Result<Option<Value>, VmError> {
+        value.as_ref().map(|value| self.value(value)).transpose()
+    }
+
+    /// Allocate an empty value for the given object.
+    fn allocate(&self, object: &SnapshotObject) -> Result<Value, VmError> {
+        Ok(match object {
+            SnapshotObject::String(string) => Value::String(Shared::new(string.clone())),
+            SnapshotObject::Bytes(bytes) => {
+                Value::Bytes(Shared::new(Bytes::from_vec(bytes.clone())))
+            }
+            SnapshotObject::Vec(..) => Value::Vec(Shared::new(Vec::new())),
+            SnapshotObject::Tuple(..) => Value::Tuple(Shared::new(Tuple::from(vec![]))),
+            SnapshotObject::Object(..) => Value::Object(Shared::new(Object::new())),
+            SnapshotObject::Range(_, _, limits) => {
+                Value::Range(Shared::new(Range::new(None, None, *limits)))
+            }
+            SnapshotObject::Option(..) => Value::Option(Shared::new(None)),
+            SnapshotObject::Result(..) => Value::Result(Shared::new(Ok(Value::Unit))),
+            SnapshotObject::GeneratorState(..) => {
+                Value::GeneratorState(Shared::new(GeneratorState::Complete(Value::Unit)))
+            }
+            SnapshotObject::UnitStruct(hash) => Value::unit_struct(self.rtti(*hash)?),
+            SnapshotObject::TupleStruct(hash, ..) => Value::tuple_struct(self.rtti(*hash)?, vec![]),
+            SnapshotObject::Struct(hash, ..) => Value::Struct(Shared::new(Struct {
+                rtti: self.rtti(*hash)?,
+                data: Object::new(),
+            })),
+            SnapshotObject::UnitVariant(hash) => Value::empty_variant(self.variant_rtti(*hash)?),
+            SnapshotObject::TupleVariant(hash, ..) => {
+                Value::tuple_variant(self.variant_rtti(*hash)?, vec![])
+            }
+            SnapshotObject::StructVariant(hash, ..) => {
+                Value::StructVariant(Shared::new(StructVariant {
+                    rtti: self.variant_rtti(*hash)?,
+                    data: Object::new(),
+                }))
+            }
+            SnapshotObject::Function(hash, None) => Value::Function(Shared::new(
+                Function::from_hash(self.context, self.unit, *hash)?,
+            )),
+            SnapshotObject::Function(hash, Some(environment)) => {
+                let environment = vec![Value::Unit; environment.len()].into_boxed_slice();
+
+                Value::Function(Shared::new(Function::closure_from_hash(
+                    self.context,
+                    self.unit,
+                    *hash,
+                    environment,
+                )?))
+            }
+            SnapshotObject::Generator(execution) => Value::Generator(Shared::new(Generator {
+                execution: None,
+                first: execution.first,
+            })),
+            SnapshotObject::Stream(execution) => Value::Stream(Shared::new(Stream {
+                execution: None,
+                first: execution.first,
+            })),
+            SnapshotObject::Any(..) => Value::Any(Shared::new(AnyObj::new(Pending))),
+        })
+    }
+
+    /// Fill in the value allocated for the given object.
+    fn fill(&self, object: &SnapshotObject, value: Value) -> Result<(), VmError> {
+        match (object, value) {
+            (SnapshotObject::String(..), _) | (SnapshotObject::Bytes(..), _) => (),
+            (SnapshotObject::UnitStruct(..), _) | (SnapshotObject::UnitVariant(..), _) => (),
+            (SnapshotObject::Vec(values), Value::Vec(vec)) => {
+                *vec.borrow_mut()? = Vec::from(self.values(values)?);
+            }
+            (SnapshotObject::Tuple(values), Value::Tuple(tuple)) => {
+                *tuple.borrow_mut()? = Tuple::from(self.values(values)?);
+            }
+            (SnapshotObject::Object(entries), Value::Object(object)) => {
+                *object.borrow_mut()? = self.object(entries)?;
+            }
+            (SnapshotObject::Range(start, end, _), Value::Range(range)) => {
+                let mut range = range.borrow_mut()?;
+                range.start = self.option(start)?;
+                range.end = self.option(end)?;
+            }
+            (SnapshotObject::Option(value), Value::Option(option)) => {
+                *option.borrow_mut()? = self.option(value)?;
+            }
+            (SnapshotObject::Result(value), Value::Result(result)) => {
+                *result.borrow_mut()? = match value {
+                    Ok(value) => Ok(self.value(value)?),
+                    Err(value) => Err(self.value(value)?),
+                };
+            }
+            (SnapshotObject::GeneratorState(yielded, value), Value::GeneratorState(state)) => {
+                let value = self.value(value)?;
+
+                *state.borrow_mut()? = if *yielded {
+                    GeneratorState::Yielded(value)
+                } else {
+                    GeneratorState::Complete(value)
+                };
+            }
+            (SnapshotObject::TupleStruct(_, values), Value::TupleStruct(tuple_struct)) => {
+                tuple_struct.borrow_mut()?.data = Tuple::from(self.values(values)?);
+            }
+            (SnapshotObject::Struct(_, entries), Value::Struct(object)) => {
+                object.borrow_mut()?.data = self.object(entries)?;
+            }
+            (SnapshotObject::TupleVariant(_, values), Value::TupleVariant(variant)) => {
+                variant.borrow_mut()?.data = Tuple::from(self.values(values)?);
+            }
+            (SnapshotObject::StructVariant(_, entries), Value::StructVariant(variant)) => {
+                variant.borrow_mut()?.data = self.object(entries)?;
+            }
+            (SnapshotObject::Function(_, environment), Value::Function(function)) => {
+                let environment = match environment {
+                    Some(environment) => environment,
+                    None => return Ok(()),
+                };
+
+                let mut function = function.borrow_mut()?;
+
+                let out = function
+                    .environment_mut()
+                    .ok_or_else(|| VmErrorKind::InvalidSnapshot)?;
+
+                for (out, value) in out.iter_mut().zip(environment) {
+                    *out = self.value(value)?;
+                }
+            }
+            (SnapshotObject::Generator(execution), Value::Generator(generator)) => {
+                generator.borrow_mut()?.execution = self.execution(execution)?;
+            }
+            (SnapshotObject::Stream(execution), Value::Stream(stream)) => {
+                stream.borrow_mut()?.execution = self.execution(execution)?;
+            }
+            (SnapshotObject::Any(hash, value), Value::Any(any)) => {
+                let snapshot = self
+                    .context
+                    .lookup_snapshot(*hash)
+                    .ok_or_else(|| VmErrorKind::InvalidSnapshot)?;
+
+                *any.borrow_mut()? = (snapshot.restore)(self.value(value)?)?;
+            }
+            _ => return Err(VmError::from(VmErrorKind::InvalidSnapshot)),
+        }
+
+        Ok(())
+    }
+
+    fn rtti(&self, hash: Hash) -> Result<Arc<crate::Rtti>, VmError> {
+        Ok(self
+            .unit
+            .lookup_rtti(hash)
+            .ok_or_else(|| VmErrorKind::MissingRtti { hash })?
+            .clone())
+    }
+
+    fn variant_rtti(&self, hash: Hash) -> Result<Arc<crate::VariantRtti>, VmError> {
+        Ok(self
+            .unit
+            .lookup_variant_rtti(hash)
+            .ok_or_else(|| VmErrorKind::MissingVariantRtti { hash })?
+            .clone())
+    }
+}
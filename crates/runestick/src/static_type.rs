@@ -69,14 +69,49 @@ pub static INTEGER_TYPE: &StaticType = &StaticType {
 };
 
 impl_static_type!(i8 => INTEGER_TYPE);
-impl_static_type!(u16 => INTEGER_TYPE);
 impl_static_type!(i16 => INTEGER_TYPE);
-impl_static_type!(u32 => INTEGER_TYPE);
-impl_static_type!(i32 => INTEGER_TYPE);
-impl_static_type!(u64 => INTEGER_TYPE);
 impl_static_type!(i64 => INTEGER_TYPE);
 impl_static_type!(u128 => INTEGER_TYPE);
-impl_static_type!(i128 => INTEGER_TYPE);
+
+/// The specialized type information for an unsigned 16-bit integer type.
+pub static U16_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("u16"),
+    hash: Hash::new(0x6a4e0e3b8c2e5f71),
+};
+
+impl_static_type!(u16 => U16_TYPE);
+
+/// The specialized type information for an unsigned 32-bit integer type.
+pub static U32_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("u32"),
+    hash: Hash::new(0x2f9b1c4d7e3a8065),
+};
+
+impl_static_type!(u32 => U32_TYPE);
+
+/// The specialized type information for an unsigned 64-bit integer type.
+pub static U64_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("u64"),
+    hash: Hash::new(0xd3c86a1f0b947e2d),
+};
+
+impl_static_type!(u64 => U64_TYPE);
+
+/// The specialized type information for a signed 32-bit integer type.
+pub static I32_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("i32"),
+    hash: Hash::new(0x8e17f5a2c6d04b93),
+};
+
+impl_static_type!(i32 => I32_TYPE);
+
+/// The specialized type information for a signed 128-bit integer type.
+pub static I128_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("i128"),
+    hash: Hash::new(0x4b72d9e8a1f365c0),
+};
+
+impl_static_type!(i128 => I128_TYPE);
 
 /// The specialized type information for a float type.
 pub static FLOAT_TYPE: &StaticType = &StaticType {
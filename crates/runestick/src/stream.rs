@@ -7,8 +7,8 @@ use std::mem;
 
 /// A stream with a stored virtual machine.
 pub struct Stream {
-    execution: Option<VmExecution>,
-    first: bool,
+    pub(crate) execution: Option<VmExecution>,
+    pub(crate) first: bool,
 }
 
 impl Stream {
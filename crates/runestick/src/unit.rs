@@ -9,7 +9,10 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::fmt::Write as _;
+use std::hash::Hasher as _;
 use std::sync::Arc;
+use twox_hash::XxHash64;
 
 /// Instructions from a single source file.
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -66,6 +69,34 @@ impl Unit {
         }
     }
 
+    /// Calculate a fingerprint of the instructions and constants in the unit.
+    ///
+    /// This is stored in snapshots, so that they can't be restored with a
+    /// different unit than they were taken with.
+    pub(crate) fn fingerprint(&self) -> Hash {
+        let mut functions = self.functions.iter().collect::<Vec<_>>();
+        functions.sort_by_key(|(hash, _)| **hash);
+
+        let mut constants = self.constants.iter().collect::<Vec<_>>();
+        constants.sort_by_key(|(hash, _)| **hash);
+
+        let mut fingerprint = Fingerprint(XxHash64::default());
+
+        // NB: writing to the fingerprint can't fail.
+        let _ = write!(
+            fingerprint,
+            "{:?}{:?}{:?}{:?}{:?}{:?}",
+            self.instructions,
+            functions,
+            self.static_strings,
+            self.static_bytes,
+            self.static_object_keys,
+            constants,
+        );
+
+        Hash::new(fingerprint.0.finish())
+    }
+
     /// Access debug information for the given location if it is available.
     pub fn debug_info(&self) -> Option<&DebugInfo> {
         let debug = self.debug.as_ref()?;
@@ -145,6 +176,16 @@ impl Unit {
     }
 }
 
+/// Adapter to hash formatted values.
+struct Fingerprint(XxHash64);
+
+impl fmt::Write for Fingerprint {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write(s.as_bytes());
+        Ok(())
+    }
+}
+
 /// The kind and necessary information on registered functions.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum UnitFn {
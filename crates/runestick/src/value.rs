@@ -1,10 +1,11 @@
 use crate::access::AccessKind;
 use crate::{
-    Any, AnyObj, Bytes, Format, Function, Future, Generator, GeneratorState, Hash, Item, Iterator,
-    Mut, Object, Range, RawMut, RawRef, Ref, Shared, StaticString, Stream, Tuple, TypeInfo, Vec,
-    VmError, VmErrorKind,
+    Any, AnyObj, Bytes, Format, Function, Future, Generator, GeneratorState, Hash, Interface, Item,
+    Iterator, Mut, Object, Range, RawMut, RawRef, Ref, Shared, StaticString, Stream, Tuple,
+    TypeInfo, Vec, VmError, VmErrorKind,
 };
 use serde::{de, ser, Deserialize, Serialize};
+use std::cmp;
 use std::fmt;
 use std::sync::Arc;
 use std::vec;
@@ -296,6 +297,18 @@ pub enum Value {
     Char(char),
     /// A number.
     Integer(i64),
+    /// An unsigned 16-bit integer.
+    U16(u16),
+    /// An unsigned 32-bit integer.
+    U32(u32),
+    /// An unsigned 64-bit integer.
+    U64(u64),
+    /// A signed 32-bit integer.
+    I32(i32),
+    /// A signed 128-bit integer.
+    ///
+    /// This is boxed to ensure that a stack value is 16 bytes in size.
+    I128(Box<i128>),
     /// A float.
     Float(f64),
     /// A type hash. Describes a type in the virtual machine.
@@ -400,6 +413,11 @@ impl Value {
             Self::Byte(value) => Self::Byte(value),
             Self::Char(value) => Self::Char(value),
             Self::Integer(value) => Self::Integer(value),
+            Self::U16(value) => Self::U16(value),
+            Self::U32(value) => Self::U32(value),
+            Self::U64(value) => Self::U64(value),
+            Self::I32(value) => Self::I32(value),
+            Self::I128(value) => Self::I128(value),
             Self::Float(value) => Self::Float(value),
             Self::Type(value) => Self::Type(value),
             Self::StaticString(value) => Self::StaticString(value),
@@ -482,6 +500,23 @@ impl Value {
         }
     }
 
+    /// Try to coerce any integer value into a 128-bit integer.
+    ///
+    /// This is used to losslessly convert between the different integer types
+    /// supported by the virtual machine.
+    #[inline]
+    pub fn into_wide_integer(self) -> Result<i128, VmError> {
+        match self {
+            Self::Integer(integer) => Ok(i128::from(integer)),
+            Self::U16(integer) => Ok(i128::from(integer)),
+            Self::U32(integer) => Ok(i128::from(integer)),
+            Self::U64(integer) => Ok(i128::from(integer)),
+            Self::I32(integer) => Ok(i128::from(integer)),
+            Self::I128(integer) => Ok(*integer),
+            actual => Err(VmError::expected::<i64>(actual.type_info()?)),
+        }
+    }
+
     /// Try to coerce value into a float.
     #[inline]
     pub fn into_float(self) -> Result<f64, VmError> {
@@ -693,6 +728,11 @@ impl Value {
             Self::Byte(..) => crate::BYTE_TYPE.hash,
             Self::Char(..) => crate::CHAR_TYPE.hash,
             Self::Integer(..) => crate::INTEGER_TYPE.hash,
+            Self::U16(..) => crate::U16_TYPE.hash,
+            Self::U32(..) => crate::U32_TYPE.hash,
+            Self::U64(..) => crate::U64_TYPE.hash,
+            Self::I32(..) => crate::I32_TYPE.hash,
+            Self::I128(..) => crate::I128_TYPE.hash,
             Self::Float(..) => crate::FLOAT_TYPE.hash,
             Self::StaticString(..) => crate::STRING_TYPE.hash,
             Self::String(..) => crate::STRING_TYPE.hash,
@@ -729,6 +769,11 @@ impl Value {
             Self::Byte(..) => TypeInfo::StaticType(crate::BYTE_TYPE),
             Self::Char(..) => TypeInfo::StaticType(crate::CHAR_TYPE),
             Self::Integer(..) => TypeInfo::StaticType(crate::INTEGER_TYPE),
+            Self::U16(..) => TypeInfo::StaticType(crate::U16_TYPE),
+            Self::U32(..) => TypeInfo::StaticType(crate::U32_TYPE),
+            Self::U64(..) => TypeInfo::StaticType(crate::U64_TYPE),
+            Self::I32(..) => TypeInfo::StaticType(crate::I32_TYPE),
+            Self::I128(..) => TypeInfo::StaticType(crate::I128_TYPE),
             Self::Float(..) => TypeInfo::StaticType(crate::FLOAT_TYPE),
             Self::StaticString(..) => TypeInfo::StaticType(crate::STRING_TYPE),
             Self::String(..) => TypeInfo::StaticType(crate::STRING_TYPE),
@@ -757,17 +802,46 @@ impl Value {
         })
     }
 
+    /// Test if the given value is of a kind which can implement the
+    /// comparison protocols.
+    pub(crate) fn is_protocol_comparable(&self) -> bool {
+        matches!(
+            self,
+            Self::UnitStruct(..)
+                | Self::TupleStruct(..)
+                | Self::Struct(..)
+                | Self::UnitVariant(..)
+                | Self::TupleVariant(..)
+                | Self::StructVariant(..)
+                | Self::Any(..)
+        )
+    }
+
     /// Optimized function to test if two value pointers are deeply equal to
     /// each other.
     ///
-    /// This is the basis for the eq operation (`==`).
+    /// This is the basis for the eq operation (`==`). Values which implement
+    /// the [PARTIAL_EQ][crate::Protocol::PARTIAL_EQ] or
+    /// [EQ][crate::Protocol::EQ] protocol are compared with it when called
+    /// from a virtual machine, including when they're nested in other values.
     pub(crate) fn value_ptr_eq(a: &Value, b: &Value) -> Result<bool, VmError> {
+        if a.is_protocol_comparable() {
+            if let Some(eq) = Interface::protocol_eq(a, b)? {
+                return Ok(eq);
+            }
+        }
+
         match (a, b) {
             (Self::Unit, Self::Unit) => return Ok(true),
             (Self::Bool(a), Self::Bool(b)) => return Ok(a == b),
             (Self::Byte(a), Self::Byte(b)) => return Ok(a == b),
             (Self::Char(a), Self::Char(b)) => return Ok(a == b),
             (Self::Integer(a), Self::Integer(b)) => return Ok(a == b),
+            (Self::U16(a), Self::U16(b)) => return Ok(a == b),
+            (Self::U32(a), Self::U32(b)) => return Ok(a == b),
+            (Self::U64(a), Self::U64(b)) => return Ok(a == b),
+            (Self::I32(a), Self::I32(b)) => return Ok(a == b),
+            (Self::I128(a), Self::I128(b)) => return Ok(a == b),
             (Self::Float(a), Self::Float(b)) => return Ok(a == b),
             (Self::Vec(a), Self::Vec(b)) => {
                 let a = a.borrow_ref()?;
@@ -878,6 +952,90 @@ impl Value {
             rhs: b.type_info()?,
         }))
     }
+
+    /// Compare two values using their built-in ordering.
+    ///
+    /// Sequences like vectors and tuples are compared lexicographically.
+    /// Values which implement the [PARTIAL_CMP][crate::Protocol::PARTIAL_CMP]
+    /// or [CMP][crate::Protocol::CMP] protocol are compared with it when called
+    /// from a virtual machine. Values of different types, values which aren't
+    /// comparable like `NaN`, and types without an ordering result in an error.
+    pub(crate) fn value_ptr_cmp(a: &Value, b: &Value) -> Result<cmp::Ordering, VmError> {
+        let protocol = if a.is_protocol_comparable() {
+            Interface::protocol_cmp(a, b)?
+        } else {
+            None
+        };
+
+        let ordering = match protocol {
+            Some(ordering) => ordering,
+            None => Self::builtin_cmp(a, b)?,
+        };
+
+        match ordering {
+            Some(ordering) => Ok(ordering),
+            None => Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+                op: "cmp",
+                lhs: a.type_info()?,
+                rhs: b.type_info()?,
+            })),
+        }
+    }
+
+    /// Compare two values using their built-in ordering, returning `None` if
+    /// they don't have one.
+    fn builtin_cmp(a: &Value, b: &Value) -> Result<Option<cmp::Ordering>, VmError> {
+        Ok(match (a, b) {
+            (Self::Unit, Self::Unit) => Some(cmp::Ordering::Equal),
+            (Self::Bool(a), Self::Bool(b)) => Some(a.cmp(b)),
+            (Self::Byte(a), Self::Byte(b)) => Some(a.cmp(b)),
+            (Self::Char(a), Self::Char(b)) => Some(a.cmp(b)),
+            (Self::Integer(a), Self::Integer(b)) => Some(a.cmp(b)),
+            (Self::U16(a), Self::U16(b)) => Some(a.cmp(b)),
+            (Self::U32(a), Self::U32(b)) => Some(a.cmp(b)),
+            (Self::U64(a), Self::U64(b)) => Some(a.cmp(b)),
+            (Self::I32(a), Self::I32(b)) => Some(a.cmp(b)),
+            (Self::I128(a), Self::I128(b)) => Some(a.cmp(b)),
+            (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
+            (Self::Vec(a), Self::Vec(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
+                return Self::sequence_cmp(&*a, &*b).map(Some);
+            }
+            (Self::Tuple(a), Self::Tuple(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
+                return Self::sequence_cmp(&*a, &*b).map(Some);
+            }
+            (Self::String(a), Self::String(b)) => {
+                Some(a.borrow_ref()?.as_str().cmp(b.borrow_ref()?.as_str()))
+            }
+            (Self::StaticString(a), Self::String(b)) => {
+                Some(a.as_str().cmp(b.borrow_ref()?.as_str()))
+            }
+            (Self::String(a), Self::StaticString(b)) => {
+                Some(a.borrow_ref()?.as_str().cmp(b.as_str()))
+            }
+            (Self::StaticString(a), Self::StaticString(b)) => Some(a.as_str().cmp(b.as_str())),
+            (Self::Option(a), Self::Option(b)) => match (&*a.borrow_ref()?, &*b.borrow_ref()?) {
+                (Some(a), Some(b)) => return Self::value_ptr_cmp(a, b).map(Some),
+                (a, b) => Some(a.is_some().cmp(&b.is_some())),
+            },
+            _ => None,
+        })
+    }
+
+    /// Compare two sequences of values lexicographically.
+    fn sequence_cmp(a: &[Value], b: &[Value]) -> Result<cmp::Ordering, VmError> {
+        for (a, b) in a.iter().zip(b.iter()) {
+            match Self::value_ptr_cmp(a, b)? {
+                cmp::Ordering::Equal => (),
+                ordering => return Ok(ordering),
+            }
+        }
+
+        Ok(a.len().cmp(&b.len()))
+    }
 }
 
 impl fmt::Debug for Value {
@@ -898,6 +1056,21 @@ impl fmt::Debug for Value {
             Value::Integer(value) => {
                 write!(f, "{:?}", value)?;
             }
+            Value::U16(value) => {
+                write!(f, "{:?}u16", value)?;
+            }
+            Value::U32(value) => {
+                write!(f, "{:?}u32", value)?;
+            }
+            Value::U64(value) => {
+                write!(f, "{:?}u64", value)?;
+            }
+            Value::I32(value) => {
+                write!(f, "{:?}i32", value)?;
+            }
+            Value::I128(value) => {
+                write!(f, "{:?}i128", value)?;
+            }
             Value::Float(value) => {
                 write!(f, "{:?}", value)?;
             }
@@ -1055,11 +1228,27 @@ impl_from! {
     Bool => bool,
     Char => char,
     Integer => i64,
+    U16 => u16,
+    U32 => u32,
+    U64 => u64,
+    I32 => i32,
     Float => f64,
     Option => Shared<Option<Value>>,
     Result => Shared<Result<Value, Value>>,
 }
 
+impl From<i128> for Value {
+    fn from(value: i128) -> Self {
+        Self::I128(Box::new(value))
+    }
+}
+
+impl crate::ToValue for i128 {
+    fn to_value(self) -> Result<Value, VmError> {
+        Ok(Value::from(self))
+    }
+}
+
 impl_from_wrapper! {
     StaticString => Arc<StaticString>,
     Format => Box<Format>,
@@ -1109,6 +1298,11 @@ impl ser::Serialize for Value {
             Value::Char(c) => serializer.serialize_char(*c),
             Value::Byte(c) => serializer.serialize_u8(*c),
             Value::Integer(integer) => serializer.serialize_i64(*integer),
+            Value::U16(integer) => serializer.serialize_u16(*integer),
+            Value::U32(integer) => serializer.serialize_u32(*integer),
+            Value::U64(integer) => serializer.serialize_u64(*integer),
+            Value::I32(integer) => serializer.serialize_i32(*integer),
+            Value::I128(integer) => serializer.serialize_i128(**integer),
             Value::Float(float) => serializer.serialize_f64(*float),
             Value::StaticString(string) => serializer.serialize_str(string.as_ref()),
             Value::String(string) => {
@@ -1254,7 +1448,12 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::Integer(v as i64))
+        use std::convert::TryFrom as _;
+
+        match i64::try_from(v) {
+            Ok(v) => Ok(Value::Integer(v)),
+            Err(..) => Ok(Value::from(v)),
+        }
     }
 
     #[inline]
@@ -1286,7 +1485,12 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::Integer(v as i64))
+        use std::convert::TryFrom as _;
+
+        match i64::try_from(v) {
+            Ok(v) => Ok(Value::Integer(v)),
+            Err(..) => Ok(Value::U64(v)),
+        }
     }
 
     #[inline]
@@ -1294,7 +1498,16 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::Integer(v as i64))
+        use std::convert::TryFrom as _;
+
+        if let Ok(v) = i64::try_from(v) {
+            return Ok(Value::Integer(v));
+        }
+
+        match i128::try_from(v) {
+            Ok(v) => Ok(Value::from(v)),
+            Err(..) => Err(E::custom("u128 value is out of range")),
+        }
     }
 
     #[inline]
@@ -1,6 +1,6 @@
 use crate::{
-    FromValue, InstallWith, Interface, Mut, Named, RawMut, RawRef, RawStr, Ref, Shared, ToValue,
-    UnsafeFromValue, Value, VmError,
+    limits, FromValue, InstallWith, Interface, Mut, Named, RawMut, RawRef, RawStr, Ref, Shared,
+    ToValue, UnsafeFromValue, Value, VmError,
 };
 use std::cmp;
 use std::fmt;
@@ -129,6 +129,49 @@ impl Vec {
         self.inner.pop()
     }
 
+    /// Inserts an element at the given index, shifting all elements after it
+    /// to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: Value) {
+        self.inner.insert(index, value);
+    }
+
+    /// Removes and returns the element at the given index, shifting all
+    /// elements after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Value {
+        self.inner.remove(index)
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping the
+    /// rest.
+    pub fn truncate(&mut self, len: usize) {
+        self.inner.truncate(len);
+    }
+
+    /// Retains only the elements specified by the predicate.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Value) -> bool,
+    {
+        self.inner.retain(f);
+    }
+
+    /// Removes all but the first of consecutive elements in the vector
+    /// satisfying the given equality relation.
+    pub fn dedup_by<F>(&mut self, mut same: F)
+    where
+        F: FnMut(&Value, &Value) -> bool,
+    {
+        self.inner.dedup_by(|a, b| same(a, b));
+    }
+
     /// Clears the vector, removing all values.
     ///
     /// Note that this method has no effect on the allocated capacity of the
@@ -143,6 +186,7 @@ impl Vec {
         let mut it = interface.into_iter()?;
 
         while let Some(value) = it.next()? {
+            limits::charge_values(1)?;
             self.push(value);
         }
 
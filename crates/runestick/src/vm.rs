@@ -1,16 +1,24 @@
 use crate::budget;
+use crate::coverage::{self, Coverage, VmCoverage};
+use crate::fuel::{self, Fuel, FuelCosts};
 use crate::future::SelectFuture;
+use crate::limits::{self, Limits, Memory};
+use crate::observer::{self, VmObserve, VmObserver};
+use crate::profiler::{self, Profiler, VmProfile};
 use crate::unit::UnitFn;
 use crate::{
     Args, Awaited, BorrowMut, Bytes, Call, Format, FormatSpec, FromValue, Function, Future,
-    Generator, GuardedArgs, Hash, Inst, InstAddress, InstAssignOp, InstFnNameHash, InstOp,
-    InstRangeLimits, InstTarget, InstValue, InstVariant, IntoTypeHash, Object, Panic, Protocol,
-    Range, RangeLimits, RuntimeContext, Select, Shared, Stack, Stream, Struct, StructVariant,
-    Tuple, TypeCheck, Unit, UnitStruct, UnitVariant, Value, Vec, VmError, VmErrorKind, VmExecution,
-    VmHalt, VmIntegerRepr, VmSendExecution,
+    Generator, GuardedArgs, Hash, Inst, InstAddress, InstAssignOp, InstCast, InstFnNameHash,
+    InstOp, InstRangeLimits, InstTarget, InstValue, InstVariant, IntoTypeHash, Object, Panic,
+    Protocol, Range, RangeLimits, RuntimeContext, Select, Shared, Stack, Stream, Struct,
+    StructVariant, Tuple, TypeCheck, Unit, UnitStruct, UnitVariant, Value, Vec, VmError,
+    VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmSendExecution, VmSnapshot,
 };
+use serde::{Deserialize, Serialize};
+use std::cmp;
 use std::fmt;
 use std::mem;
+use std::rc::Rc;
 use std::sync::Arc;
 use std::vec;
 
@@ -66,11 +74,26 @@ pub struct Vm {
     /// Unit associated with virtual machine.
     pub(crate) unit: Arc<Unit>,
     /// The current instruction pointer.
-    ip: usize,
+    pub(crate) ip: usize,
     /// The current stack.
     pub(crate) stack: Stack,
     /// Frames relative to the stack.
-    call_frames: vec::Vec<CallFrame>,
+    pub(crate) call_frames: vec::Vec<CallFrame>,
+    /// The fuel remaining, if fuel metering is enabled.
+    fuel: Option<Fuel>,
+    /// The fuel consumed by each class of instructions.
+    fuel_costs: FuelCosts,
+    /// If running out of fuel halts the virtual machine so that it can be
+    /// resumed, instead of raising an error.
+    fuel_halts: bool,
+    /// The memory and call depth limits.
+    limits: Limits,
+    /// The profiling state, if profiling is enabled.
+    profile: Option<VmProfile>,
+    /// The coverage state, if coverage is enabled.
+    coverage: Option<VmCoverage>,
+    /// The observer state, if an observer is registered.
+    observe: Option<VmObserve>,
 }
 
 impl Vm {
@@ -91,6 +114,16 @@ impl Vm {
             ip: 0,
             stack,
             call_frames: vec::Vec::new(),
+            fuel: None,
+            fuel_costs: FuelCosts::DEFAULT,
+            fuel_halts: true,
+            limits: Limits {
+                memory: None,
+                call_depth: None,
+            },
+            profile: None,
+            coverage: None,
+            observe: None,
         }
     }
 
@@ -155,6 +188,255 @@ impl Vm {
         self.ip
     }
 
+    /// Enable fuel metering with the given amount of fuel, or disable it by
+    /// passing `None`.
+    ///
+    /// When the virtual machine runs out of fuel it halts with
+    /// [VmHalt::OutOfFuel] before executing the next instruction. Using
+    /// [VmExecution::resume] this is reported as a [VmErrorKind::Halted] error,
+    /// after which fuel can be added through [VmExecution::add_fuel] and the
+    /// execution resumed.
+    ///
+    /// Fuel is shared with any virtual machines spawned by this one, like the
+    /// ones used by generators, async functions, and closures called from
+    /// native functions.
+    ///
+    /// Only this virtual machine can halt when it runs out of fuel. Code which
+    /// is run to completion from within an instruction can't be suspended,
+    /// like closures called by native functions such as `Iterator::map`, and
+    /// comparison protocol functions like `eq` and `cmp` implemented in the
+    /// script. If the fuel runs out while such code executes, execution fails
+    /// with [VmErrorKind::OutOfFuel] instead, and can't be resumed. Make sure
+    /// to provide enough fuel for them, or treat the error as the script
+    /// exceeding its budget.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use runestick::{Context, Unit, Vm, VmErrorKind, VmHaltInfo};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> runestick::Result<()> {
+    /// let context = Arc::new(Context::with_default_modules()?.runtime());
+    /// let unit = Arc::new(Unit::default());
+    ///
+    /// let mut vm = Vm::new(context, unit);
+    /// vm.set_fuel(Some(1000));
+    ///
+    /// let mut execution = vm.execute(&["main"], ())?;
+    ///
+    /// let output = loop {
+    ///     match execution.complete() {
+    ///         Ok(output) => break output,
+    ///         Err(e) => match e.kind() {
+    ///             VmErrorKind::Halted { halt: VmHaltInfo::OutOfFuel } => {
+    ///                 execution.add_fuel(1000)?;
+    ///             }
+    ///             _ => return Err(e.into()),
+    ///         },
+    ///     }
+    /// };
+    /// # Ok(()) }
+    /// ```
+    pub fn set_fuel(&mut self, fuel: Option<u64>) {
+        self.fuel = match (self.fuel.take(), fuel) {
+            (Some(current), Some(fuel)) => {
+                current.set(fuel);
+                Some(current)
+            }
+            (None, Some(fuel)) => Some(Fuel::new(fuel)),
+            (_, None) => None,
+        };
+    }
+
+    /// Get the fuel remaining, or `None` if fuel metering isn't enabled.
+    pub fn fuel(&self) -> Option<u64> {
+        self.fuel.as_ref().map(Fuel::remaining)
+    }
+
+    /// Add fuel to the virtual machine.
+    ///
+    /// This does nothing if fuel metering isn't enabled.
+    pub fn add_fuel(&mut self, amount: u64) {
+        if let Some(fuel) = &self.fuel {
+            fuel.add(amount);
+        }
+    }
+
+    /// Set the fuel consumed by each class of instructions. Defaults to
+    /// [FuelCosts::DEFAULT].
+    pub fn set_fuel_costs(&mut self, costs: FuelCosts) {
+        self.fuel_costs = costs;
+    }
+
+    /// Get the fuel consumed by each class of instructions.
+    pub fn fuel_costs(&self) -> FuelCosts {
+        self.fuel_costs
+    }
+
+    /// Limit the memory that can be allocated by the virtual machine to the
+    /// given number of bytes, or disable the limit by passing `None`.
+    ///
+    /// Once the limit is exceeded, execution fails with
+    /// [VmErrorKind::MemoryLimitExceeded]. Memory is charged as it's allocated
+    /// and never credited back, so this is a budget for the total amount of
+    /// memory allocated over the lifetime of the virtual machine rather than a
+    /// limit on the amount in use. Changing the limit keeps the memory charged
+    /// so far.
+    ///
+    /// The limit is shared with any virtual machines spawned by this one, like
+    /// the ones used by generators, async functions, and closures called from
+    /// native functions.
+    pub fn set_memory_limit(&mut self, limit: Option<usize>) {
+        self.limits.memory = match (&self.limits.memory, limit) {
+            (Some(memory), Some(limit)) => Some(memory.with_limit(limit)),
+            (None, Some(limit)) => Some(Memory::new(limit)),
+            (_, None) => None,
+        };
+    }
+
+    /// Get the memory limit, or `None` if it isn't enabled.
+    pub fn memory_limit(&self) -> Option<usize> {
+        self.limits.memory.as_ref().map(Memory::limit)
+    }
+
+    /// Get the memory charged so far, or `None` if the memory limit isn't
+    /// enabled.
+    pub fn memory_used(&self) -> Option<usize> {
+        self.limits.memory.as_ref().map(Memory::used)
+    }
+
+    /// Limit the depth of calls in the virtual machine, or disable the limit
+    /// by passing `None`.
+    ///
+    /// Once the limit is exceeded, execution fails with
+    /// [VmErrorKind::CallDepthExceeded]. Virtual machines executed by native
+    /// functions, like when calling a closure, count towards the depth of the
+    /// virtual machine calling them.
+    pub fn set_call_depth_limit(&mut self, limit: Option<usize>) {
+        self.limits.call_depth = limit;
+    }
+
+    /// Get the call depth limit, or `None` if it isn't enabled.
+    pub fn call_depth_limit(&self) -> Option<usize> {
+        self.limits.call_depth
+    }
+
+    /// Enable profiling by recording into the given profiler, or disable it by
+    /// passing `None`.
+    ///
+    /// The profiler is shared with any virtual machines spawned by this one,
+    /// like the ones used by generators, async functions, and closures called
+    /// from native functions. See the [profiler] module for more information.
+    pub fn set_profiler(&mut self, profiler: Option<Profiler>) {
+        self.profile = profiler.map(VmProfile::new);
+    }
+
+    /// Get the profiler being recorded into, or `None` if profiling isn't
+    /// enabled.
+    pub fn profiler(&self) -> Option<&Profiler> {
+        self.profile.as_ref().map(VmProfile::profiler)
+    }
+
+    /// Enable coverage by recording into the given collector, or disable it by
+    /// passing `None`.
+    ///
+    /// Like the profiler, the collector is shared with any virtual machines
+    /// spawned by this one. See the [coverage] module for more information.
+    pub fn set_coverage(&mut self, coverage: Option<Coverage>) {
+        self.coverage = coverage.map(|c| VmCoverage::new(c, &self.unit));
+    }
+
+    /// Get the coverage collector being recorded into, or `None` if coverage
+    /// isn't enabled.
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref().map(VmCoverage::coverage)
+    }
+
+    /// Register an observer which is notified as the virtual machine
+    /// executes, or remove it by passing `None`.
+    ///
+    /// The observer is shared with any virtual machines spawned by this one.
+    /// It's told that the function being executed is entered when the virtual
+    /// machine next runs, even if it's already partway through it. See
+    /// [VmObserver] for more information.
+    pub fn set_observer(&mut self, observer: Option<Rc<dyn VmObserver>>) {
+        self.observe = observer.map(VmObserve::new);
+    }
+
+    /// Get the registered observer, or `None` if there isn't one.
+    pub fn observer(&self) -> Option<&Rc<dyn VmObserver>> {
+        self.observe.as_ref().map(VmObserve::observer)
+    }
+
+    /// Take a snapshot of the virtual machine, which can be serialized and
+    /// later restored with [Vm::restore], possibly in a different process.
+    ///
+    /// The snapshot includes every value reachable from the stack, and fails
+    /// with [VmErrorKind::UnsupportedSnapshot] if any of them can't be stored.
+    /// See [VmSnapshot] for more information.
+    pub fn snapshot(&self) -> Result<VmSnapshot, VmError> {
+        VmSnapshot::of_vm(self)
+    }
+
+    /// Restore a virtual machine from a snapshot taken with [Vm::snapshot].
+    ///
+    /// The unit must be the same unit that the snapshot was taken with, and
+    /// the context must provide the same native functions. Restoring with a
+    /// different unit fails with [VmErrorKind::InvalidSnapshot].
+    pub fn restore(
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+        snapshot: &VmSnapshot,
+    ) -> Result<Self, VmError> {
+        snapshot.restore_vm(&context, &unit)
+    }
+
+    /// Share the fuel and the limits of the given virtual machine with this
+    /// one.
+    pub(crate) fn inherit(&mut self, parent: &Vm) {
+        self.inherit_fuel(parent);
+        self.inherit_limits(parent);
+        self.profile = parent.profile.as_ref().map(VmProfile::spawn);
+        self.set_coverage(parent.coverage().cloned());
+        self.set_observer(parent.observer().cloned());
+    }
+
+    /// Share the fuel of the given virtual machine with this one.
+    ///
+    /// Running out of fuel raises an error instead of halting, since the
+    /// virtual machine might be executed by something which can't be
+    /// suspended, like a native function. See [Vm::set_fuel_halts].
+    pub(crate) fn inherit_fuel(&mut self, parent: &Vm) {
+        self.fuel = parent.fuel.clone();
+        self.fuel_costs = parent.fuel_costs;
+        self.fuel_halts = false;
+    }
+
+    /// Share the memory and call depth limits of the given virtual machine
+    /// with this one.
+    pub(crate) fn inherit_limits(&mut self, parent: &Vm) {
+        self.limits = parent.limits.clone();
+    }
+
+    /// Notify the observer that the native function with the given hash is
+    /// being called, and record it in the profile until the returned guard is
+    /// dropped.
+    #[inline]
+    pub(crate) fn enter_native(&self, hash: Hash) -> Option<profiler::Guard> {
+        if let Some(observe) = &self.observe {
+            observe.observer().on_native_call(self, hash);
+        }
+
+        self.profile.as_ref().map(|profile| profile.native(hash))
+    }
+
+    /// Set if running out of fuel halts the virtual machine so that it can be
+    /// resumed, instead of raising an error.
+    pub(crate) fn set_fuel_halts(&mut self, fuel_halts: bool) {
+        self.fuel_halts = fuel_halts;
+    }
+
     /// Advance the instruction pointer.
     #[inline]
     pub(crate) fn advance(&mut self) {
@@ -228,11 +510,33 @@ impl Vm {
     /// This is accomplished by preventing values escaping from being
     /// non-exclusively sent with the execution or escaping the execution. We
     /// only support encoding arguments which themselves are `Send`.
+    ///
+    /// A profiler, coverage collector or observer can't be shared across
+    /// threads, so this errors if any of them are attached to the virtual
+    /// machine.
     pub fn send_execute<A, N>(mut self, name: N, args: A) -> Result<VmSendExecution, VmError>
     where
         N: IntoTypeHash,
         A: Send + Args,
     {
+        if self.profile.is_some() {
+            return Err(VmError::from(VmErrorKind::UnsupportedSendExecution {
+                state: "a profiler",
+            }));
+        }
+
+        if self.coverage.is_some() {
+            return Err(VmError::from(VmErrorKind::UnsupportedSendExecution {
+                state: "a coverage collector",
+            }));
+        }
+
+        if self.observe.is_some() {
+            return Err(VmError::from(VmErrorKind::UnsupportedSendExecution {
+                state: "an observer",
+            }));
+        }
+
         // Safety: make sure the stack is clear, preventing any values from
         // being sent along with the virtual machine.
         self.stack.clear();
@@ -394,6 +698,7 @@ impl Vm {
         };
 
         let _guard = crate::interface::EnvGuard::new(&self.context, &self.unit);
+        let _native = self.enter_native(hash);
         handler(&mut self.stack, count)?;
         Ok(true)
     }
@@ -439,14 +744,122 @@ impl Vm {
         };
 
         let _guard = crate::interface::EnvGuard::new(&self.context, &self.unit);
+        let _native = self.enter_native(hash);
         handler(&mut self.stack, count)?;
         Ok(true)
     }
 
+    /// Helper to call a protocol function implemented for the given target and
+    /// return its value immediately.
+    ///
+    /// Returns `None` if the target doesn't implement the protocol.
+    fn call_protocol_fn<A>(
+        &mut self,
+        protocol: Protocol,
+        target: &Value,
+        args: A,
+    ) -> Result<Option<Value>, VmError>
+    where
+        A: Args,
+    {
+        // NB: +1 to include the instance as well.
+        let count = args.count() + 1;
+        let hash = Hash::instance_function(target.type_hash()?, protocol.hash);
+
+        if let Some(UnitFn::Offset {
+            offset,
+            call,
+            args: expected,
+        }) = self.unit.lookup(hash)
+        {
+            Self::check_args(count, expected)?;
+
+            // NB: protocol functions implemented in the unit are run to
+            // completion on the call frames of this virtual machine, so that
+            // they share its fuel, limits and instrumentation.
+            if let Call::Immediate = call {
+                self.stack.push(target.clone());
+                args.into_stack(&mut self.stack)?;
+                return Ok(Some(self.call_isolated(offset, count)?));
+            }
+
+            let mut vm = Vm::new(self.context.clone(), self.unit.clone());
+            vm.set_ip(offset);
+            vm.stack.push(target.clone());
+            args.into_stack(&mut vm.stack)?;
+            return Ok(Some(call.call_with_vm(vm)?));
+        }
+
+        let handler = match self.context.lookup(hash) {
+            Some(handler) => handler,
+            None => return Ok(None),
+        };
+
+        self.stack.push(target.clone());
+        args.into_stack(&mut self.stack)?;
+
+        let _guard = crate::interface::EnvGuard::new(&self.context, &self.unit);
+        let _native = self.enter_native(hash);
+        handler(&mut self.stack, count)?;
+        Ok(Some(self.stack.pop()?))
+    }
+
+    /// Call the unit function at the given offset with `args` arguments from
+    /// the top of the stack, running it to completion on a new call frame
+    /// and returning its value.
+    ///
+    /// The function can't halt the virtual machine, so running out of fuel
+    /// while it's executing raises [VmErrorKind::OutOfFuel] instead of
+    /// halting, since the virtual machine can't be resumed in the middle of an
+    /// instruction.
+    fn call_isolated(&mut self, offset: usize, args: usize) -> Result<Value, VmError> {
+        let ip = self.ip;
+
+        self.push_call_frame(offset, args)?;
+
+        if let Some(frame) = self.call_frames.last_mut() {
+            frame.isolated = true;
+        }
+
+        self.advance();
+        let halt = self.run_loop()?;
+        self.ip = ip;
+
+        match halt {
+            VmHalt::Exited => Ok(self.stack.pop()?),
+            VmHalt::OutOfFuel => Err(VmError::from(VmErrorKind::OutOfFuel)),
+            halt => Err(VmError::from(VmErrorKind::Halted {
+                halt: halt.into_info(),
+            })),
+        }
+    }
+
+    /// Internal implementation of the `==` operation, which consults the
+    /// [Protocol::PARTIAL_EQ] and [Protocol::EQ] protocols before falling back
+    /// to the built-in comparison.
+    fn internal_eq(&mut self, lhs: InstAddress, rhs: InstAddress) -> Result<bool, VmError> {
+        let rhs = self.stack.address(rhs)?;
+        let lhs = self.stack.address(lhs)?;
+
+        if lhs.is_protocol_comparable() {
+            for protocol in &[Protocol::PARTIAL_EQ, Protocol::EQ] {
+                if let Some(value) = self.call_protocol_fn(*protocol, &lhs, (&rhs,))? {
+                    return bool::from_value(value);
+                }
+            }
+        }
+
+        // NB: values nested in the operands are compared with their
+        // protocols through the environment.
+        let _guard = crate::interface::EnvGuard::new(&self.context, &self.unit);
+        Value::value_ptr_eq(&lhs, &rhs)
+    }
+
     fn internal_boolean_ops(
         &mut self,
         int_op: fn(i64, i64) -> bool,
         float_op: fn(f64, f64) -> bool,
+        ordering_op: fn(cmp::Ordering) -> bool,
         op: &'static str,
         lhs: InstAddress,
         rhs: InstAddress,
@@ -458,11 +871,27 @@ impl Vm {
             (Value::Integer(lhs), Value::Integer(rhs)) => int_op(lhs, rhs),
             (Value::Float(lhs), Value::Float(rhs)) => float_op(lhs, rhs),
             (lhs, rhs) => {
-                return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
-                    op,
-                    lhs: lhs.type_info()?,
-                    rhs: rhs.type_info()?,
-                }))
+                if let Some(ordering) = sized_integer_cmp(&lhs, &rhs) {
+                    self.stack.push(ordering_op(ordering));
+                    return Ok(());
+                }
+
+                let ordering = if lhs.is_protocol_comparable() {
+                    self.internal_protocol_cmp(&lhs, &rhs)?
+                } else {
+                    None
+                };
+
+                match ordering {
+                    Some(ordering) => ordering.map(ordering_op).unwrap_or_default(),
+                    None => {
+                        return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+                            op,
+                            lhs: lhs.type_info()?,
+                            rhs: rhs.type_info()?,
+                        }))
+                    }
+                }
             }
         };
 
@@ -470,23 +899,56 @@ impl Vm {
         Ok(())
     }
 
+    /// Compare two values using the [Protocol::PARTIAL_CMP] or [Protocol::CMP]
+    /// protocols.
+    ///
+    /// Returns `None` if neither protocol is implemented for `lhs`.
+    fn internal_protocol_cmp(
+        &mut self,
+        lhs: &Value,
+        rhs: &Value,
+    ) -> Result<Option<Option<cmp::Ordering>>, VmError> {
+        if let Some(value) = self.call_protocol_fn(Protocol::PARTIAL_CMP, lhs, (rhs,))? {
+            return Ok(Some(<Option<cmp::Ordering>>::from_value(value)?));
+        }
+
+        if let Some(value) = self.call_protocol_fn(Protocol::CMP, lhs, (rhs,))? {
+            return Ok(Some(Some(cmp::Ordering::from_value(value)?)));
+        }
+
+        Ok(None)
+    }
+
     /// Push a new call frame.
     ///
     /// This will cause the `args` number of elements on the stack to be
     /// associated and accessible to the new call frame.
     pub(crate) fn push_call_frame(&mut self, ip: usize, args: usize) -> Result<(), VmError> {
+        if let Some(limit) = self.limits.call_depth {
+            if self.call_frames.len() + limits::nesting() >= limit {
+                return Err(VmError::from(VmErrorKind::CallDepthExceeded { limit }));
+            }
+        }
+
         let stack_top = self.stack.swap_stack_bottom(args)?;
 
         self.call_frames.push(CallFrame {
             ip: self.ip,
             stack_bottom: stack_top,
+            isolated: false,
         });
 
         self.ip = ip.overflowing_sub(1).0;
+
+        if let Some(observe) = &self.observe {
+            observe.observer().on_call(self, ip);
+        }
+
         Ok(())
     }
 
-    /// Pop a call frame and return it.
+    /// Pop a call frame, returning `true` if execution should halt because
+    /// there are no more call frames or the frame was isolated.
     fn pop_call_frame(&mut self) -> Result<bool, VmError> {
         let frame = match self.call_frames.pop() {
             Some(frame) => frame,
@@ -498,7 +960,7 @@ impl Vm {
 
         self.stack.pop_stack_top(frame.stack_bottom)?;
         self.ip = frame.ip;
-        Ok(false)
+        Ok(frame.isolated)
     }
 
     /// Try to convert the given value into a future.
@@ -1058,6 +1520,7 @@ impl Vm {
     fn call_generator_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
         let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
+        vm.inherit(self);
         vm.ip = offset;
         self.stack.push(Generator::new(vm));
         Ok(())
@@ -1067,6 +1530,7 @@ impl Vm {
     fn call_stream_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
         let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
+        vm.inherit(self);
         vm.ip = offset;
         self.stack.push(Stream::new(vm));
         Ok(())
@@ -1076,6 +1540,7 @@ impl Vm {
     fn call_async_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
         let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
+        vm.inherit(self);
         vm.ip = offset;
         self.stack.push(Future::new(vm.async_complete()));
         Ok(())
@@ -1108,6 +1573,7 @@ impl Vm {
 
     fn internal_num_assign(
         &mut self,
+        op: InstOp,
         target: InstTarget,
         protocol: Protocol,
         error: fn() -> VmErrorKind,
@@ -1129,7 +1595,13 @@ impl Vm {
                     *lhs = out;
                     return Ok(());
                 }
-                (lhs, rhs) => TargetFallback::Value(lhs.clone(), rhs),
+                (lhs, rhs) => match sized_integer_op(op, lhs, &rhs)? {
+                    Some(value) => {
+                        *lhs = value;
+                        return Ok(());
+                    }
+                    None => TargetFallback::Value(lhs.clone(), rhs),
+                },
             },
             TargetValue::Fallback(fallback) => fallback,
         };
@@ -1145,15 +1617,17 @@ impl Vm {
     ) -> Result<(), VmError> {
         match fallback {
             TargetFallback::Value(lhs, rhs) => {
-                if !self.call_instance_fn(&lhs, protocol, (&rhs,))? {
-                    return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
-                        op: protocol.name,
-                        lhs: lhs.type_info()?,
-                        rhs: rhs.type_info()?,
-                    }));
-                }
+                let value = match self.call_protocol_fn(protocol, &lhs, (&rhs,))? {
+                    Some(value) => value,
+                    None => {
+                        return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+                            op: protocol.name,
+                            lhs: lhs.type_info()?,
+                            rhs: rhs.type_info()?,
+                        }));
+                    }
+                };
 
-                let value = self.stack.pop()?;
                 <()>::from_value(value)?;
                 Ok(())
             }
@@ -1177,10 +1651,14 @@ impl Vm {
     }
 
     /// Internal impl of a numeric operation.
+    ///
+    /// If the integer operation fails, `error` is called with the right-hand
+    /// operand to construct the error to raise.
     fn internal_num(
         &mut self,
+        op: InstOp,
         protocol: Protocol,
-        error: fn() -> VmErrorKind,
+        error: fn(i64) -> VmErrorKind,
         integer_op: fn(i64, i64) -> Option<i64>,
         float_op: fn(f64, f64) -> f64,
         lhs: InstAddress,
@@ -1191,14 +1669,22 @@ impl Vm {
 
         let (lhs, rhs) = match (lhs, rhs) {
             (Value::Integer(lhs), Value::Integer(rhs)) => {
-                self.stack.push(integer_op(lhs, rhs).ok_or_else(error)?);
+                self.stack
+                    .push(integer_op(lhs, rhs).ok_or_else(|| error(rhs))?);
                 return Ok(());
             }
             (Value::Float(lhs), Value::Float(rhs)) => {
                 self.stack.push(float_op(lhs, rhs));
                 return Ok(());
             }
-            (lhs, rhs) => (lhs, rhs),
+            (lhs, rhs) => {
+                if let Some(value) = sized_integer_op(op, &lhs, &rhs)? {
+                    self.stack.push(value);
+                    return Ok(());
+                }
+
+                (lhs, rhs)
+            }
         };
 
         if !self.call_instance_fn(&lhs, protocol, (&rhs,))? {
@@ -1215,6 +1701,7 @@ impl Vm {
     /// Internal impl of a numeric operation.
     fn internal_infallible_bitwise(
         &mut self,
+        op: InstOp,
         protocol: Protocol,
         integer_op: fn(i64, i64) -> i64,
         lhs: InstAddress,
@@ -1228,7 +1715,14 @@ impl Vm {
                 self.stack.push(integer_op(lhs, rhs));
                 return Ok(());
             }
-            (lhs, rhs) => (lhs, rhs),
+            (lhs, rhs) => {
+                if let Some(value) = sized_integer_op(op, &lhs, &rhs)? {
+                    self.stack.push(value);
+                    return Ok(());
+                }
+
+                (lhs, rhs)
+            }
         };
 
         if !self.call_instance_fn(&lhs, protocol, (&rhs,))? {
@@ -1245,6 +1739,7 @@ impl Vm {
     /// Internal impl of a numeric operation.
     fn internal_infallible_bitwise_bool(
         &mut self,
+        op: InstOp,
         protocol: Protocol,
         integer_op: fn(i64, i64) -> i64,
         bool_op: fn(bool, bool) -> bool,
@@ -1263,7 +1758,14 @@ impl Vm {
                 self.stack.push(bool_op(lhs, rhs));
                 return Ok(());
             }
-            (lhs, rhs) => (lhs, rhs),
+            (lhs, rhs) => {
+                if let Some(value) = sized_integer_op(op, &lhs, &rhs)? {
+                    self.stack.push(value);
+                    return Ok(());
+                }
+
+                (lhs, rhs)
+            }
         };
 
         if !self.call_instance_fn(&lhs, protocol, (&rhs,))? {
@@ -1279,6 +1781,7 @@ impl Vm {
 
     fn internal_infallible_bitwise_assign(
         &mut self,
+        op: InstOp,
         target: InstTarget,
         protocol: Protocol,
         integer_op: fn(&mut i64, i64),
@@ -1292,7 +1795,13 @@ impl Vm {
                     integer_op(lhs, rhs);
                     return Ok(());
                 }
-                (lhs, rhs) => TargetFallback::Value(lhs.clone(), rhs),
+                (lhs, rhs) => match sized_integer_op(op, lhs, &rhs)? {
+                    Some(value) => {
+                        *lhs = value;
+                        return Ok(());
+                    }
+                    None => TargetFallback::Value(lhs.clone(), rhs),
+                },
             },
             TargetValue::Fallback(fallback) => fallback,
         };
@@ -1302,6 +1811,7 @@ impl Vm {
 
     fn internal_bitwise(
         &mut self,
+        op: InstOp,
         protocol: Protocol,
         error: fn() -> VmErrorKind,
         integer_op: fn(i64, i64) -> Option<i64>,
@@ -1316,7 +1826,14 @@ impl Vm {
                 self.stack.push(integer_op(lhs, rhs).ok_or_else(error)?);
                 return Ok(());
             }
-            (lhs, rhs) => (lhs, rhs),
+            (lhs, rhs) => {
+                if let Some(value) = sized_integer_op(op, &lhs, &rhs)? {
+                    self.stack.push(value);
+                    return Ok(());
+                }
+
+                (lhs, rhs)
+            }
         };
 
         if !self.call_instance_fn(&lhs, protocol, (&rhs,))? {
@@ -1332,6 +1849,7 @@ impl Vm {
 
     fn internal_bitwise_assign(
         &mut self,
+        op: InstOp,
         target: InstTarget,
         protocol: Protocol,
         error: fn() -> VmErrorKind,
@@ -1347,7 +1865,13 @@ impl Vm {
                     *lhs = out;
                     return Ok(());
                 }
-                (lhs, rhs) => TargetFallback::Value(lhs.clone(), rhs),
+                (lhs, rhs) => match sized_integer_op(op, lhs, &rhs)? {
+                    Some(value) => {
+                        *lhs = value;
+                        return Ok(());
+                    }
+                    None => TargetFallback::Value(lhs.clone(), rhs),
+                },
             },
             TargetValue::Fallback(fallback) => fallback,
         };
@@ -1551,6 +2075,7 @@ impl Vm {
     /// Construct a new vec.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_vec(&mut self, count: usize) -> Result<(), VmError> {
+        limits::charge_values(count)?;
         let vec = Vec::from(self.stack.pop_sequence(count)?);
         self.stack.push(Shared::new(vec));
         Ok(())
@@ -1559,6 +2084,7 @@ impl Vm {
     /// Construct a new tuple.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_tuple(&mut self, count: usize) -> Result<(), VmError> {
+        limits::charge_values(count)?;
         let tuple = self.stack.pop_sequence(count)?;
         self.stack.push(Tuple::from(tuple));
         Ok(())
@@ -1567,6 +2093,7 @@ impl Vm {
     /// Construct a new tuple with a fixed number of arguments.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_tuple_n(&mut self, args: &[InstAddress]) -> Result<(), VmError> {
+        limits::charge_values(args.len())?;
         let mut tuple = vec![Value::Unit; args.len()];
 
         for (n, arg) in args.iter().enumerate().rev() {
@@ -1592,6 +2119,11 @@ impl Vm {
         let value = match value {
             Value::Bool(value) => Value::from(!value),
             Value::Integer(value) => Value::from(!value),
+            Value::U16(value) => Value::from(!value),
+            Value::U32(value) => Value::from(!value),
+            Value::U64(value) => Value::from(!value),
+            Value::I32(value) => Value::from(!value),
+            Value::I128(value) => Value::from(!*value),
             other => {
                 let operand = other.type_info()?;
                 return Err(VmError::from(VmErrorKind::UnsupportedUnaryOperation {
@@ -1612,6 +2144,8 @@ impl Vm {
         let value = match value {
             Value::Float(value) => Value::from(-value),
             Value::Integer(value) => Value::from(-value),
+            Value::I32(value) => Value::from(value.checked_neg().ok_or(VmErrorKind::Overflow)?),
+            Value::I128(value) => Value::from(value.checked_neg().ok_or(VmErrorKind::Overflow)?),
             other => {
                 let operand = other.type_info()?;
                 return Err(VmError::from(VmErrorKind::UnsupportedUnaryOperation {
@@ -1625,6 +2159,44 @@ impl Vm {
         Ok(())
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_cast(&mut self, ty: InstCast) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        let operand = match (ty, &value) {
+            (InstCast::Float, Value::Float(n)) => Some(Some(Value::Float(*n))),
+            (InstCast::Float, Value::Bool(..))
+            | (InstCast::Float, Value::Char(..))
+            | (InstCast::Char, Value::Bool(..))
+            | (InstCast::Char, Value::Float(..)) => None,
+            (ty, Value::Float(n)) => {
+                Some(InstCast::float_to_integer(*n).and_then(|n| cast_integer(ty, n)))
+            }
+            (ty, value) => cast_operand(value).map(|n| cast_integer(ty, n)),
+        };
+
+        let out = match operand {
+            Some(out) => out,
+            None => {
+                return Err(VmError::from(VmErrorKind::UnsupportedCast {
+                    from: value.type_info()?,
+                    to: ty,
+                }));
+            }
+        };
+
+        match out {
+            Some(out) => {
+                self.stack.push(out);
+                Ok(())
+            }
+            None => Err(VmError::from(VmErrorKind::CastOutOfRange {
+                from: value.type_info()?,
+                to: ty,
+            })),
+        }
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_op(&mut self, op: InstOp, lhs: InstAddress, rhs: InstAddress) -> Result<(), VmError> {
         use std::convert::TryFrom as _;
@@ -1632,8 +2204,9 @@ impl Vm {
         match op {
             InstOp::Add => {
                 self.internal_num(
+                    op,
                     Protocol::ADD,
-                    || VmErrorKind::Overflow,
+                    |_| VmErrorKind::Overflow,
                     i64::checked_add,
                     std::ops::Add::add,
                     lhs,
@@ -1642,8 +2215,9 @@ impl Vm {
             }
             InstOp::Sub => {
                 self.internal_num(
+                    op,
                     Protocol::SUB,
-                    || VmErrorKind::Underflow,
+                    |_| VmErrorKind::Underflow,
                     i64::checked_sub,
                     std::ops::Sub::sub,
                     lhs,
@@ -1652,8 +2226,9 @@ impl Vm {
             }
             InstOp::Mul => {
                 self.internal_num(
+                    op,
                     Protocol::MUL,
-                    || VmErrorKind::Overflow,
+                    |_| VmErrorKind::Overflow,
                     i64::checked_mul,
                     std::ops::Mul::mul,
                     lhs,
@@ -1662,8 +2237,9 @@ impl Vm {
             }
             InstOp::Div => {
                 self.internal_num(
+                    op,
                     Protocol::DIV,
-                    || VmErrorKind::DivideByZero,
+                    |_| VmErrorKind::DivideByZero,
                     i64::checked_div,
                     std::ops::Div::div,
                     lhs,
@@ -1672,17 +2248,30 @@ impl Vm {
             }
             InstOp::Rem => {
                 self.internal_num(
+                    op,
                     Protocol::REM,
-                    || VmErrorKind::DivideByZero,
+                    |_| VmErrorKind::DivideByZero,
                     i64::checked_rem,
                     std::ops::Rem::rem,
                     lhs,
                     rhs,
                 )?;
             }
+            InstOp::Pow => {
+                self.internal_num(
+                    op,
+                    Protocol::POW,
+                    pow_error,
+                    |a, b| a.checked_pow(u32::try_from(b).ok()?),
+                    f64::powf,
+                    lhs,
+                    rhs,
+                )?;
+            }
             InstOp::BitAnd => {
                 use std::ops::BitAnd as _;
                 self.internal_infallible_bitwise_bool(
+                    op,
                     Protocol::BIT_AND,
                     i64::bitand,
                     bool::bitand,
@@ -1693,6 +2282,7 @@ impl Vm {
             InstOp::BitXor => {
                 use std::ops::BitXor as _;
                 self.internal_infallible_bitwise_bool(
+                    op,
                     Protocol::BIT_XOR,
                     i64::bitxor,
                     bool::bitxor,
@@ -1703,6 +2293,7 @@ impl Vm {
             InstOp::BitOr => {
                 use std::ops::BitOr as _;
                 self.internal_infallible_bitwise_bool(
+                    op,
                     Protocol::BIT_OR,
                     i64::bitor,
                     bool::bitor,
@@ -1712,6 +2303,7 @@ impl Vm {
             }
             InstOp::Shl => {
                 self.internal_bitwise(
+                    op,
                     Protocol::SHL,
                     || VmErrorKind::Overflow,
                     |a, b| a.checked_shl(u32::try_from(b).ok()?),
@@ -1720,30 +2312,54 @@ impl Vm {
                 )?;
             }
             InstOp::Shr => {
-                self.internal_infallible_bitwise(Protocol::SHR, std::ops::Shr::shr, lhs, rhs)?;
+                self.internal_infallible_bitwise(op, Protocol::SHR, std::ops::Shr::shr, lhs, rhs)?;
             }
             InstOp::Gt => {
-                self.internal_boolean_ops(|a, b| a > b, |a, b| a > b, ">", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a > b,
+                    |a, b| a > b,
+                    cmp::Ordering::is_gt,
+                    ">",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Gte => {
-                self.internal_boolean_ops(|a, b| a >= b, |a, b| a >= b, ">=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a >= b,
+                    |a, b| a >= b,
+                    cmp::Ordering::is_ge,
+                    ">=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lt => {
-                self.internal_boolean_ops(|a, b| a < b, |a, b| a < b, "<", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a < b,
+                    |a, b| a < b,
+                    cmp::Ordering::is_lt,
+                    "<",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lte => {
-                self.internal_boolean_ops(|a, b| a <= b, |a, b| a <= b, "<=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a <= b,
+                    |a, b| a <= b,
+                    cmp::Ordering::is_le,
+                    "<=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Eq => {
-                let rhs = self.stack.address(rhs)?;
-                let lhs = self.stack.address(lhs)?;
-                let test = Value::value_ptr_eq(&lhs, &rhs)?;
+                let test = self.internal_eq(lhs, rhs)?;
                 self.stack.push(test);
             }
             InstOp::Neq => {
-                let rhs = self.stack.address(rhs)?;
-                let lhs = self.stack.address(lhs)?;
-                let test = Value::value_ptr_eq(&lhs, &rhs)?;
+                let test = self.internal_eq(lhs, rhs)?;
                 self.stack.push(!test);
             }
             InstOp::And => {
@@ -1772,6 +2388,7 @@ impl Vm {
         match op {
             InstAssignOp::Add => {
                 self.internal_num_assign(
+                    InstOp::Add,
                     target,
                     Protocol::ADD_ASSIGN,
                     || VmErrorKind::Overflow,
@@ -1781,6 +2398,7 @@ impl Vm {
             }
             InstAssignOp::Sub => {
                 self.internal_num_assign(
+                    InstOp::Sub,
                     target,
                     Protocol::SUB_ASSIGN,
                     || VmErrorKind::Underflow,
@@ -1790,6 +2408,7 @@ impl Vm {
             }
             InstAssignOp::Mul => {
                 self.internal_num_assign(
+                    InstOp::Mul,
                     target,
                     Protocol::MUL_ASSIGN,
                     || VmErrorKind::Overflow,
@@ -1799,6 +2418,7 @@ impl Vm {
             }
             InstAssignOp::Div => {
                 self.internal_num_assign(
+                    InstOp::Div,
                     target,
                     Protocol::DIV_ASSIGN,
                     || VmErrorKind::DivideByZero,
@@ -1808,6 +2428,7 @@ impl Vm {
             }
             InstAssignOp::Rem => {
                 self.internal_num_assign(
+                    InstOp::Rem,
                     target,
                     Protocol::REM_ASSIGN,
                     || VmErrorKind::DivideByZero,
@@ -1817,6 +2438,7 @@ impl Vm {
             }
             InstAssignOp::BitAnd => {
                 self.internal_infallible_bitwise_assign(
+                    InstOp::BitAnd,
                     target,
                     Protocol::BIT_AND_ASSIGN,
                     std::ops::BitAndAssign::bitand_assign,
@@ -1824,6 +2446,7 @@ impl Vm {
             }
             InstAssignOp::BitXor => {
                 self.internal_infallible_bitwise_assign(
+                    InstOp::BitXor,
                     target,
                     Protocol::BIT_XOR_ASSIGN,
                     std::ops::BitXorAssign::bitxor_assign,
@@ -1831,6 +2454,7 @@ impl Vm {
             }
             InstAssignOp::BitOr => {
                 self.internal_infallible_bitwise_assign(
+                    InstOp::BitOr,
                     target,
                     Protocol::BIT_OR_ASSIGN,
                     std::ops::BitOrAssign::bitor_assign,
@@ -1838,6 +2462,7 @@ impl Vm {
             }
             InstAssignOp::Shl => {
                 self.internal_bitwise_assign(
+                    InstOp::Shl,
                     target,
                     Protocol::SHL_ASSIGN,
                     || VmErrorKind::Overflow,
@@ -1846,6 +2471,7 @@ impl Vm {
             }
             InstAssignOp::Shr => {
                 self.internal_infallible_bitwise_assign(
+                    InstOp::Shr,
                     target,
                     Protocol::SHR_ASSIGN,
                     std::ops::ShrAssign::shr_assign,
@@ -1933,6 +2559,11 @@ impl Vm {
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_return(&mut self) -> Result<bool, VmError> {
         let return_value = self.stack.pop()?;
+
+        if let Some(observe) = &self.observe {
+            observe.observer().on_return(self, &return_value);
+        }
+
         let exit = self.pop_call_frame()?;
         self.stack.push(return_value);
         Ok(exit)
@@ -1940,6 +2571,10 @@ impl Vm {
 
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_return_unit(&mut self) -> Result<bool, VmError> {
+        if let Some(observe) = &self.observe {
+            observe.observer().on_return(self, &Value::Unit);
+        }
+
         let exit = self.pop_call_frame()?;
         self.stack.push(());
         Ok(exit)
@@ -2001,6 +2636,10 @@ impl Vm {
                 }
                 _ => break,
             };
+
+            // NB: none of the fast paths above applied, so fall back to the
+            // `INDEX_GET` protocol instead of trying them again.
+            break;
         }
 
         let target = target.into_owned();
@@ -2132,6 +2771,7 @@ impl Vm {
         let values = self.stack.drain_stack_top(keys.len())?;
 
         for (key, value) in keys.iter().zip(values) {
+            limits::charge_entry(key)?;
             object.insert(key.clone(), value);
         }
 
@@ -2184,6 +2824,7 @@ impl Vm {
         let mut data = Object::with_capacity(keys.len());
 
         for (key, value) in keys.iter().zip(values) {
+            limits::charge_entry(key)?;
             data.insert(key.clone(), value);
         }
 
@@ -2224,6 +2865,7 @@ impl Vm {
         let values = self.stack.drain_stack_top(keys.len())?;
 
         for (key, value) in keys.iter().zip(values) {
+            limits::charge_entry(key)?;
             data.insert(key.clone(), value);
         }
 
@@ -2244,7 +2886,9 @@ impl Vm {
 
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_bytes(&mut self, slot: usize) -> Result<(), VmError> {
-        let bytes = self.unit.lookup_bytes(slot)?.to_owned();
+        let bytes = self.unit.lookup_bytes(slot)?;
+        limits::charge(bytes.len())?;
+        let bytes = bytes.to_owned();
         self.stack.push(Bytes::from_vec(bytes));
         Ok(())
     }
@@ -2275,6 +2919,26 @@ impl Vm {
                     let mut buffer = itoa::Buffer::new();
                     out.push_str(buffer.format(integer));
                 }
+                Value::U16(n) => {
+                    let mut buffer = itoa::Buffer::new();
+                    out.push_str(buffer.format(n));
+                }
+                Value::U32(n) => {
+                    let mut buffer = itoa::Buffer::new();
+                    out.push_str(buffer.format(n));
+                }
+                Value::U64(n) => {
+                    let mut buffer = itoa::Buffer::new();
+                    out.push_str(buffer.format(n));
+                }
+                Value::I32(n) => {
+                    let mut buffer = itoa::Buffer::new();
+                    out.push_str(buffer.format(n));
+                }
+                Value::I128(n) => {
+                    use std::fmt::Write as _;
+                    write!(out, "{}", n).map_err(|_| VmErrorKind::FormatError)?;
+                }
                 Value::Float(float) => {
                     let mut buffer = ryu::Buffer::new();
                     out.push_str(buffer.format(float));
@@ -2304,6 +2968,7 @@ impl Vm {
             }
         }
 
+        limits::charge(out.len())?;
         self.stack.push(out);
         Ok(())
     }
@@ -2403,6 +3068,64 @@ impl Vm {
 
         self.stack.push(match value {
             Value::Integer(actual) => actual == integer,
+            Value::U16(..) | Value::U32(..) | Value::U64(..) | Value::I32(..) | Value::I128(..) => {
+                value.into_wide_integer()? == i128::from(integer)
+            }
+            _ => false,
+        });
+
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_match_byte_range(
+        &mut self,
+        start: u8,
+        end: Option<u8>,
+        limits: InstRangeLimits,
+    ) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        self.stack.push(match value {
+            Value::Byte(actual) => limits.contains(start, end, actual),
+            _ => false,
+        });
+
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_match_character_range(
+        &mut self,
+        start: char,
+        end: Option<char>,
+        limits: InstRangeLimits,
+    ) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        self.stack.push(match value {
+            Value::Char(actual) => limits.contains(start, end, actual),
+            _ => false,
+        });
+
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_match_integer_range(
+        &mut self,
+        start: i64,
+        end: Option<i64>,
+        limits: InstRangeLimits,
+    ) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        self.stack.push(match value {
+            Value::Integer(actual) => limits.contains(start, end, actual),
+            Value::U16(..) | Value::U32(..) | Value::U64(..) | Value::I32(..) | Value::I128(..) => {
+                let end = end.map(i128::from);
+                limits.contains(i128::from(start), end, value.into_wide_integer()?)
+            }
             _ => false,
         });
 
@@ -2508,59 +3231,7 @@ impl Vm {
     /// Load a function as a value onto the stack.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_load_fn(&mut self, hash: Hash) -> Result<(), VmError> {
-        let function = match self.unit.lookup(hash) {
-            Some(info) => match info {
-                UnitFn::Offset { offset, call, args } => Function::from_offset(
-                    self.context.clone(),
-                    self.unit.clone(),
-                    offset,
-                    call,
-                    args,
-                    hash,
-                ),
-                UnitFn::UnitStruct { hash } => {
-                    let rtti = self
-                        .unit
-                        .lookup_rtti(hash)
-                        .ok_or_else(|| VmErrorKind::MissingRtti { hash })?;
-
-                    Function::from_unit_struct(rtti.clone())
-                }
-                UnitFn::TupleStruct { hash, args } => {
-                    let rtti = self
-                        .unit
-                        .lookup_rtti(hash)
-                        .ok_or_else(|| VmErrorKind::MissingRtti { hash })?;
-
-                    Function::from_tuple_struct(rtti.clone(), args)
-                }
-                UnitFn::UnitVariant { hash } => {
-                    let rtti = self
-                        .unit
-                        .lookup_variant_rtti(hash)
-                        .ok_or_else(|| VmErrorKind::MissingVariantRtti { hash })?;
-
-                    Function::from_empty_variant(rtti.clone())
-                }
-                UnitFn::TupleVariant { hash, args } => {
-                    let rtti = self
-                        .unit
-                        .lookup_variant_rtti(hash)
-                        .ok_or_else(|| VmErrorKind::MissingVariantRtti { hash })?;
-
-                    Function::from_tuple_variant(rtti.clone(), args)
-                }
-            },
-            None => {
-                let handler = self
-                    .context
-                    .lookup(hash)
-                    .ok_or_else(|| VmErrorKind::MissingFunction { hash })?;
-
-                Function::from_handler(handler.clone(), hash)
-            }
-        };
-
+        let function = Function::from_hash(&self.context, &self.unit, hash)?;
         self.stack.push(Value::Function(Shared::new(function)));
         Ok(())
     }
@@ -2578,6 +3249,7 @@ impl Vm {
             _ => return Err(VmError::from(VmErrorKind::MissingFunction { hash })),
         };
 
+        limits::charge_values(count)?;
         let environment = self.stack.pop_sequence(count)?.into_boxed_slice();
 
         let function = Function::from_closure(
@@ -2663,6 +3335,7 @@ impl Vm {
                     .ok_or_else(|| VmErrorKind::MissingFunction { hash })?;
 
                 let _guard = crate::interface::EnvGuard::new(&self.context, &self.unit);
+                let _native = self.enter_native(hash);
                 handler(&mut self.stack, args)?;
             }
         }
@@ -2716,6 +3389,7 @@ impl Vm {
                 };
 
                 let _guard = crate::interface::EnvGuard::new(&self.context, &self.unit);
+                let _native = self.enter_native(hash);
                 handler(&mut self.stack, args)?;
             }
         }
@@ -2772,8 +3446,91 @@ impl Vm {
         Ok(())
     }
 
-    /// Evaluate a single instruction.
+    /// Run the virtual machine until it halts, notifying the observer of any
+    /// errors raised.
     pub(crate) fn run(&mut self) -> Result<VmHalt, VmError> {
+        // NB: the observer is inherited like fuel below.
+        if self.observe.is_none() {
+            self.set_observer(observer::current());
+        }
+
+        let _observer_guard = self
+            .observe
+            .as_ref()
+            .map(|o| observer::enter(o.observer().clone()));
+
+        if let Some(observe) = &mut self.observe {
+            if observe.enter() {
+                let observer = observe.observer().clone();
+                observer.on_call(self, self.ip);
+            }
+        }
+
+        let result = self.run_instructions();
+
+        if let (Err(error), Some(observe)) = (&result, &self.observe) {
+            observe.observer().on_error(self, error);
+        }
+
+        result
+    }
+
+    /// Evaluate instructions until the virtual machine halts.
+    fn run_instructions(&mut self) -> Result<VmHalt, VmError> {
+        // NB: virtual machines without fuel of their own which are executed
+        // by another virtual machine, like through a native function calling
+        // a closure, share the fuel of the virtual machine executing them.
+        if self.fuel.is_none() {
+            if let Some((fuel, costs)) = fuel::current() {
+                self.fuel = Some(fuel);
+                self.fuel_costs = costs;
+                self.fuel_halts = false;
+            }
+        }
+
+        let _guard = self
+            .fuel
+            .as_ref()
+            .map(|fuel| fuel::enter(fuel.clone(), self.fuel_costs));
+
+        // NB: limits are inherited the same way, and running nested virtual
+        // machines counts towards the call depth since they use the host
+        // stack.
+        self.limits.or_inherit(&limits::current());
+        let _limits_guard = limits::enter(self.limits.clone());
+
+        if let Some(limit) = self.limits.call_depth {
+            if self.call_frames.len() + limits::nesting() > limit {
+                return Err(VmError::from(VmErrorKind::CallDepthExceeded { limit }));
+            }
+        }
+
+        // NB: the profiler is inherited the same way.
+        if self.profile.is_none() {
+            self.profile = profiler::current().map(VmProfile::new);
+        }
+
+        let _profile_guard = match &mut self.profile {
+            Some(profile) => Some(profile.enter(&self.unit, &self.call_frames, self.ip)),
+            None => None,
+        };
+
+        // NB: and so is coverage.
+        if self.coverage.is_none() {
+            self.set_coverage(coverage::current());
+        }
+
+        let _coverage_guard = self
+            .coverage
+            .as_ref()
+            .map(|c| coverage::enter(c.coverage().clone()));
+
+        self.run_loop()
+    }
+
+    /// Evaluate instructions until the virtual machine halts, without setting
+    /// up the state it shares with the virtual machines it spawns.
+    fn run_loop(&mut self) -> Result<VmHalt, VmError> {
         loop {
             if !budget::take() {
                 return Ok(VmHalt::Limited);
@@ -2784,6 +3541,28 @@ impl Vm {
                 .instruction_at(self.ip)
                 .ok_or_else(|| VmErrorKind::IpOutOfBounds)?;
 
+            if let Some(fuel) = &self.fuel {
+                if !fuel.take(&self.fuel_costs, &inst) {
+                    if !self.fuel_halts {
+                        return Err(VmError::from(VmErrorKind::OutOfFuel));
+                    }
+
+                    return Ok(VmHalt::OutOfFuel);
+                }
+            }
+
+            if let Some(profile) = &mut self.profile {
+                profile.instruction(&self.call_frames, self.ip);
+            }
+
+            if let Some(coverage) = &self.coverage {
+                coverage.hit(self.ip);
+            }
+
+            if let Some(observe) = &self.observe {
+                observe.observer().on_instruction(self, &inst);
+            }
+
             log::trace!("{}: {}", self.ip, inst);
 
             match inst {
@@ -2793,6 +3572,9 @@ impl Vm {
                 Inst::Neg => {
                     self.op_neg()?;
                 }
+                Inst::Cast { ty } => {
+                    self.op_cast(ty)?;
+                }
                 Inst::Closure { hash, count } => {
                     self.op_closure(hash, count)?;
                 }
@@ -2980,6 +3762,15 @@ impl Vm {
                 Inst::EqStaticString { slot } => {
                     self.op_eq_static_string(slot)?;
                 }
+                Inst::MatchByteRange { start, end, limits } => {
+                    self.op_match_byte_range(start, end, limits)?;
+                }
+                Inst::MatchCharacterRange { start, end, limits } => {
+                    self.op_match_character_range(start, end, limits)?;
+                }
+                Inst::MatchIntegerRange { start, end, limits } => {
+                    self.op_match_integer_range(start, end, limits)?;
+                }
                 Inst::MatchSequence {
                     type_check,
                     len,
@@ -3027,10 +3818,122 @@ impl Vm {
     }
 }
 
+/// Get the integer representation of a value which is being cast.
+fn cast_operand(value: &Value) -> Option<i128> {
+    Some(match value {
+        Value::Bool(b) => i128::from(*b),
+        Value::Byte(b) => i128::from(*b),
+        Value::Char(c) => i128::from(u32::from(*c)),
+        Value::Integer(n) => i128::from(*n),
+        Value::U16(n) => i128::from(*n),
+        Value::U32(n) => i128::from(*n),
+        Value::U64(n) => i128::from(*n),
+        Value::I32(n) => i128::from(*n),
+        Value::I128(n) => **n,
+        _ => return None,
+    })
+}
+
+/// Cast an integer into the given type.
+///
+/// Returns `None` if the integer is out of range for the type.
+fn cast_integer(ty: InstCast, n: i128) -> Option<Value> {
+    use std::convert::TryFrom as _;
+
+    Some(match ty {
+        InstCast::Integer => Value::Integer(i64::try_from(n).ok()?),
+        InstCast::Float => Value::Float(n as f64),
+        InstCast::Byte => Value::Byte(u8::try_from(n).ok()?),
+        InstCast::Char => Value::Char(std::char::from_u32(u32::try_from(n).ok()?)?),
+        InstCast::U16 => Value::U16(u16::try_from(n).ok()?),
+        InstCast::U32 => Value::U32(u32::try_from(n).ok()?),
+        InstCast::U64 => Value::U64(u64::try_from(n).ok()?),
+        InstCast::I32 => Value::I32(i32::try_from(n).ok()?),
+        InstCast::I128 => Value::from(n),
+    })
+}
+
+/// Apply a binary operation to two sized integers of the same type.
+///
+/// Returns `None` if the operands are not sized integers of the same type, in
+/// which case the operation should fall back to its protocol.
+fn sized_integer_op(op: InstOp, lhs: &Value, rhs: &Value) -> Result<Option<Value>, VmError> {
+    use std::convert::TryFrom as _;
+
+    macro_rules! sized_op {
+        ($a:expr, $b:expr, $variant:expr) => {{
+            let (a, b) = ($a, $b);
+
+            let (out, error) = match op {
+                InstOp::Add => (a.checked_add(b), VmErrorKind::Overflow),
+                InstOp::Sub => (a.checked_sub(b), VmErrorKind::Underflow),
+                InstOp::Mul => (a.checked_mul(b), VmErrorKind::Overflow),
+                InstOp::Div => (a.checked_div(b), VmErrorKind::DivideByZero),
+                InstOp::Rem => (a.checked_rem(b), VmErrorKind::DivideByZero),
+                InstOp::Pow => match u32::try_from(b) {
+                    Ok(exp) => (a.checked_pow(exp), VmErrorKind::Overflow),
+                    Err(..) => return Err(VmError::from(pow_error(b))),
+                },
+                InstOp::BitAnd => (Some(a & b), VmErrorKind::Overflow),
+                InstOp::BitXor => (Some(a ^ b), VmErrorKind::Overflow),
+                InstOp::BitOr => (Some(a | b), VmErrorKind::Overflow),
+                InstOp::Shl => (
+                    u32::try_from(b).ok().and_then(|b| a.checked_shl(b)),
+                    VmErrorKind::Overflow,
+                ),
+                InstOp::Shr => (
+                    u32::try_from(b).ok().and_then(|b| a.checked_shr(b)),
+                    VmErrorKind::Overflow,
+                ),
+                _ => return Ok(None),
+            };
+
+            match out {
+                Some(out) => Ok(Some($variant(out))),
+                None => Err(VmError::from(error)),
+            }
+        }};
+    }
+
+    match (lhs, rhs) {
+        (Value::U16(a), Value::U16(b)) => sized_op!(*a, *b, Value::U16),
+        (Value::U32(a), Value::U32(b)) => sized_op!(*a, *b, Value::U32),
+        (Value::U64(a), Value::U64(b)) => sized_op!(*a, *b, Value::U64),
+        (Value::I32(a), Value::I32(b)) => sized_op!(*a, *b, Value::I32),
+        (Value::I128(a), Value::I128(b)) => sized_op!(**a, **b, Value::from),
+        _ => Ok(None),
+    }
+}
+
+/// The error raised when raising an integer to the given power fails.
+fn pow_error(exponent: impl Into<VmIntegerRepr>) -> VmErrorKind {
+    let exponent = exponent.into();
+
+    if exponent.is_negative() {
+        VmErrorKind::NegativeExponent { exponent }
+    } else {
+        VmErrorKind::Overflow
+    }
+}
+
+/// Compare two sized integers of the same type.
+///
+/// Returns `None` if the operands are not sized integers of the same type.
+fn sized_integer_cmp(lhs: &Value, rhs: &Value) -> Option<cmp::Ordering> {
+    Some(match (lhs, rhs) {
+        (Value::U16(a), Value::U16(b)) => a.cmp(b),
+        (Value::U32(a), Value::U32(b)) => a.cmp(b),
+        (Value::U64(a), Value::U64(b)) => a.cmp(b),
+        (Value::I32(a), Value::I32(b)) => a.cmp(b),
+        (Value::I128(a), Value::I128(b)) => a.cmp(b),
+        _ => return None,
+    })
+}
+
 /// A call frame.
 ///
 /// This is used to store the return point after an instruction has been run.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct CallFrame {
     /// The stored instruction pointer.
     ip: usize,
@@ -3040,6 +3943,10 @@ pub struct CallFrame {
     /// I.e. a function should not be able to manipulate the size of any other
     /// stack than its own.
     stack_bottom: usize,
+    /// If returning from the call frame halts the virtual machine, because
+    /// it's run to completion from within an instruction.
+    #[serde(default)]
+    isolated: bool,
 }
 
 impl CallFrame {
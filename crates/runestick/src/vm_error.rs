@@ -1,6 +1,8 @@
+use crate::debug::{DebugInst, DebugSignature};
 use crate::panic::BoxedPanic;
 use crate::{
-    AccessError, Hash, Item, Panic, Protocol, StackError, TypeInfo, TypeOf, Unit, Value, VmHaltInfo,
+    AccessError, Hash, InstCast, Item, Panic, Protocol, StackError, TypeInfo, TypeOf, Unit, Value,
+    VmHaltInfo,
 };
 use std::fmt;
 use std::sync::Arc;
@@ -72,13 +74,50 @@ impl VmError {
             kind: self.kind,
             unit: unit.clone(),
             ip,
+            backtrace: vec![VmErrorFrame::new(unit.clone(), ip)],
         })
     }
 
+    /// Convert into an unwinded vm error with the given frames, starting with
+    /// the innermost one.
+    ///
+    /// If the error has already been unwound, the frames are added to the
+    /// end of its backtrace. This happens when an error propagates out of a
+    /// nested execution.
+    pub(crate) fn with_backtrace(mut self, frames: Vec<VmErrorFrame>) -> Self {
+        if let VmErrorKind::Unwound { backtrace, .. } = &mut *self.kind {
+            backtrace.extend(frames);
+            return self;
+        }
+
+        let (unit, ip) = match frames.first() {
+            Some(frame) => (frame.unit.clone(), frame.ip),
+            None => return self,
+        };
+
+        Self::from(VmErrorKind::Unwound {
+            kind: self.kind,
+            unit,
+            ip,
+            backtrace: frames,
+        })
+    }
+
+    /// Get the call frames which were active when the error happened,
+    /// starting with the innermost one.
+    ///
+    /// This is empty unless the error has been unwound.
+    pub fn backtrace(&self) -> &[VmErrorFrame] {
+        match &*self.kind {
+            VmErrorKind::Unwound { backtrace, .. } => backtrace,
+            _ => &[],
+        }
+    }
+
     /// Unpack an unwinded error, if it is present.
     pub fn as_unwound<'a>(&'a self) -> (&'a VmErrorKind, Option<(&'a Arc<Unit>, usize)>) {
         match &*self.kind {
-            VmErrorKind::Unwound { kind, unit, ip } => (&*kind, Some((unit, *ip))),
+            VmErrorKind::Unwound { kind, unit, ip, .. } => (&*kind, Some((unit, *ip))),
             kind => (kind, None),
         }
     }
@@ -86,7 +125,7 @@ impl VmError {
     /// Unpack an unwinded error, if it is present.
     pub fn into_unwound(self) -> (Self, Option<(Arc<Unit>, usize)>) {
         match *self.kind {
-            VmErrorKind::Unwound { kind, unit, ip } => {
+            VmErrorKind::Unwound { kind, unit, ip, .. } => {
                 let error = Self { kind };
                 (error, Some((unit, ip)))
             }
@@ -143,6 +182,8 @@ pub enum VmErrorKind {
         unit: Arc<Unit>,
         /// The instruction pointer of where the original error happened.
         ip: usize,
+        /// The call frames which were active when the error happened.
+        backtrace: Vec<VmErrorFrame>,
     },
     #[error("{error}")]
     AccessError {
@@ -155,6 +196,18 @@ pub enum VmErrorKind {
     NoRunningVm,
     #[error("halted for unexpected reason `{halt}`")]
     Halted { halt: VmHaltInfo },
+    #[error("ran out of fuel in a virtual machine which can't be resumed")]
+    OutOfFuel,
+    #[error("exceeded the memory limit of {limit} bytes")]
+    MemoryLimitExceeded { limit: usize },
+    #[error("exceeded the call depth limit of {limit}")]
+    CallDepthExceeded { limit: usize },
+    #[error("values of type `{actual}` can't be stored in a snapshot")]
+    UnsupportedSnapshot { actual: TypeInfo },
+    #[error("snapshot is invalid or was taken with a different unit")]
+    InvalidSnapshot,
+    #[error("a virtual machine with {state} attached can't be sent to another thread")]
+    UnsupportedSendExecution { state: &'static str },
     #[error("failed to format argument")]
     FormatError,
     #[error("stack error: {error}")]
@@ -166,6 +219,10 @@ pub enum VmErrorKind {
     Overflow,
     #[error("numerical underflow")]
     Underflow,
+    #[error("exponent `{exponent}` is negative")]
+    NegativeExponent { exponent: VmIntegerRepr },
+    #[error("scale `{scale}` is larger than the maximum decimal scale of {max}")]
+    DecimalScaleOutOfRange { scale: u32, max: u32 },
     #[error("division by zero")]
     DivideByZero,
     #[error("missing constant with hash `{hash}`")]
@@ -188,6 +245,10 @@ pub enum VmErrorKind {
     },
     #[error("unsupported vm operation `{op}{operand}`")]
     UnsupportedUnaryOperation { op: &'static str, operand: TypeInfo },
+    #[error("unsupported cast `{from} as {to}`")]
+    UnsupportedCast { from: TypeInfo, to: InstCast },
+    #[error("value of type `{from}` is out of range for `{to}`")]
+    CastOutOfRange { from: TypeInfo, to: InstCast },
     #[error("`{actual}` does not implement the `{protocol}` protocol")]
     MissingProtocol {
         protocol: Protocol,
@@ -313,12 +374,70 @@ impl VmErrorKind {
     /// Unpack an unwound error, if it is present.
     pub fn as_unwound_ref(&self) -> (&Self, Option<(Arc<Unit>, usize)>) {
         match self {
-            VmErrorKind::Unwound { kind, unit, ip } => (&*kind, Some((unit.clone(), *ip))),
+            VmErrorKind::Unwound { kind, unit, ip, .. } => (&*kind, Some((unit.clone(), *ip))),
             kind => (kind, None),
         }
     }
 }
 
+/// A call frame in the backtrace of a [VmError].
+#[derive(Clone)]
+pub struct VmErrorFrame {
+    unit: Arc<Unit>,
+    ip: usize,
+}
+
+impl VmErrorFrame {
+    /// Construct a new frame at the given instruction pointer.
+    pub(crate) fn new(unit: Arc<Unit>, ip: usize) -> Self {
+        Self { unit, ip }
+    }
+
+    /// Get the unit the frame belongs to.
+    pub fn unit(&self) -> &Arc<Unit> {
+        &self.unit
+    }
+
+    /// Get the instruction pointer of the frame.
+    ///
+    /// For every frame except the innermost one, this is the instruction
+    /// which called into the next frame.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// Get the signature of the function the frame belongs to, if debug info
+    /// is available.
+    pub fn signature(&self) -> Option<&DebugSignature> {
+        let debug = self.unit.debug_info()?;
+        let start = debug.function_start(self.ip)?;
+        Some(debug.function_at(start)?.1)
+    }
+
+    /// Get debug information for the instruction of the frame, if it's
+    /// available.
+    pub fn instruction(&self) -> Option<&DebugInst> {
+        self.unit.debug_info()?.instruction_at(self.ip)
+    }
+
+    /// Get the hash of the function the frame belongs to, if debug info is
+    /// available.
+    fn function(&self) -> Option<Hash> {
+        let debug = self.unit.debug_info()?;
+        let start = debug.function_start(self.ip)?;
+        Some(debug.function_at(start)?.0)
+    }
+}
+
+impl fmt::Debug for VmErrorFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VmErrorFrame")
+            .field("function", &self.function())
+            .field("ip", &self.ip)
+            .finish()
+    }
+}
+
 /// A type-erased rust number.
 #[derive(Debug, Clone)]
 pub struct VmIntegerRepr(num_bigint::BigInt);
@@ -332,6 +451,13 @@ where
     }
 }
 
+impl VmIntegerRepr {
+    /// Test if the number is negative.
+    pub(crate) fn is_negative(&self) -> bool {
+        self.0.sign() == num_bigint::Sign::Minus
+    }
+}
+
 impl fmt::Display for VmIntegerRepr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
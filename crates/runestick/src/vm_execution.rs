@@ -1,11 +1,16 @@
 use crate::budget;
+use crate::coverage::Coverage;
 use crate::internal;
-use crate::{GeneratorState, Value, Vm, VmError, VmErrorKind, VmHalt, VmHaltInfo};
+use crate::profiler::Profiler;
+use crate::{
+    GeneratorState, Value, Vm, VmError, VmErrorFrame, VmErrorKind, VmHalt, VmHaltInfo, VmObserver,
+};
 use std::future::Future;
+use std::rc::Rc;
 
 /// The execution environment for a virtual machine.
 pub struct VmExecution {
-    vms: Vec<Vm>,
+    pub(crate) vms: Vec<Vm>,
 }
 
 impl VmExecution {
@@ -30,6 +35,130 @@ impl VmExecution {
         }
     }
 
+    /// Get the fuel remaining, or `None` if fuel metering isn't enabled.
+    ///
+    /// See [Vm::set_fuel] for more information.
+    pub fn fuel(&self) -> Result<Option<u64>, VmError> {
+        Ok(self.vm()?.fuel())
+    }
+
+    /// Enable fuel metering with the given amount of fuel, or disable it by
+    /// passing `None`.
+    ///
+    /// See [Vm::set_fuel] for more information.
+    pub fn set_fuel(&mut self, fuel: Option<u64>) -> Result<(), VmError> {
+        let (first, rest) = match self.vms.split_first_mut() {
+            Some(vms) => vms,
+            None => return Err(VmError::from(VmErrorKind::NoRunningVm)),
+        };
+
+        first.set_fuel(fuel);
+
+        for vm in rest {
+            vm.inherit_fuel(first);
+        }
+
+        Ok(())
+    }
+
+    /// Add fuel to the execution, allowing it to be resumed after it halted
+    /// because it ran out of fuel.
+    ///
+    /// This does nothing if fuel metering isn't enabled.
+    pub fn add_fuel(&mut self, amount: u64) -> Result<(), VmError> {
+        self.vm_mut()?.add_fuel(amount);
+        Ok(())
+    }
+
+    /// Limit the memory that can be allocated by the execution to the given
+    /// number of bytes, or disable the limit by passing `None`.
+    ///
+    /// See [Vm::set_memory_limit] for more information.
+    pub fn set_memory_limit(&mut self, limit: Option<usize>) -> Result<(), VmError> {
+        self.set_limits(|vm| vm.set_memory_limit(limit))
+    }
+
+    /// Get the memory charged so far, or `None` if the memory limit isn't
+    /// enabled.
+    ///
+    /// See [Vm::set_memory_limit] for more information.
+    pub fn memory_used(&self) -> Result<Option<usize>, VmError> {
+        Ok(self.vm()?.memory_used())
+    }
+
+    /// Limit the depth of calls in the execution, or disable the limit by
+    /// passing `None`.
+    ///
+    /// See [Vm::set_call_depth_limit] for more information.
+    pub fn set_call_depth_limit(&mut self, limit: Option<usize>) -> Result<(), VmError> {
+        self.set_limits(|vm| vm.set_call_depth_limit(limit))
+    }
+
+    /// Enable profiling by recording into the given profiler, or disable it by
+    /// passing `None`.
+    ///
+    /// See [Vm::set_profiler] for more information.
+    pub fn set_profiler(&mut self, profiler: Option<Profiler>) -> Result<(), VmError> {
+        if self.vms.is_empty() {
+            return Err(VmError::from(VmErrorKind::NoRunningVm));
+        }
+
+        for vm in &mut self.vms {
+            vm.set_profiler(profiler.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Enable coverage by recording into the given collector, or disable it by
+    /// passing `None`.
+    ///
+    /// See [Vm::set_coverage] for more information.
+    pub fn set_coverage(&mut self, coverage: Option<Coverage>) -> Result<(), VmError> {
+        if self.vms.is_empty() {
+            return Err(VmError::from(VmErrorKind::NoRunningVm));
+        }
+
+        for vm in &mut self.vms {
+            vm.set_coverage(coverage.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Register an observer which is notified as the execution proceeds, or
+    /// remove it by passing `None`.
+    ///
+    /// See [Vm::set_observer] for more information.
+    pub fn set_observer(&mut self, observer: Option<Rc<dyn VmObserver>>) -> Result<(), VmError> {
+        if self.vms.is_empty() {
+            return Err(VmError::from(VmErrorKind::NoRunningVm));
+        }
+
+        for vm in &mut self.vms {
+            vm.set_observer(observer.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Change the limits of the first virtual machine and share them with the
+    /// rest.
+    fn set_limits(&mut self, f: impl FnOnce(&mut Vm)) -> Result<(), VmError> {
+        let (first, rest) = match self.vms.split_first_mut() {
+            Some(vms) => vms,
+            None => return Err(VmError::from(VmErrorKind::NoRunningVm)),
+        };
+
+        f(first);
+
+        for vm in rest {
+            vm.inherit_limits(first);
+        }
+
+        Ok(())
+    }
+
     /// Complete the current execution without support for async instructions.
     ///
     /// This will error if the execution is suspended through yielding.
@@ -61,10 +190,18 @@ impl VmExecution {
             let len = self.vms.len();
             let vm = self.vm_mut()?;
 
-            match Self::run(vm)? {
+            let halt = match vm.run() {
+                Ok(halt) => halt,
+                Err(error) => return Err(self.unwind(error)),
+            };
+
+            match halt {
                 VmHalt::Exited => (),
                 VmHalt::Awaited(awaited) => {
-                    awaited.into_vm(vm).await?;
+                    if let Err(error) = awaited.into_vm(vm).await {
+                        return Err(self.unwind(error));
+                    }
+
                     continue;
                 }
                 VmHalt::VmCall(vm_call) => {
@@ -98,7 +235,12 @@ impl VmExecution {
             let len = self.vms.len();
             let vm = self.vm_mut()?;
 
-            match Self::run(vm)? {
+            let halt = match vm.run() {
+                Ok(halt) => halt,
+                Err(error) => return Err(self.unwind(error)),
+            };
+
+            match halt {
                 VmHalt::Exited => (),
                 VmHalt::VmCall(vm_call) => {
                     vm_call.into_execution(self)?;
@@ -131,7 +273,12 @@ impl VmExecution {
         let len = self.vms.len();
         let vm = self.vm_mut()?;
 
-        match budget::with(1, || Self::run(vm)).call()? {
+        let halt = match budget::with(1, || vm.run()).call() {
+            Ok(halt) => halt,
+            Err(error) => return Err(self.unwind(error)),
+        };
+
+        match halt {
             VmHalt::Exited => (),
             VmHalt::VmCall(vm_call) => {
                 vm_call.into_execution(self)?;
@@ -161,10 +308,18 @@ impl VmExecution {
         let len = self.vms.len();
         let vm = self.vm_mut()?;
 
-        match budget::with(1, || Self::run(vm)).call()? {
+        let halt = match budget::with(1, || vm.run()).call() {
+            Ok(halt) => halt,
+            Err(error) => return Err(self.unwind(error)),
+        };
+
+        match halt {
             VmHalt::Exited => (),
             VmHalt::Awaited(awaited) => {
-                awaited.into_vm(vm).await?;
+                if let Err(error) = awaited.into_vm(vm).await {
+                    return Err(self.unwind(error));
+                }
+
                 return Ok(None);
             }
             VmHalt::VmCall(vm_call) => {
@@ -190,7 +345,9 @@ impl VmExecution {
     }
 
     /// Push a virtual machine state onto the execution.
-    pub(crate) fn push_vm(&mut self, vm: Vm) {
+    pub(crate) fn push_vm(&mut self, mut vm: Vm) {
+        // NB: virtual machines which are part of the execution can be resumed.
+        vm.set_fuel_halts(true);
         self.vms.push(vm);
     }
 
@@ -209,12 +366,20 @@ impl VmExecution {
         Ok(())
     }
 
-    #[inline]
-    fn run(vm: &mut Vm) -> Result<VmHalt, VmError> {
-        match vm.run() {
-            Ok(reason) => Ok(reason),
-            Err(error) => Err(error.into_unwinded(vm.unit(), vm.ip())),
+    /// Unwind an error raised by the current virtual machine, recording the
+    /// call frames of every virtual machine in the execution.
+    fn unwind(&self, error: VmError) -> VmError {
+        let mut frames = Vec::new();
+
+        for vm in self.vms.iter().rev() {
+            frames.push(VmErrorFrame::new(vm.unit().clone(), vm.ip()));
+
+            for frame in vm.call_frames().iter().rev() {
+                frames.push(VmErrorFrame::new(vm.unit().clone(), frame.ip()));
+            }
         }
+
+        error.with_backtrace(frames)
     }
 }
 
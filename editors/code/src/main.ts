@@ -68,6 +68,14 @@ async function tryActivate(context: vscode.ExtensionContext) {
 
     log.info(`command: ${command}`);
     client.start();
+
+    const debugCommand = command as string;
+
+    context.subscriptions.push(vscode.debug.registerDebugAdapterDescriptorFactory('rune', {
+        createDebugAdapterDescriptor() {
+            return new vscode.DebugAdapterExecutable(debugCommand, ['--dap']);
+        }
+    }));
 }
 
 /**
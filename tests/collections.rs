@@ -37,3 +37,368 @@ fn test_hash_set_tuple() {
         }
     }
 }
+
+#[test]
+fn test_hash_map_typed_keys() {
+    rune! { () =>
+        pub fn main() {
+            use std::collections::HashMap;
+
+            enum Color {
+                Red,
+                Custom(r, g, b),
+                Named { name },
+            }
+
+            struct Point {
+                x,
+                y,
+            }
+
+            let m = HashMap::new();
+
+            m.insert(Color::Red, 1);
+            m.insert(Color::Custom(1, 2, 3), 2);
+            m.insert(Color::Named { name: "teal" }, 3);
+            m.insert(Point { x: 1, y: 2 }, 4);
+
+            assert_eq!(m.get(Color::Red), Some(1));
+            assert_eq!(m.get(Color::Custom(1, 2, 3)), Some(2));
+            assert_eq!(m.get(Color::Custom(1, 2, 4)), None);
+            assert_eq!(m.get(Color::Named { name: "teal" }), Some(3));
+            assert_eq!(m.get(Point { y: 2, x: 1 }), Some(4));
+            assert_eq!(m.get(Point { x: 2, y: 1 }), None);
+        }
+    }
+}
+
+#[test]
+fn test_hash_set_native_keys() {
+    use runestick::{Any, Module, Protocol};
+
+    #[derive(Any)]
+    struct Id(i64);
+
+    impl Id {
+        fn hash(&self) -> i64 {
+            self.0
+        }
+
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    /// A type whose values all hash the same.
+    #[derive(Any)]
+    struct Colliding(i64);
+
+    impl Colliding {
+        fn hash(&self) -> i64 {
+            0
+        }
+
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    /// A type which can be hashed but not compared.
+    #[derive(Any)]
+    struct Unequal;
+
+    impl Unequal {
+        fn hash(&self) -> i64 {
+            0
+        }
+    }
+
+    let mut module = Module::new();
+    module.ty::<Id>().unwrap();
+    module.inst_fn(Protocol::HASH, Id::hash).unwrap();
+    module.inst_fn(Protocol::EQ, Id::eq).unwrap();
+    module.ty::<Colliding>().unwrap();
+    module.inst_fn(Protocol::HASH, Colliding::hash).unwrap();
+    module.inst_fn(Protocol::EQ, Colliding::eq).unwrap();
+    module.inst_fn(Protocol::CMP, Colliding::cmp).unwrap();
+    module.ty::<Unequal>().unwrap();
+    module.inst_fn(Protocol::HASH, Unequal::hash).unwrap();
+
+    assert_eq! {
+        rune_n! { module, (Id(1), Id(1), Id(2)), (bool, bool) =>
+            pub fn main(a, b, c) {
+                use std::collections::HashSet;
+
+                let s = HashSet::new();
+                s.insert(a);
+                (s.contains(b), s.contains(c))
+            }
+        },
+        (true, false),
+    };
+
+    let args = (Colliding(1), Colliding(2), Colliding(1));
+
+    assert_eq! {
+        rune_n! { module, args, (usize, usize, bool) =>
+            pub fn main(a, b, c) {
+                use std::collections::{BTreeSet, HashSet};
+
+                let s = HashSet::new();
+                s.insert(a);
+                s.insert(b);
+
+                let t = BTreeSet::new();
+                t.insert(a);
+                t.insert(b);
+                t.insert(c);
+
+                (s.len(), t.len(), s.contains(c))
+            }
+        },
+        (2, 2, true),
+    };
+
+    let mut context = rune_modules::default_context().unwrap();
+    context.install(&module).unwrap();
+    let context = std::sync::Arc::new(context);
+
+    let source = r#"pub fn main(a) { std::collections::HashSet::new().insert(a) }"#;
+    let e = rune_tests::run::<_, _, ()>(&context, source, &["main"], (Unequal,)).unwrap_err();
+
+    let e = match e {
+        rune_tests::RunError::VmError(e) => e.into_unwound().0.into_kind(),
+        actual => panic!("expected a vm error but was `{:?}`", actual),
+    };
+
+    // NB: the key is converted when the argument is passed to `insert`.
+    match e {
+        runestick::VmErrorKind::BadArgument { error, .. } => {
+            assert!(matches!(
+                error.into_kind(),
+                runestick::VmErrorKind::KeyNotSupported { .. }
+            ));
+        }
+        actual => panic!("expected a bad argument but was `{:?}`", actual),
+    }
+}
+
+#[test]
+fn test_native_key_errors() {
+    use runestick::{Any, Module, Protocol, VmError, VmErrorKind};
+
+    /// A type whose equality protocol always fails.
+    #[derive(Any)]
+    struct Failing;
+
+    impl Failing {
+        fn hash(&self) -> i64 {
+            0
+        }
+
+        fn eq(&self, _: &Self) -> Result<bool, VmError> {
+            Err(VmError::panic("not comparable"))
+        }
+    }
+
+    let mut module = Module::new();
+    module.ty::<Failing>().unwrap();
+    module.inst_fn(Protocol::HASH, Failing::hash).unwrap();
+    module.inst_fn(Protocol::EQ, Failing::eq).unwrap();
+
+    let mut context = rune_modules::default_context().unwrap();
+    context.install(&module).unwrap();
+    let context = std::sync::Arc::new(context);
+
+    let run = |source: &str| {
+        let args = (Failing, Failing);
+        let e = rune_tests::run::<_, _, ()>(&context, source, &["main"], args).unwrap_err();
+
+        match e {
+            rune_tests::RunError::VmError(e) => e.into_unwound().0.into_kind(),
+            actual => panic!("expected a vm error but was `{:?}`", actual),
+        }
+    };
+
+    let e = run(r#"
+        pub fn main(a, b) {
+            let s = std::collections::HashSet::new();
+            s.insert(a);
+            s.insert(b);
+        }
+    "#);
+
+    match e {
+        VmErrorKind::Panic { reason } => assert_eq!(reason.to_string(), "not comparable"),
+        actual => panic!("expected a panic but was `{:?}`", actual),
+    }
+
+    let e = run(r#"
+        pub fn main(a, b) {
+            std::collections::BTreeSet::new().insert(a);
+        }
+    "#);
+
+    assert!(matches!(
+        e,
+        VmErrorKind::MissingProtocol { protocol, .. } if protocol == Protocol::CMP
+    ));
+}
+
+#[test]
+fn test_btree_map() {
+    rune! { () =>
+        pub fn main() {
+            use std::collections::BTreeMap;
+
+            let m = BTreeMap::new();
+            m["c"] = 3;
+            m["a"] = 1;
+            m.insert("b", 2);
+
+            assert_eq!(m.len(), 3);
+            assert_eq!(m["b"], Some(2));
+            assert_eq!(m.get("d"), None);
+            assert_eq!(m.iter().collect_vec(), [("a", 1), ("b", 2), ("c", 3)]);
+            assert_eq!(m.keys().rev().collect_vec(), ["c", "b", "a"]);
+            assert_eq!(m.range("b"..).collect_vec(), [("b", 2), ("c", 3)]);
+            assert_eq!(m.range("a"..="b").collect_vec(), [("a", 1), ("b", 2)]);
+            assert_eq!(m.range("c".."a").collect_vec(), []);
+            assert_eq!(m.first(), Some(("a", 1)));
+            assert_eq!(m.last(), Some(("c", 3)));
+            assert_eq!(m.remove("a"), Some(1));
+            assert!(!m.contains_key("a"));
+
+            let m = BTreeMap::from([(3, "c"), (1, "a")]);
+            let out = [];
+
+            for (k, v) in m {
+                out.push(v);
+            }
+
+            assert_eq!(out, ["a", "c"]);
+        }
+    }
+}
+
+#[test]
+fn test_btree_map_native_keys() {
+    rune! { () =>
+        pub fn main() {
+            use std::collections::{BTreeMap, BTreeSet, HashMap};
+            use std::num::{BigInt, Decimal};
+
+            let m = BTreeMap::new();
+            m[BigInt::new(300)] = "c";
+            m[BigInt::new(2).pow(70)] = "d";
+            m[BigInt::new(-5)] = "a";
+            m[BigInt::new(20)] = "b";
+
+            assert_eq!(m.values().collect_vec(), ["a", "b", "c", "d"]);
+            assert_eq!(m.get(BigInt::new(20)), Some("b"));
+
+            let s = BTreeSet::new();
+            s.insert(Decimal::parse("2.5").unwrap());
+            s.insert(Decimal::parse("10").unwrap());
+            s.insert(Decimal::parse("-0.25").unwrap());
+            s.insert(Decimal::parse("2.50").unwrap());
+
+            let values = s.iter().map(|d| d.to_float()).collect_vec();
+            assert_eq!(values, [-0.25, 2.5, 10.0]);
+
+            let m = HashMap::new();
+            m[Decimal::parse("1.0").unwrap()] = 1;
+            m[Decimal::parse("1.00").unwrap()] = 2;
+            assert_eq!(m.len(), 1);
+        }
+    }
+}
+
+#[test]
+fn test_btree_set() {
+    rune! { () =>
+        pub fn main() {
+            use std::collections::BTreeSet;
+
+            let s = BTreeSet::from([3, 1, 2, 1]);
+            assert_eq!(s.len(), 3);
+            assert_eq!(s.iter().collect_vec(), [1, 2, 3]);
+            assert_eq!(s.range(2..3).collect_vec(), [2]);
+            assert_eq!(s.first(), Some(1));
+            assert_eq!(s.last(), Some(3));
+            assert!(!s.insert(2));
+            assert!(s.remove(2));
+            assert!(!s.contains(2));
+        }
+    }
+}
+
+#[test]
+fn test_vec_deque() {
+    rune! { () =>
+        pub fn main() {
+            use std::collections::VecDeque;
+
+            let d = VecDeque::new();
+            d.push_back(2);
+            d.push_front(1);
+            d.push_back(3);
+
+            assert_eq!(d.len(), 3);
+            assert_eq!(d.front(), Some(1));
+            assert_eq!(d.back(), Some(3));
+            assert_eq!(d[1], 2);
+            d[1] = 20;
+            assert_eq!(d.get(1), Some(20));
+            assert_eq!(d.get(3), None);
+            assert_eq!(d.range(1..).collect_vec(), [20, 3]);
+            assert_eq!(d.iter().rev().collect_vec(), [3, 20, 1]);
+            assert_eq!(d.pop_front(), Some(1));
+            assert_eq!(d.pop_back(), Some(3));
+            assert!(!d.is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_vec_deque_errors() {
+    use runestick::VmErrorKind::*;
+
+    assert_vm_error!(
+        r#"pub fn main() { let d = std::collections::VecDeque::from([1]); d[1] }"#,
+        OutOfRange { index, len } => {
+            assert_eq!(index.to_string(), "1");
+            assert_eq!(len.to_string(), "1");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { std::collections::VecDeque::with_capacity(4611686018427387904) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "memory allocation failed");
+        }
+    );
+}
+
+#[test]
+fn test_binary_heap() {
+    rune! { () =>
+        pub fn main() {
+            use std::collections::BinaryHeap;
+
+            let h = BinaryHeap::from([3, 1, 4, 1, 5]);
+            assert_eq!(h.len(), 5);
+            assert_eq!(h.peek(), Some(5));
+            assert_eq!(h.into_sorted_vec(), [1, 1, 3, 4, 5]);
+            assert_eq!(h.pop(), Some(5));
+            assert_eq!(h.pop(), Some(4));
+            h.push(2);
+            assert_eq!(h.pop(), Some(3));
+            assert_eq!(h.pop(), Some(2));
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use runestick::{Any, Module, Protocol};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, Any)]
+struct Version {
+    major: i64,
+    minor: i64,
+}
+
+impl Version {
+    fn partial_eq(&self, other: &Self) -> bool {
+        self.major == other.major && self.minor == other.minor
+    }
+
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor).cmp(&(other.major, other.minor))
+    }
+}
+
+fn module() -> Module {
+    let mut module = Module::new();
+    module.ty::<Version>().unwrap();
+    module
+        .inst_fn(Protocol::PARTIAL_EQ, Version::partial_eq)
+        .unwrap();
+    module.inst_fn(Protocol::CMP, Version::cmp).unwrap();
+    module
+}
+
+#[test]
+fn test_external_eq() {
+    let a = Version { major: 1, minor: 2 };
+    let b = Version { major: 1, minor: 2 };
+    let c = Version { major: 1, minor: 3 };
+
+    assert_eq! {
+        rune_n! { module(), (a, b, c), (bool, bool, bool, bool) =>
+            pub fn main(a, b, c) {
+                (a == b, a != b, a == c, a != c)
+            }
+        },
+        (true, false, false, true),
+    };
+}
+
+#[test]
+fn test_external_cmp() {
+    let a = Version { major: 1, minor: 2 };
+    let b = Version { major: 2, minor: 0 };
+
+    assert_eq! {
+        rune_n! { module(), (a, b), (bool, bool, bool, bool, bool) =>
+            pub fn main(a, b) {
+                (a < b, a <= b, a > b, a >= b, b > a)
+            }
+        },
+        (true, true, false, false, true),
+    };
+}
+
+#[test]
+fn test_ordering() {
+    assert_eq! {
+        rune_n! { module(), (Ordering::Less, Ordering::Less, Ordering::Greater), (bool, bool, Ordering) =>
+            pub fn main(a, b, c) {
+                (a == b, a == c, a.reverse())
+            }
+        },
+        (true, false, Ordering::Greater),
+    };
+}
+
+#[test]
+fn test_external_nested() {
+    let a = Version { major: 1, minor: 2 };
+    let b = Version { major: 1, minor: 2 };
+    let c = Version { major: 2, minor: 0 };
+
+    assert_eq! {
+        rune_n! { module(), (a, b, c), (bool, bool, bool, Ordering) =>
+            pub fn main(a, b, c) {
+                ([a, 1] == [b, 1], (a, c) == (b, b), Some(a) == Some(b), std::cmp::cmp(c, a))
+            }
+        },
+        (true, false, true, Ordering::Greater),
+    };
+}
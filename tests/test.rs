@@ -145,6 +145,7 @@ mod compiler_visibility;
 mod compiler_warnings;
 mod core_macros;
 mod destructuring;
+mod external_cmp;
 mod external_ops;
 mod for_loop;
 mod getter_setter;
@@ -157,26 +158,44 @@ mod test_iter;
 mod test_option;
 mod test_range;
 mod test_result;
+mod test_string;
+mod test_vec;
 mod type_name_native;
 mod type_name_rune;
 mod vm_arithmetic;
 mod vm_assign_exprs;
 mod vm_async_block;
+mod vm_backtrace;
 mod vm_blocks;
+mod vm_cast;
 mod vm_closures;
 mod vm_const_exprs;
+mod vm_coverage;
+mod vm_debugger;
 mod vm_early_termination;
+mod vm_fuel;
 mod vm_function;
 mod vm_general;
 mod vm_generators;
+mod vm_impl_ops;
 mod vm_is;
 mod vm_lazy_and_or;
+mod vm_let_else;
+mod vm_limits;
 mod vm_literals;
 mod vm_match;
 mod vm_not_used;
+mod vm_objects;
+mod vm_observer;
+mod vm_num;
 mod vm_option;
 mod vm_pat;
+mod vm_pat_at;
+mod vm_pat_or_range;
+mod vm_profiler;
 mod vm_result;
+mod vm_sized_integers;
+mod vm_snapshot;
 mod vm_streams;
 mod vm_test_external_fn_ptr;
 mod vm_test_from_value_derive;
@@ -184,6 +203,7 @@ mod vm_test_imports;
 mod vm_test_instance_fns;
 mod vm_test_linked_list;
 mod vm_test_mod;
+mod vm_traits;
 mod vm_try;
 mod vm_tuples;
 mod vm_typed_tuple;
@@ -1,5 +1,7 @@
 //! Test for iterator functions
 
+use runestick::VmErrorKind::*;
+
 #[test]
 fn test_sum() {
     assert_eq!(rune!(u32 => pub fn main() { [1, 2, 3].iter().sum() }), 6)
@@ -41,3 +43,183 @@ fn test_prod_float_negative() {
         -3.0
     )
 }
+
+#[test]
+fn test_sum_float() {
+    assert_eq!(
+        rune!(f64 => pub fn main() { [1.0, 0.5, 2.0].iter().sum() }),
+        3.5
+    )
+}
+
+#[test]
+fn test_iter_searching() {
+    rune! { () =>
+        pub fn main() {
+            assert!([1, 2, 3].iter().any(|n| n == 2));
+            assert!(![1, 2, 3].iter().any(|n| n == 4));
+            assert_eq!([1, 2, 3].iter().position(|n| n == 3), Some(2));
+            assert_eq!([1, 2, 3].iter().position(|n| n == 4), None);
+            assert_eq!([1, 2, 3].iter().last(), Some(3));
+            assert_eq!([].iter().last(), None);
+
+            let it = [1, 2, 3].iter();
+            assert_eq!(it.nth(1), Some(2));
+            assert_eq!(it.nth(0), Some(3));
+            assert_eq!(it.nth(0), None);
+        }
+    };
+}
+
+#[test]
+fn test_iter_min_max() {
+    rune! { () =>
+        pub fn main() {
+            assert_eq!([3, 1, 2].iter().min(), Some(1));
+            assert_eq!([3, 1, 2].iter().max(), Some(3));
+            assert_eq!(["b", "c", "a"].iter().max(), Some("c"));
+            assert_eq!([].iter().min(), None);
+
+            let v = [(1, "a"), (0, "b"), (1, "c"), (0, "d")];
+            assert_eq!(v.iter().min_by_key(|t| t.0), Some((0, "b")));
+            assert_eq!(v.iter().max_by_key(|t| t.0), Some((1, "c")));
+            assert_eq!(v.iter().min_by(|a, b| std::cmp::cmp(b.0, a.0)), Some((1, "a")));
+            assert_eq!(v.iter().max_by(|a, b| std::cmp::cmp(b.0, a.0)), Some((0, "d")));
+        }
+    };
+}
+
+#[test]
+fn test_iter_adapters() {
+    rune! { () =>
+        pub fn main() {
+            let it = [1, 2, 3].iter().zip(["a", "b"]);
+            assert_eq!(it.collect_vec(), [(1, "a"), (2, "b")]);
+
+            let it = [1, 2, 3, 1].iter().take_while(|n| n < 3);
+            assert_eq!(it.collect_vec(), [1, 2]);
+
+            let it = [1, 2, 3, 1].iter().skip_while(|n| n < 3);
+            assert_eq!(it.collect_vec(), [3, 1]);
+
+            let it = std::iter::range(0, 10).step_by(3);
+            assert_eq!(it.collect_vec(), [0, 3, 6, 9]);
+
+            let it = std::iter::range(0, 5).chunks(2);
+            assert_eq!(it.collect_vec(), [[0, 1], [2, 3], [4]]);
+
+            let it = [1, 2, 3, 4].iter().scan(0, |sum, n| if n < 4 { Some(sum + n) } else { None });
+            assert_eq!(it.collect_vec(), [1, 3, 6]);
+
+            let seen = [];
+            let it = [1, 2].iter().inspect(|n| seen.push(n)).map(|n| n * 10);
+            assert_eq!(it.collect_vec(), [10, 20]);
+            assert_eq!(seen, [1, 2]);
+
+            let it = [1, 2].iter().cycle().take(5);
+            assert_eq!(it.collect_vec(), [1, 2, 1, 2, 1]);
+            assert_eq!([].iter().cycle().next(), None);
+
+            let it = [[1, 2], [], [3]].iter().flatten();
+            assert_eq!(it.collect_vec(), [1, 2, 3]);
+
+            let it = [1, 1, 2, 3, 3, 1].iter().dedup();
+            assert_eq!(it.collect_vec(), [1, 2, 3, 1]);
+
+            let (even, odd) = std::iter::range(0, 6).partition(|n| n % 2 == 0);
+            assert_eq!(even, [0, 2, 4]);
+            assert_eq!(odd, [1, 3, 5]);
+        }
+    };
+}
+
+#[test]
+fn test_iter_collect() {
+    rune! { () =>
+        pub fn main() {
+            let map = [("a", 1), ("b", 2)].iter().collect_hash_map();
+            assert_eq!(map.len(), 2);
+            assert_eq!(map.get("b"), Some(2));
+
+            let set = [1, 2, 1].iter().collect_hash_set();
+            assert_eq!(set.len(), 2);
+            assert!(set.contains(1));
+
+            assert_eq!("hello".chars().rev().collect_string(), "olleh");
+            assert_eq!(["a", "b"].iter().collect_string(), "ab");
+        }
+    };
+}
+
+#[test]
+fn test_iter_step_by_zero() {
+    assert_vm_error!(
+        r#"pub fn main() { std::iter::range(0, 10).step_by(0) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "step must be non-zero");
+        }
+    );
+}
+
+#[test]
+fn test_iter_collect_endless() {
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let seen = [];
+            let it = [1, 2].iter().cycle();
+            it.next();
+            it.inspect(|n| { seen.push(n); if seen.len() > 3 { panic("stop") } }).collect_vec()
+        }
+        "#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "stop");
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let it = std::iter::range(0, 9223372036854775807);
+            it.map(|n| if n > 3 { panic("stop") } else { (`{}`, n) }).collect_object()
+        }
+        "#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "stop");
+        }
+    );
+}
+
+#[test]
+fn test_iter_protocols() {
+    rune! { () =>
+        struct Version { major, minor, build }
+
+        impl Version {
+            fn eq(self, other) {
+                self.major == other.major && self.minor == other.minor
+            }
+
+            fn cmp(self, other) {
+                std::cmp::cmp((self.major, self.minor), (other.major, other.minor))
+            }
+        }
+
+        pub fn main() {
+            let v = [
+                Version { major: 1, minor: 10, build: 1 },
+                Version { major: 1, minor: 10, build: 2 },
+                Version { major: 1, minor: 2, build: 3 },
+                Version { major: 2, minor: 0, build: 4 },
+                Version { major: 1, minor: 2, build: 5 },
+            ];
+
+            assert_eq!(v.iter().min().map(|v| v.build), Some(3));
+            assert_eq!(v.iter().max().map(|v| v.build), Some(4));
+            assert_eq!(v.iter().min_by_key(|v| v).map(|v| v.build), Some(3));
+
+            let builds = v.iter().dedup().map(|v| v.build).collect_vec();
+            assert_eq!(builds, [1, 3, 4, 5]);
+        }
+    };
+}
@@ -0,0 +1,108 @@
+//! Tests for the `std::string` module.
+
+use runestick::VmErrorKind::*;
+
+#[test]
+fn test_string_trim_and_case() {
+    rune! { () =>
+        pub fn main() {
+            let s = "  Hello, Wörld! ";
+            assert_eq!(s.trim(), "Hello, Wörld!");
+            assert_eq!(s.trim_start(), "Hello, Wörld! ");
+            assert_eq!(s.trim_end(), "  Hello, Wörld!");
+            assert_eq!(s.to_uppercase(), "  HELLO, WÖRLD! ");
+            assert_eq!(s.to_lowercase(), "  hello, wörld! ");
+            assert_eq!("ab".repeat(3), "ababab");
+            assert_eq!("ab".repeat(0), "");
+            assert_eq!("straße".to_uppercase(), "STRASSE");
+        }
+    };
+}
+
+#[test]
+fn test_string_search() {
+    rune! { () =>
+        pub fn main() {
+            let s = "hello world";
+            assert_eq!(s.find("o"), Some(4));
+            assert_eq!(s.find('o'), Some(4));
+            assert_eq!(s.rfind('o'), Some(7));
+            assert_eq!(s.find("xyz"), None);
+            assert!(s.contains("lo w"));
+            assert!(s.contains('w'));
+            assert!(!s.contains('x'));
+            assert_eq!(s.replace("o", "0"), "hell0 w0rld");
+            assert_eq!(s.replace('l', ""), "heo word");
+            assert_eq!("ab".replace("", "-"), "-a-b-");
+            assert_eq!("aa".replace("a", "bcd"), "bcdbcd");
+        }
+    };
+}
+
+#[test]
+fn test_string_iterators() {
+    rune! { () =>
+        pub fn main() {
+            let s = "first line\n  second  line \r\nthird";
+            assert_eq!(s.lines().collect_vec(), ["first line", "  second  line ", "third"]);
+            assert_eq!(
+                s.split_whitespace().collect_vec(),
+                ["first", "line", "second", "line", "third"]
+            );
+            assert_eq!("hé!".char_indices().collect_vec(), [(0, 'h'), (1, 'é'), (3, '!')]);
+            assert_eq!(["a", "b", "c"].iter().join(", "), "a, b, c");
+            assert_eq!([].iter().join(", "), "");
+            assert_eq!("a b".split_whitespace().rev().join("-"), "b-a");
+        }
+    };
+}
+
+#[test]
+fn test_string_parse_float() {
+    rune! { () =>
+        pub fn main() {
+            assert_eq!(std::string::parse_float("1.5"), Ok(1.5));
+            assert!(std::string::parse_float("nope").is_err());
+        }
+    };
+}
+
+#[test]
+fn test_string_slicing() {
+    rune! { () =>
+        pub fn main() {
+            let s = "héllo";
+            assert_eq!(s[0..1], "h");
+            assert_eq!(s[1..3], "é");
+            assert_eq!(s[3..], "llo");
+            assert_eq!(s[..=2], "hé");
+            assert_eq!(s.get(0..2), None);
+        }
+    };
+
+    assert_vm_error!(
+        r#"pub fn main() { "héllo"[0..2] }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "index outside of character boundary");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { "héllo"[..=1] }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "index outside of character boundary");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { ["a", 1].iter().join(", ") }"#,
+        Expected { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { "ab".repeat(4611686018427387904) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "capacity overflow");
+        }
+    );
+}
@@ -0,0 +1,218 @@
+//! Tests for the `std::vec` module.
+
+use runestick::VmErrorKind::*;
+
+#[test]
+fn test_vec_mutation() {
+    rune! { () =>
+        pub fn main() {
+            let v = [1, 2, 3];
+            v.insert(1, 10);
+            assert_eq!(v, [1, 10, 2, 3]);
+            assert_eq!(v.remove(0), 1);
+            assert_eq!(v, [10, 2, 3]);
+            v.swap(0, 2);
+            assert_eq!(v, [3, 2, 10]);
+            v.reverse();
+            assert_eq!(v, [10, 2, 3]);
+            v.truncate(1);
+            assert_eq!(v, [10]);
+            assert!(v.contains(10));
+            assert!(!v.contains(2));
+        }
+    };
+}
+
+#[test]
+fn test_vec_sort() {
+    rune! { () =>
+        pub fn main() {
+            let v = [3, 1, 2];
+            v.sort();
+            assert_eq!(v, [1, 2, 3]);
+
+            let v = ["b", "c", "a"];
+            v.sort();
+            assert_eq!(v, ["a", "b", "c"]);
+
+            let v = [(2, "a"), (1, "b"), (2, "b")];
+            v.sort();
+            assert_eq!(v, [(1, "b"), (2, "a"), (2, "b")]);
+
+            let v = [3, 1, 2];
+            v.sort_by(|a, b| b.cmp(a));
+            assert_eq!(v, [3, 2, 1]);
+
+            let v = [[1, 2], [1], []];
+            v.sort_by(|a, b| std::cmp::cmp(a.len(), b.len()));
+            assert_eq!(v, [[], [1], [1, 2]]);
+        }
+    };
+}
+
+#[test]
+fn test_vec_filtering() {
+    rune! { () =>
+        pub fn main() {
+            let v = [1, 1, 2, 3, 3, 3, 1];
+            v.dedup();
+            assert_eq!(v, [1, 2, 3, 1]);
+
+            v.retain(|n| n != 1);
+            assert_eq!(v, [2, 3]);
+        }
+    };
+}
+
+#[test]
+fn test_vec_search() {
+    rune! { () =>
+        pub fn main() {
+            let v = [1, 3, 5, 7];
+            assert_eq!(v.binary_search(5), Ok(2));
+            assert_eq!(v.binary_search(4), Err(2));
+            assert_eq!(v.binary_search(8), Err(4));
+
+            assert_eq!(v.first(), Some(1));
+            assert_eq!(v.last(), Some(7));
+            assert_eq!([].first(), None);
+            assert_eq!(v.get(1), Some(3));
+            assert_eq!(v.get(4), None);
+            assert_eq!(v.get(1..3), Some([3, 5]));
+            assert_eq!(v.get(2..5), None);
+        }
+    };
+}
+
+#[test]
+fn test_vec_slices() {
+    rune! { () =>
+        pub fn main() {
+            let v = [1, 2, 3, 4, 5];
+            assert_eq!(v[1..3], [2, 3]);
+            assert_eq!(v[..2], [1, 2]);
+            assert_eq!(v[3..], [4, 5]);
+            assert_eq!(v[1..=1], [2]);
+
+            let (a, b) = v.split_at(2);
+            assert_eq!(a, [1, 2]);
+            assert_eq!(b, [3, 4, 5]);
+
+            assert_eq!(v.chunks(2).collect_vec(), [[1, 2], [3, 4], [5]]);
+            assert_eq!(v.windows(4).collect_vec(), [[1, 2, 3, 4], [2, 3, 4, 5]]);
+            assert_eq!(v.windows(6).collect_vec(), []);
+        }
+    };
+}
+
+#[test]
+fn test_vec_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { let v = [1]; v.insert(2, 0); }"#,
+        OutOfRange { index, len } => {
+            assert_eq!(index.to_string(), "2");
+            assert_eq!(len.to_string(), "1");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let v = [1]; v.remove(1); }"#,
+        OutOfRange { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let v = [1, "a"]; v.sort(); }"#,
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(op, "cmp");
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let v = std::iter::range(0, 200).map(|n| (n * 7919 % 200) as float).collect_vec();
+            v[100] = 0.0 / 0.0;
+            v.sort();
+        }
+        "#,
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(op, "cmp");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2][1..3] }"#,
+        OutOfRange { index, len } => {
+            assert_eq!(index.to_string(), "3");
+            assert_eq!(len.to_string(), "2");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2][0..=2] }"#,
+        OutOfRange { index, .. } => {
+            assert_eq!(index.to_string(), "2");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2][2..1] }"#,
+        OutOfRange { index, .. } => {
+            assert_eq!(index.to_string(), "2");
+        }
+    );
+}
+
+#[test]
+fn test_vec_sort_inconsistent() {
+    assert_eq!(
+        rune! { i64 =>
+            pub fn main() {
+                let v = std::iter::range(0, 200).map(|n| n * 7919 % 200).collect_vec();
+                v.sort_by(|a, b| std::cmp::cmp((a + b * 2) % 7, 3));
+                v.iter().sum()
+            }
+        },
+        19900
+    );
+}
+
+#[test]
+fn test_vec_protocols() {
+    rune! { () =>
+        struct Version { major, minor, build }
+
+        impl Version {
+            fn eq(self, other) {
+                self.major == other.major && self.minor == other.minor
+            }
+
+            fn cmp(self, other) {
+                std::cmp::cmp((self.major, self.minor), (other.major, other.minor))
+            }
+        }
+
+        pub fn main() {
+            let v = [
+                Version { major: 2, minor: 0, build: 1 },
+                Version { major: 1, minor: 10, build: 2 },
+                Version { major: 1, minor: 10, build: 3 },
+                Version { major: 1, minor: 2, build: 4 },
+            ];
+
+            assert!(v.contains(Version { major: 1, minor: 2, build: 0 }));
+            assert!(!v.contains(Version { major: 1, minor: 3, build: 0 }));
+
+            v.sort();
+            let builds = v.iter().map(|v| v.build).collect_vec();
+            assert_eq!(builds, [4, 2, 3, 1]);
+
+            assert_eq!(v.binary_search(Version { major: 2, minor: 0, build: 0 }), Ok(3));
+            assert_eq!(v.binary_search(Version { major: 1, minor: 5, build: 0 }), Err(1));
+
+            v.dedup();
+            let builds = v.iter().map(|v| v.build).collect_vec();
+            assert_eq!(builds, [4, 2, 1]);
+        }
+    };
+}
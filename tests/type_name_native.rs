@@ -73,7 +73,7 @@ fn test_field_fn() {
             (t1, ),
             String => pub fn main(val) { std::any::type_name_of_val(val.x) }
         },
-        "::std::int"
+        "::std::u32"
     );
 }
 
@@ -166,3 +166,87 @@ fn test_bitwise_not() {
         !0b10100,
     };
 }
+
+#[test]
+fn test_pow() {
+    assert_eq! {
+        rune_s!((i64, f64, i64) => r#"
+            pub fn main() {
+                let a = 3;
+                let b = 4;
+                (a ** b, 2.0 ** 0.5 * 2.0 ** 0.5, 2 * 3 ** 2)
+            }
+        "#),
+        (81, 2.0000000000000004, 18),
+    };
+
+    assert_vm_error!(
+        r#"pub fn main() { let a = 2; let b = 63; a ** b }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let a = 2; let b = -1; a ** b }"#,
+        NegativeExponent { exponent } => {
+            assert_eq!(exponent.to_string(), "-1");
+        }
+    );
+
+    assert_parse_error!(
+        r#"pub fn main() { 2 ** 3 ** 2 }"#,
+        span, PrecedenceGroupRequired => {
+            assert_eq!(span, Span::new(16, 22));
+        }
+    );
+}
+
+#[test]
+fn test_int_methods() {
+    assert_eq! {
+        rune!((i64, i64, i64, i64, i64, i64) =>
+            pub fn main() {
+                let max = 9223372036854775807;
+                let min = -9223372036854775808;
+
+                (
+                    max.wrapping_add(1),
+                    min.wrapping_sub(1),
+                    max.saturating_mul(2),
+                    min.saturating_sub(1),
+                    (3).pow(4),
+                    min.wrapping_abs(),
+                )
+            }
+        ),
+        (i64::MIN, i64::MAX, i64::MAX, i64::MIN, 81, i64::MIN),
+    };
+
+    assert_eq! {
+        rune!((Option<i64>, Option<i64>, Option<i64>, Option<i64>) =>
+            pub fn main() {
+                let max = 9223372036854775807;
+                let min = -9223372036854775808;
+
+                (max.checked_add(1), (2).checked_pow(10), min.checked_neg(), (10).checked_rem(0))
+            }
+        ),
+        (None, Some(1024), None, None),
+    };
+
+    assert_vm_error!(
+        r#"pub fn main() { let a = 2; a.pow(64) }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let a = 2; a.pow(-1) }"#,
+        NegativeExponent { exponent } => {
+            assert_eq!(exponent.to_string(), "-1");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let a = 10; a.wrapping_div(0) }"#,
+        DivideByZero => {}
+    );
+}
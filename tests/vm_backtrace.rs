@@ -0,0 +1,72 @@
+use rune::{EmitDiagnostics as _, Sources};
+use runestick::{Context, Source, VmErrorKind};
+
+const SOURCE: &str = r#"
+fn divide(a, b) {
+    a / b
+}
+
+fn compute(n) {
+    [1, 2, 0].iter().map(|v| divide(n, v)).collect_vec()
+}
+
+pub fn main() {
+    compute(10)
+}
+"#;
+
+#[test]
+fn test_backtrace() {
+    let context = Context::with_default_modules().unwrap();
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let vm = rune_tests::vm(&context, &mut sources).unwrap();
+    let error = vm.execute(&["main"], ()).unwrap().complete().unwrap_err();
+
+    let (kind, _) = error.as_unwound();
+    assert!(matches!(kind, VmErrorKind::DivideByZero));
+
+    // NB: the closure is called from a nested execution by `map`.
+    let frames = error
+        .backtrace()
+        .iter()
+        .map(|frame| frame.signature().map(|s| s.path.to_string()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        frames,
+        vec![
+            Some(String::from("divide")),
+            Some(String::from("compute::$0::$0")),
+            Some(String::from("compute")),
+            Some(String::from("main")),
+        ]
+    );
+
+    let lines = error
+        .backtrace()
+        .iter()
+        .map(|frame| {
+            let inst = frame.instruction().unwrap();
+            let source = sources.get(inst.source_id).unwrap();
+            source.position_to_unicode_line_char(inst.span.start.into_usize()).0
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(lines, vec![2, 6, 6, 10]);
+
+    // NB: frames only print the function and instruction pointer, not the
+    // unit they belong to.
+    let debug = format!("{:?}", error.backtrace()[0]);
+    assert!(debug.starts_with("VmErrorFrame { function: Some("));
+    assert!(!debug.contains("instructions"));
+
+    let mut out = rune::termcolor::NoColor::new(Vec::new());
+    error.emit_diagnostics(&mut out, &sources).unwrap();
+    let out = String::from_utf8(out.into_inner()).unwrap();
+
+    assert!(out.contains("division by zero"));
+    assert!(out.contains("called from compute(n)"));
+    assert!(out.contains("called from main()"));
+}
@@ -0,0 +1,103 @@
+use rune::IrErrorKind;
+use rune_tests::*;
+
+#[test]
+fn test_numeric_casts() {
+    assert_eq! {
+        rune!((f64, i64, i64, u8, char) =>
+            pub fn main() {
+                let n = 42;
+                let f = -3.9;
+                let c = 'a';
+                (n as float, f as int, c as int, n as byte, (n + 55) as char)
+            }
+        ),
+        (42.0, -3, 97, 42, 'a'),
+    };
+
+    assert_eq! {
+        rune!((i64, f64, f64) =>
+            pub fn main() {
+                let n = 7;
+                (n as float as int, -n as float, 1.0 + n as float * 2.0)
+            }
+        ),
+        (7, -7.0, 15.0),
+    }
+}
+
+#[test]
+fn test_const_casts() {
+    assert_eq! {
+        rune!((i64, u8, char, f64) =>
+            const A = 3.7 as int;
+            const B = 255 as byte;
+            const C = (0x41 + A) as char;
+
+            pub fn main() {
+                (A, B, C, 2 as float)
+            }
+        ),
+        (3, 255, 'D', 2.0),
+    };
+
+    // NB: types in constants are resolved like any other path.
+    assert_eq! {
+        rune!((i64, i64, u8) =>
+            use std::int as integer;
+
+            const A = 1e10 as std::int;
+            const B = 2.5 as integer;
+            const C = 255.9 as byte;
+
+            pub fn main() {
+                (A, B, C)
+            }
+        ),
+        (10_000_000_000, 2, 255),
+    };
+}
+
+#[test]
+fn test_cast_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { let n = 256; n as byte }"#,
+        CastOutOfRange { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let n = -1; n as char }"#,
+        CastOutOfRange { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let f = 1.0 / 0.0; f as int }"#,
+        CastOutOfRange { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let s = "1"; s as int }"#,
+        UnsupportedCast { .. } => {}
+    );
+
+    assert_compile_error! {
+        r#"pub fn main() { 1000 as byte }"#,
+        span, CompileErrorKind::IrError { error: IrErrorKind::CastOutOfRange { .. } } => {
+            assert_eq!(span, Span::new(16, 28));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const A = 1e19 as int; pub fn main() { A }"#,
+        span, QueryError { error: QueryErrorKind::IrError { error: IrErrorKind::CastOutOfRange { .. } } } => {
+            assert_eq!(span, Span::new(10, 21));
+        }
+    };
+
+    assert_compile_error! {
+        r#"struct Foo; pub fn main() { 1 as Foo }"#,
+        span, UnsupportedCastType { .. } => {
+            assert_eq!(span, Span::new(33, 36));
+        }
+    };
+}
@@ -1,3 +1,6 @@
+use rune::{IrErrorKind, QueryErrorKind};
+use rune_tests::*;
+
 macro_rules! test_op {
     ($ty:ty => $lhs:literal $op:tt $rhs:literal = $result:literal) => {{
         let program = format!(
@@ -57,6 +60,40 @@ fn test_integer_ops() {
     test_op!(bool => 0 >= 2 = false);
 }
 
+#[test]
+fn test_pow_ops() {
+    assert_eq!(
+        rune_s!(i64 => r#"const A = 2; const VALUE = A ** 10; pub fn main() { VALUE }"#),
+        1024
+    );
+
+    assert_eq!(
+        rune_s!(f64 => r#"const VALUE = 4.0 ** 0.5; pub fn main() { VALUE }"#),
+        2.0
+    );
+
+    assert_compile_error! {
+        r#"const VALUE = 2 ** 64; pub fn main() { VALUE }"#,
+        span, QueryError { error: QueryErrorKind::IrError { error: IrErrorKind::IntegerOverflow } } => {
+            assert_eq!(span, Span::new(14, 21));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const VALUE = 2 ** 4294967295; pub fn main() { VALUE }"#,
+        span, QueryError { error: QueryErrorKind::IrError { error: IrErrorKind::IntegerOverflow } } => {
+            assert_eq!(span, Span::new(14, 29));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const B = 0 - 1; const VALUE = 2 ** B; pub fn main() { VALUE }"#,
+        span, QueryError { error: QueryErrorKind::IrError { error: IrErrorKind::NegativeExponent { .. } } } => {
+            assert_eq!(span, Span::new(36, 37));
+        }
+    };
+}
+
 macro_rules! test_float_op {
     ($ty:ty => $lhs:literal $op:tt $rhs:literal = $result:literal) => {{
         let program = format!(
@@ -0,0 +1,88 @@
+use rune::Sources;
+use runestick::coverage::{Coverage, CoverageReport, LineCoverage};
+use runestick::{Context, Source, VmErrorKind};
+
+const SOURCE: &str = r#"
+fn double(n) {
+    n * 2
+}
+
+pub fn main(n) {
+    let values = [1, 2, 3].iter().map(|v| double(v)).collect_vec();
+
+    if n > 10 {
+        return 0;
+    }
+
+    values.len()
+}
+"#;
+
+fn coverage(n: i64) -> LineCoverage {
+    let context = Context::with_default_modules().unwrap();
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let coverage = Coverage::new();
+
+    let mut vm = rune_tests::vm(&context, &mut sources).unwrap();
+    vm.set_coverage(Some(coverage.clone()));
+    vm.execute(&["main"], (n,)).unwrap().complete().unwrap();
+
+    assert!(coverage.spans().iter().any(|s| s.hits == 0));
+    coverage.lines(0, sources.get(0).unwrap())
+}
+
+#[test]
+fn test_coverage_lines() {
+    let coverage = coverage(1);
+
+    // NB: the closure is called by a native function, which executes it in a
+    // nested virtual machine. Line 7 counts it since it's on the same line.
+    assert_eq!(coverage.lines.get(&3), Some(&3));
+    assert_eq!(coverage.lines.get(&7), Some(&3));
+    assert_eq!(coverage.lines.get(&10), Some(&0));
+    assert_eq!(coverage.lines.get(&13), Some(&1));
+    assert_eq!(coverage.lines.get(&5), None);
+}
+
+#[test]
+fn test_coverage_report() {
+    let mut report = CoverageReport::new();
+    report.insert(coverage(1));
+    report.insert(coverage(20));
+
+    assert_eq!(report.files.len(), 1);
+    assert_eq!(report.files[0].lines.get(&3), Some(&6));
+    assert_eq!(report.files[0].lines.get(&10), Some(&1));
+    assert_eq!(report.files[0].lines.get(&13), Some(&1));
+
+    let mut out = Vec::new();
+    report.write_lcov(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+
+    assert!(out.starts_with("TN:\nSF:main\n"));
+    assert!(out.contains("DA:3,6\n"));
+    assert!(out.contains("DA:13,1\n"));
+    assert!(out.ends_with("end_of_record\n"));
+}
+
+#[test]
+fn test_coverage_send_execute() {
+    let context = Context::with_default_modules().unwrap();
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let mut vm = rune_tests::vm(&context, &mut sources).unwrap();
+    vm.set_coverage(Some(Coverage::new()));
+
+    let error = match vm.send_execute(&["main"], (1,)) {
+        Ok(..) => panic!("expected an execution with coverage to not be sendable"),
+        Err(error) => error,
+    };
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::UnsupportedSendExecution { .. }
+    ));
+}
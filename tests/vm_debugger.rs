@@ -0,0 +1,163 @@
+use rune::Sources;
+use runestick::debugger::{Debugger, SourceLine, Stop};
+use runestick::{Context, Source, Value};
+
+const SOURCE: &str = r#"
+fn add(a, b) {
+    let sum = a + b;
+    sum
+}
+
+pub fn main() {
+    let object = #{field: (1, 2)};
+    let x = add(1, 2);
+    let y = add(x, 3);
+    y
+}
+"#;
+
+fn debugger() -> Debugger {
+    debugger_for(SOURCE)
+}
+
+fn debugger_for(source: &str) -> Debugger {
+    let context = Context::with_default_modules().unwrap();
+    let mut sources = Sources::new();
+    let source_id = sources.insert(Source::new("main", source));
+
+    let vm = rune_tests::vm(&context, &mut sources).unwrap();
+    let mut debugger = Debugger::new(vm.execute(&["main"], ()).unwrap());
+    debugger.insert_source(source_id, sources.get(source_id).unwrap().clone());
+    debugger
+}
+
+fn line(line: usize) -> SourceLine {
+    SourceLine { source_id: 0, line }
+}
+
+fn locals(debugger: &Debugger, frame: usize) -> Vec<(String, i64)> {
+    debugger.frames()[frame]
+        .locals()
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_owned(), value.clone().into_integer().ok()?)))
+        .collect()
+}
+
+#[test]
+fn test_breakpoints() {
+    let mut debugger = debugger();
+    assert!(debugger.set_breakpoint(0, 2));
+    assert!(!debugger.set_breakpoint(0, 5));
+
+    assert!(matches!(debugger.resume().unwrap(), Stop::Breakpoint(l) if l == line(2)));
+    assert_eq!(
+        locals(&debugger, 0),
+        vec![("a".to_owned(), 1), ("b".to_owned(), 2)]
+    );
+
+    let frames = debugger.frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].signature().unwrap().to_string(), "add(a, b)");
+    assert_eq!(frames[1].signature().unwrap().to_string(), "main()");
+
+    assert!(matches!(debugger.resume().unwrap(), Stop::Breakpoint(l) if l == line(2)));
+    assert_eq!(
+        locals(&debugger, 0),
+        vec![("a".to_owned(), 3), ("b".to_owned(), 3)]
+    );
+    assert_eq!(locals(&debugger, 1), vec![("x".to_owned(), 3)]);
+
+    assert!(debugger.clear_breakpoint(0, 2));
+
+    match debugger.resume().unwrap() {
+        Stop::Complete(Value::Integer(6)) => (),
+        stop => panic!("unexpected stop: {:?}", stop),
+    }
+}
+
+#[test]
+fn test_block_scoped_locals() {
+    let mut debugger = debugger_for(
+        r#"
+pub fn main() {
+    let a = 1;
+    if a > 0 {
+        let b = 2;
+        a + b;
+    }
+    for n in [3] {
+        n + a;
+    }
+}
+"#,
+    );
+
+    assert!(debugger.set_breakpoint(0, 5));
+    assert!(debugger.set_breakpoint(0, 8));
+
+    assert!(matches!(debugger.resume().unwrap(), Stop::Breakpoint(l) if l == line(5)));
+    assert_eq!(
+        locals(&debugger, 0),
+        vec![("a".to_owned(), 1), ("b".to_owned(), 2)]
+    );
+
+    // NB: the loop iterator is stored where `b` used to be.
+    assert!(matches!(debugger.resume().unwrap(), Stop::Breakpoint(l) if l == line(8)));
+    let names = debugger.frames()[0]
+        .locals()
+        .into_iter()
+        .map(|(name, _)| name.to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["a".to_owned(), "n".to_owned()]);
+}
+
+#[test]
+fn test_stepping() {
+    let mut debugger = debugger();
+    assert_eq!(debugger.current_line(), Some(line(7)));
+
+    assert!(matches!(debugger.step_over().unwrap(), Stop::Step));
+    assert_eq!(debugger.current_line(), Some(line(8)));
+
+    assert!(matches!(debugger.step_into().unwrap(), Stop::Step));
+    assert_eq!(debugger.current_line(), Some(line(2)));
+    assert_eq!(debugger.frames().len(), 2);
+
+    assert!(matches!(debugger.step_over().unwrap(), Stop::Step));
+    assert_eq!(debugger.current_line(), Some(line(3)));
+    assert_eq!(
+        locals(&debugger, 0),
+        vec![
+            ("a".to_owned(), 1),
+            ("b".to_owned(), 2),
+            ("sum".to_owned(), 3)
+        ]
+    );
+
+    assert!(matches!(debugger.step_out().unwrap(), Stop::Step));
+    assert_eq!(debugger.frames().len(), 1);
+
+    // Stepping over a call doesn't stop inside of it.
+    let line_before = debugger.current_line();
+    assert!(matches!(debugger.step_over().unwrap(), Stop::Step));
+    assert_ne!(debugger.current_line(), line_before);
+    assert_eq!(debugger.frames().len(), 1);
+}
+
+#[test]
+fn test_evaluate() {
+    let mut debugger = debugger();
+    debugger.set_breakpoint(0, 3);
+    debugger.resume().unwrap();
+
+    assert_eq!(
+        debugger.evaluate(0, "sum").unwrap().into_integer().unwrap(),
+        3
+    );
+    assert!(debugger.evaluate(0, "object").is_none());
+
+    let value = debugger.evaluate(1, "object.field.1").unwrap();
+    assert_eq!(value.into_integer().unwrap(), 2);
+    assert!(debugger.evaluate(1, "object.missing").is_none());
+    assert!(debugger.evaluate(1, "object.field.2").is_none());
+}
@@ -0,0 +1,181 @@
+use runestick::fuel::FuelCosts;
+use runestick::{GeneratorState, Vm, VmError, VmErrorKind, VmHaltInfo};
+
+/// Run the given virtual machine to completion with the given amount of fuel,
+/// topping it up each time it runs out. Returns the output and the number of
+/// times it ran out of fuel.
+fn run_with_fuel(mut vm: Vm, fuel: u64, costs: FuelCosts) -> Result<(i64, usize), VmError> {
+    vm.set_fuel(Some(fuel));
+    vm.set_fuel_costs(costs);
+    let mut execution = vm.execute(["main"], ())?;
+    let mut halts = 0;
+
+    loop {
+        match execution.resume() {
+            Ok(GeneratorState::Complete(value)) => {
+                return Ok((value.into_integer()?, halts));
+            }
+            Ok(GeneratorState::Yielded(..)) => panic!("unexpected yield"),
+            Err(e) => match e.kind() {
+                VmErrorKind::Halted {
+                    halt: VmHaltInfo::OutOfFuel,
+                } => {
+                    assert!(execution.fuel()?.unwrap() < costs.call.max(costs.alloc));
+                    execution.add_fuel(fuel)?;
+                    halts += 1;
+                }
+                _ => return Err(e),
+            },
+        }
+    }
+}
+
+#[test]
+fn test_fuel_resume() {
+    let vm = || {
+        rune_tests::rune_vm! {
+            fn add(a, b) {
+                a + b
+            }
+
+            pub fn main() {
+                let n = 0;
+
+                for i in 0..100 {
+                    n = add(n, i);
+                }
+
+                n
+            }
+        }
+    };
+
+    let (output, halts) = run_with_fuel(vm(), 100, FuelCosts::DEFAULT).unwrap();
+    assert_eq!(output, 4950);
+    assert!(halts > 10);
+
+    // Fuel metering is deterministic.
+    assert_eq!(
+        run_with_fuel(vm(), 100, FuelCosts::DEFAULT).unwrap(),
+        (4950, halts)
+    );
+
+    let (output, more_halts) = run_with_fuel(vm(), 10, FuelCosts::DEFAULT).unwrap();
+    assert_eq!(output, 4950);
+    assert!(more_halts > halts);
+}
+
+#[test]
+fn test_fuel_costs() {
+    let vm = || {
+        rune_tests::rune_vm! {
+            fn one() {
+                1
+            }
+
+            pub fn main() {
+                one() + one()
+            }
+        }
+    };
+
+    // NB: with a single unit of fuel added at a time, the virtual machine
+    // runs out of fuel once for every unit of fuel it needs.
+    let fuel = |costs: FuelCosts| {
+        let (output, halts) = run_with_fuel(vm(), 1, costs).unwrap();
+        assert_eq!(output, 2);
+        halts
+    };
+
+    let cheap = fuel(FuelCosts {
+        instruction: 1,
+        call: 1,
+        alloc: 1,
+    });
+
+    let expensive = fuel(FuelCosts {
+        instruction: 1,
+        call: 101,
+        alloc: 1,
+    });
+
+    // Two calls which cost 100 more each.
+    assert_eq!(expensive - cheap, 200);
+}
+
+#[test]
+fn test_fuel_disabled() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            let n = 0;
+            for i in 0..1000 { n += i; }
+            n
+        }
+    };
+
+    assert_eq!(vm.fuel(), None);
+    vm.add_fuel(10);
+    assert_eq!(vm.fuel(), None);
+
+    vm.set_fuel(Some(10));
+    assert_eq!(vm.fuel(), Some(10));
+    vm.set_fuel(None);
+
+    let output = vm.call(["main"], ()).unwrap();
+    assert_eq!(output.into_integer().unwrap(), 499500);
+}
+
+#[test]
+fn test_fuel_nested() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            [1, 2, 3].iter().map(|n| {
+                let sum = 0;
+                for i in 0..1000 { sum += i * n; }
+                sum
+            }).collect_vec()
+        }
+    };
+
+    vm.set_fuel(Some(1000));
+
+    let e = vm.call(["main"], ()).unwrap_err();
+    let (kind, _) = e.as_unwound();
+    assert!(matches!(kind, VmErrorKind::OutOfFuel), "{:?}", kind);
+}
+
+#[test]
+fn test_fuel_protocol_fn() {
+    let source = || {
+        rune_tests::rune_vm! {
+            struct Slow { n }
+
+            impl Slow {
+                fn eq(self, other) {
+                    let sum = 0;
+                    for i in 0..100 { sum += i; }
+                    self.n + sum == other.n + sum
+                }
+            }
+
+            pub fn main() {
+                let a = Slow { n: 1 };
+                a == a
+            }
+        }
+    };
+
+    let mut vm = source();
+    vm.set_fuel(Some(100_000));
+    let output = vm.call(["main"], ()).unwrap();
+    assert!(output.into_bool().unwrap());
+
+    // NB: the protocol function consumes the fuel of the virtual machine
+    // using the operator, but it's run to completion from within the
+    // instruction, so running out of fuel while it executes fails.
+    let mut vm = source();
+    vm.set_fuel(Some(100));
+    let e = vm.call(["main"], ()).unwrap_err();
+    let (kind, _) = e.as_unwound();
+    assert!(matches!(kind, VmErrorKind::OutOfFuel), "{:?}", kind);
+}
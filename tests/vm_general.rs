@@ -752,3 +752,25 @@ fn test_index_get() {
         32,
     };
 }
+
+#[test]
+fn test_index_get_protocol() {
+    // NB: string and integer indexes which aren't handled by the built-in
+    // fast paths fall back to the `INDEX_GET` protocol.
+    assert_eq! {
+        rune! { i64 =>
+            pub fn main() {
+                let m = std::collections::HashMap::new();
+                m.insert("a", 1);
+                let d = std::collections::VecDeque::from([2, 3]);
+                m["a"].unwrap() + d[1]
+            }
+        },
+        4,
+    };
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2]["a"] }"#,
+        UnsupportedIndexGet { .. } => {}
+    );
+}
@@ -0,0 +1,167 @@
+use rune_tests::*;
+
+#[test]
+fn test_impl_binary_ops() {
+    assert_eq! {
+        rune! { (i64, i64, i64, i64) =>
+            struct Vec2 { x, y }
+
+            impl Vec2 {
+                fn add(self, other) {
+                    Vec2 { x: self.x + other.x, y: self.y + other.y }
+                }
+
+                fn sub(self, other) {
+                    Vec2 { x: self.x - other.x, y: self.y - other.y }
+                }
+
+                fn mul(self, n) {
+                    Vec2 { x: self.x * n, y: self.y * n }
+                }
+            }
+
+            pub fn main() {
+                let a = Vec2 { x: 1, y: 2 };
+                let b = Vec2 { x: 3, y: 4 };
+                let c = (a + b) * 2 - Vec2 { x: 1, y: 1 };
+                let d = a.add(b);
+                (c.x, c.y, d.x, d.y)
+            }
+        },
+        (7, 11, 4, 6),
+    };
+}
+
+#[test]
+fn test_impl_bitwise_ops() {
+    assert_eq! {
+        rune! { (i64, i64, i64) =>
+            struct Flags(bits);
+
+            impl Flags {
+                fn bitor(self, other) {
+                    Flags(self.0 | other.0)
+                }
+
+                fn bitand(self, other) {
+                    Flags(self.0 & other.0)
+                }
+
+                fn shl(self, n) {
+                    Flags(self.0 << n)
+                }
+            }
+
+            pub fn main() {
+                let a = Flags(0b0011);
+                let b = Flags(0b0110);
+                ((a | b).0, (a & b).0, (a << 2).0)
+            }
+        },
+        (0b0111, 0b0010, 0b1100),
+    };
+}
+
+#[test]
+fn test_impl_assign_ops() {
+    assert_eq! {
+        rune! { (i64, i64) =>
+            struct Counter { n }
+
+            impl Counter {
+                fn add_assign(self, n) {
+                    self.n += n;
+                }
+            }
+
+            pub fn main() {
+                let c = Counter { n: 1 };
+                c += 2;
+                c += 3;
+                let o = #{ counter: Counter { n: 10 } };
+                o.counter += 5;
+                (c.n, o.counter.n)
+            }
+        },
+        (6, 15),
+    };
+}
+
+#[test]
+fn test_impl_cmp_ops() {
+    assert_eq! {
+        rune! { (bool, bool, bool, bool, bool, bool) =>
+            struct Version { major, minor, build }
+
+            impl Version {
+                fn eq(self, other) {
+                    self.major == other.major && self.minor == other.minor
+                }
+
+                fn cmp(self, other) {
+                    std::cmp::cmp((self.major, self.minor), (other.major, other.minor))
+                }
+            }
+
+            pub fn main() {
+                let a = Version { major: 1, minor: 2, build: 1 };
+                let b = Version { major: 1, minor: 2, build: 2 };
+                let c = Version { major: 1, minor: 10, build: 1 };
+                (a == b, a != c, a < c, c >= b, [a, c] == [b, c], a > c)
+            }
+        },
+        (true, true, true, true, true, false),
+    };
+
+    assert_eq! {
+        rune! { (bool, bool, bool) =>
+            struct Interval { lo, hi }
+
+            impl Interval {
+                fn partial_cmp(self, other) {
+                    if self.hi < other.lo || self.lo > other.hi {
+                        Some(std::cmp::cmp(self.lo, other.lo))
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            pub fn main() {
+                let a = Interval { lo: 0, hi: 1 };
+                let b = Interval { lo: 2, hi: 3 };
+                let c = Interval { lo: 1, hi: 2 };
+                (a < b, a < c, a >= c)
+            }
+        },
+        (true, false, false),
+    };
+}
+
+#[test]
+fn test_impl_protocol_signatures() {
+    // NB: functions which don't have the signature of a protocol function
+    // are plain instance functions.
+    assert_eq! {
+        rune! { (bool, bool) =>
+            struct Foo { n }
+
+            impl Foo {
+                fn eq(self) {
+                    true
+                }
+
+                fn hash(self, seed) {
+                    seed
+                }
+            }
+
+            pub fn main() {
+                let a = Foo { n: 1 };
+                let b = Foo { n: 2 };
+                (a == b, a.eq())
+            }
+        },
+        (false, true),
+    };
+}
@@ -0,0 +1,109 @@
+use rune::{Errors, Options, Sources, Warnings};
+use rune_tests::*;
+
+#[test]
+fn test_let_else() {
+    assert_eq! {
+        rune! { Vec<i64> =>
+            fn unwrap_or_zero(value) {
+                let Some(n) = value else { return 0; };
+                n
+            }
+
+            pub fn main() {
+                [unwrap_or_zero(Some(42)), unwrap_or_zero(None)]
+            }
+        },
+        vec![42, 0],
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            pub fn main() {
+                let values = [Some(1), None, Some(2), Some(3)];
+                let total = 0;
+
+                for value in values {
+                    let Some(n) = value else { continue; };
+                    total += n;
+                }
+
+                total
+            }
+        },
+        6,
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            pub fn main() {
+                let outer = 10;
+                let (a, [b, ..]) = (1, [2, 3]) else { return 0; };
+                let count = 0;
+
+                loop {
+                    let [head, ..] = [] else { break; };
+                    count += head;
+                }
+
+                outer + a + b + count
+            }
+        },
+        13,
+    };
+}
+
+#[test]
+fn test_let_else_errors() {
+    assert_compile_error! {
+        r#"pub fn main() { let Some(n) = None else { 1 }; n }"#,
+        span, LetElseMustDiverge => {
+            assert_eq!(span, Span::new(40, 45));
+        }
+    };
+}
+
+#[test]
+fn test_strict_let() {
+    let errors = compile_strict(r#"pub fn main() { let Some(n) = None; n }"#).unwrap_err();
+    let e = errors.into_iter().next().expect("expected one error");
+
+    let e = match e.into_kind() {
+        rune::ErrorKind::CompileError(e) => e,
+        kind => panic!("expected compile error but was `{:?}`", kind),
+    };
+
+    assert_eq!(rune::Spanned::span(&e), Span::new(20, 27));
+    assert!(matches!(e.into_kind(), RefutableLetPattern));
+
+    assert!(compile_strict(r#"pub fn main() { let Some(n) = None else { return; }; n }"#).is_ok());
+    assert!(
+        compile_strict(r#"pub fn main() { let (a, b) = (1, 2) else { return; }; a + b }"#).is_ok()
+    );
+    assert!(compile_strict(r#"pub fn main() { let a = 1; let _ = 2; a }"#).is_ok());
+    assert!(compile_strict(r#"pub fn main() { let (a, b) = (1, 2); a + b }"#).is_err());
+}
+
+/// Compile the given source with strict `let` patterns enabled.
+fn compile_strict(source: &str) -> Result<(), Errors> {
+    let context = runestick::Context::with_default_modules().unwrap();
+
+    let mut options = Options::default();
+    options.strict_let(true);
+
+    let mut sources = Sources::new();
+    sources.insert(runestick::Source::new("main", source));
+
+    let mut errors = Errors::new();
+
+    match rune::load_sources(
+        &context,
+        &options,
+        &mut sources,
+        &mut errors,
+        &mut Warnings::disabled(),
+    ) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(errors),
+    }
+}
@@ -0,0 +1,249 @@
+use runestick::{Vm, VmErrorKind};
+
+/// Run the `main` function of the given virtual machine to completion,
+/// returning the kind of error it failed with.
+fn run(vm: Vm) -> VmErrorKind {
+    let result = vm.execute(["main"], ()).and_then(|mut e| e.complete());
+    let (e, _) = result.unwrap_err().into_unwound();
+    e.into_kind()
+}
+
+#[test]
+fn test_memory_limit() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            let v = [];
+
+            loop {
+                v.push(1);
+            }
+        }
+    };
+
+    vm.set_memory_limit(Some(1 << 16));
+    let e = run(vm);
+
+    assert!(matches!(
+        e,
+        VmErrorKind::MemoryLimitExceeded { limit } if limit == 1 << 16
+    ));
+}
+
+#[test]
+fn test_memory_limit_strings() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            let s = "a";
+
+            loop {
+                s = s + s;
+            }
+        }
+    };
+
+    vm.set_memory_limit(Some(1 << 20));
+    let e = run(vm);
+    assert!(matches!(e, VmErrorKind::MemoryLimitExceeded { .. }));
+}
+
+#[test]
+fn test_memory_limit_string_replace() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            "a".repeat(1000).replace("a", "a".repeat(100))
+        }
+    };
+
+    vm.set_memory_limit(Some(1 << 16));
+    let e = run(vm);
+    assert!(matches!(e, VmErrorKind::MemoryLimitExceeded { .. }));
+}
+
+#[test]
+fn test_memory_limit_bigint_pow() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            std::num::BigInt::new(3).pow(1000000)
+        }
+    };
+
+    vm.set_memory_limit(Some(1 << 16));
+    let e = run(vm);
+    assert!(matches!(e, VmErrorKind::MemoryLimitExceeded { .. }));
+}
+
+#[test]
+fn test_memory_limit_bigint_mul() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            let n = std::num::BigInt::new(3);
+
+            loop {
+                n = n * n;
+            }
+        }
+    };
+
+    vm.set_memory_limit(Some(1 << 16));
+    let e = run(vm);
+    assert!(matches!(e, VmErrorKind::MemoryLimitExceeded { .. }));
+}
+
+#[test]
+fn test_memory_limit_decimal_mul() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            let n = std::num::Decimal::parse("3.5")?;
+
+            loop {
+                n = n * n;
+            }
+        }
+    };
+
+    vm.set_memory_limit(Some(1 << 16));
+    let e = run(vm);
+    assert!(matches!(e, VmErrorKind::MemoryLimitExceeded { .. }));
+}
+
+#[test]
+fn test_memory_limit_cumulative() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            loop {
+                // NB: the string is dropped every iteration, but what it
+                // allocated is still charged.
+                let s = "a".repeat(100);
+            }
+        }
+    };
+
+    vm.set_memory_limit(Some(1 << 16));
+    let e = run(vm);
+    assert!(matches!(e, VmErrorKind::MemoryLimitExceeded { .. }));
+}
+
+#[test]
+fn test_memory_limit_within() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            let v = [];
+
+            for n in 0..10 {
+                v.push(#{a: [n, (n, n)], b: "hello"});
+            }
+
+            v.len()
+        }
+    };
+
+    assert_eq!(vm.memory_used(), None);
+    vm.set_memory_limit(Some(1 << 16));
+
+    let mut execution = vm.execute(["main"], ()).unwrap();
+    let used = execution.memory_used().unwrap().unwrap();
+    assert_eq!(used, 0);
+
+    let output = execution.complete().unwrap();
+    assert_eq!(output.into_integer().unwrap(), 10);
+}
+
+#[test]
+fn test_memory_limit_closures() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            let v = [];
+
+            // NB: the closures are called by a native function.
+            (0..1000000).iter().map(|n| v.push(n)).count()
+        }
+    };
+
+    vm.set_memory_limit(Some(1 << 16));
+    let e = run(vm);
+    assert!(matches!(e, VmErrorKind::MemoryLimitExceeded { .. }));
+}
+
+#[test]
+fn test_memory_limit_collect_endless() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            [1].iter().cycle().collect_vec()
+        }
+    };
+
+    vm.set_memory_limit(Some(1 << 16));
+    let e = run(vm);
+    assert!(matches!(e, VmErrorKind::MemoryLimitExceeded { .. }));
+
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            [1, 2].iter().cycle().partition(|n| n == 1)
+        }
+    };
+
+    vm.set_memory_limit(Some(1 << 16));
+    let e = run(vm);
+    assert!(matches!(e, VmErrorKind::MemoryLimitExceeded { .. }));
+}
+
+#[test]
+fn test_call_depth_limit() {
+    let mut vm = rune_tests::rune_vm! {
+        fn recurse(n) {
+            recurse(n + 1)
+        }
+
+        pub fn main() {
+            recurse(0)
+        }
+    };
+
+    vm.set_call_depth_limit(Some(100));
+    let e = run(vm);
+
+    assert!(matches!(
+        e,
+        VmErrorKind::CallDepthExceeded { limit } if limit == 100
+    ));
+}
+
+#[test]
+fn test_call_depth_limit_within() {
+    let mut vm = rune_tests::rune_vm! {
+        fn count(n) {
+            if n == 0 {
+                0
+            } else {
+                1 + count(n - 1)
+            }
+        }
+
+        pub fn main() {
+            count(50)
+        }
+    };
+
+    vm.set_call_depth_limit(Some(100));
+    let output = vm.execute(["main"], ()).unwrap().complete().unwrap();
+    assert_eq!(output.into_integer().unwrap(), 50);
+}
+
+#[test]
+fn test_call_depth_limit_nested() {
+    let mut vm = rune_tests::rune_vm! {
+        fn recurse(n) {
+            // NB: every call runs a nested virtual machine through a native
+            // function, which would otherwise overflow the host stack.
+            [n].iter().map(|n| recurse(n + 1)).collect_vec()
+        }
+
+        pub fn main() {
+            recurse(0)
+        }
+    };
+
+    vm.set_call_depth_limit(Some(100));
+    let e = run(vm);
+    assert!(matches!(e, VmErrorKind::CallDepthExceeded { .. }));
+}
@@ -0,0 +1,183 @@
+use rune_tests::*;
+use runestick::Decimal;
+
+#[test]
+fn test_bigint() {
+    assert_eq! {
+        rune!(String =>
+            use std::num::BigInt;
+
+            pub fn main() {
+                let a = BigInt::parse("18446744073709551616")?;
+                let b = BigInt::new(2);
+                let c = a * b - BigInt::new(1);
+                c += BigInt::new(3);
+                format!("{}", c)
+            }
+        ),
+        "36893488147419103234",
+    };
+
+    assert_eq! {
+        rune!((bool, bool, bool, Option<i64>, Option<i64>) =>
+            use std::num::BigInt;
+
+            pub fn main() {
+                let a = BigInt::new(2).pow(100);
+                let b = BigInt::new(7);
+                (a > b, a == BigInt::new(2).pow(100), b % BigInt::new(4) == BigInt::new(3), a.to_int(), (BigInt::new(-7) / BigInt::new(2)).to_int())
+            }
+        ),
+        (true, true, true, None, Some(-3)),
+    };
+
+    assert_vm_error!(
+        r#"
+        use std::num::BigInt;
+        pub fn main() { BigInt::new(1) / BigInt::new(0) }
+        "#,
+        DivideByZero => {}
+    );
+
+    assert_vm_error!(
+        r#"
+        use std::num::BigInt;
+        pub fn main() { BigInt::new(3).pow(4294967295) }
+        "#,
+        Overflow => {}
+    );
+
+    assert!(rune!(bool =>
+        use std::num::BigInt;
+
+        pub fn main() {
+            BigInt::new(-1).pow(4294967295) == BigInt::new(-1)
+        }
+    ));
+}
+
+#[test]
+fn test_decimal() {
+    assert_eq! {
+        rune!(String =>
+            use std::num::Decimal;
+
+            pub fn main() {
+                let total = Decimal::from_integer(0);
+
+                for price in ["19.99", "5.01", "0.10"] {
+                    total += Decimal::parse(price)?;
+                }
+
+                let tax = (total * Decimal::parse("0.25")?).round(2);
+                format!("{} {} {}", total, tax, total + tax)
+            }
+        ),
+        "25.10 6.28 31.38",
+    };
+
+    assert_eq! {
+        rune!((String, String, String, bool, String, bool) =>
+            use std::num::Decimal;
+
+            pub fn main() {
+                let a = Decimal::parse("10.00")?;
+                let b = Decimal::parse("3")?;
+                let c = Decimal::parse("2.5")?;
+                (
+                    format!("{}", a / b),
+                    format!("{}", a % b),
+                    format!("{}", (a - c * b).trunc(1)),
+                    a / Decimal::from_integer(4) == c,
+                    format!("{}", Decimal::new(1250, 2)),
+                    c < b,
+                )
+            }
+        ),
+        (
+            String::from("3.33333333333333333333"),
+            String::from("1.00"),
+            String::from("2.5"),
+            true,
+            String::from("12.50"),
+            true,
+        ),
+    };
+
+    assert_vm_error!(
+        r#"
+        use std::num::Decimal;
+        pub fn main() { Decimal::from_integer(1) / Decimal::new(0, 2) }
+        "#,
+        DivideByZero => {}
+    );
+
+    assert!(rune!(bool =>
+        use std::num::Decimal;
+
+        pub fn main() {
+            Decimal::parse("1.2.3").is_err() && Decimal::parse("-").is_err()
+        }
+    ));
+}
+
+#[test]
+fn test_decimal_scale() {
+    assert_eq! {
+        rune!((String, bool, String) =>
+            use std::num::Decimal;
+
+            pub fn main() {
+                let a = Decimal::parse("0.0000000000000000000000000001")?;
+                (
+                    format!("{}", a.round(28)),
+                    Decimal::parse("0.00000000000000000000000000001").is_err(),
+                    format!("{}", Decimal::parse("0.5")? * a),
+                )
+            }
+        ),
+        (
+            String::from("0.0000000000000000000000000001"),
+            true,
+            String::from("0.0000000000000000000000000001"),
+        ),
+    };
+
+    assert_vm_error!(
+        r#"
+        use std::num::Decimal;
+        pub fn main() { Decimal::new(1, 29) }
+        "#,
+        DecimalScaleOutOfRange { scale, max } => {
+            assert_eq!(scale, 29);
+            assert_eq!(max, 28);
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        use std::num::Decimal;
+        pub fn main() { Decimal::from_integer(1).round(4294967295) }
+        "#,
+        DecimalScaleOutOfRange { scale, .. } => {
+            assert_eq!(scale, 4294967295);
+        }
+    );
+}
+
+#[test]
+fn test_decimal_values() {
+    let value: Decimal = rune!(Decimal =>
+        use std::num::Decimal;
+
+        pub fn main() {
+            Decimal::parse("-0.05")? * Decimal::from_integer(3)
+        }
+    );
+
+    assert_eq!(value.to_string(), "-0.15");
+    assert_eq!(value, "-0.150".parse::<Decimal>().unwrap());
+    assert_eq!(value.round(1).unwrap().to_string(), "-0.2");
+    assert!(value.round(Decimal::MAX_SCALE + 1).is_none());
+    assert_eq!(value.to_integer(), Some(0));
+}
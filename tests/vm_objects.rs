@@ -0,0 +1,54 @@
+use runestick::Object;
+
+#[test]
+fn test_object_insertion_order() {
+    rune! { () =>
+        pub fn main() {
+            let o = #{c: 1, a: 2, b: 3};
+            o["d"] = 4;
+
+            assert_eq!(o.keys().collect_vec(), ["c", "a", "b", "d"]);
+            assert_eq!(o.values().collect_vec(), [1, 2, 3, 4]);
+
+            let out = [];
+
+            for (key, value) in o {
+                out.push(key);
+            }
+
+            assert_eq!(out, ["c", "a", "b", "d"]);
+
+            o.remove("a");
+            o["a"] = 5;
+            assert_eq!(o.iter().collect_vec(), [("c", 1), ("b", 3), ("d", 4), ("a", 5)]);
+        }
+    };
+}
+
+#[test]
+fn test_const_object_insertion_order() {
+    rune! { () =>
+        const OBJECT = #{c: 1, a: 2, b: #{z: 3, y: 4}};
+
+        pub fn main() {
+            assert_eq!(OBJECT.keys().collect_vec(), ["c", "a", "b"]);
+            assert_eq!(OBJECT["b"].keys().collect_vec(), ["z", "y"]);
+        }
+    };
+}
+
+#[test]
+fn test_object_order_from_native() {
+    let object = rune!(Object => pub fn main() { #{z: 1, y: 2, x: 3} });
+    let keys = object.keys().map(String::as_str).collect::<Vec<_>>();
+    assert_eq!(keys, ["z", "y", "x"]);
+    assert_eq!(format!("{:?}", object), "{\"z\": 1, \"y\": 2, \"x\": 3}");
+}
+
+#[test]
+fn test_object_json_order() {
+    assert_eq!(
+        rune!(String => pub fn main() { json::to_string(#{b: 1, a: #{d: 2, c: 3}}).unwrap() }),
+        r#"{"b":1,"a":{"d":2,"c":3}}"#
+    );
+}
@@ -0,0 +1,138 @@
+use rune::Sources;
+use runestick::{Context, Hash, Inst, Source, Value, Vm, VmError, VmErrorKind, VmObserver};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Default)]
+struct Events {
+    events: RefCell<Vec<String>>,
+    instructions: RefCell<usize>,
+}
+
+impl Events {
+    fn push(&self, vm: &Vm, kind: &str, ip: usize) {
+        let name = vm
+            .unit()
+            .debug_info()
+            .and_then(|d| d.function_at(ip))
+            .map(|(_, signature)| signature.path.to_string());
+
+        let name = name.unwrap_or_else(|| String::from("?"));
+        self.events.borrow_mut().push(format!("{} {}", kind, name));
+    }
+}
+
+impl VmObserver for Events {
+    fn on_call(&self, vm: &Vm, offset: usize) {
+        self.push(vm, "call", offset);
+    }
+
+    fn on_return(&self, _: &Vm, value: &Value) {
+        self.events.borrow_mut().push(format!("return {:?}", value));
+    }
+
+    fn on_instruction(&self, _: &Vm, _: &Inst) {
+        *self.instructions.borrow_mut() += 1;
+    }
+
+    fn on_native_call(&self, _: &Vm, _: Hash) {
+        self.events.borrow_mut().push(String::from("native"));
+    }
+
+    fn on_error(&self, _: &Vm, error: &VmError) {
+        self.events.borrow_mut().push(format!("error {}", error));
+    }
+}
+
+fn observe(source: &str) -> (Result<Value, VmError>, Rc<Events>) {
+    let context = Context::with_default_modules().unwrap();
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let events = Rc::new(Events::default());
+
+    let mut vm = rune_tests::vm(&context, &mut sources).unwrap();
+    vm.set_observer(Some(events.clone()));
+    let result = vm.execute(&["main"], ()).unwrap().complete();
+    (result, events)
+}
+
+#[test]
+fn test_observe_calls() {
+    let (result, events) = observe(
+        r#"
+        fn add(a, b) { a + b }
+
+        pub fn main() {
+            let values = [1].iter().map(|n| add(n, 1)).collect_vec();
+            add(values[0], 2)
+        }
+        "#,
+    );
+
+    assert_eq!(result.unwrap().into_integer().unwrap(), 4);
+
+    // NB: `iter`, `map` and `collect_vec` are native, where the last one calls
+    // the closure in a nested virtual machine.
+    assert_eq!(
+        *events.events.borrow(),
+        vec![
+            "call main",
+            "native",
+            "native",
+            "native",
+            "call main::$0::$0",
+            "call add",
+            "return 2",
+            "return 2",
+            "call add",
+            "return 4",
+            "return 4",
+        ]
+    );
+
+    assert!(*events.instructions.borrow() > 0);
+}
+
+#[test]
+fn test_observe_error() {
+    let (result, events) = observe(
+        r#"
+        fn divide(n) { n / 0 }
+
+        pub fn main() {
+            divide(1)
+        }
+        "#,
+    );
+
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        VmErrorKind::Unwound { .. }
+    ));
+
+    assert_eq!(
+        *events.events.borrow(),
+        vec!["call main", "call divide", "error division by zero"]
+    );
+}
+
+#[test]
+fn test_observe_send_execute() {
+    let context = Context::with_default_modules().unwrap();
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", "pub fn main() { 42 }"));
+
+    let mut vm = rune_tests::vm(&context, &mut sources).unwrap();
+    vm.set_observer(Some(Rc::new(Events::default())));
+
+    let error = match vm.send_execute(&["main"], ()) {
+        Ok(..) => panic!("expected an observed execution to not be sendable"),
+        Err(error) => error,
+    };
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::UnsupportedSendExecution { .. }
+    ));
+}
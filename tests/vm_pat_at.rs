@@ -0,0 +1,108 @@
+use rune_tests::*;
+
+#[test]
+fn test_pat_at() {
+    assert_eq! {
+        rune! { (Option<i64>, i64) =>
+            pub fn main() {
+                match Some(3) { whole @ Some(n) => (whole, n), _ => (None, 0) }
+            }
+        },
+        (Some(3), 3),
+    };
+
+    assert_eq! {
+        rune! { Vec<i64> =>
+            fn double_digits(n) {
+                match n { n @ 0..10 => n * 2, n => n }
+            }
+
+            pub fn main() {
+                [double_digits(4), double_digits(12)]
+            }
+        },
+        vec![8, 12],
+    };
+
+    assert_eq! {
+        rune! { (Vec<i64>, i64) =>
+            pub fn main() {
+                let all @ [first, ..] = [1, 2, 3];
+                (all, first)
+            }
+        },
+        (vec![1, 2, 3], 1),
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            pub fn main() {
+                match 5 { small @ 0..3 => small, big @ _ => big * 10 }
+            }
+        },
+        50,
+    };
+}
+
+#[test]
+fn test_pat_at_nested_object() {
+    assert_eq! {
+        rune! { (i64, Option<i64>, i64, i64) =>
+            struct Foo { a, b }
+
+            pub fn main() {
+                match (Foo { a: Some(1), b: 2 }) {
+                    foo @ Foo { a: inner @ Some(x), b: renamed } => (foo.b, inner, x, renamed),
+                    _ => (0, None, 0, 0),
+                }
+            }
+        },
+        (2, Some(1), 1, 2),
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            enum Expr { Add { lhs, rhs }, Lit { value } }
+
+            pub fn main() {
+                let expr = Expr::Add {
+                    lhs: Expr::Lit { value: 1 },
+                    rhs: Expr::Add { lhs: Expr::Lit { value: 2 }, rhs: Expr::Lit { value: 3 } },
+                };
+
+                match expr {
+                    Expr::Add { lhs: Expr::Lit { value: a }, rhs: Expr::Add { lhs: Expr::Lit { value: b }, .. } } => a + b,
+                    _ => 0,
+                }
+            }
+        },
+        3,
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            pub fn main() {
+                let #{"a": x, b: y @ [_, ..]} = #{a: 5, b: [6, 7]};
+                x + y.len()
+            }
+        },
+        7,
+    };
+}
+
+#[test]
+fn test_pat_at_errors() {
+    assert_parse_error! {
+        r#"pub fn main() { match 1 { a::b @ 1 => 1, _ => 0 } }"#,
+        span, ParseErrorKind::Expected { actual: _, expected: "identifier" } => {
+            assert_eq!(span, Span::new(26, 30));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { match 1 { a @ 1 | a @ 2 => a, _ => 0 } }"#,
+        span, UnsupportedOrPatternBinding => {
+            assert_eq!(span, Span::new(26, 31));
+        }
+    };
+}
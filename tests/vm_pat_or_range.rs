@@ -0,0 +1,204 @@
+use rune_tests::*;
+
+#[test]
+fn test_pat_range_integer() {
+    assert_eq! {
+        rune! { Vec<i64> =>
+            fn classify(n) {
+                match n {
+                    -100..0 => 0,
+                    0..10 => 1,
+                    10..=20 => 2,
+                    21.. => 3,
+                }
+            }
+
+            pub fn main() {
+                [classify(-5), classify(0), classify(9), classify(10), classify(20), classify(21)]
+            }
+        },
+        vec![0, 1, 1, 2, 2, 3],
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            pub fn main() {
+                match -3 { -10..-2 => 1, _ => 2 }
+            }
+        },
+        1,
+    };
+
+    assert!(rune! { bool =>
+        pub fn main() {
+            match 10u32 { 0..=10 => true, _ => false }
+        }
+    });
+}
+
+#[test]
+fn test_pat_range_char_and_byte() {
+    assert_eq! {
+        rune! { Vec<bool> =>
+            fn is_ident(c) {
+                match c { 'a'..='z' | 'A'..='Z' | '_' => true, _ => false }
+            }
+
+            pub fn main() {
+                [is_ident('a'), is_ident('Q'), is_ident('_'), is_ident('1')]
+            }
+        },
+        vec![true, true, true, false],
+    };
+
+    assert!(rune! { bool =>
+        pub fn main() {
+            match b'7' { b'0'..=b'9' => true, _ => false }
+        }
+    });
+
+    assert!(!rune! { bool =>
+        pub fn main() {
+            match "a" { 'a'..='z' => true, _ => false }
+        }
+    });
+}
+
+#[test]
+fn test_pat_or() {
+    assert_eq! {
+        rune! { Vec<i64> =>
+            fn classify(n) {
+                match n { 1 | 2 | 3 => 1, 4 | 5 => 2, _ => 3 }
+            }
+
+            pub fn main() {
+                [classify(1), classify(3), classify(5), classify(6)]
+            }
+        },
+        vec![1, 1, 2, 3],
+    };
+
+    assert_eq! {
+        rune! { Vec<bool> =>
+            fn empty(n) {
+                match n { None | Some(0) => true, _ => false }
+            }
+
+            pub fn main() {
+                [empty(None), empty(Some(0)), empty(Some(1))]
+            }
+        },
+        vec![true, true, false],
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            pub fn main() {
+                match (1, 'b') { (0, _) | (_, 'a') => 1, (1, 'b') | (2, 'c') => 2, _ => 3 }
+            }
+        },
+        2,
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            pub fn main() {
+                let f = |a, b| a + b;
+                match f(1, 2) { 3 | 4 => 1, _ => 2 }
+            }
+        },
+        1,
+    };
+}
+
+#[test]
+fn test_pat_or_nested() {
+    assert_eq! {
+        rune! { Vec<i64> =>
+            fn classify(v) {
+                match v {
+                    (1 | 2, n) => n,
+                    [0 | 1, 'a'..='c' | 'x', ..] => 10,
+                    Some('a' | 'b') => 20,
+                    #{kind: "add" | "sub", n} => n * 2,
+                    _ => 0,
+                }
+            }
+
+            pub fn main() {
+                [
+                    classify((2, 5)),
+                    classify((3, 5)),
+                    classify([1, 'x', 5]),
+                    classify([0, 'b']),
+                    classify([2, 'b']),
+                    classify(Some('b')),
+                    classify(Some('c')),
+                    classify(#{kind: "sub", n: 4}),
+                    classify(#{kind: "mul", n: 4}),
+                ]
+            }
+        },
+        vec![5, 0, 10, 10, 0, 20, 0, 8, 0],
+    };
+
+    assert_eq! {
+        rune! { Vec<i64> =>
+            struct Token { kind, len }
+            enum Op { Unary(a), Binary(a, b) }
+
+            fn classify(v) {
+                match v {
+                    Token { kind: 'a'..='z' | '_', len } => len,
+                    Op::Binary(0 | 1, 0 | 1) => 1,
+                    Op::Unary(Some(1 | 2) | None) => 2,
+                    _ => 3,
+                }
+            }
+
+            pub fn main() {
+                [
+                    classify(Token { kind: '_', len: 7 }),
+                    classify(Token { kind: '1', len: 7 }),
+                    classify(Op::Binary(1, 0)),
+                    classify(Op::Binary(1, 2)),
+                    classify(Op::Unary(None)),
+                    classify(Op::Unary(Some(2))),
+                    classify(Op::Unary(Some(3))),
+                ]
+            }
+        },
+        vec![7, 3, 1, 3, 2, 2, 3],
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            pub fn main() {
+                let f = |(a, b), c| a + b + c;
+                f((1, 2), 3)
+            }
+        },
+        6,
+    };
+}
+
+#[test]
+fn test_pat_or_binding() {
+    assert_compile_error! {
+        r#"pub fn main() { match Some(1) { Some(a) | Some(a) => a, _ => 0 } }"#,
+        span, UnsupportedOrPatternBinding => {
+            assert_eq!(span, Span::new(32, 39));
+        }
+    };
+}
+
+#[test]
+fn test_pat_range_mismatch() {
+    assert_compile_error! {
+        r#"pub fn main() { match 1 { 'a'..10 => 1, _ => 0 } }"#,
+        span, UnsupportedRangePattern => {
+            assert_eq!(span, Span::new(26, 33));
+        }
+    };
+}
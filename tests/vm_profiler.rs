@@ -0,0 +1,95 @@
+use rune::Sources;
+use runestick::profiler::{Profile, Profiler};
+use runestick::{Context, Source, VmErrorKind};
+
+const SOURCE: &str = r#"
+fn fib(n) {
+    if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+}
+
+pub fn main() {
+    [1, 2, 3].iter().map(|n| fib(n)).collect_vec();
+    fib(5)
+}
+"#;
+
+fn profile() -> Profile {
+    let context = Context::with_default_modules().unwrap();
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let profiler = Profiler::new();
+
+    let mut vm = rune_tests::vm(&context, &mut sources).unwrap();
+    vm.set_profiler(Some(profiler.clone()));
+    vm.execute(&["main"], ()).unwrap().complete().unwrap();
+
+    let mut profile = profiler.profile();
+    profile.resolve_names(&context);
+    profile
+}
+
+#[test]
+fn test_profile_functions() {
+    let profile = profile();
+
+    let function = |name: &str| {
+        profile
+            .functions
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("missing function `{}`", name))
+    };
+
+    // NB: fib(1) + fib(2) + fib(3) from the closure and fib(5) from main.
+    assert_eq!(function("fib").calls, 1 + 3 + 5 + 15);
+    assert_eq!(function("main").calls, 1);
+    assert_eq!(function("main::$0::$0").calls, 3);
+    assert!(function("fib").instructions > 0);
+
+    let collect = function("::std::iter::Iterator::collect_vec");
+    assert!(collect.native);
+    assert_eq!(collect.calls, 1);
+    assert_eq!(collect.instructions, 0);
+
+    assert!(!profile.spans.is_empty());
+}
+
+#[test]
+fn test_profile_folded() {
+    let profile = profile();
+
+    let mut out = Vec::new();
+    profile.write_folded(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+
+    let stacks = out
+        .lines()
+        .filter_map(|line| Some(line.rsplitn(2, ' ').nth(1)?.to_owned()))
+        .collect::<Vec<_>>();
+
+    assert!(stacks.iter().any(|s| s == "main;fib;fib"));
+    assert!(stacks
+        .iter()
+        .any(|s| s == "main;::std::iter::Iterator::collect_vec;main::$0::$0;fib"));
+}
+
+#[test]
+fn test_profile_send_execute() {
+    let context = Context::with_default_modules().unwrap();
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let mut vm = rune_tests::vm(&context, &mut sources).unwrap();
+    vm.set_profiler(Some(Profiler::new()));
+
+    let error = match vm.send_execute(&["main"], ()) {
+        Ok(..) => panic!("expected a profiled execution to not be sendable"),
+        Err(error) => error,
+    };
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::UnsupportedSendExecution { .. }
+    ));
+}
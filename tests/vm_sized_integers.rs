@@ -0,0 +1,167 @@
+use rune_tests::*;
+
+#[test]
+fn test_sized_literals() {
+    assert_eq! {
+        rune!((u16, u32, u64, i32, i128, u8, i64) =>
+            pub fn main() {
+                (10u16, 0xffu32, 18446744073709551615u64, -5i32, -170141183460469231731687303715884105728i128, 7u8, 42i64)
+            }
+        ),
+        (10, 255, u64::MAX, -5, i128::MIN, 7, 42),
+    };
+
+    assert! {
+        rune!(bool =>
+            pub fn main() {
+                10u16 is u16 && 10u32 is u32 && 10u64 is u64 && 10i32 is i32 && 10i128 is i128
+                    && 10u8 is byte && 10i64 is int
+            }
+        )
+    };
+
+    assert_eq! {
+        rune_s!(String => r#"
+            pub fn main() {
+                let a = 18446744073709551615u64;
+                let b = -3i32;
+                `${a} ${b}`
+            }
+        "#),
+        "18446744073709551615 -3",
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { 70000u16 }"#,
+        span, CompileErrorKind::ParseError { error: BadNumberOutOfBounds } => {
+            assert_eq!(span, Span::new(16, 24));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { -1u32 }"#,
+        span, CompileErrorKind::ParseError { error: BadNumberOutOfBounds } => {
+            assert_eq!(span, Span::new(16, 21));
+        }
+    };
+}
+
+#[test]
+fn test_sized_arithmetic() {
+    assert_eq! {
+        rune_s!((u64, u32, i32, i128, u16) => r#"
+            pub fn main() {
+                let a = 9223372036854775807u64;
+                let b = 10u32;
+                b *= 3u32;
+                (a + a, b % 7u32, -(12i32 / 5i32), 2i128 ** 100i128, 0xf0u16 | 0x0fu16)
+            }
+        "#),
+        (18446744073709551614, 2, -2, 1 << 100, 0xff),
+    };
+
+    assert_eq! {
+        rune!((bool, bool, bool) =>
+            pub fn main() {
+                let a = 18446744073709551615u64;
+                let b = 1u64;
+                (a > b, b <= 1u64, a == 18446744073709551615u64)
+            }
+        ),
+        (true, true, true),
+    };
+
+    assert_vm_error!(
+        r#"pub fn main() { let a = 18446744073709551615u64; a + 1u64 }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let a = 0u32; a - 1u32 }"#,
+        Underflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let a = 1u32; a / 0u32 }"#,
+        DivideByZero => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let a = 1u32; a + 1 }"#,
+        UnsupportedBinaryOperation { .. } => {}
+    );
+}
+
+#[test]
+fn test_sized_casts() {
+    assert_eq! {
+        rune!((u64, i64, u32, f64, i128, u8) =>
+            pub fn main() {
+                let n = 42;
+                let big = 18446744073709551615u64;
+                (n as u64, 10u32 as int, 'a' as u32, big as float, big as i128, 200u16 as byte)
+            }
+        ),
+        (42, 10, 97, 18446744073709551615.0, 18446744073709551615, 200),
+    };
+
+    assert_vm_error!(
+        r#"pub fn main() { let n = -1; n as u64 }"#,
+        CastOutOfRange { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let n = 18446744073709551615u64; n as int }"#,
+        CastOutOfRange { .. } => {}
+    );
+}
+
+#[test]
+fn test_sized_patterns_and_keys() {
+    assert_eq! {
+        rune!(i64 =>
+            pub fn main() {
+                match 2u32 {
+                    1 => 10,
+                    2 => 20,
+                    _ => 30,
+                }
+            }
+        ),
+        20,
+    };
+
+    assert_eq! {
+        rune!(Option<u64> =>
+            use std::collections::HashMap;
+
+            pub fn main() {
+                let m = HashMap::new();
+                m.insert(18446744073709551615u64, 1u64);
+                m.insert(1u64, 2u64);
+                Some(m.get(18446744073709551615u64)? + m.get(1u64)?)
+            }
+        ),
+        Some(3),
+    };
+}
+
+#[test]
+fn test_sized_values_roundtrip() {
+    use runestick::{FromValue as _, ToValue as _, Value};
+
+    let value = u64::MAX.to_value().unwrap();
+    assert!(matches!(value, Value::U64(u64::MAX)));
+    assert_eq!(u64::from_value(value).unwrap(), u64::MAX);
+
+    let value = i128::MIN.to_value().unwrap();
+    assert_eq!(i128::from_value(value).unwrap(), i128::MIN);
+
+    let value = 7u32.to_value().unwrap();
+    assert!(matches!(value, Value::U32(7)));
+    assert_eq!(u16::from_value(value).unwrap(), 7);
+
+    assert!(u16::from_value(Value::U32(70000)).is_err());
+    assert!(u64::from_value(Value::Integer(-1)).is_err());
+    assert_eq!(i64::from_value(Value::I32(-3)).unwrap(), -3);
+}
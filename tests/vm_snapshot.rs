@@ -0,0 +1,284 @@
+use rune::{Errors, Options, Sources, Warnings};
+use runestick::{
+    Any, Context, FromValue as _, Generator, GeneratorState, Module, Snapshot, Source,
+    ToValue as _, Value, Vm, VmError, VmErrorKind, VmHaltInfo, VmSnapshot,
+};
+use std::sync::Arc;
+
+/// Round-trip the snapshot through its serialized form.
+fn round_trip(snapshot: &VmSnapshot) -> VmSnapshot {
+    let bytes = bincode::serialize(snapshot).unwrap();
+    bincode::deserialize(&bytes).unwrap()
+}
+
+#[test]
+fn test_generator_snapshot() {
+    let vm = || {
+        rune_tests::rune_vm! {
+            fn workflow() {
+                let shared = #{count: 0};
+                let items = [shared, shared];
+
+                let a = yield 1;
+                shared.count += a;
+                let b = yield 2;
+                shared.count += b;
+                items.push(b);
+
+                (items[0].count, items[1].count, items.len())
+            }
+
+            pub fn main() {
+                workflow()
+            }
+        }
+    };
+
+    let mut generator = Generator::from_value(vm().call(["main"], ()).unwrap()).unwrap();
+
+    let state = generator.resume(Value::Unit).unwrap();
+    assert!(matches!(state, GeneratorState::Yielded(Value::Integer(1))));
+    let state = generator.resume(Value::Integer(10)).unwrap();
+    assert!(matches!(state, GeneratorState::Yielded(Value::Integer(2))));
+
+    let snapshot = round_trip(&generator.snapshot().unwrap());
+    drop(generator);
+
+    // Restore into a separately compiled copy of the same unit.
+    let vm = vm();
+    let mut generator =
+        Generator::restore(vm.context().clone(), vm.unit().clone(), &snapshot).unwrap();
+
+    let output = match generator.resume(Value::Integer(20)).unwrap() {
+        GeneratorState::Complete(output) => output,
+        GeneratorState::Yielded(..) => panic!("unexpected yield"),
+    };
+
+    let output = <(i64, i64, i64)>::from_value(output).unwrap();
+    assert_eq!(output, (30, 30, 3));
+
+    // The generator is complete, so resuming it again fails the same way it
+    // would have without the snapshot.
+    assert!(generator.resume(Value::Unit).is_err());
+}
+
+#[test]
+fn test_vm_snapshot() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            let items = [];
+            let object = #{items};
+            object.parent = object;
+            let n = 0;
+            let i = 0;
+
+            while i < 100 {
+                items.push(i);
+                n += i;
+                i += 1;
+            }
+
+            object.parent.items.len() + n
+        }
+    };
+
+    vm.set_fuel(Some(200));
+    let mut execution = vm.execute(["main"], ()).unwrap();
+
+    let e = execution.resume().unwrap_err();
+
+    assert!(matches!(
+        e.kind(),
+        VmErrorKind::Halted {
+            halt: VmHaltInfo::OutOfFuel
+        }
+    ));
+
+    let snapshot = round_trip(&execution.vm().unwrap().snapshot().unwrap());
+    let vm = execution.vm().unwrap();
+    let vm = Vm::restore(vm.context().clone(), vm.unit().clone(), &snapshot).unwrap();
+    assert_eq!(vm.fuel(), None);
+
+    let output = vm.complete().unwrap();
+    assert_eq!(output.into_integer().unwrap(), 100 + 4950);
+}
+
+#[test]
+fn test_snapshot_different_unit() {
+    let mut vm = rune_tests::rune_vm! {
+        pub fn main() {
+            let n = 0;
+
+            loop {
+                n += 1;
+            }
+        }
+    };
+
+    vm.set_fuel(Some(10));
+    let mut execution = vm.execute(["main"], ()).unwrap();
+    assert!(execution.resume().is_err());
+    let snapshot = round_trip(&execution.vm().unwrap().snapshot().unwrap());
+
+    let other = rune_tests::rune_vm! {
+        pub fn main() {
+            let n = 0;
+
+            loop {
+                n += 2;
+            }
+        }
+    };
+
+    let e = Vm::restore(other.context().clone(), other.unit().clone(), &snapshot).unwrap_err();
+    assert!(matches!(e.kind(), VmErrorKind::InvalidSnapshot));
+
+    // A separately compiled copy of the same unit is accepted.
+    let same = rune_tests::rune_vm! {
+        pub fn main() {
+            let n = 0;
+
+            loop {
+                n += 1;
+            }
+        }
+    };
+
+    assert!(Vm::restore(same.context().clone(), same.unit().clone(), &snapshot).is_ok());
+}
+
+#[test]
+fn test_unsupported_snapshot() {
+    let vm = rune_tests::rune_vm! {
+        fn workflow() {
+            let it = [1, 2, 3].iter();
+            yield it.next();
+            it.next()
+        }
+
+        pub fn main() {
+            workflow()
+        }
+    };
+
+    let mut generator = Generator::from_value(vm.call(["main"], ()).unwrap()).unwrap();
+    generator.resume(Value::Unit).unwrap();
+
+    let e = generator.snapshot().unwrap_err();
+    assert!(matches!(e.kind(), VmErrorKind::UnsupportedSnapshot { .. }));
+}
+
+#[derive(Any)]
+struct Counter {
+    count: i64,
+}
+
+impl Snapshot for Counter {
+    fn snapshot(&self) -> Result<Value, VmError> {
+        self.count.to_value()
+    }
+
+    fn restore(value: Value) -> Result<Self, VmError> {
+        Ok(Self {
+            count: i64::from_value(value)?,
+        })
+    }
+}
+
+#[derive(Any)]
+struct Opaque;
+
+fn counter_new() -> Counter {
+    Counter { count: 0 }
+}
+
+fn counter_increment(counter: &mut Counter) {
+    counter.count += 1;
+}
+
+fn counter_count(counter: &Counter) -> i64 {
+    counter.count
+}
+
+fn vm_with_counter(source: &str) -> Vm {
+    let mut module = Module::new();
+    module.ty::<Counter>().unwrap();
+    module.ty::<Opaque>().unwrap();
+    module.snapshot::<Counter>().unwrap();
+    module.function(&["Counter", "new"], counter_new).unwrap();
+    module.function(&["Opaque", "new"], || Opaque).unwrap();
+    module.inst_fn("increment", counter_increment).unwrap();
+    module.inst_fn("count", counter_count).unwrap();
+
+    let mut context = Context::with_default_modules().unwrap();
+    context.install(&module).unwrap();
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("test", source));
+
+    let unit = rune::load_sources(
+        &context,
+        &Options::default(),
+        &mut sources,
+        &mut Errors::new(),
+        &mut Warnings::disabled(),
+    )
+    .unwrap();
+
+    Vm::new(Arc::new(context.runtime()), Arc::new(unit))
+}
+
+#[test]
+fn test_native_snapshot() {
+    let source = r#"
+        fn workflow() {
+            let counter = Counter::new();
+            counter.increment();
+            yield;
+            counter.increment();
+            counter.count()
+        }
+
+        pub fn main() {
+            workflow()
+        }
+    "#;
+
+    let vm = vm_with_counter(source);
+    let mut generator = Generator::from_value(vm.call(["main"], ()).unwrap()).unwrap();
+    generator.resume(Value::Unit).unwrap();
+
+    let snapshot = round_trip(&generator.snapshot().unwrap());
+
+    let vm = vm_with_counter(source);
+    let mut generator =
+        Generator::restore(vm.context().clone(), vm.unit().clone(), &snapshot).unwrap();
+
+    let output = match generator.resume(Value::Unit).unwrap() {
+        GeneratorState::Complete(output) => output,
+        GeneratorState::Yielded(..) => panic!("unexpected yield"),
+    };
+
+    assert_eq!(output.into_integer().unwrap(), 2);
+
+    // Native types which haven't opted in can't be stored.
+    let vm = vm_with_counter(
+        r#"
+        fn workflow() {
+            let opaque = Opaque::new();
+            yield;
+            opaque
+        }
+
+        pub fn main() {
+            workflow()
+        }
+        "#,
+    );
+
+    let mut generator = Generator::from_value(vm.call(["main"], ()).unwrap()).unwrap();
+    generator.resume(Value::Unit).unwrap();
+
+    let e = generator.snapshot().unwrap_err();
+    assert!(matches!(e.kind(), VmErrorKind::UnsupportedSnapshot { .. }));
+}
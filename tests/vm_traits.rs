@@ -0,0 +1,101 @@
+use rune::CompileErrorKind::*;
+use rune::QueryErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_trait_default_functions() {
+    assert_eq! {
+        rune! { (i64, i64, i64) =>
+            trait Shape {
+                fn area(self);
+
+                fn double_area(self) {
+                    self.area() * 2
+                }
+            }
+
+            struct Square { side }
+            struct Rect { w, h }
+
+            impl Shape for Square {
+                fn area(self) {
+                    self.side * self.side
+                }
+            }
+
+            impl Shape for Rect {
+                fn area(self) {
+                    self.w * self.h
+                }
+
+                fn double_area(self) {
+                    0
+                }
+            }
+
+            pub fn main() {
+                let s = Square { side: 3 };
+                let r = Rect { w: 2, h: 5 };
+                (s.area(), s.double_area(), r.double_area())
+            }
+        },
+        (9, 18, 0),
+    };
+}
+
+#[test]
+fn test_trait_in_module() {
+    assert_eq! {
+        rune! { i64 =>
+            mod shapes {
+                pub trait Named {
+                    fn name(self) {
+                        self.id * 10
+                    }
+                }
+            }
+
+            struct Foo { id }
+
+            impl shapes::Named for Foo {}
+
+            pub fn main() {
+                Foo { id: 4 }.name()
+            }
+        },
+        40,
+    };
+}
+
+#[test]
+fn test_trait_errors() {
+    assert_compile_error! {
+        r#"trait Shape { fn area(self); } struct Foo; impl Shape for Foo {} pub fn main() {}"#,
+        span, MissingTraitFunction { name, .. } => {
+            assert_eq!(&*name, "area");
+            assert_eq!(span, Span::new(43, 64));
+        }
+    };
+
+    assert_compile_error! {
+        r#"trait Shape {} struct Foo; impl Shape for Foo { fn area(self) {} } pub fn main() {}"#,
+        span, UnknownTraitFunction { name, .. } => {
+            assert_eq!(&*name, "area");
+            assert_eq!(span, Span::new(48, 64));
+        }
+    };
+
+    assert_compile_error! {
+        r#"trait Shape {} struct Foo; impl Shape for Foo {} impl Shape for Foo {} pub fn main() {}"#,
+        span, QueryError { error: TraitImplConflict { .. } } => {
+            assert_eq!(span, Span::new(49, 70));
+        }
+    };
+
+    assert_compile_error! {
+        r#"struct Foo; impl Foo for Foo {} pub fn main() {}"#,
+        span, ExpectedMeta { meta: CompileMeta { kind: CompileMetaKind::UnitStruct { .. }, .. }, .. } => {
+            assert_eq!(span, Span::new(17, 20));
+        }
+    };
+}